@@ -1,4 +1,7 @@
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
 use serde::Serialize;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Unified error type for all AI providers
@@ -30,7 +33,7 @@ pub enum ProviderError {
 
     /// Rate limit exceeded
     #[error("Rate limit exceeded")]
-    RateLimit,
+    RateLimit(RateLimitInfo),
 
     /// Authentication is missing (no API key or OAuth token)
     #[error("Authentication missing")]
@@ -97,6 +100,136 @@ impl From<std::string::FromUtf8Error> for ProviderError {
 /// Legacy alias for backward compatibility
 pub type CommonRequestError = ProviderError;
 
+/// Rate limit state reported by a provider alongside a 429 response
+///
+/// Every field is optional because providers expose different headers. Besides
+/// the standard `Retry-After`, the `x-ratelimit-*` and `anthropic-ratelimit-*`
+/// families are understood, with resets given as durations, timestamps or
+/// epoch milliseconds.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RateLimitInfo {
+    /// Delay requested by the server before retrying
+    pub retry_after: Option<Duration>,
+    /// When the exhausted quota resets
+    pub reset_at: Option<DateTime<Utc>>,
+    /// Requests left in the current window
+    pub remaining_requests: Option<u64>,
+    /// Tokens left in the current window
+    pub remaining_tokens: Option<u64>,
+}
+
+impl RateLimitInfo {
+    /// Parse rate limit information from response headers
+    #[must_use]
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self::from_headers_at(headers, Utc::now())
+    }
+
+    /// Parse rate limit information, resolving relative resets against `now`
+    fn from_headers_at(headers: &HeaderMap, now: DateTime<Utc>) -> Self {
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+        let number = |names: &[&str]| {
+            names
+                .iter()
+                .find_map(|name| header(name).and_then(|v| v.trim().parse::<u64>().ok()))
+        };
+
+        let retry_after = header("retry-after-ms")
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .map(Duration::from_millis)
+            .or_else(|| header("retry-after").and_then(|v| parse_retry_after(v, now)));
+
+        let reset_at = [
+            "x-ratelimit-reset-requests",
+            "x-ratelimit-reset-tokens",
+            "anthropic-ratelimit-requests-reset",
+            "anthropic-ratelimit-tokens-reset",
+            "x-ratelimit-reset",
+        ]
+        .iter()
+        .filter_map(|name| header(name).and_then(|v| parse_reset(v, now)))
+        .max();
+
+        Self {
+            retry_after,
+            reset_at,
+            remaining_requests: number(&[
+                "x-ratelimit-remaining-requests",
+                "anthropic-ratelimit-requests-remaining",
+                "x-ratelimit-remaining",
+            ]),
+            remaining_tokens: number(&[
+                "x-ratelimit-remaining-tokens",
+                "anthropic-ratelimit-tokens-remaining",
+            ]),
+        }
+    }
+
+    /// How long to wait before the next request, preferring `Retry-After`
+    #[must_use]
+    pub fn wait_time(&self) -> Option<Duration> {
+        self.retry_after.or_else(|| {
+            self.reset_at
+                .map(|reset| (reset - Utc::now()).to_std().unwrap_or_default())
+        })
+    }
+}
+
+/// Parse a `Retry-After` value given either as seconds or as an HTTP date
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+/// Parse a reset header given as an RFC 3339 timestamp, epoch milliseconds or
+/// a duration such as `1s`, `6m0s` or `20ms`
+fn parse_reset(value: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Some(date.with_timezone(&Utc));
+    }
+    if let Ok(millis) = value.parse::<i64>() {
+        return DateTime::from_timestamp_millis(millis);
+    }
+    let duration = parse_duration(value)?;
+    Some(now + chrono::Duration::from_std(duration).ok()?)
+}
+
+/// Parse Go-style durations such as `1h2m3.5s` or `250ms`
+fn parse_duration(value: &str) -> Option<Duration> {
+    let mut total = Duration::ZERO;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let split = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let (number, tail) = rest.split_at(split);
+        let number: f64 = number.parse().ok()?;
+        let unit_len = tail
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+        let seconds = match unit {
+            "h" => number * 3600.0,
+            "m" => number * 60.0,
+            "s" => number,
+            "ms" => number / 1000.0,
+            _ => return None,
+        };
+        total += Duration::try_from_secs_f64(seconds).ok()?;
+        rest = tail;
+    }
+    Some(total)
+}
+
 /// Unified error parsing for all providers
 /// Handles multiple API error response formats (OpenAI, Anthropic, Mistral, OpenRouter, etc.)
 pub fn parse_api_error_response(status: reqwest::StatusCode, body: &[u8]) -> ProviderError {
@@ -249,3 +382,96 @@ fn extract_structured_error(
 pub fn parse_error_response(status: reqwest::StatusCode, body: bytes::Bytes) -> ProviderError {
     parse_api_error_response(status, &body)
 }
+
+/// Parse an error response, turning HTTP 429 into [`ProviderError::RateLimit`]
+/// with the rate limit information from its headers
+#[must_use]
+pub fn parse_error_response_with_headers(
+    status: reqwest::StatusCode,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> ProviderError {
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        ProviderError::RateLimit(RateLimitInfo::from_headers(headers))
+    } else {
+        parse_api_error_response(status, body)
+    }
+}
+
+/// Read a failed response and convert it into a [`ProviderError`]
+pub async fn error_from_response(response: reqwest::Response) -> ProviderError {
+    let status = response.status();
+    let headers = response.headers().clone();
+    match response.bytes().await {
+        Ok(bytes) => parse_error_response_with_headers(status, &headers, &bytes),
+        Err(e) => e.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn test_rate_limit_info_openai_headers() {
+        let now = Utc::now();
+        let info = RateLimitInfo::from_headers_at(
+            &headers(&[
+                ("retry-after", "7"),
+                ("x-ratelimit-remaining-requests", "0"),
+                ("x-ratelimit-remaining-tokens", "1500"),
+                ("x-ratelimit-reset-requests", "1m30s"),
+                ("x-ratelimit-reset-tokens", "250ms"),
+            ]),
+            now,
+        );
+
+        assert_eq!(info.retry_after, Some(Duration::from_secs(7)));
+        assert_eq!(info.remaining_requests, Some(0));
+        assert_eq!(info.remaining_tokens, Some(1500));
+        assert_eq!(info.reset_at, Some(now + chrono::Duration::seconds(90)));
+    }
+
+    #[test]
+    fn test_rate_limit_info_anthropic_headers() {
+        let info = RateLimitInfo::from_headers(&headers(&[
+            ("anthropic-ratelimit-tokens-remaining", "0"),
+            ("anthropic-ratelimit-tokens-reset", "2030-01-01T00:00:30Z"),
+        ]));
+
+        assert_eq!(info.retry_after, None);
+        assert_eq!(info.remaining_tokens, Some(0));
+        assert_eq!(
+            info.reset_at,
+            DateTime::parse_from_rfc3339("2030-01-01T00:00:30Z")
+                .ok()
+                .map(|d| d.with_timezone(&Utc))
+        );
+    }
+
+    #[test]
+    fn test_too_many_requests_maps_to_rate_limit() {
+        let error = parse_error_response_with_headers(
+            reqwest::StatusCode::TOO_MANY_REQUESTS,
+            &headers(&[("retry-after-ms", "1500")]),
+            b"{}",
+        );
+
+        assert!(matches!(
+            error,
+            ProviderError::RateLimit(RateLimitInfo {
+                retry_after: Some(d),
+                ..
+            }) if d == Duration::from_millis(1500)
+        ));
+    }
+}
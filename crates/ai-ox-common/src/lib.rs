@@ -14,15 +14,19 @@ pub mod error;
 pub mod openai_format;
 pub mod request_builder;
 pub mod response_format;
+pub mod retry;
 pub mod streaming;
 pub mod timestamp;
+pub mod transport;
 pub mod usage;
 
-pub use error::CommonRequestError;
+pub use error::{CommonRequestError, RateLimitInfo};
 pub use openai_format::*;
 pub use request_builder::{Endpoint, HttpMethod, MultipartForm, RequestBuilder};
+pub use retry::RetryPolicy;
 pub use streaming::SseParser;
 pub use timestamp::Timestamp;
+pub use transport::{Cassette, Transport};
 pub use usage::TokenUsage;
 
 /// Re-export common types for convenience
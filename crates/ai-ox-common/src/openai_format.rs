@@ -63,8 +63,12 @@ pub struct Function {
 }
 
 /// Tool choice options (identical across providers)
+///
+/// Serializes to the wire format shared by OpenAI-compatible APIs: the plain
+/// strings `"none"`, `"auto"` and `"required"`, or a `{"type", "function"}`
+/// object naming a specific function.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
+#[serde(into = "ToolChoiceRepr", from = "ToolChoiceRepr")]
 pub enum ToolChoice {
     None,
     Auto,
@@ -72,6 +76,69 @@ pub enum ToolChoice {
     Specific { r#type: String, function: Function },
 }
 
+impl ToolChoice {
+    /// Forces the model to call the function with the given name.
+    pub fn function(name: impl Into<String>) -> Self {
+        Self::Specific {
+            r#type: "function".to_string(),
+            function: Function {
+                name: name.into(),
+                description: None,
+                parameters: None,
+            },
+        }
+    }
+}
+
+/// Wire representation of [`ToolChoice`].
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum ToolChoiceRepr {
+    /// One of the string modes.
+    Mode(ToolChoiceMode),
+    /// A specific function.
+    Specific {
+        /// Tool type, usually `"function"`.
+        r#type: String,
+        /// The function to call.
+        function: Function,
+    },
+}
+
+/// String modes of [`ToolChoice`].
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ToolChoiceMode {
+    /// `"none"`
+    None,
+    /// `"auto"`
+    Auto,
+    /// `"required"`
+    Required,
+}
+
+impl From<ToolChoice> for ToolChoiceRepr {
+    fn from(choice: ToolChoice) -> Self {
+        match choice {
+            ToolChoice::None => Self::Mode(ToolChoiceMode::None),
+            ToolChoice::Auto => Self::Mode(ToolChoiceMode::Auto),
+            ToolChoice::Required => Self::Mode(ToolChoiceMode::Required),
+            ToolChoice::Specific { r#type, function } => Self::Specific { r#type, function },
+        }
+    }
+}
+
+impl From<ToolChoiceRepr> for ToolChoice {
+    fn from(repr: ToolChoiceRepr) -> Self {
+        match repr {
+            ToolChoiceRepr::Mode(ToolChoiceMode::None) => Self::None,
+            ToolChoiceRepr::Mode(ToolChoiceMode::Auto) => Self::Auto,
+            ToolChoiceRepr::Mode(ToolChoiceMode::Required) => Self::Required,
+            ToolChoiceRepr::Specific { r#type, function } => Self::Specific { r#type, function },
+        }
+    }
+}
+
 /// Core chat request fields present in ALL OpenAI-format providers
 #[derive(Debug, Clone, Serialize, Deserialize, Builder)]
 #[builder(builder_type(vis = "pub"))]
@@ -257,3 +324,27 @@ impl Message {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tool_choice_serializes_to_wire_format() {
+        assert_eq!(
+            serde_json::to_value(ToolChoice::Auto).unwrap(),
+            serde_json::json!("auto")
+        );
+        assert_eq!(
+            serde_json::to_value(ToolChoice::Required).unwrap(),
+            serde_json::json!("required")
+        );
+        assert_eq!(
+            serde_json::to_value(ToolChoice::function("lookup")).unwrap(),
+            serde_json::json!({"type": "function", "function": {"name": "lookup"}})
+        );
+
+        let parsed: ToolChoice = serde_json::from_str("\"none\"").unwrap();
+        assert!(matches!(parsed, ToolChoice::None));
+    }
+}
@@ -1,9 +1,14 @@
 use crate::{
     error::{self, CommonRequestError},
+    retry::RetryPolicy,
     streaming::SseParser,
+    transport::Transport,
 };
 use async_stream::try_stream;
-use futures_util::stream::{self, BoxStream};
+use futures_util::{
+    StreamExt,
+    stream::{self, BoxStream},
+};
 use reqwest::{Method, RequestBuilder as ReqwestRequestBuilder, Response};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -83,6 +88,8 @@ pub struct RequestConfig {
     pub auth: Option<AuthMethod>,
     pub default_headers: HashMap<String, String>,
     pub user_agent: Option<String>,
+    pub retry_policy: RetryPolicy,
+    pub transport: Transport,
 }
 
 impl RequestConfig {
@@ -92,6 +99,8 @@ impl RequestConfig {
             auth: None,
             default_headers: HashMap::new(),
             user_agent: None,
+            retry_policy: RetryPolicy::default(),
+            transport: Transport::default(),
         }
     }
 
@@ -109,6 +118,19 @@ impl RequestConfig {
         self.user_agent = Some(user_agent.into());
         self
     }
+
+    #[must_use]
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Send requests through `transport`, e.g. to record or replay a cassette
+    #[must_use]
+    pub fn with_transport(mut self, transport: Transport) -> Self {
+        self.transport = transport;
+        self
+    }
 }
 
 /// Generic request builder that handles common HTTP patterns
@@ -240,7 +262,7 @@ impl RequestBuilder {
             req = req.json(&val);
         }
 
-        let res = req.send().await?;
+        let res = self.send(req).await?;
         self.handle_response(res).await
     }
 
@@ -250,21 +272,19 @@ impl RequestBuilder {
         endpoint: &Endpoint,
     ) -> Result<T, CommonRequestError> {
         let req = self.build_request(endpoint)?;
-        let res = req.send().await?;
+        let res = self.send(req).await?;
         self.handle_response(res).await
     }
 
     /// Execute a request and return unit type (for delete operations)
     pub async fn request_unit(&self, endpoint: &Endpoint) -> Result<(), CommonRequestError> {
         let req = self.build_request(endpoint)?;
-        let res = req.send().await?;
+        let res = self.send(req).await?;
 
         if res.status().is_success() {
             Ok(())
         } else {
-            let status = res.status();
-            let bytes = res.bytes().await?;
-            Err(error::parse_error_response(status, bytes))
+            Err(error::error_from_response(res).await)
         }
     }
 
@@ -274,17 +294,45 @@ impl RequestBuilder {
         endpoint: &Endpoint,
     ) -> Result<bytes::Bytes, CommonRequestError> {
         let req = self.build_request(endpoint)?;
-        let res = req.send().await?;
+        let res = self.send(req).await?;
 
         if res.status().is_success() {
             Ok(res.bytes().await?)
         } else {
-            let status = res.status();
-            let bytes = res.bytes().await?;
-            Err(error::parse_error_response(status, bytes))
+            Err(error::error_from_response(res).await)
         }
     }
 
+    /// Execute a request with JSON body and stream the raw response body (for
+    /// generated audio)
+    pub fn stream_bytes<B: Serialize>(
+        &self,
+        endpoint: &Endpoint,
+        body: Option<&B>,
+    ) -> BoxStream<'static, Result<bytes::Bytes, CommonRequestError>> {
+        let req = self.build_request(endpoint).and_then(|req| match body {
+            Some(body) => serde_json::to_value(body)
+                .map(|val| req.json(&val))
+                .map_err(|e| CommonRequestError::Json(e.to_string())),
+            None => Ok(req),
+        });
+        let config = self.config.clone();
+
+        Box::pin(try_stream! {
+            let response = config.transport.send(req?, &config).await?;
+
+            if response.status().is_success() {
+                let mut chunks = response.bytes_stream();
+
+                while let Some(chunk) = chunks.next().await {
+                    yield chunk?;
+                }
+            } else {
+                Err(error::error_from_response(response).await)?;
+            }
+        })
+    }
+
     /// Execute a streaming request
     pub fn stream<T, B>(
         &self,
@@ -311,6 +359,9 @@ impl RequestBuilder {
     }
 
     /// Execute a streaming request with fine-grained configuration.
+    ///
+    /// Failed attempts are retried only while establishing the stream; once the
+    /// first event has been delivered, errors are returned to the caller.
     pub fn stream_with_options<T>(
         &self,
         endpoint: &Endpoint,
@@ -365,12 +416,10 @@ impl RequestBuilder {
                 req = req.json(&payload);
             }
 
-            let response = req.send().await?;
-            let status = response.status();
+            let response = config.transport.send(req, &config).await?;
 
-            if !status.is_success() {
-                let bytes = response.bytes().await?;
-                Err(error::parse_error_response(status, bytes))?;
+            if !response.status().is_success() {
+                Err(error::error_from_response(response).await)?;
             } else {
                 let mut parser = SseParser::new(response);
 
@@ -381,29 +430,36 @@ impl RequestBuilder {
         })
     }
 
+    /// Send a request with this builder's transport and retry policy
+    ///
+    /// For requests the other methods can't express, such as resumable
+    /// uploads; build them with [`build_request`](Self::build_request).
+    pub async fn send(&self, req: ReqwestRequestBuilder) -> Result<Response, CommonRequestError> {
+        self.config.transport.send(req, &self.config).await
+    }
+
     /// Handle response and parse errors
     async fn handle_response<T: for<'de> Deserialize<'de>>(
         &self,
         res: Response,
     ) -> Result<T, CommonRequestError> {
         let status = res.status();
+        if !status.is_success() {
+            return Err(error::error_from_response(res).await);
+        }
         let bytes = res.bytes().await?;
 
-        if status.is_success() {
-            match serde_json::from_slice::<T>(&bytes) {
-                Ok(val) => Ok(val),
-                Err(e) => {
-                    let body_str = String::from_utf8_lossy(&bytes);
-                    Err(CommonRequestError::UnexpectedResponse(format!(
-                        "HTTP {} but failed to decode JSON: {}; body: {}",
-                        status.as_u16(),
-                        e,
-                        body_str
-                    )))
-                }
+        match serde_json::from_slice::<T>(&bytes) {
+            Ok(val) => Ok(val),
+            Err(e) => {
+                let body_str = String::from_utf8_lossy(&bytes);
+                Err(CommonRequestError::UnexpectedResponse(format!(
+                    "HTTP {} but failed to decode JSON: {}; body: {}",
+                    status.as_u16(),
+                    e,
+                    body_str
+                )))
             }
-        } else {
-            Err(error::parse_error_response(status, bytes))
         }
     }
 
@@ -416,7 +472,7 @@ impl RequestBuilder {
         let req = self.build_request_with_options(endpoint, false)?; // Don't add JSON content-type for multipart
         let req = req.multipart(form);
 
-        let res = req.send().await?;
+        let res = self.send(req).await?;
         self.handle_response(res).await
     }
 }
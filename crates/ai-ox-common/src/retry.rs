@@ -0,0 +1,313 @@
+use crate::error::{CommonRequestError, RateLimitInfo};
+use reqwest::{RequestBuilder as ReqwestRequestBuilder, Response, StatusCode};
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+/// Retry policy for HTTP requests
+///
+/// Any request is retried when the server refused it without processing it:
+/// on HTTP 429, on 503 with a `Retry-After`, and when the connection could not
+/// be established. Idempotent requests (GET, PUT, DELETE) are also retried on
+/// other 5xx responses and transport failures such as timeouts, where a POST
+/// that generates content might already have been processed. Retries use
+/// exponential backoff and jitter. When the server says how long to wait
+/// (`Retry-After` or a provider rate limit reset header), that delay is used
+/// instead, as long as it does not exceed `max_retry_after`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one
+    pub max_attempts: u32,
+    /// Backoff before the first retry
+    pub initial_backoff: Duration,
+    /// Upper bound for the computed backoff
+    pub max_backoff: Duration,
+    /// Randomize each backoff between half and the full value
+    pub jitter: bool,
+    /// Longest server-requested delay to honour; longer waits fail immediately
+    pub max_retry_after: Duration,
+}
+
+impl Default for RetryPolicy {
+    #[allow(
+        clippy::duration_suboptimal_units,
+        reason = "Duration::from_mins needs a newer toolchain"
+    )]
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+            max_retry_after: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that sends each request exactly once
+    #[must_use]
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Set the total number of attempts, including the first one
+    #[must_use]
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Set the initial and maximum backoff
+    #[must_use]
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Enable or disable jitter
+    #[must_use]
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set the longest server-requested delay to honour
+    #[must_use]
+    pub fn with_max_retry_after(mut self, max_retry_after: Duration) -> Self {
+        self.max_retry_after = max_retry_after;
+        self
+    }
+
+    /// Whether a response with this status should be retried
+    #[must_use]
+    pub fn is_retryable_status(status: StatusCode) -> bool {
+        status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+    }
+
+    /// Computed backoff before retry number `retry` (starting at 1)
+    #[must_use]
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff);
+        if self.jitter {
+            jittered(backoff)
+        } else {
+            backoff
+        }
+    }
+
+    /// Whether a response can be retried even if the request is not idempotent,
+    /// because the server refused it without processing it
+    fn is_refused(status: StatusCode, rate_limit: &RateLimitInfo) -> bool {
+        status == StatusCode::TOO_MANY_REQUESTS
+            || (status == StatusCode::SERVICE_UNAVAILABLE && rate_limit.retry_after.is_some())
+    }
+
+    /// Delay before retry number `retry`, or `None` if the server asked for a
+    /// longer wait than this policy honours
+    fn delay(&self, retry: u32, server_wait: Option<Duration>) -> Option<Duration> {
+        match server_wait {
+            Some(wait) if wait > self.max_retry_after => None,
+            Some(wait) => Some(wait),
+            None => Some(self.backoff(retry)),
+        }
+    }
+}
+
+/// Pick a random duration between half and the full `backoff`
+fn jittered(backoff: Duration) -> Duration {
+    let millis = u64::try_from(backoff.as_millis()).unwrap_or(u64::MAX);
+    let half = millis / 2;
+    let random = RandomState::new().build_hasher().finish();
+    Duration::from_millis(half + random % (millis - half + 1))
+}
+
+/// Whether a transport error is worth retrying
+fn is_transient(error: &reqwest::Error) -> bool {
+    if error.is_connect() || error.is_timeout() {
+        return true;
+    }
+    let mut source = std::error::Error::source(error);
+    while let Some(err) = source {
+        if let Some(io) = err.downcast_ref::<std::io::Error>() {
+            return matches!(
+                io.kind(),
+                std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::BrokenPipe
+                    | std::io::ErrorKind::UnexpectedEof
+            );
+        }
+        source = err.source();
+    }
+    false
+}
+
+/// Send a request, retrying according to `policy`
+///
+/// Only the sending of the request and the response status are retried; the
+/// returned response may still carry an error status once attempts run out.
+/// Requests whose body can't be cloned, such as multipart uploads, are sent once.
+pub(crate) async fn send_with_retry(
+    request: ReqwestRequestBuilder,
+    policy: &RetryPolicy,
+) -> Result<Response, CommonRequestError> {
+    let idempotent = request
+        .try_clone()
+        .and_then(|request| request.build().ok())
+        .is_some_and(|request| request.method().is_idempotent());
+    let mut attempt = 1;
+    loop {
+        let retry = attempt < policy.max_attempts;
+        let Some(current) = retry.then(|| request.try_clone()).flatten() else {
+            return Ok(request.send().await?);
+        };
+
+        let delay = match current.send().await {
+            Ok(response) if RetryPolicy::is_retryable_status(response.status()) => {
+                let rate_limit = RateLimitInfo::from_headers(response.headers());
+                if !idempotent && !RetryPolicy::is_refused(response.status(), &rate_limit) {
+                    return Ok(response);
+                }
+                match policy.delay(attempt, rate_limit.wait_time()) {
+                    Some(delay) => delay,
+                    None => return Ok(response),
+                }
+            }
+            Ok(response) => return Ok(response),
+            Err(e) if e.is_connect() || (idempotent && is_transient(&e)) => policy.backoff(attempt),
+            Err(e) => return Err(e.into()),
+        };
+
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request_builder::{Endpoint, HttpMethod, RequestBuilder, RequestConfig};
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+    };
+
+    /// Serve `responses` in order on a local port, one per connection
+    fn serve(responses: Vec<&'static str>) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind test server");
+        let url = format!("http://{}", listener.local_addr().expect("local addr"));
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        std::thread::spawn(move || {
+            for (stream, response) in listener.incoming().zip(responses) {
+                let mut stream = stream.expect("accept");
+                let mut buf = [0; 4096];
+                let _ = stream.read(&mut buf);
+                counter.fetch_add(1, Ordering::SeqCst);
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        (url, hits)
+    }
+
+    const UNAVAILABLE: &str = "HTTP/1.1 503 Service Unavailable\r\nretry-after-ms: 1\r\nconnection: close\r\ncontent-length: 0\r\n\r\n";
+    const INTERNAL: &str =
+        "HTTP/1.1 500 Internal Server Error\r\nconnection: close\r\ncontent-length: 0\r\n\r\n";
+    const RATE_LIMITED: &str = "HTTP/1.1 429 Too Many Requests\r\nretry-after-ms: 1\r\nconnection: close\r\ncontent-length: 0\r\n\r\n";
+    const TOO_MANY: &str = "HTTP/1.1 429 Too Many Requests\r\nretry-after: 3600\r\nx-ratelimit-remaining-requests: 0\r\nconnection: close\r\ncontent-length: 2\r\n\r\n{}";
+    const OK: &str = "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\nconnection: close\r\ncontent-length: 11\r\n\r\n{\"ok\":true}";
+
+    fn builder(url: &str, policy: RetryPolicy) -> RequestBuilder {
+        RequestBuilder::new(
+            reqwest::Client::new(),
+            RequestConfig::new(url).with_retry_policy(policy),
+        )
+    }
+
+    #[test]
+    fn test_backoff_is_exponential_and_capped() {
+        let policy = RetryPolicy::default()
+            .with_backoff(Duration::from_millis(100), Duration::from_millis(350))
+            .with_jitter(false);
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(350));
+
+        let jittered = RetryPolicy::default().with_jitter(true).backoff(1);
+        assert!(jittered >= Duration::from_millis(250) && jittered <= Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_retries_server_errors_until_success() {
+        let (url, hits) = serve(vec![UNAVAILABLE, UNAVAILABLE, OK]);
+        let endpoint = Endpoint::new("test", HttpMethod::Get);
+
+        let value: serde_json::Value = builder(&url, RetryPolicy::default())
+            .request(&endpoint)
+            .await
+            .expect("request succeeds after retries");
+
+        assert_eq!(value["ok"], true);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_post_is_not_resent_after_server_error() {
+        let (url, hits) = serve(vec![INTERNAL, OK]);
+        let endpoint = Endpoint::new("test", HttpMethod::Post);
+
+        let result: Result<serde_json::Value, _> = builder(&url, RetryPolicy::default())
+            .request_json(&endpoint, Some(&serde_json::json!({})))
+            .await;
+
+        assert!(result.is_err(), "expected the 500 to be returned");
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_post_is_resent_after_refusal() {
+        let (url, hits) = serve(vec![RATE_LIMITED, UNAVAILABLE, OK]);
+        let endpoint = Endpoint::new("test", HttpMethod::Post);
+
+        let value: serde_json::Value = builder(&url, RetryPolicy::default())
+            .request_json(&endpoint, Some(&serde_json::json!({})))
+            .await
+            .expect("request succeeds after the server stops refusing it");
+
+        assert_eq!(value["ok"], true);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_long_retry_after_returns_rate_limit() {
+        let (url, hits) = serve(vec![TOO_MANY, OK]);
+        let endpoint = Endpoint::new("test", HttpMethod::Get);
+
+        let result: Result<serde_json::Value, _> = builder(&url, RetryPolicy::default())
+            .request(&endpoint)
+            .await;
+
+        let Err(CommonRequestError::RateLimit(info)) = result else {
+            panic!("expected rate limit error, got {result:?}");
+        };
+        assert_eq!(info.retry_after.map(|wait| wait.as_secs()), Some(3600));
+        assert_eq!(info.remaining_requests, Some(0));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,647 @@
+use crate::{
+    error::CommonRequestError,
+    request_builder::{AuthMethod, RequestConfig},
+    retry::send_with_retry,
+};
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use futures_util::{StreamExt, stream};
+use reqwest::{Request, RequestBuilder as ReqwestRequestBuilder, Response};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+/// Placeholder written in place of scrubbed credentials
+const REDACTED: &str = "[REDACTED]";
+
+/// Headers that carry credentials and are never written to a cassette
+const SECRET_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "x-api-key",
+    "x-goog-api-key",
+    "api-key",
+    "cookie",
+    "set-cookie",
+];
+
+/// Query parameters that carry credentials and are never written to a cassette
+const SECRET_QUERY_PARAMS: &[&str] = &["key", "api_key", "access_token"];
+
+/// Environment variable read by [`Transport::from_env`]
+const TRANSPORT_ENV: &str = "AOX_TRANSPORT";
+
+/// How a [`RequestBuilder`](crate::RequestBuilder) exchanges requests with the API
+///
+/// `Live` sends every request over the network. `Record` does too, but saves
+/// each request/response pair to a cassette file with credentials scrubbed;
+/// `Replay` serves the recorded responses without network access, so the same
+/// tests can run against live or recorded traffic. Streaming responses are
+/// recorded with their original chunk boundaries.
+#[derive(Debug, Clone, Default)]
+pub enum Transport {
+    /// Send requests over the network
+    #[default]
+    Live,
+    /// Send requests over the network and record them to a cassette
+    Record(Arc<Cassette>),
+    /// Serve responses recorded in a cassette
+    Replay(Arc<Cassette>),
+}
+
+impl Transport {
+    /// Record traffic to a cassette at `path`, replacing any existing file
+    #[must_use]
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self::Record(Arc::new(Cassette::new(path)))
+    }
+
+    /// Replay traffic from the cassette at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cassette can't be read or parsed.
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self, CommonRequestError> {
+        Ok(Self::Replay(Arc::new(Cassette::load(path)?)))
+    }
+
+    /// Select the transport for the cassette at `path` from `AOX_TRANSPORT`
+    ///
+    /// `live` sends requests over the network, `record` records them to `path`,
+    /// and any other value, including none, replays `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if replaying and the cassette can't be read or parsed.
+    pub fn from_env(path: impl Into<PathBuf>) -> Result<Self, CommonRequestError> {
+        match std::env::var(TRANSPORT_ENV).as_deref() {
+            Ok("live") => Ok(Self::Live),
+            Ok("record") => Ok(Self::record(path)),
+            _ => Self::replay(path),
+        }
+    }
+
+    /// Send a request through this transport
+    ///
+    /// In record mode the response body is read in full before it is returned.
+    pub(crate) async fn send(
+        &self,
+        request: ReqwestRequestBuilder,
+        config: &RequestConfig,
+    ) -> Result<Response, CommonRequestError> {
+        match self {
+            Self::Live => send_with_retry(request, &config.retry_policy).await,
+            Self::Record(cassette) => {
+                let (client, request) = request.build_split();
+                let request = request?;
+                let recorded_request = RecordedRequest::new(&request, config);
+                let request = ReqwestRequestBuilder::from_parts(client, request);
+
+                let response = send_with_retry(request, &config.retry_policy).await?;
+                let recorded_response = RecordedResponse::read(response).await?;
+                let response = recorded_response.to_response()?;
+
+                cassette.push(Interaction {
+                    request: recorded_request,
+                    response: recorded_response.scrubbed(config),
+                })?;
+                Ok(response)
+            }
+            Self::Replay(cassette) => {
+                let request = RecordedRequest::new(&request.build()?, config);
+                cassette.take(&request)?.to_response()
+            }
+        }
+    }
+}
+
+/// A file of recorded request/response pairs
+///
+/// Interactions are replayed in the order they were recorded among those with
+/// the same method, URL and body, and each is served once. Recordings without
+/// a body, such as multipart uploads, match any body.
+#[derive(Debug)]
+pub struct Cassette {
+    /// Location of the cassette file
+    path: PathBuf,
+    /// Recorded interactions and which of them have been replayed
+    state: Mutex<CassetteState>,
+}
+
+/// Mutable contents of a [`Cassette`]
+#[derive(Debug, Default)]
+struct CassetteState {
+    /// Recorded interactions in order
+    interactions: Vec<Interaction>,
+    /// Whether the interaction at the same index has been replayed
+    replayed: Vec<bool>,
+}
+
+/// On-disk format of a cassette
+#[derive(Debug, Serialize, Deserialize)]
+struct CassetteFile {
+    /// Recorded interactions in order
+    interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    /// Create an empty cassette that will be written to `path`
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            state: Mutex::default(),
+        }
+    }
+
+    /// Load a recorded cassette from `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or parsed.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, CommonRequestError> {
+        let path = path.into();
+        let contents = std::fs::read(&path).map_err(|e| {
+            CommonRequestError::Io(format!("Failed to read cassette {}: {e}", path.display()))
+        })?;
+        let file: CassetteFile = serde_json::from_slice(&contents)?;
+
+        Ok(Self {
+            path,
+            state: Mutex::new(CassetteState {
+                replayed: vec![false; file.interactions.len()],
+                interactions: file.interactions,
+            }),
+        })
+    }
+
+    /// Location of the cassette file
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of recorded interactions
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().interactions.len()
+    }
+
+    /// Whether no interactions have been recorded
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Lock the cassette state, recovering it if another thread panicked
+    fn lock(&self) -> std::sync::MutexGuard<'_, CassetteState> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Append an interaction and rewrite the cassette file
+    fn push(&self, interaction: Interaction) -> Result<(), CommonRequestError> {
+        let mut state = self.lock();
+        state.interactions.push(interaction);
+        state.replayed.push(false);
+
+        let file = CassetteFile {
+            interactions: state.interactions.clone(),
+        };
+        let json = serde_json::to_vec_pretty(&file)?;
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, json).map_err(|e| {
+            CommonRequestError::Io(format!(
+                "Failed to write cassette {}: {e}",
+                self.path.display()
+            ))
+        })
+    }
+
+    /// Take the next unreplayed response recorded for `request`
+    fn take(&self, request: &RecordedRequest) -> Result<RecordedResponse, CommonRequestError> {
+        let mut state = self.lock();
+        let CassetteState {
+            interactions,
+            replayed,
+        } = &mut *state;
+
+        let index = interactions
+            .iter()
+            .zip(replayed.iter())
+            .position(|(interaction, replayed)| !replayed && interaction.request.matches(request))
+            .ok_or_else(|| {
+                CommonRequestError::UnexpectedResponse(format!(
+                    "No recorded response for {} {} with body {} in cassette {}",
+                    request.method,
+                    request.url,
+                    request.body.as_ref().unwrap_or(&Value::Null),
+                    self.path.display()
+                ))
+            })?;
+
+        replayed[index] = true;
+        Ok(interactions[index].response.clone())
+    }
+}
+
+/// A recorded request and the response it received
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    /// The request as sent, with credentials scrubbed
+    request: RecordedRequest,
+    /// The response as received
+    response: RecordedResponse,
+}
+
+/// A request with credentials scrubbed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedRequest {
+    /// HTTP method
+    method: String,
+    /// Full URL, with credential query parameters redacted
+    url: String,
+    /// Request headers, with credential headers redacted
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    headers: BTreeMap<String, String>,
+    /// JSON body, or the body as a string if it isn't JSON
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<Value>,
+}
+
+impl RecordedRequest {
+    /// Capture `request`, scrubbing the credentials configured in `config`
+    fn new(request: &Request, config: &RequestConfig) -> Self {
+        let mut url = request.url().clone();
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(name, value)| {
+                let value = if is_secret_param(&name, config) {
+                    REDACTED.to_string()
+                } else {
+                    value.into_owned()
+                };
+                (name.into_owned(), value)
+            })
+            .collect();
+        if !pairs.is_empty() {
+            url.query_pairs_mut().clear().extend_pairs(pairs);
+        }
+
+        let headers = request
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                let value = if is_secret_header(name.as_str(), config) {
+                    REDACTED.to_string()
+                } else {
+                    String::from_utf8_lossy(value.as_bytes()).into_owned()
+                };
+                (name.as_str().to_string(), value)
+            })
+            .collect();
+
+        let body = request
+            .body()
+            .and_then(reqwest::Body::as_bytes)
+            .map(|bytes| {
+                let mut text = String::from_utf8_lossy(bytes).into_owned();
+                if let Some(secret) = auth_secret(config).filter(|secret| !secret.is_empty()) {
+                    text = text.replace(secret, REDACTED);
+                }
+                serde_json::from_str(&text).unwrap_or(Value::String(text))
+            });
+
+        Self {
+            method: request.method().to_string(),
+            url: url.to_string(),
+            headers,
+            body,
+        }
+    }
+
+    /// Whether a replayed request should be served this recording's response
+    fn matches(&self, other: &RecordedRequest) -> bool {
+        self.method == other.method
+            && self.url == other.url
+            && (self.body.is_none() || self.body == other.body)
+    }
+}
+
+/// A response with its body split into the chunks it arrived in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedResponse {
+    /// HTTP status code
+    status: u16,
+    /// Response headers
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    headers: BTreeMap<String, String>,
+    /// Body chunks in the order they were received
+    #[serde(default)]
+    chunks: Vec<Chunk>,
+}
+
+/// A body chunk, stored as text when it is valid UTF-8
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum Chunk {
+    /// UTF-8 text
+    Text(String),
+    /// Binary data
+    Binary {
+        /// Base64-encoded bytes
+        base64: String,
+    },
+}
+
+impl Chunk {
+    /// Store `bytes` as text if possible
+    fn new(bytes: &[u8]) -> Self {
+        match std::str::from_utf8(bytes) {
+            Ok(text) => Self::Text(text.to_string()),
+            Err(_) => Self::Binary {
+                base64: BASE64.encode(bytes),
+            },
+        }
+    }
+
+    /// The raw bytes of this chunk
+    fn to_bytes(&self) -> Result<bytes::Bytes, CommonRequestError> {
+        match self {
+            Self::Text(text) => Ok(bytes::Bytes::from(text.clone())),
+            Self::Binary { base64 } => BASE64
+                .decode(base64)
+                .map(bytes::Bytes::from)
+                .map_err(|e| CommonRequestError::InvalidEventData(e.to_string())),
+        }
+    }
+}
+
+impl RecordedResponse {
+    /// Read `response` to the end, keeping its chunk boundaries
+    async fn read(response: Response) -> Result<Self, CommonRequestError> {
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                let value = if name.as_str() == "set-cookie" {
+                    REDACTED.to_string()
+                } else {
+                    String::from_utf8_lossy(value.as_bytes()).into_owned()
+                };
+                (name.as_str().to_string(), value)
+            })
+            .collect();
+
+        let mut chunks = Vec::new();
+        let mut body = response.bytes_stream();
+        while let Some(chunk) = body.next().await {
+            chunks.push(Chunk::new(&chunk?));
+        }
+
+        Ok(Self {
+            status,
+            headers,
+            chunks,
+        })
+    }
+
+    /// Copy of this response with the credential configured in `config`
+    /// redacted from its text chunks
+    ///
+    /// Servers that echo the request, such as error messages quoting an
+    /// invalid key, would otherwise leak it into the cassette.
+    fn scrubbed(&self, config: &RequestConfig) -> Self {
+        let Some(secret) = auth_secret(config).filter(|secret| !secret.is_empty()) else {
+            return self.clone();
+        };
+        let chunks = self
+            .chunks
+            .iter()
+            .map(|chunk| match chunk {
+                Chunk::Text(text) => Chunk::Text(text.replace(secret, REDACTED)),
+                Chunk::Binary { .. } => chunk.clone(),
+            })
+            .collect();
+
+        Self {
+            status: self.status,
+            headers: self.headers.clone(),
+            chunks,
+        }
+    }
+
+    /// Rebuild a response that yields the recorded chunks
+    fn to_response(&self) -> Result<Response, CommonRequestError> {
+        let chunks = self
+            .chunks
+            .iter()
+            .map(Chunk::to_bytes)
+            .collect::<Result<Vec<_>, _>>()?;
+        let body = reqwest::Body::wrap_stream(stream::iter(
+            chunks.into_iter().map(Ok::<_, std::io::Error>),
+        ));
+
+        let mut response = http::Response::builder().status(self.status);
+        for (name, value) in &self.headers {
+            response = response.header(name, value);
+        }
+        let response = response
+            .body(body)
+            .map_err(|e| CommonRequestError::UnexpectedResponse(e.to_string()))?;
+        Ok(Response::from(response))
+    }
+}
+
+/// Whether a header carries credentials
+fn is_secret_header(name: &str, config: &RequestConfig) -> bool {
+    let auth_header = match &config.auth {
+        Some(AuthMethod::ApiKey { header_name, .. } | AuthMethod::OAuth { header_name, .. }) => {
+            Some(header_name.as_str())
+        }
+        _ => None,
+    };
+    SECRET_HEADERS
+        .iter()
+        .copied()
+        .chain(auth_header)
+        .any(|secret| secret.eq_ignore_ascii_case(name))
+}
+
+/// Whether a query parameter carries credentials
+fn is_secret_param(name: &str, config: &RequestConfig) -> bool {
+    let auth_param = match &config.auth {
+        Some(AuthMethod::QueryParam(param, _)) => Some(param.as_str()),
+        _ => None,
+    };
+    SECRET_QUERY_PARAMS
+        .iter()
+        .copied()
+        .chain(auth_param)
+        .any(|secret| secret == name)
+}
+
+/// The credential configured for requests, if any
+fn auth_secret(config: &RequestConfig) -> Option<&str> {
+    match config.auth.as_ref()? {
+        AuthMethod::Bearer(token) | AuthMethod::OAuth { token, .. } => Some(token),
+        AuthMethod::ApiKey { key, .. } => Some(key),
+        AuthMethod::QueryParam(_, value) => Some(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request_builder::{Endpoint, HttpMethod, RequestBuilder};
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+    };
+
+    /// Serve a chunked SSE response on a local port, once
+    fn serve_sse(events: &'static [&'static str]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind test server");
+        let url = format!("http://{}", listener.local_addr().expect("local addr"));
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("accept");
+            let mut buf = [0; 4096];
+            let _ = stream.read(&mut buf);
+            let _ = stream.write_all(
+                b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\nset-cookie: session=1\r\ntransfer-encoding: chunked\r\nconnection: close\r\n\r\n",
+            );
+            for event in events {
+                let _ = write!(stream, "{:x}\r\n{event}\r\n", event.len());
+                let _ = stream.flush();
+                std::thread::sleep(std::time::Duration::from_millis(20));
+            }
+            let _ = stream.write_all(b"0\r\n\r\n");
+        });
+        url
+    }
+
+    fn cassette_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("ai-ox-common-{}", std::process::id()))
+            .join(format!("{name}.json"))
+    }
+
+    fn builder(url: &str, transport: Transport) -> RequestBuilder {
+        RequestBuilder::new(
+            reqwest::Client::new(),
+            RequestConfig::new(url)
+                .with_auth(AuthMethod::QueryParam(
+                    "key".to_string(),
+                    "sk-secret".to_string(),
+                ))
+                .with_header("x-api-key", "sk-secret")
+                .with_transport(transport),
+        )
+    }
+
+    async fn collect(builder: &RequestBuilder) -> Vec<Value> {
+        let endpoint = Endpoint::new("v1/stream", HttpMethod::Post);
+        let body = serde_json::json!({"prompt": "hi", "echo": "sk-secret"});
+        let events: Vec<Result<Value, _>> = builder
+            .stream(&endpoint, Some(&body))
+            .collect::<Vec<_>>()
+            .await;
+        events
+            .into_iter()
+            .map(|event| event.expect("stream event"))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay_stream() {
+        static EVENTS: &[&str] = &["data: {\"n\":1}\n\n", "data: {\"n\"", ":2}\n\n"];
+        let url = serve_sse(EVENTS);
+        let path = cassette_path("stream");
+
+        let recorded = collect(&builder(&url, Transport::record(&path))).await;
+        assert_eq!(recorded.len(), 2);
+
+        let cassette = std::fs::read_to_string(&path).expect("cassette written");
+        assert!(!cassette.contains("sk-secret"), "{cassette}");
+        assert!(!cassette.contains("session=1"), "{cassette}");
+
+        // The server only answers once, so these events come from the cassette.
+        let loaded = Arc::new(Cassette::load(&path).expect("load cassette"));
+        assert_eq!(loaded.len(), 1);
+        let transport = Transport::Replay(loaded.clone());
+        let replayed = collect(&builder(&url, transport.clone())).await;
+        assert_eq!(replayed, recorded);
+
+        let chunks = loaded.lock().interactions[0].response.chunks.len();
+        assert_eq!(chunks, EVENTS.len());
+
+        // Each recorded interaction is served once.
+        let endpoint = Endpoint::new("v1/stream", HttpMethod::Post);
+        let body = serde_json::json!({"prompt": "hi", "echo": "sk-secret"});
+        let again: Option<Result<Value, _>> = builder(&url, transport)
+            .stream(&endpoint, Some(&body))
+            .next()
+            .await;
+        assert!(matches!(
+            again,
+            Some(Err(CommonRequestError::UnexpectedResponse(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_replay_matches_request_body() {
+        static EVENTS: &[&str] = &["data: {\"n\":1}\n\n"];
+        let url = serve_sse(EVENTS);
+        let path = cassette_path("body-match");
+        collect(&builder(&url, Transport::record(&path))).await;
+
+        let transport = Transport::replay(&path).expect("load cassette");
+        let endpoint = Endpoint::new("v1/stream", HttpMethod::Post);
+        let body = serde_json::json!({"prompt": "bye", "echo": "sk-secret"});
+        let other: Option<Result<Value, _>> = builder(&url, transport.clone())
+            .stream(&endpoint, Some(&body))
+            .next()
+            .await;
+        assert!(matches!(
+            other,
+            Some(Err(CommonRequestError::UnexpectedResponse(_)))
+        ));
+
+        assert_eq!(collect(&builder(&url, transport)).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_record_scrubs_secrets_from_response_bodies() {
+        static BODY: &[&str] = &["{\"error\":\"invalid key sk-secret\"}"];
+        let url = serve_sse(BODY);
+        let path = cassette_path("body");
+
+        let endpoint = Endpoint::new("v1/echo", HttpMethod::Get);
+        let live: Value = builder(&url, Transport::record(&path))
+            .request(&endpoint)
+            .await
+            .expect("recorded response");
+        assert_eq!(live["error"], "invalid key sk-secret");
+
+        let cassette = std::fs::read_to_string(&path).expect("cassette written");
+        assert!(!cassette.contains("sk-secret"), "{cassette}");
+        assert!(cassette.contains("invalid key [REDACTED]"), "{cassette}");
+    }
+
+    #[test]
+    fn test_binary_chunks_round_trip() {
+        let chunk = Chunk::new(&[0xff, 0x00, 0x10]);
+        assert!(matches!(chunk, Chunk::Binary { .. }));
+        assert_eq!(
+            chunk.to_bytes().expect("decode").as_ref(),
+            &[0xff, 0x00, 0x10]
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use std::sync::Arc;
+
+use bon::Builder;
+
+use super::{
+    Agent,
+    error::AgentError,
+    trim::{self, DropOldestTurns, TrimStrategy},
+};
+use crate::{ModelResponse, content::message::Message, usage::Usage};
+
+/// A stateful conversation with an [`Agent`].
+///
+/// Unlike [`Agent::run`], which forgets everything once it returns, a
+/// `Conversation` owns the message history: every user, assistant and tool
+/// message is kept for the next call, and the usage of every model request is
+/// accumulated. When a token budget is set, the history is trimmed with the
+/// configured [`TrimStrategy`] whenever it exceeds the budget, both before a
+/// request and after the messages of the agent's tool loop are appended.
+#[derive(Debug, Clone, Builder)]
+pub struct Conversation {
+    /// The agent that answers each message.
+    agent: Agent,
+    /// Messages exchanged so far.
+    #[builder(default)]
+    messages: Vec<Message>,
+    /// Usage accumulated across all requests in this conversation.
+    #[builder(skip)]
+    usage: Usage,
+    /// Estimated token count above which the history is trimmed.
+    token_budget: Option<u64>,
+    /// How the history is trimmed once it exceeds the budget.
+    #[builder(
+        with = |strategy: impl TrimStrategy| Arc::new(strategy) as Arc<dyn TrimStrategy>,
+        default = Arc::new(DropOldestTurns),
+    )]
+    trim_strategy: Arc<dyn TrimStrategy>,
+}
+
+impl Conversation {
+    /// Returns the agent driving this conversation.
+    pub fn agent(&self) -> &Agent {
+        &self.agent
+    }
+
+    /// Returns the message history.
+    pub fn messages(&self) -> &[Message] {
+        &self.messages
+    }
+
+    /// Returns the usage accumulated so far.
+    pub fn usage(&self) -> &Usage {
+        &self.usage
+    }
+
+    /// Returns the token budget if set.
+    pub fn token_budget(&self) -> Option<u64> {
+        self.token_budget
+    }
+
+    /// Sets the token budget used for subsequent requests.
+    pub fn set_token_budget(&mut self, budget: Option<u64>) {
+        self.token_budget = budget;
+    }
+
+    /// Returns the estimated token count of the current history.
+    pub fn estimated_tokens(&self) -> u64 {
+        trim::estimate_tokens(&self.messages)
+    }
+
+    /// Appends a message to the history without sending it.
+    pub fn push(&mut self, message: impl Into<Message>) {
+        self.messages.push(message.into());
+    }
+
+    /// Clears the history and the accumulated usage.
+    pub fn clear(&mut self) {
+        self.messages.clear();
+        self.usage = Usage::new();
+    }
+
+    /// Sends messages and runs the agent's tool loop on the full history.
+    ///
+    /// On success the new messages, every intermediate assistant and tool
+    /// message, and the final response are appended to the history. On error
+    /// the history is left unchanged, although usage already incurred is kept.
+    pub async fn send(
+        &mut self,
+        messages: impl IntoIterator<Item = impl Into<Message>> + Send,
+    ) -> Result<ModelResponse, AgentError> {
+        let mut history = self.messages.clone();
+        history.extend(messages.into_iter().map(Into::into));
+        history = self.trim(history).await?;
+
+        let response = self
+            .agent
+            .run_conversation(&mut history, &mut self.usage, &mut 0.0)
+            .await?;
+        self.messages = self.trim(history).await?;
+        Ok(response)
+    }
+
+    /// Trims `history` with the configured strategy if it exceeds the budget.
+    async fn trim(&mut self, history: Vec<Message>) -> Result<Vec<Message>, AgentError> {
+        match self.token_budget {
+            Some(budget) if trim::estimate_tokens(&history) > budget => {
+                self.trim_strategy
+                    .trim(history, budget, self.agent.model.as_ref(), &mut self.usage)
+                    .await
+            }
+            _ => Ok(history),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        agent::trim::Summarize,
+        content::{Part, message::MessageRole},
+        model::mock::{MockModel, MockReply},
+        usage::Modality,
+    };
+
+    /// A model answering `replies` requests with a fixed text, each billed as
+    /// one request of 10 input tokens.
+    fn reply_model(replies: usize) -> MockModel {
+        (0..replies).fold(MockModel::new("reply"), |model, _| {
+            let mut usage = Usage::new();
+            usage.requests = 1;
+            usage.input_tokens_by_modality.insert(Modality::Text, 10);
+            model.with_reply(MockReply::Response(Box::new(ModelResponse {
+                message: Message::new(MessageRole::Assistant, vec![Part::text("reply")]),
+                model_name: "reply".to_string(),
+                vendor_name: "mock".to_string(),
+                usage,
+            })))
+        })
+    }
+
+    /// The messages of every request `model` received.
+    fn sent(model: &MockModel) -> Vec<Vec<Message>> {
+        model
+            .requests()
+            .into_iter()
+            .map(|request| request.messages)
+            .collect()
+    }
+
+    fn user(text: &str) -> Message {
+        Message::new(MessageRole::User, vec![Part::text(text)])
+    }
+
+    #[tokio::test]
+    async fn test_history_and_usage_accumulate() {
+        let model = reply_model(2);
+        let mut conversation = Agent::model(model.clone()).build().conversation();
+
+        conversation.send([user("one")]).await.unwrap();
+        conversation.send([user("two")]).await.unwrap();
+
+        assert_eq!(conversation.messages().len(), 4);
+        assert_eq!(conversation.usage().requests, 2);
+        assert_eq!(conversation.usage().input_tokens(), 20);
+        assert_eq!(sent(&model)[1].len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_budget_drops_oldest_turns() {
+        let model = reply_model(2);
+        let mut conversation = Conversation::builder()
+            .agent(Agent::model(model.clone()).build())
+            .token_budget(10)
+            .build();
+
+        conversation.send([user("one")]).await.unwrap();
+        conversation.send([user("two")]).await.unwrap();
+
+        // The 16-token history exceeds the budget, so only "two" is sent.
+        assert_eq!(sent(&model)[1], vec![user("two")]);
+        assert_eq!(conversation.messages().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_budget_applies_after_the_run() {
+        let model = reply_model(2);
+        let mut conversation = Conversation::builder()
+            .agent(Agent::model(model.clone()).build())
+            .token_budget(16)
+            .build();
+
+        conversation.send([user("one")]).await.unwrap();
+        conversation.send([user("two")]).await.unwrap();
+
+        // Each exchange fits on its own, but the first is dropped as soon as
+        // the second one's reply is appended.
+        assert_eq!(sent(&model)[1].len(), 3);
+        assert_eq!(conversation.messages()[0], user("two"));
+        assert_eq!(conversation.messages().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_summarize_replaces_older_turns() {
+        let model = reply_model(3);
+        let mut conversation = Conversation::builder()
+            .agent(Agent::model(model.clone()).build())
+            .token_budget(26)
+            .trim_strategy(Summarize::new(1))
+            .build();
+
+        // Long enough that the history exceeds the budget once "two" is
+        // added, but fits again after the summary and the reply.
+        conversation.send([user(&"one ".repeat(15))]).await.unwrap();
+        conversation.send([user("two")]).await.unwrap();
+
+        let requests = sent(&model);
+        assert_eq!(requests.len(), 3);
+        assert_eq!(
+            requests[2][0].content[0],
+            Part::text("Summary of the earlier conversation:\nreply")
+        );
+        assert_eq!(requests[2][1], user("two"));
+        assert_eq!(conversation.usage().requests, 3);
+    }
+}
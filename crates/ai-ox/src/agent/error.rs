@@ -28,6 +28,17 @@ pub enum AgentError {
         expected_schema: String,
     },
 
+    /// The model's structured response does not conform to the expected schema.
+    #[error(
+        "Response does not match expected schema: {}. Response text: \"{response_text}\". Expected schema: {expected_schema}",
+        errors.join("; ")
+    )]
+    SchemaValidationFailed {
+        errors: Vec<String>,
+        response_text: String,
+        expected_schema: String,
+    },
+
     /// Schema generation failed for structured output.
     #[error("Schema generation failed: {0}")]
     SchemaGenerationFailed(String),
@@ -44,6 +55,10 @@ pub enum AgentError {
     #[error("Method not yet implemented")]
     NotImplemented,
 
+    /// A run cost more than the agent's spend limit.
+    #[error("Spend limit of {limit} exceeded: {spent} spent")]
+    SpendLimitExceeded { limit: f64, spent: f64 },
+
     /// Model generated tool calls but no tools are available.
     #[error("Model generated tool calls but no tools are available")]
     ToolCallsWithoutTools,
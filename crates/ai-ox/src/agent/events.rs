@@ -12,6 +12,12 @@ pub enum AgentEvent {
     /// Agent received a streaming event from the model.
     StreamEvent(StreamEvent),
 
+    /// The tool interceptor is being asked to review a tool call.
+    ToolApprovalRequested(ToolUse),
+
+    /// The tool interceptor denied a tool call; `reason` was reported to the model.
+    ToolDenied { call: ToolUse, reason: String },
+
     /// Agent is executing a tool call.
     ToolExecution(ToolUse),
 
@@ -31,6 +37,8 @@ impl AgentEvent {
         match self {
             AgentEvent::Started => "Started",
             AgentEvent::StreamEvent(_) => "StreamEvent",
+            AgentEvent::ToolApprovalRequested(_) => "ToolApprovalRequested",
+            AgentEvent::ToolDenied { .. } => "ToolDenied",
             AgentEvent::ToolExecution(_) => "ToolExecution",
             AgentEvent::ToolResult(_) => "ToolResult",
             AgentEvent::Completed(_) => "Completed",
@@ -0,0 +1,210 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{content::Part, tool::ToolUse};
+
+/// The outcome of reviewing a tool call before it is executed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToolDecision {
+    /// Execute the tool call unchanged.
+    Approve,
+    /// Skip the tool call and report the given message back to the model
+    /// as the tool's result.
+    Deny(String),
+    /// Execute the provided tool call instead of the original one.
+    Rewrite(ToolUse),
+}
+
+impl ToolDecision {
+    /// Creates a denial with a message the model will see.
+    pub fn deny(message: impl Into<String>) -> Self {
+        Self::Deny(message.into())
+    }
+}
+
+/// Hooks invoked by [`Agent`](super::Agent) around every tool call.
+///
+/// Interceptors are the place to implement approval flows, policy checks,
+/// argument sanitising and result redaction without re-implementing the
+/// agent loop. The same interceptor is applied by `run`, `execute_typed`
+/// and `stream`.
+#[async_trait]
+pub trait ToolInterceptor: Send + Sync + 'static {
+    /// Called before a tool call is executed.
+    ///
+    /// Calls are reviewed one at a time in the order the model requested them,
+    /// so interactive approval prompts never overlap.
+    async fn before_call(&self, call: &ToolUse) -> ToolDecision {
+        let _ = call;
+        ToolDecision::Approve
+    }
+
+    /// Called with the `Part::ToolResult` of an executed tool call, including
+    /// error results produced by the agent's tool error strategy.
+    ///
+    /// The returned part replaces the original result in the conversation.
+    async fn after_call(&self, call: &ToolUse, result: Part) -> Part {
+        let _ = call;
+        result
+    }
+}
+
+#[async_trait]
+impl<T: ToolInterceptor + ?Sized> ToolInterceptor for Arc<T> {
+    async fn before_call(&self, call: &ToolUse) -> ToolDecision {
+        self.as_ref().before_call(call).await
+    }
+
+    async fn after_call(&self, call: &ToolUse, result: Part) -> Part {
+        self.as_ref().after_call(call, result).await
+    }
+}
+
+impl std::fmt::Debug for dyn ToolInterceptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ToolInterceptor")
+    }
+}
+
+/// Builds the tool result reported to the model for a denied call.
+pub(crate) fn denied_tool_result(call: &ToolUse, message: &str) -> Part {
+    Part::tool_result(&call.id, &call.name, vec![Part::text(message)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        agent::Agent,
+        content::message::{Message, MessageRole},
+        model::mock::{MockModel, MockReply},
+        tool::{FunctionMetadata, Tool, ToolBox, ToolError},
+    };
+    use futures_util::{FutureExt, StreamExt, future::BoxFuture};
+    use serde_json::json;
+
+    struct EchoTool;
+
+    impl ToolBox for EchoTool {
+        fn tools(&self) -> Vec<Tool> {
+            vec![Tool::FunctionDeclarations(vec![FunctionMetadata {
+                name: "echo".to_string(),
+                description: None,
+                parameters: json!({"type": "object"}),
+            }])]
+        }
+
+        fn invoke(&self, call: ToolUse) -> BoxFuture<'_, Result<Part, ToolError>> {
+            async move {
+                let text = call.args["text"].as_str().unwrap_or_default().to_string();
+                Ok(Part::tool_result(
+                    call.id,
+                    call.name,
+                    vec![Part::text(text)],
+                ))
+            }
+            .boxed()
+        }
+    }
+
+    struct Fixed(ToolDecision);
+
+    #[async_trait]
+    impl ToolInterceptor for Fixed {
+        async fn before_call(&self, _call: &ToolUse) -> ToolDecision {
+            self.0.clone()
+        }
+
+        async fn after_call(&self, call: &ToolUse, result: Part) -> Part {
+            match result {
+                Part::ToolResult { parts, .. } if parts == vec![Part::text("secret")] => {
+                    Part::tool_result(&call.id, &call.name, vec![Part::text("[redacted]")])
+                }
+                other => other,
+            }
+        }
+    }
+
+    /// A model that requests `echo` once, then answers.
+    fn echo_once() -> MockModel {
+        MockModel::new("echo-once")
+            .with_reply(MockReply::tool_call(
+                "call-1",
+                "echo",
+                json!({"text": "secret"}),
+            ))
+            .with_reply(MockReply::text("done"))
+    }
+
+    async fn tool_result_seen_by_model(decision: ToolDecision) -> Part {
+        let model = echo_once();
+        let agent = Agent::model(model.clone())
+            .tools(EchoTool)
+            .tool_interceptor(Fixed(decision))
+            .build();
+
+        let response = agent
+            .run(vec![Message::new(
+                MessageRole::User,
+                vec![Part::text("hi")],
+            )])
+            .await
+            .unwrap();
+        assert_eq!(response.to_string().as_deref(), Some("done"));
+
+        let seen = model.last_request().unwrap().messages;
+        seen.last().unwrap().content[0].clone()
+    }
+
+    #[tokio::test]
+    async fn test_approved_result_is_redacted() {
+        let part = tool_result_seen_by_model(ToolDecision::Approve).await;
+        assert_eq!(
+            part,
+            Part::tool_result("call-1", "echo", vec![Part::text("[redacted]")])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_denied_call_reports_message() {
+        let part = tool_result_seen_by_model(ToolDecision::deny("not allowed")).await;
+        assert_eq!(
+            part,
+            Part::tool_result("call-1", "echo", vec![Part::text("not allowed")])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rewritten_call_is_executed() {
+        let rewritten = ToolUse::new("call-1", "echo", json!({"text": "public"}));
+        let part = tool_result_seen_by_model(ToolDecision::Rewrite(rewritten)).await;
+        assert_eq!(
+            part,
+            Part::tool_result("call-1", "echo", vec![Part::text("public")])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_sends_tool_results_like_run() {
+        let hi = || Message::new(MessageRole::User, vec![Part::text("hi")]);
+
+        let run_model = echo_once();
+        Agent::model(run_model.clone())
+            .tools(EchoTool)
+            .build()
+            .run([hi()])
+            .await
+            .unwrap();
+
+        let stream_model = echo_once();
+        let agent = Agent::model(stream_model.clone()).tools(EchoTool).build();
+        let events: Vec<_> = agent.stream([hi()]).collect().await;
+        assert!(events.iter().all(Result::is_ok));
+
+        assert_eq!(
+            stream_model.last_request().unwrap().messages,
+            run_model.last_request().unwrap().messages
+        );
+    }
+}
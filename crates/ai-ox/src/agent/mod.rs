@@ -6,24 +6,34 @@ use serde::de::DeserializeOwned;
 use crate::{
     content::{
         Part,
-        delta::StreamEvent,
+        delta::{ReasoningDelta, StreamEvent},
         message::{Message, MessageRole},
     },
     errors::GenerateContentError,
     model::{
         Model,
-        request::ModelRequest,
+        cache::CacheBreakpoints,
+        partial::{self, StructuredStreamEvent},
+        request::{GenerationConfig, ModelRequest},
         response::{ModelResponse, StructuredResponse},
+        vendor_name,
     },
     tool::{ToolBox, ToolError, ToolSet, ToolUse},
-    usage::Usage,
+    usage::{PriceTable, Usage},
 };
 
+pub mod conversation;
 pub mod error;
 pub mod events;
+pub mod interceptor;
+pub(crate) mod repair;
+pub mod tool_error;
+pub mod trim;
 
 use bon::Builder;
 use error::AgentError;
+use interceptor::{ToolDecision, ToolInterceptor};
+use tool_error::ToolErrorStrategy;
 
 /// Configuration for the agent's behavior.
 #[derive(Debug, Clone, Builder)]
@@ -39,6 +49,25 @@ pub struct Agent {
     /// Maximum number of iterations for tool execution loops.
     #[builder(default = 12)]
     max_iterations: u32,
+    /// Generation parameters applied to every model request.
+    generation_config: Option<GenerationConfig>,
+    /// Hooks that can approve, deny, rewrite or redact tool calls.
+    #[builder(with = |interceptor: impl ToolInterceptor| Arc::new(interceptor) as Arc<dyn ToolInterceptor>)]
+    tool_interceptor: Option<Arc<dyn ToolInterceptor>>,
+    /// How failed tool calls are handled.
+    #[builder(default)]
+    tool_error_strategy: ToolErrorStrategy,
+    /// Prices used to track the cost of each run.
+    #[builder(into)]
+    price_table: Option<Arc<PriceTable>>,
+    /// Maximum cost of a single run, in the price table's currency.
+    spend_limit: Option<f64>,
+    /// How many times typed output that fails schema validation is sent back
+    /// to the model for correction. Zero disables validation.
+    #[builder(default)]
+    max_repair_attempts: u32,
+    /// Parts of the prompt that providers may cache between requests.
+    cache: Option<CacheBreakpoints>,
 }
 
 impl Agent {
@@ -80,6 +109,84 @@ impl Agent {
         self.system_instruction = None;
     }
 
+    /// Returns the generation config if set.
+    pub fn generation_config(&self) -> Option<&GenerationConfig> {
+        self.generation_config.as_ref()
+    }
+
+    /// Sets the generation config used for subsequent requests.
+    pub fn set_generation_config(&mut self, config: GenerationConfig) {
+        self.generation_config = Some(config);
+    }
+
+    /// Sets the interceptor consulted around every tool call.
+    pub fn set_tool_interceptor(&mut self, interceptor: impl ToolInterceptor) {
+        self.tool_interceptor = Some(Arc::new(interceptor));
+    }
+
+    /// Removes the tool interceptor.
+    pub fn clear_tool_interceptor(&mut self) {
+        self.tool_interceptor = None;
+    }
+
+    /// Returns the strategy used for failed tool calls.
+    pub fn tool_error_strategy(&self) -> ToolErrorStrategy {
+        self.tool_error_strategy
+    }
+
+    /// Sets the strategy used for failed tool calls.
+    pub fn set_tool_error_strategy(&mut self, strategy: ToolErrorStrategy) {
+        self.tool_error_strategy = strategy;
+    }
+
+    /// Returns the spend limit if set.
+    pub fn spend_limit(&self) -> Option<f64> {
+        self.spend_limit
+    }
+
+    /// Sets the maximum cost of subsequent runs.
+    ///
+    /// A limit requires a price table covering every model the agent calls.
+    pub fn set_spend_limit(&mut self, limit: Option<f64>) {
+        self.spend_limit = limit;
+    }
+
+    /// Sets the prices used to track the cost of each run.
+    pub fn set_price_table(&mut self, prices: impl Into<Arc<PriceTable>>) {
+        self.price_table = Some(prices.into());
+    }
+
+    /// Returns how many times invalid typed output is sent back for correction.
+    pub fn max_repair_attempts(&self) -> u32 {
+        self.max_repair_attempts
+    }
+
+    /// Sets how many times invalid typed output is sent back for correction.
+    ///
+    /// Zero disables schema validation and re-asking; code fences and
+    /// surrounding prose are still stripped from text responses.
+    pub fn set_max_repair_attempts(&mut self, attempts: u32) {
+        self.max_repair_attempts = attempts;
+    }
+
+    /// Returns the prompt cache breakpoints if set.
+    pub fn cache(&self) -> Option<&CacheBreakpoints> {
+        self.cache.as_ref()
+    }
+
+    /// Sets the parts of the prompt that providers may cache.
+    pub fn set_cache(&mut self, cache: Option<CacheBreakpoints>) {
+        self.cache = cache;
+    }
+
+    /// Starts a [`Conversation`](conversation::Conversation) that keeps its
+    /// history between calls.
+    pub fn conversation(&self) -> conversation::Conversation {
+        conversation::Conversation::builder()
+            .agent(self.clone())
+            .build()
+    }
+
     /// Generates a response without tool execution.
     ///
     /// This method sends the messages to the model and returns the response
@@ -105,6 +212,20 @@ impl Agent {
         messages: impl IntoIterator<Item = impl Into<Message>> + Send,
     ) -> Result<ModelResponse, AgentError> {
         let mut conversation = self.build_messages(messages)?;
+        let mut usage = Usage::new();
+        self.run_conversation(&mut conversation, &mut usage, &mut 0.0)
+            .await
+    }
+
+    /// Runs the tool loop on `conversation`, appending every assistant and
+    /// tool message to it, adding the usage of each model call to `usage` and
+    /// charging its cost to `spent`.
+    pub(crate) async fn run_conversation(
+        &self,
+        conversation: &mut Vec<Message>,
+        usage: &mut Usage,
+        spent: &mut f64,
+    ) -> Result<ModelResponse, AgentError> {
         let mut iteration = 0;
 
         loop {
@@ -115,6 +236,13 @@ impl Agent {
             let request = self.build_request(conversation.clone());
             let response = self.model.request(request).await?;
 
+            *usage += response.usage.clone();
+            self.charge(
+                spent,
+                &response.vendor_name,
+                &response.model_name,
+                &response.usage,
+            )?;
             conversation.push(response.message.clone());
 
             if let Some(tool_calls) = response.to_tool_calls() {
@@ -125,11 +253,29 @@ impl Agent {
                 let mut join_set = tokio::task::JoinSet::new();
 
                 for call in tool_calls {
+                    let call = match self.review_tool_call(&call).await {
+                        ToolDecision::Approve => call,
+                        ToolDecision::Rewrite(rewritten) => rewritten,
+                        ToolDecision::Deny(message) => {
+                            let denial = interceptor::denied_tool_result(&call, &message);
+                            conversation.push(Message::new(MessageRole::Assistant, vec![denial]));
+                            continue;
+                        }
+                    };
+
                     let tools = self.tools.clone();
+                    let interceptor = self.tool_interceptor.clone();
+                    let strategy = self.tool_error_strategy;
                     let call_clone = call.clone();
 
                     join_set.spawn(async move {
-                        let result = tools.invoke(call_clone.clone()).await;
+                        let result = invoke_tool(
+                            &tools,
+                            interceptor.as_deref(),
+                            strategy,
+                            call_clone.clone(),
+                        )
+                        .await;
                         (call_clone, result)
                     });
                 }
@@ -160,6 +306,10 @@ impl Agent {
     /// This method constrains the model to return a JSON response that conforms
     /// to the schema of type `O`, then deserializes it and returns it along
     /// with response metadata.
+    ///
+    /// If [`max_repair_attempts`](Self::max_repair_attempts) is non-zero, the
+    /// response is validated against the schema and, when it does not conform,
+    /// sent back to the model along with the validation errors.
     pub async fn generate_typed<O>(
         &self,
         messages: impl IntoIterator<Item = impl Into<Message>> + Send,
@@ -167,49 +317,73 @@ impl Agent {
     where
         O: DeserializeOwned + JsonSchema + Send,
     {
-        let conversation = self.build_messages(messages)?;
-        let request = self.build_request(conversation.clone());
+        let mut conversation = self.build_messages(messages)?;
 
         // For structured requests, we use the schema of the target type.
         let schema = crate::tool::schema_for_type::<O>();
-
         let schema_string = schema.to_string();
-        match self
-            .model
-            .request_structured_internal(request.clone(), schema_string.clone())
-            .await
-        {
-            Ok(raw_structured_content) => {
-                let response_text = raw_structured_content.json.to_string();
-                let data: O = serde_json::from_value(raw_structured_content.json).map_err(|e| {
-                    AgentError::response_parsing_failed(e, response_text, schema_string.clone())
-                })?;
-                Ok(StructuredResponse {
-                    data,
-                    model_name: raw_structured_content.model_name,
-                    usage: raw_structured_content.usage,
-                    vendor_name: raw_structured_content.vendor_name,
-                })
-            }
-            Err(GenerateContentError::UnsupportedFeature(_)) => {
-                // Fallback to regular generation and manual parsing if the model doesn't support structured output.
-                let response = self.model.request(request).await?;
-                let data = parse_response_as_typed(&response)?;
-                Ok(StructuredResponse {
-                    data,
-                    model_name: response.model_name,
-                    usage: response.usage,
-                    vendor_name: response.vendor_name,
-                })
+        let validate = self.max_repair_attempts > 0;
+        let mut usage = Usage::new();
+        let mut spent = 0.0;
+        let mut attempt = 0;
+
+        loop {
+            let request = self.build_request(conversation.clone());
+            let (result, output, response) = match self
+                .model
+                .request_structured_internal(request.clone(), schema_string.clone())
+                .await
+            {
+                Ok(raw) => {
+                    let output = raw.json.to_string();
+                    let result = repair::parse_value::<O>(raw.json, &schema, validate);
+                    (result, output, (raw.model_name, raw.vendor_name, raw.usage))
+                }
+                Err(GenerateContentError::UnsupportedFeature(_)) => {
+                    // Fallback to regular generation and manual parsing if the model doesn't support structured output.
+                    let response = self.model.request(request).await?;
+                    let output = response.to_string().ok_or(AgentError::NoResponse)?;
+                    let result = repair::parse_text::<O>(&output, &schema, validate);
+                    (
+                        result,
+                        output,
+                        (response.model_name, response.vendor_name, response.usage),
+                    )
+                }
+                Err(e) => return Err(AgentError::Api(e)),
+            };
+
+            let (model_name, vendor_name, response_usage) = response;
+            self.charge(&mut spent, &vendor_name, &model_name, &response_usage)?;
+            usage += response_usage;
+
+            match result {
+                Ok(data) => {
+                    return Ok(StructuredResponse {
+                        data,
+                        model_name,
+                        usage,
+                        vendor_name,
+                    });
+                }
+                Err(e) if attempt < self.max_repair_attempts && repair::is_repairable(&e) => {
+                    conversation.push(Message::new(
+                        MessageRole::Assistant,
+                        vec![Part::text(output)],
+                    ));
+                    conversation.push(repair_request(&e));
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
             }
-            Err(e) => Err(AgentError::Api(e)),
         }
     }
 
     /// Executes a conversation with tool handling and returns a structured response.
     ///
     /// This method combines the multi-turn tool execution of `run()` with
-    /// the structured output parsing of `generate_typed()`.
+    /// the structured output parsing of `generate_typed()`, including its
+    /// repair loop.
     pub async fn execute_typed<O>(
         &self,
         messages: impl IntoIterator<Item = impl Into<Message>> + Send,
@@ -224,17 +398,38 @@ impl Agent {
             return self.generate_typed(messages_vec).await;
         }
 
-        // Execute the conversation with tools to get the final text response.
-        let final_response = self.run(messages_vec).await?;
+        let mut conversation = self.build_messages(messages_vec)?;
+        let schema = crate::tool::schema_for_type::<O>();
+        let validate = self.max_repair_attempts > 0;
+        let mut usage = Usage::new();
+        let mut spent = 0.0;
+        let mut attempt = 0;
 
-        // Parse the final response from the model as the structured type.
-        let data = parse_response_as_typed(&final_response)?;
-        Ok(StructuredResponse {
-            data,
-            model_name: final_response.model_name,
-            usage: final_response.usage,
-            vendor_name: final_response.vendor_name,
-        })
+        loop {
+            // Execute the conversation with tools to get the final text response.
+            let final_response = self
+                .run_conversation(&mut conversation, &mut usage, &mut spent)
+                .await?;
+
+            // Parse the final response from the model as the structured type.
+            let text = final_response.to_string().ok_or(AgentError::NoResponse)?;
+            match repair::parse_text::<O>(&text, &schema, validate) {
+                Ok(data) => {
+                    return Ok(StructuredResponse {
+                        data,
+                        model_name: final_response.model_name,
+                        usage,
+                        vendor_name: final_response.vendor_name,
+                    });
+                }
+                Err(e) if attempt < self.max_repair_attempts && repair::is_repairable(&e) => {
+                    // The final response is already part of the conversation.
+                    conversation.push(repair_request(&e));
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
     }
 
     /// Streams agent execution events for real-time processing.
@@ -260,6 +455,7 @@ impl Agent {
 
             let mut conversation = conversation;
             let mut iteration = 0;
+            let mut spent = 0.0;
 
             loop {
                 if iteration >= self.max_iterations {
@@ -271,6 +467,7 @@ impl Agent {
                 let mut model_stream = self.model.request_stream(request);
                 let mut accumulator = StreamAccumulator::new();
                 let mut response_complete = false;
+                let mut served_by = None;
 
                 while let Some(stream_event_result) = model_stream.next().await {
                     let stream_event = stream_event_result.map_err(AgentError::Api)?;
@@ -279,7 +476,8 @@ impl Agent {
                         StreamEvent::TextDelta(_) => {
                             yield events::AgentEvent::StreamEvent(stream_event.clone());
                         }
-                        StreamEvent::StreamStop(_) => {
+                        StreamEvent::StreamStop(stop) => {
+                            served_by = stop.vendor_name.clone().zip(stop.model_name.clone());
                             response_complete = true;
                             break;
                         }
@@ -297,6 +495,11 @@ impl Agent {
                 }
 
                 let final_usage = accumulator.get_usage();
+                // Combinators such as `FallbackModel` report the model that answered.
+                let (vendor, model) = served_by.unwrap_or_else(|| {
+                    (vendor_name(self.model.as_ref()), self.model.name().to_string())
+                });
+                self.charge(&mut spent, &vendor, &model, &final_usage)?;
                 let (assistant_message, tool_calls) = accumulator.finalize();
                 conversation.push(assistant_message.clone());
 
@@ -304,8 +507,8 @@ impl Agent {
                     if self.tools.get_all_tools().is_empty() {
                         yield events::AgentEvent::Completed(ModelResponse {
                             message: assistant_message,
-                            model_name: self.model.name().to_string(),
-                            vendor_name: self.model.info().to_string(),
+                            model_name: model,
+                            vendor_name: vendor,
                             usage: final_usage.clone(),
                         });
                         yield events::AgentEvent::Failed("Model generated tool calls but no tools are available".to_string());
@@ -314,14 +517,30 @@ impl Agent {
 
                     let mut join_set = tokio::task::JoinSet::new();
 
-                    for tool_call in &tool_calls {
+                    for tool_call in tool_calls {
+                        if self.tool_interceptor.is_some() {
+                            yield events::AgentEvent::ToolApprovalRequested(tool_call.clone());
+                        }
+
+                        let tool_call = match self.review_tool_call(&tool_call).await {
+                            ToolDecision::Approve => tool_call,
+                            ToolDecision::Rewrite(rewritten) => rewritten,
+                            ToolDecision::Deny(reason) => {
+                                let denial = interceptor::denied_tool_result(&tool_call, &reason);
+                                conversation.push(Message::new(MessageRole::Assistant, vec![denial]));
+                                yield events::AgentEvent::ToolDenied { call: tool_call, reason };
+                                continue;
+                            }
+                        };
+
                         yield events::AgentEvent::ToolExecution(tool_call.clone());
 
                         let tools = self.tools.clone();
-                        let call_clone = tool_call.clone();
+                        let interceptor = self.tool_interceptor.clone();
+                        let strategy = self.tool_error_strategy;
 
                         join_set.spawn(async move {
-                            tools.invoke(call_clone).await
+                            invoke_tool(&tools, interceptor.as_deref(), strategy, tool_call).await
                         });
                     }
 
@@ -336,35 +555,9 @@ impl Agent {
 
                         match tool_result {
                             Ok(tool_part) => {
-                                if let crate::content::Part::ToolResult { parts, .. } = &tool_part {
-                                    let messages: Vec<crate::content::Message> = parts.iter()
-                                        .filter_map(|part| {
-                                            if let crate::content::Part::Text { .. } = part {
-                                                Some(crate::content::Message::new(
-                                                    crate::content::MessageRole::Assistant,
-                                                    vec![part.clone()]
-                                                ))
-                                            } else {
-                                                None
-                                            }
-                                        })
-                                        .collect();
-
-                                    if messages.is_empty() {
-                                        let messages = vec![crate::content::Message::new(
-                                            crate::content::MessageRole::Assistant,
-                                            parts.clone()
-                                        )];
-                                        yield events::AgentEvent::ToolResult(messages.clone());
-                                        conversation.extend(messages);
-                                    } else {
-                                        yield events::AgentEvent::ToolResult(messages.clone());
-                                        conversation.extend(messages);
-                                    }
-                                } else {
-                                    yield events::AgentEvent::Failed("Invalid tool result format".to_string());
-                                    return;
-                                }
+                                let messages = vec![Message::new(MessageRole::Assistant, vec![tool_part])];
+                                yield events::AgentEvent::ToolResult(messages.clone());
+                                conversation.extend(messages);
                             }
                             Err(tool_error) => {
                                 yield events::AgentEvent::Failed(format!("Tool execution failed: {tool_error}"));
@@ -378,8 +571,8 @@ impl Agent {
                 } else {
                     yield events::AgentEvent::Completed(ModelResponse {
                         message: assistant_message,
-                        model_name: self.model.name().to_string(),
-                        vendor_name: self.model.info().to_string(),
+                        model_name: model,
+                        vendor_name: vendor,
                         usage: final_usage,
                     });
                     break;
@@ -389,29 +582,65 @@ impl Agent {
 
         Box::pin(stream)
     }
+
+    /// Streams a structured response of type `O` as it is generated.
+    ///
+    /// Yields a [`StructuredStreamEvent::Partial`] snapshot each time the JSON
+    /// received so far describes a more complete value, then the final value,
+    /// validated against the schema of `O`. Tools are not offered to the
+    /// model. Unlike [`generate_typed`](Self::generate_typed), invalid output
+    /// is not sent back for correction because its snapshots have already
+    /// been yielded.
+    pub fn stream_typed<O>(
+        &self,
+        messages: impl IntoIterator<Item = impl Into<Message>> + Send,
+    ) -> futures_util::stream::BoxStream<'_, Result<StructuredStreamEvent<O>, AgentError>>
+    where
+        O: DeserializeOwned + JsonSchema + Send + 'static,
+    {
+        let conversation = match self.build_messages(messages) {
+            Ok(msgs) => msgs,
+            Err(e) => return Box::pin(futures_util::stream::once(async move { Err(e) })),
+        };
+        let request = self.build_request(conversation);
+        let schema = crate::tool::schema_for_type::<O>();
+
+        partial::typed_stream(
+            self.model
+                .request_structured_stream(request, schema.to_string()),
+            vendor_name(self.model.as_ref()),
+            self.model.name().to_string(),
+            move |text| repair::parse_text(text, &schema, true),
+        )
+    }
 }
 
 /// Helper struct for accumulating streaming events into a final Message.
-struct StreamAccumulator {
+pub(crate) struct StreamAccumulator {
+    reasoning: Vec<Part>,
     text: String,
     tool_calls: Vec<ToolUse>,
     usage: Option<Usage>,
 }
 
 impl StreamAccumulator {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self {
+            reasoning: Vec::new(),
             text: String::new(),
             tool_calls: Vec::new(),
             usage: None,
         }
     }
 
-    fn accumulate(&mut self, event: &StreamEvent) {
+    pub(crate) fn accumulate(&mut self, event: &StreamEvent) {
         match event {
             StreamEvent::TextDelta(text) => {
                 self.text.push_str(text);
             }
+            StreamEvent::ReasoningDelta(delta) => {
+                self.accumulate_reasoning(delta);
+            }
             StreamEvent::ToolCall(tool_call) => {
                 self.tool_calls.push(tool_call.clone());
             }
@@ -424,12 +653,39 @@ impl StreamAccumulator {
         }
     }
 
-    fn get_usage(&self) -> Usage {
+    fn accumulate_reasoning(&mut self, delta: &ReasoningDelta) {
+        let block_open = !delta.redacted
+            && matches!(
+                self.reasoning.last(),
+                Some(Part::Reasoning { signature: None, redacted: false, ext, .. }) if ext.is_empty()
+            );
+        if !block_open {
+            self.reasoning.push(Part::reasoning(""));
+        }
+
+        if let Some(Part::Reasoning {
+            text,
+            signature,
+            redacted,
+            ext,
+        }) = self.reasoning.last_mut()
+        {
+            text.push_str(&delta.text);
+            if delta.signature.is_some() {
+                signature.clone_from(&delta.signature);
+            }
+            *redacted |= delta.redacted;
+            ext.extend(delta.ext.clone());
+        }
+    }
+
+    pub(crate) fn get_usage(&self) -> Usage {
         self.usage.clone().unwrap_or_default()
     }
 
-    fn finalize(self) -> (Message, Vec<ToolUse>) {
-        let mut content = vec![];
+    pub(crate) fn finalize(self) -> (Message, Vec<ToolUse>) {
+        // Providers expect reasoning to precede the answer it led to.
+        let mut content = self.reasoning;
         if !self.text.is_empty() {
             content.push(Part::Text {
                 text: self.text,
@@ -457,11 +713,51 @@ impl StreamAccumulator {
 impl Agent {
     // Helper methods
 
+    /// Asks the interceptor, if any, whether a tool call may run.
+    async fn review_tool_call(&self, call: &ToolUse) -> ToolDecision {
+        match &self.tool_interceptor {
+            Some(interceptor) => interceptor.before_call(call).await,
+            None => ToolDecision::Approve,
+        }
+    }
+
+    /// Adds the cost of a model call to `spent`, failing once it exceeds the
+    /// spend limit. Does nothing when no limit is set.
+    fn charge(
+        &self,
+        spent: &mut f64,
+        provider: &str,
+        model: &str,
+        usage: &Usage,
+    ) -> Result<(), AgentError> {
+        let Some(limit) = self.spend_limit else {
+            return Ok(());
+        };
+        let prices = self
+            .price_table
+            .as_deref()
+            .ok_or_else(|| AgentError::configuration("A spend limit requires a price table"))?;
+        let cost = usage.cost(prices, provider, model).ok_or_else(|| {
+            AgentError::configuration(format!("No price for model {provider}/{model}"))
+        })?;
+
+        *spent += cost.total();
+        if *spent > limit {
+            return Err(AgentError::SpendLimitExceeded {
+                limit,
+                spent: *spent,
+            });
+        }
+        Ok(())
+    }
+
     fn build_request(&self, messages: Vec<Message>) -> ModelRequest {
         let mut request = ModelRequest {
             messages,
             system_message: None,
             tools: None,
+            generation_config: self.generation_config.clone(),
+            cache: self.cache.clone(),
         };
 
         if let Some(ref system_instruction) = self.system_instruction {
@@ -492,13 +788,195 @@ impl Agent {
 
 // Helper functions
 
-/// Parses the text from a model's response into a structured type `O`.
-fn parse_response_as_typed<O>(response: &ModelResponse) -> Result<O, AgentError>
-where
-    O: DeserializeOwned + JsonSchema,
-{
-    let text = response.to_string().ok_or(AgentError::NoResponse)?;
-    let schema = crate::tool::schema_for_type::<O>();
-    serde_json::from_str(&text)
-        .map_err(|e| AgentError::response_parsing_failed(e, &text, schema.to_string()))
+/// Invokes a tool, applying the error strategy, and passes the resulting
+/// tool result through the interceptor.
+async fn invoke_tool(
+    tools: &ToolSet,
+    interceptor: Option<&dyn ToolInterceptor>,
+    strategy: ToolErrorStrategy,
+    call: ToolUse,
+) -> Result<Part, ToolError> {
+    let mut attempt = 0;
+    let result = loop {
+        match tools.invoke(call.clone()).await {
+            Err(e) if strategy.should_retry(&e, attempt) => attempt += 1,
+            result => break result,
+        }
+    };
+
+    let result = match result {
+        Ok(part) => part,
+        Err(e) if strategy.reports_to_model(&e) => {
+            Part::tool_error(&call.id, &call.name, e.report())
+        }
+        Err(e) => return Err(e),
+    };
+
+    match interceptor {
+        Some(interceptor) => Ok(interceptor.after_call(&call, result).await),
+        None => Ok(result),
+    }
+}
+
+/// Builds the user message asking the model to correct its typed output.
+fn repair_request(error: &AgentError) -> Message {
+    Message::new(
+        MessageRole::User,
+        vec![Part::text(repair::repair_prompt(error))],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::{
+            FallbackModel,
+            mock::{MockModel, MockReply},
+        },
+        usage::{Modality, ModelPrice},
+    };
+    use futures_util::StreamExt;
+
+    /// A reply with `text` using a million input tokens.
+    fn costly(text: &str) -> MockReply {
+        let mut usage = Usage::new();
+        usage.requests = 1;
+        usage
+            .input_tokens_by_modality
+            .insert(Modality::Text, 1_000_000);
+        MockReply::Response(Box::new(ModelResponse {
+            message: Message::new(MessageRole::Assistant, vec![Part::text(text)]),
+            model_name: String::new(),
+            vendor_name: "mock".to_string(),
+            usage,
+        }))
+    }
+
+    fn priced_agent(limit: f64) -> Agent {
+        Agent::model(MockModel::new("costly").with_reply(costly("reply")))
+            .price_table(PriceTable::new().with_price("mock", "costly", ModelPrice::new(2.0, 8.0)))
+            .spend_limit(limit)
+            .build()
+    }
+
+    fn hello() -> Message {
+        Message::new(MessageRole::User, vec![Part::text("hello")])
+    }
+
+    #[tokio::test]
+    async fn test_spend_limit_aborts_run() {
+        assert!(priced_agent(2.5).run([hello()]).await.is_ok());
+
+        let error = priced_agent(1.0).run([hello()]).await.unwrap_err();
+        assert!(matches!(
+            error,
+            AgentError::SpendLimitExceeded { limit, spent } if limit == 1.0 && spent == 2.0
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_spend_limit_requires_price() {
+        let agent = Agent::model(MockModel::new("costly").with_reply(costly("reply")))
+            .spend_limit(1.0)
+            .build();
+        assert!(matches!(
+            agent.run([hello()]).await,
+            Err(AgentError::Configuration(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_repair_attempts_count_against_spend_limit() {
+        #[derive(Debug, serde::Deserialize, JsonSchema)]
+        #[allow(dead_code)]
+        struct Count {
+            value: u32,
+        }
+
+        let model = MockModel::new("costly")
+            .with_reply(costly(r#"{"value": "three"}"#))
+            .with_reply(costly(r#"{"value": 3}"#));
+        let agent = Agent::model(model)
+            .price_table(PriceTable::new().with_price("mock", "costly", ModelPrice::new(2.0, 8.0)))
+            .spend_limit(3.0)
+            .max_repair_attempts(1)
+            .build();
+
+        let error = agent.generate_typed::<Count>([hello()]).await.unwrap_err();
+        assert!(matches!(
+            error,
+            AgentError::SpendLimitExceeded { spent, .. } if spent == 4.0
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_stream_charges_the_model_that_answered() {
+        let overloaded = GenerateContentError::provider_error("mock", "overloaded");
+        let model = FallbackModel::new(Arc::new(
+            MockModel::new("primary").with_reply(MockReply::Error(overloaded)),
+        ))
+        .with_fallback(Arc::new(
+            MockModel::new("backup").with_reply(costly("reply")),
+        ));
+        let agent = Agent::model(model)
+            .price_table(PriceTable::new().with_price("mock", "backup", ModelPrice::new(2.0, 8.0)))
+            .spend_limit(1.0)
+            .build();
+
+        let events: Vec<_> = agent.stream([hello()]).collect().await;
+        assert!(matches!(
+            events.last(),
+            Some(Err(AgentError::SpendLimitExceeded { spent, .. })) if *spent == 2.0
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_stream_completes_with_the_model_that_answered() {
+        let overloaded = GenerateContentError::provider_error("mock", "overloaded");
+        let model = FallbackModel::new(Arc::new(
+            MockModel::new("primary").with_reply(MockReply::Error(overloaded)),
+        ))
+        .with_fallback(Arc::new(
+            MockModel::new("backup").with_reply(MockReply::text("reply")),
+        ));
+        let agent = Agent::model(model).build();
+
+        let events: Vec<_> = agent.stream([hello()]).collect().await;
+        let completed = events.iter().find_map(|event| match event {
+            Ok(events::AgentEvent::Completed(response)) => Some(response),
+            _ => None,
+        });
+        let response = completed.expect("stream completes");
+        assert_eq!(response.vendor_name, "mock");
+        assert_eq!(response.model_name, "backup");
+    }
+
+    #[test]
+    fn test_accumulator_splits_reasoning_blocks() {
+        let mut accumulator = StreamAccumulator::new();
+        for event in [
+            StreamEvent::ReasoningDelta(ReasoningDelta::text("First ")),
+            StreamEvent::ReasoningDelta(ReasoningDelta::text("thought")),
+            StreamEvent::ReasoningDelta(ReasoningDelta::signature("sig-1")),
+            StreamEvent::ReasoningDelta(ReasoningDelta::redacted("opaque")),
+            StreamEvent::ReasoningDelta(ReasoningDelta::text("Second")),
+            StreamEvent::TextDelta("Answer".to_string()),
+        ] {
+            accumulator.accumulate(&event);
+        }
+
+        let (message, tool_calls) = accumulator.finalize();
+
+        assert!(tool_calls.is_empty());
+        assert_eq!(
+            message.content,
+            vec![
+                Part::signed_reasoning("First thought", "sig-1"),
+                Part::redacted_reasoning("opaque"),
+                Part::reasoning("Second"),
+                Part::text("Answer"),
+            ]
+        );
+    }
 }
@@ -0,0 +1,284 @@
+//! Local cleanup and schema validation of structured model output.
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use super::error::AgentError;
+
+/// Converts structured output into `O`.
+///
+/// When `validate` is set, the value is first checked against `schema` so that
+/// every violation can be reported back to the model at once.
+pub(crate) fn parse_value<O: DeserializeOwned>(
+    value: Value,
+    schema: &Value,
+    validate: bool,
+) -> Result<O, AgentError> {
+    if validate {
+        validate_value(&value, schema)?;
+    }
+
+    let response_text = value.to_string();
+    serde_json::from_value(value)
+        .map_err(|e| AgentError::response_parsing_failed(e, response_text, schema.to_string()))
+}
+
+/// Parses free-form model text into `O`, repairing cheap mistakes locally.
+///
+/// If the text is not valid JSON as a whole, Markdown code fences and any
+/// prose around the outermost object or array are stripped before retrying.
+pub(crate) fn parse_text<O: DeserializeOwned>(
+    text: &str,
+    schema: &Value,
+    validate: bool,
+) -> Result<O, AgentError> {
+    let value = serde_json::from_str(text).or_else(|error| {
+        extract_json(text)
+            .and_then(|json| serde_json::from_str(json).ok())
+            .ok_or_else(|| AgentError::response_parsing_failed(error, text, schema.to_string()))
+    })?;
+    parse_value(value, schema, validate)
+}
+
+/// Returns whether asking the model again could fix `error`.
+pub(crate) fn is_repairable(error: &AgentError) -> bool {
+    matches!(
+        error,
+        AgentError::ResponseParsingFailed { .. } | AgentError::SchemaValidationFailed { .. }
+    )
+}
+
+/// Returns the JSON document embedded in `text`, if any.
+///
+/// Prefers the contents of a fenced code block, then the span from the first
+/// opening brace or bracket to the last matching closing one.
+pub(crate) fn extract_json(text: &str) -> Option<&str> {
+    let text = strip_code_fence(text).unwrap_or(text).trim();
+    let start = text.find(['{', '['])?;
+    let close = if text[start..].starts_with('{') {
+        '}'
+    } else {
+        ']'
+    };
+    let end = text.rfind(close)?;
+    (end > start).then(|| &text[start..=end])
+}
+
+/// Returns the contents of the first fenced code block in `text`.
+fn strip_code_fence(text: &str) -> Option<&str> {
+    let (_, rest) = text.split_once("```")?;
+    // Skip the info string, e.g. `json`.
+    let (_, body) = rest.split_once('\n')?;
+    let end = body.find("```")?;
+    Some(&body[..end])
+}
+
+/// Checks `value` against `schema`, reporting every violation.
+fn validate_value(value: &Value, schema: &Value) -> Result<(), AgentError> {
+    let schema = json_schema(schema.clone());
+    let validator = jsonschema::validator_for(&schema)
+        .map_err(|e| AgentError::schema_generation_failed(e.to_string()))?;
+
+    let errors: Vec<String> = validator
+        .iter_errors(value)
+        .map(|error| {
+            format!(
+                "{}: {error}",
+                path_or_root(&error.instance_path.to_string())
+            )
+        })
+        .collect();
+    if errors.is_empty() {
+        return Ok(());
+    }
+
+    Err(AgentError::SchemaValidationFailed {
+        errors,
+        response_text: value.to_string(),
+        expected_schema: schema.to_string(),
+    })
+}
+
+/// Names the document root in validation messages.
+fn path_or_root(path: &str) -> &str {
+    if path.is_empty() { "/" } else { path }
+}
+
+/// Rewrites OpenAPI `nullable` markers, as produced by
+/// [`schema_for_type`](crate::tool::schema_for_type), into JSON Schema types.
+fn json_schema(mut schema: Value) -> Value {
+    rewrite_nullable(&mut schema);
+    schema
+}
+
+fn rewrite_nullable(schema: &mut Value) {
+    match schema {
+        Value::Object(object) => {
+            if matches!(object.get("nullable"), Some(Value::Bool(_)))
+                && object.remove("nullable") == Some(Value::Bool(true))
+            {
+                match object.get_mut("type") {
+                    Some(Value::String(ty)) => {
+                        let ty = Value::String(std::mem::take(ty));
+                        object.insert("type".to_string(), Value::from(vec![ty, "null".into()]));
+                    }
+                    Some(Value::Array(types)) => types.push("null".into()),
+                    _ => {}
+                }
+                if let Some(Value::Array(variants)) = object.get_mut("enum") {
+                    variants.push(Value::Null);
+                }
+            }
+            for (keyword, value) in object.iter_mut() {
+                match (keyword.as_str(), value) {
+                    // Maps from names to schemas, whose keys aren't keywords.
+                    (
+                        "properties" | "patternProperties" | "definitions" | "$defs",
+                        Value::Object(schemas),
+                    ) => schemas.values_mut().for_each(rewrite_nullable),
+                    // Instance values, not schemas.
+                    ("const" | "default" | "enum" | "example" | "examples", _) => {}
+                    (_, value) => rewrite_nullable(value),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(rewrite_nullable),
+        _ => {}
+    }
+}
+
+/// Builds the message asking the model to correct invalid structured output.
+pub(crate) fn repair_prompt(error: &AgentError) -> String {
+    format!(
+        "Your previous response could not be used: {}\n\
+         Respond again with only a JSON value that conforms to the required schema, \
+         without code fences or commentary.",
+        repair_reason(error)
+    )
+}
+
+fn repair_reason(error: &AgentError) -> String {
+    match error {
+        AgentError::ResponseParsingFailed { source, .. } => format!("invalid JSON ({source})."),
+        AgentError::SchemaValidationFailed { errors, .. } => {
+            format!("it does not match the schema.\n- {}", errors.join("\n- "))
+        }
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use schemars::JsonSchema;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, JsonSchema, PartialEq)]
+    struct Answer {
+        value: u32,
+        note: Option<String>,
+    }
+
+    fn schema() -> Value {
+        crate::tool::schema_for_type::<Answer>()
+    }
+
+    #[test]
+    fn test_fenced_json_with_prose_is_extracted() {
+        let text =
+            "Sure! Here it is:\n```json\n{\"value\": 3, \"note\": null}\n```\nAnything else?";
+        let answer: Answer = parse_text(text, &schema(), true).unwrap();
+        assert_eq!(
+            answer,
+            Answer {
+                value: 3,
+                note: None
+            }
+        );
+
+        let text = "The answer is {\"value\": 4} as requested.";
+        let answer: Answer = parse_text(text, &schema(), true).unwrap();
+        assert_eq!(answer.value, 4);
+    }
+
+    #[test]
+    fn test_validation_reports_every_violation() {
+        let output = json!({"value": -1, "note": 7});
+        let error = parse_value::<Answer>(output, &schema(), true).unwrap_err();
+
+        let AgentError::SchemaValidationFailed { errors, .. } = &error else {
+            panic!("expected a validation error, got {error:?}");
+        };
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(repair_prompt(&error).contains("/note"));
+    }
+
+    #[test]
+    fn test_property_named_nullable_is_kept() {
+        #[derive(Deserialize, JsonSchema)]
+        #[allow(dead_code)]
+        struct Column {
+            nullable: bool,
+            default: Option<String>,
+        }
+
+        let schema = crate::tool::schema_for_type::<Column>();
+        let rewritten = json_schema(schema.clone());
+        assert_eq!(rewritten["properties"]["nullable"]["type"], "boolean");
+        assert_eq!(
+            rewritten["properties"]["default"]["type"],
+            json!(["string", "null"])
+        );
+
+        let column = json!({"nullable": true, "default": null});
+        assert!(parse_value::<Column>(column, &schema, true).is_ok());
+        let column = json!({"default": "x"});
+        assert!(parse_value::<Column>(column, &schema, true).is_err());
+    }
+
+    #[test]
+    fn test_unparseable_text_fails_to_parse() {
+        let error = parse_text::<Answer>("no json here", &schema(), false).unwrap_err();
+        assert!(matches!(error, AgentError::ResponseParsingFailed { .. }));
+        assert!(is_repairable(&error));
+    }
+
+    #[tokio::test]
+    async fn test_agent_reasks_until_output_is_valid() {
+        use crate::{
+            agent::Agent,
+            content::message::{Message, MessageRole},
+            content::part::Part,
+            model::mock::{MockModel, MockReply},
+        };
+
+        let model = MockModel::new("mock")
+            .with_reply(MockReply::json(json!({"value": "three"})))
+            .with_reply(MockReply::json(json!({"value": 3})));
+        let ask = || [Message::new(MessageRole::User, vec![Part::text("count")])];
+
+        let agent = Agent::model(model.clone()).max_repair_attempts(1).build();
+        let response = agent.generate_typed::<Answer>(ask()).await.unwrap();
+        assert_eq!(
+            response.data,
+            Answer {
+                value: 3,
+                note: None
+            }
+        );
+
+        let requests = model.requests();
+        assert_eq!(requests.len(), 2);
+        let [.., bad, reask] = requests[1].messages.as_slice() else {
+            panic!("expected the bad output and a correction request");
+        };
+        assert_eq!(bad.content, vec![Part::text(r#"{"value":"three"}"#)]);
+        assert!(matches!(&reask.content[..], [Part::Text { text, .. }] if text.contains("/value")));
+
+        model.push_reply(MockReply::json(json!({"value": "three"})));
+        let agent = Agent::model(model.clone()).build();
+        let error = agent.generate_typed::<Answer>(ask()).await.unwrap_err();
+        assert!(matches!(error, AgentError::ResponseParsingFailed { .. }));
+    }
+}
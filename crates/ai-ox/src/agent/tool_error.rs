@@ -0,0 +1,133 @@
+use crate::tool::ToolError;
+
+/// How the agent reacts when a tool call fails.
+///
+/// Whatever the strategy, [`ToolError::Internal`] always aborts the run, since
+/// it signals a bug in the tool-handling framework rather than in the call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ToolErrorStrategy {
+    /// Stop the run and return [`AgentError::Tool`](super::error::AgentError::Tool).
+    #[default]
+    Abort,
+    /// Send the error back to the model as an error tool result so it can
+    /// correct its call or take another approach.
+    ReportToModel,
+    /// Retry execution failures up to `max_retries` times before reporting the
+    /// last error to the model.
+    ///
+    /// Errors caused by the model's request (unknown tool, invalid arguments)
+    /// are reported immediately, since an identical retry cannot succeed.
+    Retry { max_retries: u32 },
+}
+
+impl ToolErrorStrategy {
+    /// Returns `true` if a call that failed `attempt` times should run again.
+    pub(crate) fn should_retry(&self, error: &ToolError, attempt: u32) -> bool {
+        match self {
+            Self::Retry { max_retries } => error.is_execution() && attempt < *max_retries,
+            Self::Abort | Self::ReportToModel => false,
+        }
+    }
+
+    /// Returns `true` if the error should be reported to the model instead of
+    /// aborting the run.
+    pub(crate) fn reports_to_model(&self, error: &ToolError) -> bool {
+        match self {
+            Self::Abort => false,
+            Self::ReportToModel | Self::Retry { .. } => !error.is_internal(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        agent::{Agent, events::AgentEvent},
+        content::{
+            message::{Message, MessageRole},
+            part::{Part, TOOL_ERROR_EXT},
+        },
+        model::mock::{MockModel, MockReply},
+        tool::{FunctionMetadata, Tool, ToolBox, ToolUse},
+    };
+    use futures_util::{FutureExt, StreamExt, future::BoxFuture};
+    use serde_json::json;
+    use std::io;
+
+    struct FlakyTool;
+
+    impl ToolBox for FlakyTool {
+        fn tools(&self) -> Vec<Tool> {
+            vec![Tool::FunctionDeclarations(vec![FunctionMetadata {
+                name: "flaky".to_string(),
+                description: None,
+                parameters: json!({"type": "object"}),
+            }])]
+        }
+
+        fn invoke(&self, _call: ToolUse) -> BoxFuture<'_, Result<Part, ToolError>> {
+            async { Err(execution_error()) }.boxed()
+        }
+    }
+
+    fn execution_error() -> ToolError {
+        ToolError::execution("flaky", io::Error::other("timeout"))
+    }
+
+    #[test]
+    fn test_retry_only_execution_errors() {
+        let strategy = ToolErrorStrategy::Retry { max_retries: 2 };
+
+        assert!(strategy.should_retry(&execution_error(), 0));
+        assert!(strategy.should_retry(&execution_error(), 1));
+        assert!(!strategy.should_retry(&execution_error(), 2));
+        assert!(!strategy.should_retry(&ToolError::not_found("missing"), 0));
+    }
+
+    #[test]
+    fn test_internal_errors_always_abort() {
+        let internal = ToolError::internal("join", io::Error::other("panicked"));
+
+        for strategy in [
+            ToolErrorStrategy::Abort,
+            ToolErrorStrategy::ReportToModel,
+            ToolErrorStrategy::Retry { max_retries: 3 },
+        ] {
+            assert!(!strategy.reports_to_model(&internal));
+        }
+        assert!(!ToolErrorStrategy::Abort.reports_to_model(&execution_error()));
+        assert!(ToolErrorStrategy::ReportToModel.reports_to_model(&execution_error()));
+    }
+
+    #[test]
+    fn test_report_includes_source() {
+        assert_eq!(
+            execution_error().report(),
+            "Tool execution failed for tool 'flaky': timeout"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_streamed_error_result_is_marked_as_error() {
+        let model = MockModel::new("flaky-once")
+            .with_reply(MockReply::tool_call("call-1", "flaky", json!({})))
+            .with_reply(MockReply::text("done"));
+        let agent = Agent::model(model.clone())
+            .tools(FlakyTool)
+            .tool_error_strategy(ToolErrorStrategy::ReportToModel)
+            .build();
+
+        let events: Vec<_> = agent
+            .stream([Message::new(MessageRole::User, vec![Part::text("hi")])])
+            .collect()
+            .await;
+        assert!(matches!(events.last(), Some(Ok(AgentEvent::Completed(_)))));
+
+        let seen = model.last_request().unwrap().messages;
+        let Part::ToolResult { ext, .. } = &seen.last().unwrap().content[0] else {
+            panic!("expected a tool result, got {seen:?}");
+        };
+        assert_eq!(ext.get(TOOL_ERROR_EXT), Some(&json!(true)));
+    }
+}
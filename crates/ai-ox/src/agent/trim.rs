@@ -0,0 +1,338 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+
+use super::error::AgentError;
+use crate::{
+    content::{
+        Part,
+        message::{Message, MessageRole},
+    },
+    model::{Model, request::ModelRequest},
+    usage::Usage,
+};
+
+pub use crate::content::tokens::estimate_tokens;
+
+/// Splits a history into turns that can be dropped independently.
+///
+/// Every turn but the first starts with a user message that is not a tool
+/// result, so dropping leading turns always leaves the history starting with
+/// the user. A message that calls tools stays in the turn of the messages
+/// carrying its tool results, so removing a turn never leaves an orphaned
+/// call or result.
+pub fn split_turns(messages: Vec<Message>) -> Vec<Vec<Message>> {
+    let starts = turn_starts(&messages);
+    let mut turns: Vec<Vec<Message>> = Vec::new();
+    for (index, message) in messages.into_iter().enumerate() {
+        if turns.is_empty() || starts.contains(&index) {
+            turns.push(Vec::new());
+        }
+        if let Some(turn) = turns.last_mut() {
+            turn.push(message);
+        }
+    }
+    turns
+}
+
+/// Returns the indices of the messages a history may be cut before: user
+/// messages without tool results, sent while no tool call is pending.
+fn turn_starts(messages: &[Message]) -> Vec<usize> {
+    let mut starts = Vec::new();
+    let mut pending = HashSet::new();
+
+    for (index, message) in messages.iter().enumerate() {
+        let has_result = message
+            .content
+            .iter()
+            .any(|part| matches!(part, Part::ToolResult { .. }));
+        if message.role == MessageRole::User && !has_result && pending.is_empty() {
+            starts.push(index);
+        }
+        for part in &message.content {
+            match part {
+                Part::ToolUse { id, .. } => {
+                    pending.insert(id.clone());
+                }
+                Part::ToolResult { id, .. } => {
+                    pending.remove(id);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    starts
+}
+
+/// Decides how a [`Conversation`](super::conversation::Conversation) shrinks
+/// its history once it exceeds the token budget.
+#[async_trait]
+pub trait TrimStrategy: Send + Sync + 'static {
+    /// Returns a history that fits within `budget` tokens where possible.
+    ///
+    /// `model` is the conversation's model, for strategies that need to
+    /// generate content. Usage of any such calls must be added to `usage`.
+    async fn trim(
+        &self,
+        messages: Vec<Message>,
+        budget: u64,
+        model: &dyn Model,
+        usage: &mut Usage,
+    ) -> Result<Vec<Message>, AgentError>;
+}
+
+impl std::fmt::Debug for dyn TrimStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TrimStrategy")
+    }
+}
+
+/// Drops the oldest messages one at a time, always keeping the latest turn.
+///
+/// The cut is then moved forward to the next user message, as with
+/// [`DropOldestTurns`], so the history never starts with an assistant message
+/// or an orphaned tool result.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DropOldest;
+
+#[async_trait]
+impl TrimStrategy for DropOldest {
+    async fn trim(
+        &self,
+        mut messages: Vec<Message>,
+        budget: u64,
+        _model: &dyn Model,
+        _usage: &mut Usage,
+    ) -> Result<Vec<Message>, AgentError> {
+        let mut total = estimate_tokens(&messages);
+        let mut drop = 0;
+        while total > budget && drop + 1 < messages.len() {
+            total -= estimate_tokens(&messages[drop..=drop]);
+            drop += 1;
+        }
+        if drop > 0 {
+            let starts = turn_starts(&messages);
+            drop = match starts.iter().find(|&&start| start >= drop) {
+                Some(&start) => start,
+                None => starts.last().copied().unwrap_or(0),
+            };
+        }
+        messages.drain(..drop);
+        Ok(messages)
+    }
+}
+
+/// Drops the oldest turns, keeping tool calls and their results together and
+/// always keeping the latest turn.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DropOldestTurns;
+
+#[async_trait]
+impl TrimStrategy for DropOldestTurns {
+    async fn trim(
+        &self,
+        messages: Vec<Message>,
+        budget: u64,
+        _model: &dyn Model,
+        _usage: &mut Usage,
+    ) -> Result<Vec<Message>, AgentError> {
+        let turns = split_turns(messages);
+        let mut total: u64 = turns.iter().map(|turn| estimate_tokens(turn)).sum();
+        let mut drop = 0;
+        while total > budget && drop + 1 < turns.len() {
+            total -= estimate_tokens(&turns[drop]);
+            drop += 1;
+        }
+        Ok(turns.into_iter().skip(drop).flatten().collect())
+    }
+}
+
+/// Replaces older turns with a summary generated by the conversation's model.
+///
+/// The most recent `keep_recent_turns` turns are kept verbatim. If the result
+/// still exceeds the budget, the oldest remaining turns are dropped.
+#[derive(Debug, Clone)]
+pub struct Summarize {
+    keep_recent_turns: usize,
+    instruction: String,
+}
+
+impl Default for Summarize {
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+impl Summarize {
+    /// Creates a strategy that keeps the given number of recent turns.
+    pub fn new(keep_recent_turns: usize) -> Self {
+        Self {
+            keep_recent_turns: keep_recent_turns.max(1),
+            instruction: "Summarize the following conversation. Preserve facts, decisions, \
+                          tool results and open questions that later turns may rely on."
+                .to_string(),
+        }
+    }
+
+    /// Overrides the instruction sent to the model along with the transcript.
+    pub fn with_instruction(mut self, instruction: impl Into<String>) -> Self {
+        self.instruction = instruction.into();
+        self
+    }
+}
+
+#[async_trait]
+impl TrimStrategy for Summarize {
+    async fn trim(
+        &self,
+        messages: Vec<Message>,
+        budget: u64,
+        model: &dyn Model,
+        usage: &mut Usage,
+    ) -> Result<Vec<Message>, AgentError> {
+        let mut turns = split_turns(messages);
+        if turns.len() <= self.keep_recent_turns {
+            let messages = turns.into_iter().flatten().collect();
+            return DropOldestTurns.trim(messages, budget, model, usage).await;
+        }
+
+        let recent = turns.split_off(turns.len() - self.keep_recent_turns);
+        let older: Vec<Message> = turns.into_iter().flatten().collect();
+
+        let request = ModelRequest {
+            messages: vec![Message::new(
+                MessageRole::User,
+                vec![Part::text(format!(
+                    "{}\n\n{}",
+                    self.instruction,
+                    render_transcript(&older)
+                ))],
+            )],
+            system_message: None,
+            tools: None,
+            generation_config: None,
+            cache: None,
+        };
+        let response = model.request(request).await?;
+        *usage += response.usage.clone();
+        let summary = response.to_string().ok_or(AgentError::NoResponse)?;
+
+        let mut messages = vec![Message::new(
+            MessageRole::User,
+            vec![Part::text(format!(
+                "Summary of the earlier conversation:\n{summary}"
+            ))],
+        )];
+        messages.extend(recent.into_iter().flatten());
+
+        DropOldestTurns.trim(messages, budget, model, usage).await
+    }
+}
+
+/// Renders messages as a plain-text transcript for summarization.
+fn render_transcript(messages: &[Message]) -> String {
+    let mut transcript = String::new();
+    for message in messages {
+        for part in &message.content {
+            let line = match part {
+                Part::Text { text, .. } => text.clone(),
+                Part::ToolUse { name, args, .. } => format!("[called tool {name} with {args}]"),
+                Part::ToolResult { name, parts, .. } => {
+                    let text: Vec<&str> = parts
+                        .iter()
+                        .filter_map(|part| match part {
+                            Part::Text { text, .. } => Some(text.as_str()),
+                            _ => None,
+                        })
+                        .collect();
+                    format!("[tool {name} returned {}]", text.join(" "))
+                }
+                Part::Blob { mime_type, .. } => format!("[{mime_type} attachment]"),
+                Part::Reasoning { .. } | Part::Opaque { .. } => continue,
+            };
+            transcript.push_str(&format!("{}: {line}\n", message.role));
+        }
+    }
+    transcript
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn text(role: MessageRole, text: &str) -> Message {
+        Message::new(role, vec![Part::text(text)])
+    }
+
+    fn tool_exchange() -> Vec<Message> {
+        vec![
+            Message::new(
+                MessageRole::Assistant,
+                vec![
+                    Part::tool_use("a", "lookup", json!({})),
+                    Part::tool_use("b", "lookup", json!({})),
+                ],
+            ),
+            Message::new(
+                MessageRole::Assistant,
+                vec![Part::tool_result("a", "lookup", vec![Part::text("1")])],
+            ),
+            Message::new(
+                MessageRole::Assistant,
+                vec![Part::tool_result("b", "lookup", vec![Part::text("2")])],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_split_turns_keeps_tool_pairs() {
+        let mut messages = vec![text(MessageRole::User, "hi")];
+        messages.extend(tool_exchange());
+        messages.push(text(MessageRole::Assistant, "done"));
+        messages.push(text(MessageRole::User, "thanks"));
+
+        let turns = split_turns(messages);
+
+        assert_eq!(turns.iter().map(Vec::len).collect::<Vec<_>>(), vec![5, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_trimmed_history_starts_with_user() {
+        let model = crate::model::mock::MockModel::new("mock");
+        let mut messages = vec![
+            text(MessageRole::Assistant, "Welcome back"),
+            text(MessageRole::User, "look these up"),
+        ];
+        // Tool results sent as user messages must not start a turn.
+        messages.push(Message::new(
+            MessageRole::Assistant,
+            vec![Part::tool_use("a", "lookup", json!({}))],
+        ));
+        messages.push(Message::new(
+            MessageRole::User,
+            vec![Part::tool_result("a", "lookup", vec![Part::text("1")])],
+        ));
+        messages.push(text(MessageRole::Assistant, "found it"));
+        messages.push(text(MessageRole::User, "thanks"));
+
+        assert_eq!(
+            split_turns(messages.clone())
+                .iter()
+                .map(Vec::len)
+                .collect::<Vec<_>>(),
+            vec![1, 4, 1]
+        );
+
+        // Both strategies would otherwise cut right after the tool call.
+        let budget = estimate_tokens(&messages[3..]);
+        for strategy in [&DropOldest as &dyn TrimStrategy, &DropOldestTurns] {
+            let trimmed = strategy
+                .trim(messages.clone(), budget, &model, &mut Usage::new())
+                .await
+                .unwrap();
+            assert_eq!(trimmed, vec![text(MessageRole::User, "thanks")]);
+        }
+    }
+}
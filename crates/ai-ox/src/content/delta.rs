@@ -2,6 +2,8 @@
 
 use crate::usage::Usage;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
 
 use super::message::MessageRole as Role;
 use crate::tool::ToolUse;
@@ -67,6 +69,60 @@ pub struct MessageDelta {
     pub tool_call_chunks: Vec<ToolCallChunk>,
 }
 
+/// A delta in the model's reasoning content.
+///
+/// Text deltas are appended to the current reasoning block. A delta carrying a
+/// `signature` or `ext` completes the current block, so the next text delta
+/// starts a new one. Redacted deltas are complete blocks on their own.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ReasoningDelta {
+    /// A delta of the reasoning text.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub text: String,
+    /// The signature or encrypted payload of the current reasoning block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Whether this delta is a redacted reasoning block.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub redacted: bool,
+    /// Provider-specific data attached to the reasoning block.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub ext: BTreeMap<String, Value>,
+}
+
+impl ReasoningDelta {
+    /// Creates a delta of reasoning text.
+    pub fn text(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Creates a delta completing the current block with its signature.
+    pub fn signature(signature: impl Into<String>) -> Self {
+        Self {
+            signature: Some(signature.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Creates a redacted reasoning block from its encrypted payload.
+    pub fn redacted(data: impl Into<String>) -> Self {
+        Self {
+            signature: Some(data.into()),
+            redacted: true,
+            ..Self::default()
+        }
+    }
+
+    /// Returns true if this delta completes the current reasoning block.
+    pub fn completes_block(&self) -> bool {
+        self.signature.is_some() || self.redacted || !self.ext.is_empty()
+    }
+}
+
 /// Represents the final event in a stream when the model stops generating content.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -75,6 +131,15 @@ pub struct StreamStop {
     pub finish_reason: FinishReason,
     /// Token usage statistics for the entire request.
     pub usage: Usage,
+    /// Name of the model that served the stream, when it differs from the
+    /// model the stream was requested from, e.g. behind a [`FallbackModel`].
+    ///
+    /// [`FallbackModel`]: crate::model::FallbackModel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_name: Option<String>,
+    /// Provider of the model that served the stream, set along with `model_name`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vendor_name: Option<String>,
 }
 
 /// An event in a model's response stream.
@@ -87,6 +152,8 @@ pub enum StreamEvent {
     MessageDelta(MessageDelta),
     /// A delta in text content.
     TextDelta(String),
+    /// A delta in reasoning content.
+    ReasoningDelta(ReasoningDelta),
     /// A complete tool call.
     ToolCall(ToolUse),
     /// A complete tool result.
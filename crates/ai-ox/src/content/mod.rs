@@ -1,6 +1,7 @@
 pub mod delta;
 pub mod message;
 pub mod part;
+pub mod tokens;
 
 // Re-export commonly used types
 pub use message::{Message, MessageRole};
@@ -35,6 +35,12 @@ impl DataRef {
     }
 }
 
+/// Extension key marking a `Part::ToolResult` as a failed tool call.
+///
+/// Providers with a native error flag (Anthropic `is_error`, Bedrock `status`)
+/// map it onto that flag; others receive the error text as ordinary content.
+pub const TOOL_ERROR_EXT: &str = "is_error";
+
 /// Simplified Part enum following Feynman's design
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "camelCase")]
@@ -82,6 +88,24 @@ pub enum Part {
         ext: BTreeMap<String, Value>,
     },
 
+    /// Reasoning produced by the model before its answer
+    ///
+    /// Providers require reasoning to be replayed verbatim in tool loops, so the
+    /// signature or encrypted payload is kept alongside the visible text.
+    Reasoning {
+        /// Reasoning text or summary (empty when redacted)
+        #[serde(default)]
+        text: String,
+        /// Signature or encrypted payload used to verify the reasoning on replay
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        signature: Option<String>,
+        /// Whether the reasoning is only available in encrypted form
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        redacted: bool,
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        ext: BTreeMap<String, Value>,
+    },
+
     /// Provider-specific content we don't understand
     Opaque {
         provider: String,
@@ -101,6 +125,41 @@ impl Part {
         }
     }
 
+    /// Create a reasoning part
+    pub fn reasoning(text: impl Into<String>) -> Self {
+        Self::Reasoning {
+            text: text.into(),
+            signature: None,
+            redacted: false,
+            ext: BTreeMap::new(),
+        }
+    }
+
+    /// Create a reasoning part carrying a signature
+    pub fn signed_reasoning(text: impl Into<String>, signature: impl Into<String>) -> Self {
+        Self::Reasoning {
+            text: text.into(),
+            signature: Some(signature.into()),
+            redacted: false,
+            ext: BTreeMap::new(),
+        }
+    }
+
+    /// Create a redacted reasoning part from its encrypted payload
+    pub fn redacted_reasoning(data: impl Into<String>) -> Self {
+        Self::Reasoning {
+            text: String::new(),
+            signature: Some(data.into()),
+            redacted: true,
+            ext: BTreeMap::new(),
+        }
+    }
+
+    /// Check if this part is reasoning
+    pub fn is_reasoning(&self) -> bool {
+        matches!(self, Self::Reasoning { .. })
+    }
+
     /// Create a blob from URI
     pub fn blob_uri(uri: impl Into<String>, mime_type: impl Into<String>) -> Self {
         Self::Blob {
@@ -153,6 +212,31 @@ impl Part {
         }
     }
 
+    /// Create a tool result reporting a failed tool call to the model
+    pub fn tool_error(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self::ToolResult {
+            id: id.into(),
+            name: name.into(),
+            parts: vec![Self::text(message)],
+            ext: BTreeMap::from([(TOOL_ERROR_EXT.to_string(), Value::Bool(true))]),
+        }
+    }
+
+    /// Check if this part is a tool result flagged as an error
+    pub fn is_tool_error(&self) -> bool {
+        match self {
+            Self::ToolResult { ext, .. } => ext
+                .get(TOOL_ERROR_EXT)
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+
     /// Get the MIME type if this is a blob
     pub fn mime_type(&self) -> Option<&str> {
         match self {
@@ -182,6 +266,7 @@ impl Part {
             | Self::Blob { ext, .. }
             | Self::ToolUse { ext, .. }
             | Self::ToolResult { ext, .. }
+            | Self::Reasoning { ext, .. }
             | Self::Opaque { ext, .. } => {
                 ext.insert(full_key, value);
             }
@@ -235,6 +320,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_reasoning_serialization() {
+        let part = Part::signed_reasoning("Let me think", "sig");
+        let json = serde_json::to_value(&part).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"type": "reasoning", "text": "Let me think", "signature": "sig"})
+        );
+        assert_eq!(serde_json::from_value::<Part>(json).unwrap(), part);
+
+        let redacted = Part::redacted_reasoning("opaque");
+        let json = serde_json::to_value(&redacted).unwrap();
+        assert_eq!(json["redacted"], serde_json::json!(true));
+        assert_eq!(serde_json::from_value::<Part>(json).unwrap(), redacted);
+    }
+
     #[test]
     fn test_data_ref_size_estimation() {
         let base64_data = "SGVsbG8gV29ybGQ="; // "Hello World" in base64
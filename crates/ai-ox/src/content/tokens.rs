@@ -0,0 +1,59 @@
+//! Provider-agnostic estimates of how much context content occupies.
+
+use super::{message::Message, part::Part};
+
+/// Rough number of characters per token used by [`estimate_tokens`].
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Flat token cost charged for each message's role and framing.
+const MESSAGE_OVERHEAD_TOKENS: u64 = 4;
+
+/// Flat token cost charged for binary content such as images and audio.
+const BLOB_TOKENS: u64 = 256;
+
+/// Estimates how many tokens `messages` occupy in a model's context window.
+///
+/// This is a provider-agnostic approximation (about four characters per
+/// token), so budgets should leave some headroom below the model's real limit.
+pub fn estimate_tokens(messages: &[Message]) -> u64 {
+    messages
+        .iter()
+        .map(|message| {
+            MESSAGE_OVERHEAD_TOKENS + message.content.iter().map(estimate_part).sum::<u64>()
+        })
+        .sum()
+}
+
+fn estimate_part(part: &Part) -> u64 {
+    match part {
+        Part::Text { text, .. } => estimate_text(text),
+        Part::Blob { .. } => BLOB_TOKENS,
+        Part::Reasoning {
+            text, signature, ..
+        } => estimate_text(text) + signature.as_deref().map_or(0, estimate_text),
+        Part::ToolUse { name, args, .. } => estimate_text(name) + estimate_text(&args.to_string()),
+        Part::ToolResult { name, parts, .. } => {
+            estimate_text(name) + parts.iter().map(estimate_part).sum::<u64>()
+        }
+        Part::Opaque { payload, .. } => estimate_text(&payload.to_string()),
+    }
+}
+
+fn estimate_text(text: &str) -> u64 {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::message::MessageRole;
+
+    #[test]
+    fn test_estimate_tokens() {
+        let messages = vec![Message::new(
+            MessageRole::User,
+            vec![Part::text("12345678")],
+        )];
+        assert_eq!(estimate_tokens(&messages), MESSAGE_OVERHEAD_TOKENS + 2);
+    }
+}
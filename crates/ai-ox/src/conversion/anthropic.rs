@@ -4,7 +4,7 @@ use anthropic_ox::{
     message::{
         Content as AnthropicContent, ImageSource as AnthropicImageSource,
         Message as AnthropicMessage, Messages as AnthropicMessages, Role as AnthropicRole,
-        StringOrContents, Text as AnthropicText,
+        StringOrContents, Text as AnthropicText, ThinkingContent,
     },
     request::ChatRequest as AnthropicRequest,
     tool::{Tool as AnthropicTool, ToolResult as AnthropicToolResult, ToolResultContent, ToolUse},
@@ -20,6 +20,22 @@ use crate::{
     tool::{FunctionMetadata, Tool},
 };
 
+/// Media type reported for Files API sources, which don't carry one.
+pub(crate) const FILE_SOURCE_MIME_TYPE: &str = "image/*";
+
+/// Converts a Files API image source to a URI blob holding the file id.
+pub(crate) fn file_blob(file_id: &str) -> Part {
+    Part::Blob {
+        data_ref: DataRef::Uri {
+            uri: file_id.to_string(),
+        },
+        mime_type: FILE_SOURCE_MIME_TYPE.to_string(),
+        name: None,
+        description: None,
+        ext: BTreeMap::new(),
+    }
+}
+
 fn anthropic_content_to_part(
     content: &AnthropicContent,
     tool_name_lookup: &HashMap<String, String>,
@@ -37,6 +53,7 @@ fn anthropic_content_to_part(
                 description: None,
                 ext: BTreeMap::new(),
             },
+            AnthropicImageSource::File { file_id } => file_blob(file_id),
         },
         AnthropicContent::ToolUse(tool_use) => Part::ToolUse {
             id: tool_use.id.clone(),
@@ -72,6 +89,7 @@ fn anthropic_content_to_part(
                             description: None,
                             ext: BTreeMap::new(),
                         }),
+                        AnthropicImageSource::File { file_id } => Ok(file_blob(file_id)),
                     },
                 })
                 .collect::<Result<Vec<_>, GenerateContentError>>()?;
@@ -82,7 +100,14 @@ fn anthropic_content_to_part(
                 ext,
             }
         }
-        AnthropicContent::Thinking(_) | AnthropicContent::SearchResult(_) => {
+        AnthropicContent::Thinking(thinking) => Part::Reasoning {
+            text: thinking.text.clone(),
+            signature: thinking.signature.clone(),
+            redacted: false,
+            ext: BTreeMap::new(),
+        },
+        AnthropicContent::RedactedThinking { data } => Part::redacted_reasoning(data.clone()),
+        AnthropicContent::SearchResult(_) => {
             return Err(GenerateContentError::unsupported_feature(
                 "Unsupported Anthropic content type for ai-ox request conversion",
             ));
@@ -132,6 +157,10 @@ fn convert_model_tools(tools: Option<&[Tool]>) -> Option<Vec<AnthropicTool>> {
                 Tool::GeminiTool(_) => {
                     // Gemini-specific tools cannot be expressed in Anthropic
                 }
+                #[cfg(feature = "openai")]
+                Tool::OpenAITool(_) => {
+                    // OpenAI built-in tools cannot be expressed in Anthropic
+                }
             }
         }
         anthropic_tools
@@ -225,6 +254,8 @@ pub fn anthropic_request_to_model_request(
         messages,
         tools,
         system_message,
+        generation_config: None,
+        cache: None,
     })
 }
 
@@ -243,6 +274,15 @@ fn convert_part_to_anthropic_content(
                 data: data.clone(),
             },
         },
+        Part::Blob {
+            data_ref: DataRef::Uri { uri },
+            mime_type,
+            ..
+        } if mime_type.starts_with("image/") && !uri.contains("://") => AnthropicContent::Image {
+            source: AnthropicImageSource::File {
+                file_id: uri.clone(),
+            },
+        },
         Part::Blob {
             data_ref: DataRef::Uri { .. },
             ..
@@ -260,7 +300,8 @@ fn convert_part_to_anthropic_content(
         Part::ToolResult { id, parts, ext, .. } => {
             let is_error = ext
                 .get("anthropic.is_error")
-                .and_then(|value| value.as_bool());
+                .and_then(|value| value.as_bool())
+                .or_else(|| part.is_tool_error().then_some(true));
             let content = parts
                 .iter()
                 .map(|part| match part {
@@ -288,6 +329,23 @@ fn convert_part_to_anthropic_content(
                 cache_control: None,
             })
         }
+        Part::Reasoning {
+            text,
+            signature,
+            redacted,
+            ..
+        } => match (redacted, signature) {
+            (true, Some(data)) => AnthropicContent::RedactedThinking { data: data.clone() },
+            (true, None) => {
+                return Err(GenerateContentError::unsupported_feature(
+                    "Redacted reasoning without its encrypted payload cannot be converted to Anthropic content",
+                ));
+            }
+            (false, signature) => AnthropicContent::Thinking(ThinkingContent {
+                text: text.clone(),
+                signature: signature.clone(),
+            }),
+        },
         Part::Opaque { .. } => {
             return Err(GenerateContentError::unsupported_feature(
                 "Opaque parts cannot be converted to Anthropic content",
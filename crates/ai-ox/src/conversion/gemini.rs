@@ -88,6 +88,8 @@ pub fn gemini_request_to_model_request(
         messages,
         tools,
         system_message,
+        generation_config: None,
+        cache: None,
     })
 }
 
@@ -110,6 +112,10 @@ fn convert_tools_to_gemini_values(tools: &[Tool]) -> Vec<gemini_ox::tool::Tool>
             Tool::GeminiTool(inner) => {
                 gemini_tools.push(inner.clone());
             }
+            #[cfg(feature = "openai")]
+            Tool::OpenAITool(_) => {
+                // OpenAI built-in tools cannot be expressed in Gemini
+            }
         }
     }
     gemini_tools
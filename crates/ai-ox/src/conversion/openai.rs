@@ -154,6 +154,8 @@ pub fn openai_chat_request_to_model_request(
         messages,
         tools: tool_definitions,
         system_message,
+        generation_config: None,
+        cache: None,
     })
 }
 
@@ -218,6 +220,8 @@ fn convert_message_to_openai(
                     tool_call_id: Some(id.clone()),
                 });
             }
+            // Chat Completions cannot replay reasoning, so it is dropped.
+            Part::Reasoning { .. } => {}
             Part::Blob { .. } | Part::Opaque { .. } => {
                 return Err(GenerateContentError::unsupported_feature(
                     "Unsupported part type for OpenAI request conversion",
@@ -32,9 +32,38 @@ pub enum GenerateContentError {
     /// An error originating from a specific provider with context.
     #[error("Provider {0} error: {1}")]
     ProviderError(String, String),
+    /// An error raised while applying the model's conversion policy, either
+    /// because content cannot be represented or because an upload failed.
+    #[error("Conversion policy error: {0}")]
+    Conversion(#[from] crate::provider::ConversionError),
 }
 
 impl GenerateContentError {
+    /// Returns `true` if the request may succeed when retried, either because
+    /// the failure was transient or because it was specific to the model that
+    /// raised it, so another model may accept the same request.
+    ///
+    /// Configuration errors, unparsable responses and requests that could not
+    /// be converted or use unsupported features are not retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            #[cfg(feature = "gemini")]
+            Self::ClientError(error) => matches!(
+                error,
+                gemini_ox::GeminiRequestError::ReqwestError(_)
+                    | gemini_ox::GeminiRequestError::UnexpectedResponse(_)
+                    | gemini_ox::GeminiRequestError::RateLimit
+                    | gemini_ox::GeminiRequestError::IoError(_)
+            ),
+            Self::ProviderError(..) | Self::NoResponse => true,
+            Self::Configuration(_)
+            | Self::ResponseParsing(_)
+            | Self::UnsupportedFeature(_)
+            | Self::MessageConversion(_)
+            | Self::Conversion(_) => false,
+        }
+    }
+
     /// Creates a new configuration error.
     pub fn configuration(message: impl Into<String>) -> Self {
         Self::Configuration(message.into())
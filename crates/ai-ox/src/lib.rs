@@ -4,9 +4,16 @@ pub mod conversion;
 pub mod errors;
 pub mod model;
 pub mod provider;
-#[cfg(any(feature = "groq", feature = "mistral", feature = "gemini"))]
+#[cfg(any(
+    feature = "groq",
+    feature = "mistral",
+    feature = "gemini",
+    feature = "openai"
+))]
 pub mod stt;
 pub mod tool;
+#[cfg(any(feature = "groq", feature = "gemini", feature = "openai"))]
+pub mod tts;
 pub mod usage;
 pub mod workflow;
 
@@ -18,8 +25,17 @@ pub use errors::GenerateContentError;
 pub use model::response::{ModelResponse, StructuredResponse};
 
 // Re-export model implementations based on features
+#[cfg(feature = "anthropic")]
+pub use model::anthropic::AnthropicUploader;
+
 #[cfg(feature = "gemini")]
-pub use model::gemini::GeminiModel;
+pub use model::gemini::{GeminiModel, GeminiUploader};
 
 #[cfg(feature = "openrouter")]
 pub use model::openrouter::OpenRouterModel;
+
+#[cfg(feature = "openai")]
+pub use model::openai::{OpenAIModel, OpenAIResponsesModel};
+
+#[cfg(feature = "testing")]
+pub use model::mock::{MockModel, MockReply};
@@ -1,8 +1,11 @@
+use std::collections::BTreeSet;
+
 use anthropic_ox::{
     message::{
-        Content as AnthropicContent, ContentBlock, ImageSource as AnthropicImageSource,
-        Message as AnthropicMessage, Messages as AnthropicMessages, Role as AnthropicRole,
-        Text as AnthropicText,
+        CacheControl, Content as AnthropicContent, ContentBlock,
+        ImageSource as AnthropicImageSource, Message as AnthropicMessage,
+        Messages as AnthropicMessages, Role as AnthropicRole, StringOrContents,
+        Text as AnthropicText, ThinkingContent,
     },
     request::ChatRequest,
     response::{
@@ -15,12 +18,17 @@ use anthropic_ox::{
 use crate::{
     ModelResponse,
     content::{
-        delta::{FinishReason, StreamEvent, StreamStop},
+        delta::{FinishReason, ReasoningDelta, StreamEvent, StreamStop},
         message::{Message, MessageRole},
-        part::{DataRef, Part},
+        part::{DataRef, Part, TOOL_ERROR_EXT},
     },
     errors::GenerateContentError,
-    model::ModelRequest,
+    model::{
+        ModelRequest,
+        cache::{CacheBreakpoints, CacheTtl},
+        options::GenerationOption,
+        request::ToolChoice as AiOxToolChoice,
+    },
     tool::{
         Tool as AiOxTool,
         encoding::{decode_tool_result_parts, encode_tool_result_parts},
@@ -28,6 +36,27 @@ use crate::{
     usage::Usage,
 };
 
+/// Whether `request` references files uploaded through the Files API.
+pub fn uses_files(request: &ChatRequest) -> bool {
+    let is_file =
+        |source: &AnthropicImageSource| matches!(source, AnthropicImageSource::File { .. });
+    request
+        .messages
+        .iter()
+        .any(|message| match &message.content {
+            StringOrContents::String(_) => false,
+            StringOrContents::Contents(contents) => contents.iter().any(|content| {
+                match content {
+            AnthropicContent::Image { source } => is_file(source),
+            AnthropicContent::ToolResult(result) => result.content.iter().any(|content| {
+                matches!(content, ToolResultContent::Image { source } if is_file(source))
+            }),
+            _ => false,
+        }
+            }),
+        })
+}
+
 /// Convert ai-ox ModelRequest to Anthropic ChatRequest
 ///
 /// # Arguments
@@ -44,6 +73,11 @@ use crate::{
 /// - Tool schemas require the "schema" feature to be enabled
 /// - Gemini-specific tools are skipped during conversion
 /// - Image content is converted to Anthropic's base64 format
+/// - `max_output_tokens` from the generation config overrides `max_tokens`
+/// - A tool choice passed in `tools` takes precedence over the generation config
+/// - The request's system message is appended to `system_instruction`
+/// - Cache breakpoints become `cache_control` markers, keeping at most
+///   [`MAX_CACHE_BREAKPOINTS`] and dropping the earliest message breakpoints
 pub fn convert_request_to_anthropic(
     request: ModelRequest,
     model: String,
@@ -52,11 +86,24 @@ pub fn convert_request_to_anthropic(
     tools: Option<(Vec<Tool>, Option<ToolChoice>)>,
 ) -> Result<ChatRequest, GenerateContentError> {
     let mut anthropic_messages = AnthropicMessages::new();
-    let system_message = system_instruction;
+    let cache = request.cache.unwrap_or_default();
+    let cache_control = cache_control_for(cache.ttl);
+    let system_message = system_prompt(
+        system_instruction,
+        request.system_message,
+        cache.system.then(|| cache_control.clone()),
+    );
+    let message_breakpoints = message_breakpoints(&cache, request.messages.len());
+
+    let config = request.generation_config.unwrap_or_default();
+    config.reject("Anthropic", &[GenerationOption::Seed])?;
 
     // Convert messages, handling system messages specially
-    for message in request.messages {
-        let content = extract_content_from_parts(&message.content)?;
+    for (index, message) in request.messages.into_iter().enumerate() {
+        let mut content = extract_content_from_parts(&message.content)?;
+        if message_breakpoints.contains(&index) {
+            mark_last_block(&mut content, cache_control.clone());
+        }
         let role = match message.role {
             MessageRole::User => AnthropicRole::User,
             MessageRole::Assistant => AnthropicRole::Assistant,
@@ -76,25 +123,131 @@ pub fn convert_request_to_anthropic(
     } else {
         (None, None)
     };
+    let tool_choice = tool_choice.or_else(|| config.tool_choice.clone().map(Into::into));
+    let tools = tools.map(|mut tools| {
+        if cache.tools {
+            mark_last_tool(&mut tools, cache_control);
+        }
+        tools
+    });
 
     Ok(ChatRequest::builder()
         .model(model)
         .messages(anthropic_messages)
-        .max_tokens(max_tokens)
-        .maybe_system(system_message.map(Into::into))
+        .max_tokens(config.max_output_tokens.unwrap_or(max_tokens))
+        .maybe_system(system_message)
+        .maybe_stop_sequences(config.stop_sequences)
+        .maybe_temperature(config.temperature.map(|t| t as f32))
+        .maybe_top_p(config.top_p.map(|p| p as f32))
+        .maybe_top_k(config.top_k.map(|k| k as i32))
         .maybe_tools(tools)
         .maybe_tool_choice(tool_choice)
         .build())
 }
 
+/// Anthropic accepts at most this many cache breakpoints per request.
+pub const MAX_CACHE_BREAKPOINTS: usize = 4;
+
+fn cache_control_for(ttl: CacheTtl) -> CacheControl {
+    match ttl {
+        CacheTtl::Short => CacheControl::ephemeral(),
+        CacheTtl::Long => CacheControl::ephemeral().with_ttl("1h"),
+    }
+}
+
+/// Returns the indices of the messages to mark, keeping the latest ones when
+/// there are more breakpoints than Anthropic accepts.
+fn message_breakpoints(cache: &CacheBreakpoints, len: usize) -> BTreeSet<usize> {
+    let available = MAX_CACHE_BREAKPOINTS - usize::from(cache.system) - usize::from(cache.tools);
+    let indices = cache.message_indices(len);
+    let skip = indices.len().saturating_sub(available);
+    indices.into_iter().skip(skip).collect()
+}
+
+/// Combines the model's system instruction with the text of the request's
+/// system message, marking the last block when the system prompt is cached.
+fn system_prompt(
+    instruction: Option<String>,
+    message: Option<Message>,
+    cache_control: Option<CacheControl>,
+) -> Option<StringOrContents> {
+    let texts: Vec<String> = instruction
+        .into_iter()
+        .chain(
+            message
+                .into_iter()
+                .flat_map(|message| message.content)
+                .filter_map(|part| match part {
+                    Part::Text { text, .. } => Some(text),
+                    _ => None,
+                }),
+        )
+        .collect();
+
+    if texts.is_empty() {
+        return None;
+    }
+    let Some(cache_control) = cache_control else {
+        return Some(StringOrContents::String(texts.join("\n\n")));
+    };
+
+    let mut blocks: Vec<AnthropicContent> = texts
+        .into_iter()
+        .map(|text| AnthropicContent::Text(AnthropicText::new(text)))
+        .collect();
+    mark_last_block(&mut blocks, cache_control);
+    Some(StringOrContents::Contents(blocks))
+}
+
+/// Sets `cache_control` on the last block that accepts it.
+fn mark_last_block(content: &mut [AnthropicContent], cache_control: CacheControl) {
+    for block in content.iter_mut().rev() {
+        let slot = match block {
+            AnthropicContent::Text(text) => &mut text.cache_control,
+            AnthropicContent::ToolUse(tool_use) => &mut tool_use.cache_control,
+            AnthropicContent::ToolResult(result) => &mut result.cache_control,
+            AnthropicContent::SearchResult(result) => &mut result.cache_control,
+            _ => continue,
+        };
+        *slot = Some(cache_control);
+        return;
+    }
+}
+
+/// Sets `cache_control` on the last custom tool, caching every definition.
+fn mark_last_tool(tools: &mut [Tool], cache_control: CacheControl) {
+    if let Some(tool) = tools.iter_mut().rev().find_map(|tool| match tool {
+        Tool::Custom(tool) => Some(tool),
+        _ => None,
+    }) {
+        tool.cache_control = Some(cache_control);
+    }
+}
+
+impl From<AiOxToolChoice> for ToolChoice {
+    fn from(choice: AiOxToolChoice) -> Self {
+        match choice {
+            AiOxToolChoice::Auto => ToolChoice::Auto,
+            AiOxToolChoice::None => ToolChoice::None,
+            AiOxToolChoice::Required => ToolChoice::Any,
+            AiOxToolChoice::Tool(name) => ToolChoice::Tool { name },
+        }
+    }
+}
+
 impl From<anthropic_ox::response::Usage> for Usage {
     fn from(usage: anthropic_ox::response::Usage) -> Self {
         let mut new_usage = Usage::new();
         new_usage.requests = 1;
-        new_usage.input_tokens_by_modality.insert(
-            crate::usage::Modality::Text,
-            usage.input_tokens.unwrap_or(0) as u64,
-        );
+        new_usage.cache_creation_tokens = usage.cache_creation_input_tokens.map(u64::from);
+        new_usage.cache_read_tokens = usage.cache_read_input_tokens.map(u64::from);
+        // Anthropic's `input_tokens` exclude cached tokens, ai-ox counts them as input.
+        let input_tokens = u64::from(usage.input_tokens.unwrap_or(0))
+            + new_usage.cache_creation_tokens.unwrap_or(0)
+            + new_usage.cache_read_tokens.unwrap_or(0);
+        new_usage
+            .input_tokens_by_modality
+            .insert(crate::usage::Modality::Text, input_tokens);
         new_usage.output_tokens_by_modality.insert(
             crate::usage::Modality::Text,
             usage.output_tokens.unwrap_or(0) as u64,
@@ -137,7 +290,17 @@ fn extract_content_from_parts(
                             )));
                         }
                     }
-                    DataRef::Uri { uri } => {
+                    // Files API uploads are referenced by id; other URIs are rejected.
+                    DataRef::Uri { uri }
+                        if mime_type.starts_with("image/") && !uri.contains("://") =>
+                    {
+                        anthropic_content.push(AnthropicContent::Image {
+                            source: AnthropicImageSource::File {
+                                file_id: uri.clone(),
+                            },
+                        });
+                    }
+                    DataRef::Uri { .. } => {
                         return Err(GenerateContentError::message_conversion(&format!(
                             "URI data references not supported by Anthropic provider for mime_type: {}",
                             mime_type
@@ -155,9 +318,33 @@ fn extract_content_from_parts(
             } => {
                 // Use the standardized encoding function to convert parts to string
                 let content_text = encode_tool_result_parts(name, parts)?;
-                let tool_result = anthropic_ox::tool::ToolResult::text(id.clone(), content_text);
+                let tool_result = if part.is_tool_error() {
+                    anthropic_ox::tool::ToolResult::error(id.clone(), content_text)
+                } else {
+                    anthropic_ox::tool::ToolResult::text(id.clone(), content_text)
+                };
                 anthropic_content.push(AnthropicContent::ToolResult(tool_result));
             }
+            Part::Reasoning {
+                text,
+                signature,
+                redacted,
+                ..
+            } => {
+                let content = match (redacted, signature) {
+                    (true, Some(data)) => AnthropicContent::RedactedThinking { data: data.clone() },
+                    (true, None) => {
+                        return Err(GenerateContentError::message_conversion(
+                            "Redacted reasoning without its encrypted payload cannot be sent to Anthropic",
+                        ));
+                    }
+                    (false, signature) => AnthropicContent::Thinking(ThinkingContent {
+                        text: text.clone(),
+                        signature: signature.clone(),
+                    }),
+                };
+                anthropic_content.push(content);
+            }
             Part::Opaque { provider, .. } => {
                 return Err(GenerateContentError::message_conversion(&format!(
                     "Opaque parts not supported by Anthropic provider. Provider: {}",
@@ -226,6 +413,10 @@ pub fn convert_anthropic_response_to_ai_ox(
                     AnthropicImageSource::Base64 { media_type, data } => {
                         (DataRef::Base64 { data }, media_type)
                     }
+                    AnthropicImageSource::File { file_id } => (
+                        DataRef::Uri { uri: file_id },
+                        crate::conversion::anthropic::FILE_SOURCE_MIME_TYPE.to_string(),
+                    ),
                 };
                 content_parts.push(Part::Blob {
                     data_ref,
@@ -285,21 +476,29 @@ pub fn convert_anthropic_response_to_ai_ox(
                     )));
                 }
 
+                let mut ext = std::collections::BTreeMap::new();
+                if tool_result.is_error == Some(true) {
+                    ext.insert(TOOL_ERROR_EXT.to_string(), true.into());
+                }
+
                 content_parts.push(Part::ToolResult {
                     id: tool_result.tool_use_id,
                     name: tool_name,
                     parts,
-                    ext: std::collections::BTreeMap::new(),
+                    ext,
                 });
             }
             AnthropicContent::Thinking(thinking) => {
-                // Thinking content is internal to Claude and not exposed in ai-ox format
-                // We can either skip it or include it as a text part
-                content_parts.push(Part::Text {
+                content_parts.push(Part::Reasoning {
                     text: thinking.text,
+                    signature: thinking.signature,
+                    redacted: false,
                     ext: std::collections::BTreeMap::new(),
                 });
             }
+            AnthropicContent::RedactedThinking { data } => {
+                content_parts.push(Part::redacted_reasoning(data));
+            }
             AnthropicContent::SearchResult(search_result) => {
                 // Convert search result to text content
                 let content = format!(
@@ -322,19 +521,7 @@ pub fn convert_anthropic_response_to_ai_ox(
         ext: Some(std::collections::BTreeMap::new()),
     };
 
-    let usage = {
-        let mut usage = Usage::new();
-        usage.requests = 1;
-        usage.input_tokens_by_modality.insert(
-            crate::usage::Modality::Text,
-            response.usage.input_tokens.unwrap_or(0) as u64,
-        );
-        usage.output_tokens_by_modality.insert(
-            crate::usage::Modality::Text,
-            response.usage.output_tokens.unwrap_or(0) as u64,
-        );
-        usage
-    };
+    let usage = Usage::from(response.usage);
 
     Ok(ModelResponse {
         message,
@@ -390,9 +577,19 @@ pub fn convert_stream_event_to_ai_ox(
                 ContentBlock::Text { .. } => {
                     // Text content blocks don't need special start handling
                 }
-                ContentBlock::Thinking { .. } => {
-                    // Thinking content blocks are internal to Claude
-                    // We can skip them or treat them as text
+                ContentBlock::Thinking { text, signature } => {
+                    if !text.is_empty() || signature.is_some() {
+                        events.push(Ok(StreamEvent::ReasoningDelta(ReasoningDelta {
+                            text,
+                            signature,
+                            ..ReasoningDelta::default()
+                        })));
+                    }
+                }
+                ContentBlock::RedactedThinking { data } => {
+                    events.push(Ok(StreamEvent::ReasoningDelta(ReasoningDelta::redacted(
+                        data,
+                    ))));
                 }
             }
         }
@@ -423,12 +620,15 @@ pub fn convert_stream_event_to_ai_ox(
                     events.push(Ok(StreamEvent::MessageDelta(message_delta)));
                 }
                 ContentBlockDelta::ThinkingDelta { text } => {
-                    // Thinking deltas are internal to Claude
-                    // We can treat them as regular text deltas or skip them
                     if !text.is_empty() {
-                        events.push(Ok(StreamEvent::TextDelta(text)));
+                        events.push(Ok(StreamEvent::ReasoningDelta(ReasoningDelta::text(text))));
                     }
                 }
+                ContentBlockDelta::SignatureDelta { signature } => {
+                    events.push(Ok(StreamEvent::ReasoningDelta(ReasoningDelta::signature(
+                        signature,
+                    ))));
+                }
             }
         }
         AnthropicStreamEvent::ContentBlockStop { .. } => {
@@ -438,18 +638,7 @@ pub fn convert_stream_event_to_ai_ox(
         AnthropicStreamEvent::MessageDelta { delta, usage } => {
             // Handle usage information when available
             if let Some(usage) = usage {
-                let mut ai_ox_usage = Usage::new();
-                ai_ox_usage.requests = 1;
-                ai_ox_usage.input_tokens_by_modality.insert(
-                    crate::usage::Modality::Text,
-                    u64::from(usage.input_tokens.unwrap_or(0)),
-                );
-                ai_ox_usage.output_tokens_by_modality.insert(
-                    crate::usage::Modality::Text,
-                    u64::from(usage.output_tokens.unwrap_or(0)),
-                );
-
-                events.push(Ok(StreamEvent::Usage(ai_ox_usage)));
+                events.push(Ok(StreamEvent::Usage(Usage::from(usage))));
             }
 
             // If we have a stop reason in delta, emit StreamStop now
@@ -462,6 +651,8 @@ pub fn convert_stream_event_to_ai_ox(
                 events.push(Ok(StreamEvent::StreamStop(StreamStop {
                     usage,
                     finish_reason,
+                    model_name: None,
+                    vendor_name: None,
                 })));
             }
         }
@@ -473,6 +664,8 @@ pub fn convert_stream_event_to_ai_ox(
             events.push(Ok(StreamEvent::StreamStop(StreamStop {
                 usage,
                 finish_reason: FinishReason::Stop, // Default since we don't have stop reason context
+                model_name: None,
+                vendor_name: None,
             })));
         }
         AnthropicStreamEvent::Error { error } => {
@@ -508,6 +701,7 @@ fn convert_tools_to_anthropic(
                             name: func.name.clone(),
                             description: func.description.clone().unwrap_or_default(),
                             input_schema: func.parameters,
+                            cache_control: None,
                         });
 
                     // Schema support requires schema feature to be enabled in anthropic-ox
@@ -530,6 +724,10 @@ fn convert_tools_to_anthropic(
             AiOxTool::GeminiTool(_) => {
                 // Skip Gemini tools like other providers do
             }
+            #[cfg(feature = "openai")]
+            AiOxTool::OpenAITool(_) => {
+                // Skip OpenAI built-in tools like other providers do
+            }
         }
     }
 
@@ -706,6 +904,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_tool_error_sets_is_error() {
+        let parts = vec![Part::tool_error("call_789", "flaky", "boom")];
+
+        let result = extract_content_from_parts(&parts).unwrap();
+
+        if let AnthropicContent::ToolResult(tool_result) = &result[0] {
+            assert_eq!(tool_result.is_error, Some(true));
+        } else {
+            panic!("Expected ToolResult content");
+        }
+    }
+
     #[test]
     fn test_stream_event_message_start_skipped() {
         use anthropic_ox::response::{StreamEvent as AnthropicStreamEvent, StreamMessage};
@@ -779,6 +990,79 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_stream_event_thinking_deltas() {
+        use anthropic_ox::response::{ContentBlockDelta, StreamEvent as AnthropicStreamEvent};
+
+        let events = [
+            ContentBlockDelta::ThinkingDelta {
+                text: "Let me think".to_string(),
+            },
+            ContentBlockDelta::SignatureDelta {
+                signature: "sig".to_string(),
+            },
+        ]
+        .into_iter()
+        .flat_map(|delta| {
+            convert_stream_event_to_ai_ox(AnthropicStreamEvent::ContentBlockDelta {
+                index: 0,
+                delta,
+            })
+        })
+        .map(Result::unwrap)
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            events,
+            vec![
+                StreamEvent::ReasoningDelta(ReasoningDelta::text("Let me think")),
+                StreamEvent::ReasoningDelta(ReasoningDelta::signature("sig")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_thinking_round_trip() {
+        let response: ChatResponse = serde_json::from_value(json!({
+            "id": "msg_1",
+            "type": "message",
+            "role": "assistant",
+            "model": "claude-sonnet-4-5",
+            "content": [
+                {"type": "thinking", "thinking": "Need the weather.", "signature": "sig"},
+                {"type": "redacted_thinking", "data": "encrypted"},
+                {"type": "tool_use", "id": "toolu_1", "name": "weather", "input": {}}
+            ],
+            "stop_reason": "tool_use",
+            "stop_sequence": null,
+            "usage": {"input_tokens": 10, "output_tokens": 5}
+        }))
+        .unwrap();
+
+        let response = convert_anthropic_response_to_ai_ox(response, "claude".to_string()).unwrap();
+        assert_eq!(
+            response.message.content[..2],
+            [
+                Part::signed_reasoning("Need the weather.", "sig"),
+                Part::redacted_reasoning("encrypted"),
+            ]
+        );
+
+        let content = extract_content_from_parts(&response.message.content).unwrap();
+        assert_eq!(
+            content[..2],
+            [
+                AnthropicContent::Thinking(ThinkingContent::with_signature(
+                    "Need the weather.".to_string(),
+                    "sig".to_string()
+                )),
+                AnthropicContent::RedactedThinking {
+                    data: "encrypted".to_string()
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_stream_event_content_block_delta_json() {
         use anthropic_ox::response::{ContentBlockDelta, StreamEvent as AnthropicStreamEvent};
@@ -818,6 +1102,8 @@ mod tests {
                 input_tokens: Some(100),
                 output_tokens: Some(50),
                 thinking_tokens: None,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
             }),
         };
 
@@ -970,6 +1256,8 @@ mod tests {
             }],
             system_message: None,
             tools: None,
+            generation_config: None,
+            cache: None,
         };
 
         let result =
@@ -980,6 +1268,169 @@ mod tests {
         assert_eq!(chat_request.messages[0].role, AnthropicRole::User); // Should map to User
     }
 
+    #[test]
+    fn test_generation_config_mapping() {
+        let request = ModelRequest::builder()
+            .messages([Message::new(
+                MessageRole::User,
+                vec![Part::Text {
+                    text: "Hi".to_string(),
+                    ext: std::collections::BTreeMap::new(),
+                }],
+            )])
+            .generation_config(
+                crate::model::request::GenerationConfig::builder()
+                    .temperature(0.5)
+                    .top_k(40)
+                    .max_output_tokens(256)
+                    .stop_sequences(["END"])
+                    .tool_choice(AiOxToolChoice::Required)
+                    .build(),
+            )
+            .build();
+
+        let chat_request =
+            convert_request_to_anthropic(request, "test-model".to_string(), None, 100, None)
+                .unwrap();
+        assert_eq!(chat_request.max_tokens, 256);
+        assert_eq!(chat_request.temperature, Some(0.5));
+        assert_eq!(chat_request.top_k, Some(40));
+        assert_eq!(chat_request.stop_sequences, Some(vec!["END".to_string()]));
+        assert_eq!(chat_request.tool_choice, Some(ToolChoice::Any));
+
+        let request = ModelRequest::builder()
+            .generation_config(
+                crate::model::request::GenerationConfig::builder()
+                    .seed(7)
+                    .build(),
+            )
+            .build();
+        let result =
+            convert_request_to_anthropic(request, "test-model".to_string(), None, 100, None);
+        assert!(matches!(
+            result,
+            Err(GenerateContentError::UnsupportedFeature(_))
+        ));
+    }
+
+    #[test]
+    fn test_system_message_is_appended_to_instruction() {
+        let request = ModelRequest::builder()
+            .messages(vec![Message::new(
+                MessageRole::User,
+                vec![Part::text("Hi")],
+            )])
+            .system_message(Message::new(MessageRole::System, vec![Part::text("rules")]))
+            .build();
+
+        let chat_request = convert_request_to_anthropic(
+            request,
+            "test-model".to_string(),
+            Some("Be brief.".to_string()),
+            100,
+            None,
+        )
+        .unwrap();
+
+        let json = serde_json::to_value(&chat_request).unwrap();
+        assert_eq!(json["system"], "Be brief.\n\nrules");
+    }
+
+    #[test]
+    fn test_cache_breakpoints_become_cache_control() {
+        use crate::model::cache::{CacheBreakpoints, CacheTtl};
+
+        let user = |text: &str| Message::new(MessageRole::User, vec![Part::text(text)]);
+        let request = ModelRequest::builder()
+            .messages((0..5).map(|i| user(&format!("turn {i}"))))
+            .system_message(Message::new(MessageRole::System, vec![Part::text("rules")]))
+            .cache(
+                CacheBreakpoints::new()
+                    .with_system()
+                    .with_message(0)
+                    .with_message(1)
+                    .with_message(2)
+                    .with_last_message()
+                    .with_ttl(CacheTtl::Long),
+            )
+            .build();
+
+        let chat_request = convert_request_to_anthropic(
+            request,
+            "test-model".to_string(),
+            Some("Be brief.".to_string()),
+            100,
+            None,
+        )
+        .unwrap();
+        let json = serde_json::to_value(&chat_request).unwrap();
+
+        let marker = json!({"type": "ephemeral", "ttl": "1h"});
+        assert_eq!(json["system"][0]["text"], "Be brief.");
+        assert!(json["system"][0].get("cache_control").is_none());
+        assert_eq!(json["system"][1]["cache_control"], marker);
+
+        // The system prompt uses one of the four breakpoints, so the earliest
+        // message breakpoint is dropped.
+        let marked: Vec<usize> = (0..5)
+            .filter(|&i| json["messages"][i]["content"][0].get("cache_control") == Some(&marker))
+            .collect();
+        assert_eq!(marked, [1, 2, 4]);
+    }
+
+    #[test]
+    fn test_usage_counts_cached_tokens_as_input() {
+        use crate::usage::ModelPrice;
+
+        let usage = Usage::from(anthropic_ox::response::Usage {
+            input_tokens: Some(100_000),
+            output_tokens: Some(10_000),
+            thinking_tokens: None,
+            cache_creation_input_tokens: Some(200_000),
+            cache_read_input_tokens: Some(700_000),
+        });
+        assert_eq!(usage.input_tokens(), 1_000_000);
+
+        let price = ModelPrice::new(3.0, 15.0)
+            .with_cache_read(0.3)
+            .with_cache_write(3.75);
+        let cost = price.cost(&usage);
+
+        assert!((cost.input - 0.1 * 3.0).abs() < 1e-9);
+        assert!((cost.cache_read - 0.7 * 0.3).abs() < 1e-9);
+        assert!((cost.cache_write - 0.2 * 3.75).abs() < 1e-9);
+        assert!((cost.output - 0.01 * 15.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_uploaded_file_ids_become_file_sources() {
+        let parts = vec![Part::blob_uri("file_011CNha8", "image/png")];
+        let content = extract_content_from_parts(&parts).unwrap();
+        assert_eq!(
+            serde_json::to_value(&content).unwrap(),
+            json!([{"type": "image", "source": {"type": "file", "file_id": "file_011CNha8"}}])
+        );
+
+        let parts = vec![Part::blob_uri("https://example.com/cat.png", "image/png")];
+        assert!(extract_content_from_parts(&parts).is_err());
+    }
+
+    #[test]
+    fn test_requests_with_file_sources_use_files() {
+        let request = |part: Part| {
+            let request = ModelRequest::builder()
+                .messages([Message::new(MessageRole::User, vec![part])])
+                .build();
+            convert_request_to_anthropic(request, "claude".to_string(), None, 1024, None).unwrap()
+        };
+
+        assert!(uses_files(&request(Part::blob_uri(
+            "file_011CNha8",
+            "image/png"
+        ))));
+        assert!(!uses_files(&request(Part::text("hello"))));
+    }
+
     #[test]
     fn test_opaque_part_error() {
         let parts = vec![Part::Opaque {
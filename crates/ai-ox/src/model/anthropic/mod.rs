@@ -1,18 +1,25 @@
 mod conversion;
 mod error;
+mod uploader;
 
 pub use error::AnthropicError;
+pub use uploader::AnthropicUploader;
 
 use crate::{
     ModelResponse,
     content::delta::StreamEvent,
     errors::GenerateContentError,
-    model::{Model, ModelInfo, ModelRequest, Provider, response::RawStructuredResponse},
+    model::{
+        Model, ModelInfo, ModelRequest, Provider, prepare_request, response::RawStructuredResponse,
+    },
+    provider::{Capabilities, ConversionPolicy},
     usage::Usage,
 };
 use anthropic_ox::{
-    Anthropic,
+    Anthropic, ChatRequest,
+    client::FILES_API_BETA,
     message::Content,
+    request::ThinkingConfig,
     tool::{Tool, ToolChoice},
 };
 use async_stream::try_stream;
@@ -37,6 +44,12 @@ pub struct AnthropicModel {
     /// Maximum tokens for response
     #[builder(default = DEFAULT_MAX_TOKENS)]
     max_tokens: u32,
+    /// Extended thinking configuration. Structured requests never enable it,
+    /// since Anthropic rejects forced tool use while thinking.
+    thinking: Option<ThinkingConfig>,
+    /// Policy for content the provider can't represent exactly.
+    #[builder(default)]
+    conversion_policy: ConversionPolicy,
 }
 
 impl<S: anthropic_model_builder::State> AnthropicModelBuilder<S> {
@@ -68,10 +81,24 @@ impl AnthropicModel {
             model: model.into(),
             system_instruction: None,
             max_tokens: 4096,
+            thinking: None,
+            conversion_policy: ConversionPolicy::default(),
         })
     }
 }
 
+impl AnthropicModel {
+    /// The client to send `request` with, enabling the Files API beta when the
+    /// request references uploaded files.
+    fn client_for(&self, request: &ChatRequest) -> Anthropic {
+        if conversion::uses_files(request) {
+            self.client.clone().with_beta(FILES_API_BETA)
+        } else {
+            self.client.clone()
+        }
+    }
+}
+
 use futures_util::stream::BoxStream;
 
 impl Model for AnthropicModel {
@@ -83,21 +110,31 @@ impl Model for AnthropicModel {
         &self.model
     }
 
+    fn capabilities(&self) -> Option<Capabilities> {
+        Some(Capabilities::anthropic())
+    }
+
+    fn conversion_policy(&self) -> &ConversionPolicy {
+        &self.conversion_policy
+    }
+
     /// Sends a request to the Anthropic API and returns the response.
     fn request(
         &self,
         request: ModelRequest,
     ) -> BoxFuture<'_, Result<ModelResponse, GenerateContentError>> {
         async move {
-            let anthropic_request = conversion::convert_request_to_anthropic(
+            let request = prepare_request(self, request).await?;
+            let mut anthropic_request = conversion::convert_request_to_anthropic(
                 request,
                 self.model.clone(),
                 self.system_instruction.clone(),
                 self.max_tokens,
                 None, // No tools for standard request
             )?;
+            anthropic_request.thinking = self.thinking.clone();
             let response = self
-                .client
+                .client_for(&anthropic_request)
                 .send(&anthropic_request)
                 .await
                 .map_err(|e| AnthropicError::Api(e))
@@ -117,17 +154,17 @@ impl Model for AnthropicModel {
         &self,
         request: ModelRequest,
     ) -> BoxStream<'_, Result<StreamEvent, GenerateContentError>> {
-        let client = self.client.clone();
-
         let stream = try_stream! {
-            let anthropic_request = conversion::convert_request_to_anthropic(
+            let request = prepare_request(self, request).await?;
+            let mut anthropic_request = conversion::convert_request_to_anthropic(
                 request,
                 self.model.clone(),
                 self.system_instruction.clone(),
                 self.max_tokens,
                 None, // No tools for streaming request
             )?;
-            let mut response_stream = client.stream(&anthropic_request);
+            anthropic_request.thinking = self.thinking.clone();
+            let mut response_stream = self.client_for(&anthropic_request).stream(&anthropic_request);
 
             while let Some(response) = response_stream.next().await {
                 let response = response
@@ -154,6 +191,7 @@ impl Model for AnthropicModel {
         const TOOL_NAME: &str = "json_data";
 
         async move {
+            let request = prepare_request(self, request).await?;
             let schema_json: serde_json::Value = serde_json::from_str(&schema)
                 .map_err(|e| AnthropicError::InvalidSchema(e.to_string()))?;
 
@@ -163,6 +201,7 @@ impl Model for AnthropicModel {
                 description: "Function call with a JSON schema for structured data extraction."
                     .to_string(),
                 input_schema: schema_json,
+                cache_control: None,
             });
 
             let tool_choice = ToolChoice::Tool {
@@ -178,7 +217,7 @@ impl Model for AnthropicModel {
             )?;
 
             let response = self
-                .client
+                .client_for(&anthropic_request)
                 .send(&anthropic_request)
                 .await
                 .map_err(|e| AnthropicError::Api(e))?;
@@ -0,0 +1,46 @@
+use std::{future::Future, pin::Pin};
+
+use anthropic_ox::{Anthropic, files::FileUploadRequest};
+
+use crate::provider::{UploadError, Uploader};
+
+/// Uploads blobs through the Anthropic Files API.
+///
+/// Use it with [`ConversionPolicy::UploadAllowed`](crate::provider::ConversionPolicy)
+/// to send images that exceed Anthropic's inline size limit as file ids.
+/// [`AnthropicModel`](super::AnthropicModel) enables the Files API beta on
+/// requests that reference uploaded files.
+#[derive(Debug, Clone)]
+pub struct AnthropicUploader {
+    client: Anthropic,
+}
+
+impl AnthropicUploader {
+    /// Creates an uploader that uses the given Anthropic client.
+    pub fn new(client: Anthropic) -> Self {
+        Self { client }
+    }
+}
+
+impl Uploader for AnthropicUploader {
+    fn upload(
+        &self,
+        data: Vec<u8>,
+        mime_type: String,
+        name: Option<String>,
+    ) -> Pin<Box<dyn Future<Output = Result<String, UploadError>> + Send>> {
+        let client = self.client.clone();
+        Box::pin(async move {
+            let request = FileUploadRequest {
+                content: data,
+                filename: name.unwrap_or_else(|| "ai-ox-upload".to_string()),
+                mime_type,
+            };
+            client
+                .upload_file(&request)
+                .await
+                .map(|file| file.id)
+                .map_err(|e| UploadError::Failed(e.to_string()))
+        })
+    }
+}
@@ -1,18 +1,19 @@
 use super::error::BedrockError;
 use crate::{
     content::{
-        delta::FinishReason,
+        delta::{FinishReason, ReasoningDelta},
         message::Message,
         message::MessageRole,
-        part::{DataRef, Part},
+        part::{DataRef, Part, TOOL_ERROR_EXT},
     },
-    model::response::ModelResponse,
+    errors::GenerateContentError,
+    model::{request::ToolChoice as AiOxToolChoice, response::ModelResponse},
     tool::{Tool, decode_tool_result_parts, encode_tool_result_parts},
     usage::Usage,
 };
 use aws_sdk_bedrockruntime::types::{
-    ContentBlock, Message as BedrockMessage, StopReason, ToolResultBlock, ToolSpecification,
-    ToolUseBlock,
+    ContentBlock, Message as BedrockMessage, ReasoningContentBlock, ReasoningTextBlock, StopReason,
+    ToolResultBlock, ToolResultStatus, ToolSpecification, ToolUseBlock,
 };
 use base64::prelude::*;
 use serde_json::Value;
@@ -218,16 +219,24 @@ fn convert_parts_to_content_blocks(parts: Vec<Part>) -> Result<Vec<ContentBlock>
                 ContentBlock::ToolUse(tool_use_block)
             }
             Part::ToolResult {
-                id, name, parts, ..
+                id,
+                name,
+                parts,
+                ext,
             } => {
                 let encoded = encode_tool_result_parts(&name, &parts).map_err(|e| {
                     BedrockError::MessageConversion(format!("Failed to encode tool result: {}", e))
                 })?;
+                let is_error = ext
+                    .get(TOOL_ERROR_EXT)
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
                 let tool_result_block = ToolResultBlock::builder()
                     .tool_use_id(id)
                     .content(aws_sdk_bedrockruntime::types::ToolResultContentBlock::Text(
                         encoded,
                     ))
+                    .set_status(is_error.then_some(ToolResultStatus::Error))
                     .build()
                     .map_err(|e| {
                         BedrockError::MessageConversion(format!(
@@ -237,6 +246,39 @@ fn convert_parts_to_content_blocks(parts: Vec<Part>) -> Result<Vec<ContentBlock>
                     })?;
                 ContentBlock::ToolResult(tool_result_block)
             }
+            Part::Reasoning {
+                text,
+                signature,
+                redacted: true,
+                ..
+            } => {
+                // Redacted reasoning keeps its encrypted bytes base64-encoded.
+                let data = signature.unwrap_or(text);
+                let bytes = BASE64_STANDARD.decode(&data).map_err(|e| {
+                    BedrockError::MessageConversion(format!(
+                        "Invalid redacted reasoning payload: {}",
+                        e
+                    ))
+                })?;
+                ContentBlock::ReasoningContent(ReasoningContentBlock::RedactedContent(
+                    aws_smithy_types::Blob::new(bytes),
+                ))
+            }
+            Part::Reasoning {
+                text, signature, ..
+            } => {
+                let reasoning_text = ReasoningTextBlock::builder()
+                    .text(text)
+                    .set_signature(signature)
+                    .build()
+                    .map_err(|e| {
+                        BedrockError::MessageConversion(format!(
+                            "Failed to build reasoning block: {}",
+                            e
+                        ))
+                    })?;
+                ContentBlock::ReasoningContent(ReasoningContentBlock::ReasoningText(reasoning_text))
+            }
             Part::Opaque { .. } => {
                 return Err(BedrockError::MessageConversion(
                     "Opaque parts are not supported by Bedrock".to_string(),
@@ -390,13 +432,28 @@ fn convert_content_blocks_to_parts(
                 };
 
                 let (decoded_name, parts) = decode_tool_result_parts(&content_text)?;
+                let mut ext = BTreeMap::new();
+                if tool_result.status() == Some(&ToolResultStatus::Error) {
+                    ext.insert(TOOL_ERROR_EXT.to_string(), true.into());
+                }
                 Part::ToolResult {
                     id: tool_result.tool_use_id().to_string(),
                     name: decoded_name,
                     parts,
+                    ext,
+                }
+            }
+            ContentBlock::ReasoningContent(ReasoningContentBlock::ReasoningText(reasoning)) => {
+                Part::Reasoning {
+                    text: reasoning.text().to_string(),
+                    signature: reasoning.signature().map(str::to_string),
+                    redacted: false,
                     ext: Default::default(),
                 }
             }
+            ContentBlock::ReasoningContent(ReasoningContentBlock::RedactedContent(data)) => {
+                Part::redacted_reasoning(BASE64_STANDARD.encode(data.as_ref()))
+            }
             _ => {
                 return Err(BedrockError::MessageConversion(
                     "Unsupported content block type".to_string(),
@@ -409,6 +466,24 @@ fn convert_content_blocks_to_parts(
     Ok(parts)
 }
 
+/// Converts a Bedrock reasoning delta to an ai-ox reasoning delta
+pub fn convert_reasoning_delta(
+    delta: aws_sdk_bedrockruntime::types::ReasoningContentBlockDelta,
+) -> Option<ReasoningDelta> {
+    use aws_sdk_bedrockruntime::types::ReasoningContentBlockDelta;
+
+    match delta {
+        ReasoningContentBlockDelta::Text(text) => Some(ReasoningDelta::text(text)),
+        ReasoningContentBlockDelta::Signature(signature) => {
+            Some(ReasoningDelta::signature(signature))
+        }
+        ReasoningContentBlockDelta::RedactedContent(data) => Some(ReasoningDelta::redacted(
+            BASE64_STANDARD.encode(data.as_ref()),
+        )),
+        _ => None,
+    }
+}
+
 /// Converts Bedrock TokenUsage to ai-ox Usage
 pub(super) fn convert_token_usage_to_ai_ox(
     token_usage: aws_sdk_bedrockruntime::types::TokenUsage,
@@ -468,12 +543,42 @@ pub(super) fn convert_ai_ox_tools_to_bedrock(
                     "Cannot convert Gemini-specific tool to Bedrock format".to_string(),
                 ));
             }
+            #[cfg(feature = "openai")]
+            Tool::OpenAITool(_) => {
+                return Err(BedrockError::MessageConversion(
+                    "Cannot convert OpenAI built-in tool to Bedrock format".to_string(),
+                ));
+            }
         }
     }
 
     Ok(tool_specs)
 }
 
+/// Converts an ai-ox tool choice to the Bedrock tool choice
+pub(super) fn convert_tool_choice_to_bedrock(
+    choice: AiOxToolChoice,
+) -> Result<aws_sdk_bedrockruntime::types::ToolChoice, GenerateContentError> {
+    use aws_sdk_bedrockruntime::types::{
+        AnyToolChoice, AutoToolChoice, SpecificToolChoice, ToolChoice,
+    };
+
+    match choice {
+        AiOxToolChoice::Auto => Ok(ToolChoice::Auto(AutoToolChoice::builder().build())),
+        AiOxToolChoice::Required => Ok(ToolChoice::Any(AnyToolChoice::builder().build())),
+        AiOxToolChoice::Tool(name) => SpecificToolChoice::builder()
+            .name(name)
+            .build()
+            .map(ToolChoice::Tool)
+            .map_err(|e| {
+                GenerateContentError::configuration(format!("Failed to build tool choice: {}", e))
+            }),
+        AiOxToolChoice::None => Err(GenerateContentError::unsupported_feature(
+            "Bedrock does not support disabling tool calls via tool_choice",
+        )),
+    }
+}
+
 /// Converts Bedrock finish reason to ai-ox FinishReason
 pub(super) fn convert_bedrock_finish_reason(stop_reason: StopReason) -> FinishReason {
     match stop_reason {
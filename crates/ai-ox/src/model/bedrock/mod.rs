@@ -6,7 +6,11 @@ pub use error::BedrockError;
 use crate::{
     content::{delta::StreamEvent, part::Part},
     errors::GenerateContentError,
-    model::{Model, ModelInfo, Provider, request::ModelRequest, response::ModelResponse},
+    model::{
+        Model, ModelInfo, Provider, options::GenerationOption, prepare_request,
+        request::ModelRequest, request_structured_via_tool, response::ModelResponse,
+    },
+    provider::{Capabilities, ConversionPolicy},
     tool::ToolUse,
 };
 use async_stream::try_stream;
@@ -21,6 +25,9 @@ pub struct BedrockModel {
     client: BedrockRuntimeClient,
     #[builder(into)]
     model_id: String,
+    /// Policy for content the provider can't represent exactly.
+    #[builder(default)]
+    conversion_policy: ConversionPolicy,
 }
 
 impl BedrockModel {
@@ -35,7 +42,7 @@ impl BedrockModel {
             .await;
 
         let client = BedrockRuntimeClient::new(&config);
-        Ok(BedrockModel { client, model_id })
+        Ok(BedrockModel::with_client(client, model_id))
     }
 
     // Note: builder() method is provided by the bon::Builder derive macro
@@ -45,6 +52,7 @@ impl BedrockModel {
         Self {
             client,
             model_id: model_id.into(),
+            conversion_policy: ConversionPolicy::default(),
         }
     }
 }
@@ -54,6 +62,10 @@ trait BedrockRequestBuilder {
     fn set_messages(self, messages: Option<Vec<aws_sdk_bedrockruntime::types::Message>>) -> Self;
     fn system(self, system: aws_sdk_bedrockruntime::types::SystemContentBlock) -> Self;
     fn tool_config(self, config: aws_sdk_bedrockruntime::types::ToolConfiguration) -> Self;
+    fn inference_config(
+        self,
+        config: aws_sdk_bedrockruntime::types::InferenceConfiguration,
+    ) -> Self;
 }
 
 impl BedrockRequestBuilder
@@ -70,6 +82,13 @@ impl BedrockRequestBuilder
     fn tool_config(self, config: aws_sdk_bedrockruntime::types::ToolConfiguration) -> Self {
         self.tool_config(config)
     }
+
+    fn inference_config(
+        self,
+        config: aws_sdk_bedrockruntime::types::InferenceConfiguration,
+    ) -> Self {
+        self.inference_config(config)
+    }
 }
 
 impl BedrockRequestBuilder
@@ -86,13 +105,23 @@ impl BedrockRequestBuilder
     fn tool_config(self, config: aws_sdk_bedrockruntime::types::ToolConfiguration) -> Self {
         self.tool_config(config)
     }
+
+    fn inference_config(
+        self,
+        config: aws_sdk_bedrockruntime::types::InferenceConfiguration,
+    ) -> Self {
+        self.inference_config(config)
+    }
 }
 
 impl BedrockModel {
     fn build_request<B: BedrockRequestBuilder>(
         mut builder: B,
         request: ModelRequest,
-    ) -> Result<B, BedrockError> {
+    ) -> Result<B, GenerateContentError> {
+        let config = request.generation_config.unwrap_or_default();
+        config.reject("Bedrock", &[GenerationOption::TopK, GenerationOption::Seed])?;
+
         // Convert ai-ox messages to Bedrock format
         let bedrock_messages = conversion::convert_ai_ox_messages_to_bedrock(request.messages)?;
         builder = builder.set_messages(Some(bedrock_messages));
@@ -118,13 +147,36 @@ impl BedrockModel {
             }
         }
 
+        // Add inference parameters if any are set
+        if config.temperature.is_some()
+            || config.top_p.is_some()
+            || config.max_output_tokens.is_some()
+            || config.stop_sequences.is_some()
+        {
+            let inference_config = aws_sdk_bedrockruntime::types::InferenceConfiguration::builder()
+                .set_temperature(config.temperature.map(|t| t as f32))
+                .set_top_p(config.top_p.map(|p| p as f32))
+                .set_max_tokens(config.max_output_tokens.map(|t| t as i32))
+                .set_stop_sequences(config.stop_sequences)
+                .build();
+            builder = builder.inference_config(inference_config);
+        }
+
+        let tool_choice = config
+            .tool_choice
+            .map(conversion::convert_tool_choice_to_bedrock)
+            .transpose()?;
+
         // Add tools if present
+        let mut has_tools = false;
         if let Some(tools) = request.tools {
             let tool_specs = conversion::convert_ai_ox_tools_to_bedrock(tools)?;
 
             if !tool_specs.is_empty() {
+                has_tools = true;
                 let tool_config = aws_sdk_bedrockruntime::types::ToolConfiguration::builder()
                     .set_tools(Some(tool_specs))
+                    .set_tool_choice(tool_choice.clone())
                     .build()
                     .map_err(|e| {
                         BedrockError::RequestBuilder(format!(
@@ -137,6 +189,12 @@ impl BedrockModel {
             }
         }
 
+        if tool_choice.is_some() && !has_tools {
+            return Err(GenerateContentError::configuration(
+                "Bedrock requires tools to be provided when a tool choice is set",
+            ));
+        }
+
         Ok(builder)
     }
 }
@@ -150,11 +208,20 @@ impl Model for BedrockModel {
         &self.model_id
     }
 
+    fn capabilities(&self) -> Option<Capabilities> {
+        Some(Capabilities::bedrock())
+    }
+
+    fn conversion_policy(&self) -> &ConversionPolicy {
+        &self.conversion_policy
+    }
+
     fn request(
         &self,
         request: ModelRequest,
     ) -> BoxFuture<'_, Result<ModelResponse, GenerateContentError>> {
         async move {
+            let request = prepare_request(self, request).await?;
             // Build the ConverseRequest from the ModelRequest
             let converse_request = self.client.converse().model_id(&self.model_id);
 
@@ -189,6 +256,7 @@ impl Model for BedrockModel {
         let model_id = self.model_id.clone();
 
         let stream = try_stream! {
+            let request = prepare_request(self, request).await?;
             // Build the initial request using the existing helper
             let converse_request = client
                 .converse_stream()
@@ -236,6 +304,11 @@ impl Model for BedrockModel {
                                     // Accumulate tool input (don't yield yet)
                                     current_tool_input.push_str(tool_delta.input());
                                 },
+                                aws_sdk_bedrockruntime::types::ContentBlockDelta::ReasoningContent(reasoning_delta) => {
+                                    if let Some(delta) = conversion::convert_reasoning_delta(reasoning_delta) {
+                                        yield StreamEvent::ReasoningDelta(delta);
+                                    }
+                                },
                                 _ => {
                                     // Handle other delta types if needed
                                 }
@@ -281,6 +354,8 @@ impl Model for BedrockModel {
                         yield StreamEvent::StreamStop(crate::content::delta::StreamStop {
                             finish_reason,
                             usage,
+                            model_name: None,
+                            vendor_name: None,
                         });
                     },
                     ConverseStreamOutput::Metadata(metadata) => {
@@ -311,17 +386,14 @@ impl Model for BedrockModel {
         stream.boxed()
     }
 
+    /// Bedrock has no native JSON schema output, so the schema is enforced by
+    /// forcing a call to a synthetic tool.
     fn request_structured_internal(
         &self,
-        _request: ModelRequest,
-        _schema: String,
+        request: ModelRequest,
+        schema: String,
     ) -> BoxFuture<'_, Result<crate::model::response::RawStructuredResponse, GenerateContentError>>
     {
-        async move {
-            Err(GenerateContentError::UnsupportedFeature(
-                "BedrockModel does not currently support structured generation.".to_string(),
-            ))
-        }
-        .boxed()
+        request_structured_via_tool(self, request, schema).boxed()
     }
 }
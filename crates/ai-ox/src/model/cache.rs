@@ -0,0 +1,131 @@
+//! Provider-neutral prompt caching controls.
+
+use std::{collections::BTreeSet, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+/// Marks the parts of a request's prompt that providers may cache.
+///
+/// A prompt is ordered as system prompt, then tools, then messages, and each
+/// breakpoint marks the end of a prefix that can be cached and reused by later
+/// requests sharing it. Anthropic receives the breakpoints as `cache_control`
+/// markers; Gemini caches the prefix ending at the last breakpoint before the
+/// final message as a `CachedContent` and reuses it while it lives; providers
+/// that cache implicitly, such as OpenAI, ignore them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheBreakpoints {
+    /// Whether the prefix ending with the system prompt is cached.
+    #[serde(default)]
+    pub system: bool,
+    /// Whether the prefix ending with the tool definitions is cached.
+    #[serde(default)]
+    pub tools: bool,
+    /// Indices of the messages that end a cached prefix.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub messages: BTreeSet<usize>,
+    /// Whether the prefix ending with the last message is cached.
+    ///
+    /// Useful for conversations, where the breakpoint moves forward as the
+    /// history grows.
+    #[serde(default)]
+    pub last_message: bool,
+    /// How long cached prefixes should be kept.
+    #[serde(default)]
+    pub ttl: CacheTtl,
+}
+
+impl CacheBreakpoints {
+    /// Creates an empty set of breakpoints.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Caches the prefix ending with the system prompt.
+    pub fn with_system(mut self) -> Self {
+        self.system = true;
+        self
+    }
+
+    /// Caches the prefix ending with the tool definitions.
+    pub fn with_tools(mut self) -> Self {
+        self.tools = true;
+        self
+    }
+
+    /// Caches the prefix ending with the message at `index`.
+    pub fn with_message(mut self, index: usize) -> Self {
+        self.messages.insert(index);
+        self
+    }
+
+    /// Caches the prefix ending with the last message of each request.
+    pub fn with_last_message(mut self) -> Self {
+        self.last_message = true;
+        self
+    }
+
+    /// Sets how long cached prefixes should be kept.
+    pub fn with_ttl(mut self, ttl: CacheTtl) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Returns whether no breakpoint is set.
+    pub fn is_empty(&self) -> bool {
+        !self.system && !self.tools && self.messages.is_empty() && !self.last_message
+    }
+
+    /// Returns the indices of the messages ending a cached prefix in a request
+    /// with `len` messages.
+    pub fn message_indices(&self, len: usize) -> BTreeSet<usize> {
+        let mut indices: BTreeSet<usize> = self.messages.range(..len).copied().collect();
+        if self.last_message && len > 0 {
+            indices.insert(len - 1);
+        }
+        indices
+    }
+}
+
+/// How long a cached prefix is kept after it was last used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheTtl {
+    /// About five minutes, the cheapest option where providers charge for it.
+    #[default]
+    Short,
+    /// About an hour.
+    Long,
+}
+
+impl CacheTtl {
+    /// Returns the lifetime as a duration.
+    pub fn duration(self) -> Duration {
+        match self {
+            Self::Short => Duration::from_secs(5 * 60),
+            Self::Long => Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_indices_follow_the_request() {
+        let breakpoints = CacheBreakpoints::new()
+            .with_message(1)
+            .with_message(9)
+            .with_last_message();
+
+        assert_eq!(breakpoints.message_indices(4), BTreeSet::from([1, 3]));
+        assert_eq!(breakpoints.message_indices(0), BTreeSet::new());
+        assert!(
+            CacheBreakpoints::new()
+                .with_tools()
+                .message_indices(4)
+                .is_empty()
+        );
+        assert!(CacheBreakpoints::new().is_empty());
+    }
+}
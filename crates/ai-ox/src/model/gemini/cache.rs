@@ -0,0 +1,346 @@
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use gemini_ox::{
+    Gemini, GeminiRequestError, generate_content::request::GenerateContentRequest, tool::Tool,
+};
+
+use crate::model::cache::CacheBreakpoints;
+
+/// How long before expiry a cached prefix stops being reused.
+const EXPIRY_MARGIN: Duration = Duration::from_secs(30);
+
+/// Prompt prefixes a [`GeminiModel`](super::GeminiModel) has cached as
+/// `CachedContent`, keyed by the prefix itself.
+///
+/// Expired entries are evicted on the next request. Cached contents that are
+/// still alive when their entry is replaced or the cache is dropped are
+/// deleted in the background.
+#[derive(Debug, Default)]
+pub(super) struct PromptCache {
+    slots: Mutex<HashMap<u64, Slot>>,
+}
+
+/// The entry for one prefix. It stays locked while its cached content is
+/// created or extended, so concurrent requests sharing the prefix wait for it
+/// instead of creating their own.
+type Slot = Arc<tokio::sync::Mutex<Option<Entry>>>;
+
+#[derive(Debug)]
+struct Entry {
+    /// The cached content, or `None` if creating it failed.
+    cached: Option<Cached>,
+    expires_at: Instant,
+}
+
+/// A cached content and the client that created it.
+#[derive(Debug)]
+struct Cached {
+    name: String,
+    client: Gemini,
+}
+
+impl Entry {
+    fn new(cached: Option<Cached>, ttl: Duration) -> Self {
+        Self {
+            cached,
+            expires_at: Instant::now() + ttl,
+        }
+    }
+}
+
+impl Drop for Entry {
+    /// Deletes the cached content if it is still alive, so dropped entries
+    /// stop being billed for storage.
+    fn drop(&mut self) {
+        let Some(Cached { name, client }) = self.cached.take() else {
+            return;
+        };
+        if self.expires_at <= Instant::now() {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        runtime.spawn(async move {
+            if let Err(error) = client.caches().delete(&name).await {
+                log::warn!("Failed to delete Gemini cached content {name}: {error}");
+            }
+        });
+    }
+}
+
+impl PromptCache {
+    /// Serves the prefix of `request` ending at the last breakpoint before
+    /// the final message from a cached content.
+    ///
+    /// The cached content is created the first time a prefix is seen and
+    /// reused until it expires; its TTL is extended once less than half of it
+    /// remains. If creating it fails, for example because the prefix is below
+    /// Gemini's minimum size, the request is sent uncached and creation is not
+    /// retried for one TTL.
+    pub(super) async fn apply(
+        &self,
+        client: &Gemini,
+        request: &mut GenerateContentRequest,
+        breakpoints: &CacheBreakpoints,
+    ) {
+        let Some(prefix_len) = prefix_len(breakpoints, request.contents.len()) else {
+            return;
+        };
+        let ttl = breakpoints.ttl.duration();
+        let slot = self.slot(prefix_key(request, prefix_len));
+        let mut entry = slot.lock().await;
+
+        let now = Instant::now();
+        let reusable = entry
+            .as_ref()
+            .is_some_and(|entry| entry.expires_at > now + EXPIRY_MARGIN);
+        if !reusable {
+            let cached = match create(client, request, prefix_len, ttl).await {
+                Ok(name) => Some(Cached {
+                    name,
+                    client: client.clone(),
+                }),
+                Err(error) => {
+                    log::warn!("Failed to create Gemini cached content: {error}");
+                    None
+                }
+            };
+            *entry = Some(Entry::new(cached, ttl));
+        }
+
+        let Some(current) = entry.as_mut() else {
+            return;
+        };
+        let Some(cached) = &current.cached else {
+            return;
+        };
+        let name = cached.name.clone();
+        if current.expires_at - now < ttl / 2 {
+            let update = client
+                .caches()
+                .update(&name)
+                .ttl(format!("{}s", ttl.as_secs()))
+                .build();
+            match update.send().await {
+                Ok(_) => current.expires_at = Instant::now() + ttl,
+                Err(error) => {
+                    log::warn!("Failed to extend Gemini cached content {name}: {error}");
+                    *entry = None;
+                    return;
+                }
+            }
+        }
+
+        use_cached_content(request, name, prefix_len);
+    }
+
+    /// Returns the slot for `key`, evicting expired entries on the way.
+    fn slot(&self, key: u64) -> Slot {
+        let now = Instant::now();
+        let mut slots = self.slots.lock().unwrap_or_else(PoisonError::into_inner);
+        // Slots held by a request are in use even if they are still empty.
+        slots.retain(|_, slot| {
+            Arc::strong_count(slot) > 1
+                || slot.try_lock().map_or(true, |entry| {
+                    entry.as_ref().is_some_and(|entry| entry.expires_at > now)
+                })
+        });
+        slots.entry(key).or_default().clone()
+    }
+}
+
+/// Returns how many leading contents of a request with `len` contents are
+/// cached, or `None` if the request isn't cached.
+///
+/// Breakpoints on the final content are ignored: a prefix including it is
+/// different on every request of a conversation, so each request would create
+/// and pay for a new cached content that is never reused.
+fn prefix_len(breakpoints: &CacheBreakpoints, len: usize) -> Option<usize> {
+    let last = breakpoints
+        .message_indices(len)
+        .range(..len.saturating_sub(1))
+        .next_back()
+        .copied();
+    match last {
+        Some(index) => Some(index + 1),
+        None => (breakpoints.system || breakpoints.tools).then_some(0),
+    }
+}
+
+/// Creates a cached content holding everything in `request` up to the first
+/// `prefix_len` contents, returning its resource name.
+async fn create(
+    client: &Gemini,
+    request: &GenerateContentRequest,
+    prefix_len: usize,
+    ttl: Duration,
+) -> Result<String, GeminiRequestError> {
+    let tools = request
+        .tools
+        .iter()
+        .flatten()
+        .map(|tool| serde_json::from_value::<Tool>(tool.clone()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let model = if request.model.starts_with("models/") {
+        request.model.clone()
+    } else {
+        format!("models/{}", request.model)
+    };
+
+    let create = client
+        .caches()
+        .create()
+        .model(model)
+        .contents(request.contents[..prefix_len].to_vec())
+        .tools(tools)
+        .ttl(ttl)
+        .maybe_system_instruction(request.system_instruction.clone())
+        .maybe_tool_config(request.tool_config.clone())
+        .build();
+    Ok(create.send().await?.name)
+}
+
+/// Identifies the cacheable prefix of `request`.
+fn prefix_key(request: &GenerateContentRequest, prefix_len: usize) -> u64 {
+    let prefix = serde_json::json!([
+        request.model,
+        request.system_instruction,
+        request.tools,
+        request.tool_config,
+        request.contents[..prefix_len],
+    ]);
+    let mut hasher = DefaultHasher::new();
+    prefix.to_string().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Replaces the cached prefix of `request` with a reference to `name`.
+///
+/// Gemini rejects requests that repeat the system instruction, tools or tool
+/// config of the cached content they use.
+fn use_cached_content(request: &mut GenerateContentRequest, name: String, prefix_len: usize) {
+    request.contents.drain(..prefix_len);
+    request.system_instruction = None;
+    request.tools = None;
+    request.tool_config = None;
+    request.cached_content = Some(name);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gemini_ox::content::Content;
+
+    fn request(turns: &[&str]) -> GenerateContentRequest {
+        GenerateContentRequest::builder()
+            .model("gemini-2.5-flash")
+            .content_list(turns.iter().copied())
+            .system_instruction(Content::text("Be brief."))
+            .build()
+    }
+
+    #[test]
+    fn test_prefix_key_ignores_the_uncached_suffix() {
+        let first = request(&["document", "question one"]);
+        let second = request(&["document", "question two"]);
+
+        assert_eq!(prefix_key(&first, 1), prefix_key(&second, 1));
+        assert_ne!(prefix_key(&first, 2), prefix_key(&second, 2));
+    }
+
+    #[test]
+    fn test_final_message_is_never_cached() {
+        let last = CacheBreakpoints::new().with_last_message();
+        assert_eq!(prefix_len(&last, 3), None);
+        assert_eq!(prefix_len(&last.clone().with_message(0), 3), Some(1));
+        assert_eq!(prefix_len(&last.with_system(), 3), Some(0));
+        assert_eq!(
+            prefix_len(&CacheBreakpoints::new().with_message(2), 3),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expired_entries_are_evicted() {
+        let cache = PromptCache::default();
+        *cache.slot(1).lock().await = Some(Entry::new(None, Duration::ZERO));
+        *cache.slot(2).lock().await = Some(Entry::new(None, Duration::from_secs(60)));
+
+        cache.slot(3);
+        let mut keys: Vec<_> = cache.slots.lock().unwrap().keys().copied().collect();
+        keys.sort_unstable();
+        assert_eq!(keys, [2, 3]);
+    }
+
+    #[test]
+    fn test_cached_prefix_is_removed_from_the_request() {
+        let mut request = request(&["document", "question"]);
+        use_cached_content(&mut request, "cachedContents/abc".to_string(), 1);
+
+        assert_eq!(request.contents.len(), 1);
+        assert!(request.system_instruction.is_none());
+        assert_eq!(
+            request.cached_content.as_deref(),
+            Some("cachedContents/abc")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cached_content_is_created_once_and_reused() {
+        use crate::{
+            content::{Message, MessageRole, Part},
+            model::{Model, ModelRequest, gemini::GeminiModel},
+        };
+        use ai_ox_common::Transport;
+
+        // A hand-written fixture in the recorded format, not a recording.
+        // Replay checks the method, URL and JSON body of every request.
+        let path = format!(
+            "{}/tests/cassettes/gemini_prompt_cache.json",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        let client = Gemini::builder()
+            .api_key("test-key")
+            .transport(Transport::replay(path).unwrap())
+            .build();
+        let model = GeminiModel::builder()
+            .model("gemini-2.5-flash")
+            .client(client)
+            .build();
+        let ask = |question: &str| {
+            let user = |text: &str| Message::new(MessageRole::User, vec![Part::text(text)]);
+            ModelRequest::builder()
+                .messages([user("Summarize the attached report."), user(question)])
+                .cache(CacheBreakpoints::new().with_message(0))
+                .build()
+        };
+
+        for question in ["Who wrote it?", "When was it published?"] {
+            let response = model.request(ask(question)).await.unwrap();
+            assert_eq!(response.usage.cache_read_tokens, Some(2048));
+        }
+
+        let slots = model.prompt_cache.slots.lock().unwrap();
+        let names: Vec<_> = slots
+            .values()
+            .map(|slot| {
+                let entry = slot.try_lock().unwrap();
+                entry
+                    .as_ref()
+                    .unwrap()
+                    .cached
+                    .as_ref()
+                    .unwrap()
+                    .name
+                    .clone()
+            })
+            .collect();
+        assert_eq!(names, ["cachedContents/k2x9f1"]);
+    }
+}
@@ -1,13 +1,19 @@
-use std::convert::TryFrom;
+use std::{collections::BTreeMap, convert::TryFrom};
+
+use serde_json::Value;
 
 use crate::{
     content::{
-        delta::{FinishReason, StreamEvent, StreamStop},
+        delta::{FinishReason, ReasoningDelta, StreamEvent, StreamStop},
         message::{Message, MessageRole},
-        part::Part,
+        part::{Part, TOOL_ERROR_EXT},
     },
     errors::GenerateContentError,
-    model::{ModelRequest, response::ModelResponse},
+    model::{
+        ModelRequest,
+        request::{GenerationConfig as AiOxGenerationConfig, ToolChoice},
+        response::ModelResponse,
+    },
     tool::{Tool, decode_tool_result_parts, encode_tool_result_parts},
 };
 use gemini_ox::{
@@ -17,9 +23,26 @@ use gemini_ox::{
         request::GenerateContentRequest as GeminiGenerateContentRequest,
         response::GenerateContentResponse,
     },
-    tool::config::ToolConfig,
+    tool::config::{FunctionCallingConfig, Mode as FunctionCallingMode, ToolConfig},
 };
 
+/// Extension key holding the thought signature Gemini attaches to non-thought
+/// parts, such as function calls, which must be replayed on the same part.
+const THOUGHT_SIGNATURE_EXT: &str = "gemini.thought_signature";
+
+fn signature_from_ext(ext: &BTreeMap<String, Value>) -> Option<String> {
+    ext.get(THOUGHT_SIGNATURE_EXT)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+fn signature_to_ext(signature: Option<String>) -> BTreeMap<String, Value> {
+    signature
+        .map(|signature| (THOUGHT_SIGNATURE_EXT.to_string(), Value::String(signature)))
+        .into_iter()
+        .collect()
+}
+
 impl From<MessageRole> for GeminiRole {
     fn from(role: MessageRole) -> Self {
         match role {
@@ -53,26 +76,53 @@ impl TryFrom<Part> for GeminiPart {
 
     fn try_from(part: Part) -> Result<Self, Self::Error> {
         match part {
-            Part::Text { text, .. } => {
-                Ok(Self::new(gemini_ox::content::PartData::Text(text.into())))
+            Part::Text { text, ext } => {
+                let mut part = Self::new(gemini_ox::content::PartData::Text(text.into()));
+                part.thought_signature = signature_from_ext(&ext);
+                Ok(part)
+            }
+            Part::Reasoning {
+                text, signature, ..
+            } => {
+                let mut part =
+                    Self::new_with_thought(gemini_ox::content::PartData::Text(text.into()), true);
+                part.thought_signature = signature;
+                Ok(part)
+            }
+            Part::ToolUse {
+                id,
+                name,
+                args,
+                ext,
+            } => {
+                let mut part = Self::new(gemini_ox::content::PartData::FunctionCall(
+                    gemini_ox::content::FunctionCall {
+                        id: Some(id),
+                        name,
+                        args: Some(args),
+                    },
+                ));
+                part.thought_signature = signature_from_ext(&ext);
+                Ok(part)
             }
-            Part::ToolUse { id, name, args, .. } => Ok(Self::new(
-                gemini_ox::content::PartData::FunctionCall(gemini_ox::content::FunctionCall {
-                    id: Some(id),
-                    name,
-                    args: Some(args),
-                }),
-            )),
             Part::ToolResult {
-                id, name, parts, ..
+                id,
+                name,
+                parts,
+                ext,
             } => {
                 let encoded_content = encode_tool_result_parts(&name, &parts)?;
+                let mut response =
+                    serde_json::from_str(&encoded_content).unwrap_or(serde_json::Value::Null);
+                // Gemini reports failed calls under an "error" key.
+                if ext.get(TOOL_ERROR_EXT).and_then(|v| v.as_bool()) == Some(true) {
+                    response = serde_json::json!({ "error": response });
+                }
                 Ok(Self::new(gemini_ox::content::PartData::FunctionResponse(
                     gemini_ox::content::FunctionResponse {
                         id: Some(id),
                         name,
-                        response: serde_json::from_str(&encoded_content)
-                            .unwrap_or(serde_json::Value::Null),
+                        response,
                         will_continue: None,
                         scheduling: None,
                     },
@@ -133,14 +183,57 @@ impl TryFrom<GeminiContent> for Message {
     }
 }
 
+/// Decodes a Gemini function response, unwrapping the "error" envelope used
+/// for failed tool calls.
+fn function_response_to_part(
+    function_response: gemini_ox::content::FunctionResponse,
+) -> Result<Part, GenerateContentError> {
+    let (response, is_error) = match function_response.response {
+        serde_json::Value::Object(mut map) if map.len() == 1 && map.contains_key("error") => {
+            (map.remove("error").unwrap_or_default(), true)
+        }
+        response => (response, false),
+    };
+    let encoded_response = serde_json::to_string(&response)?;
+    let (decoded_name, parts) = decode_tool_result_parts(&encoded_response)?;
+    // Verify the decoded name matches the expected function name
+    if decoded_name != function_response.name {
+        return Err(GenerateContentError::message_conversion(&format!(
+            "Function name mismatch: expected '{}', got '{}'",
+            function_response.name, decoded_name
+        )));
+    }
+    let mut ext = std::collections::BTreeMap::new();
+    if is_error {
+        ext.insert(TOOL_ERROR_EXT.to_string(), true.into());
+    }
+    Ok(Part::ToolResult {
+        id: function_response
+            .id
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        name: function_response.name,
+        parts,
+        ext,
+    })
+}
+
 impl TryFrom<GeminiPart> for Part {
     type Error = GenerateContentError;
 
     fn try_from(part: GeminiPart) -> Result<Self, Self::Error> {
+        let signature = part.thought_signature;
         match part.data {
+            gemini_ox::content::PartData::Text(text) if part.thought == Some(true) => {
+                Ok(Part::Reasoning {
+                    text: text.to_string(),
+                    signature,
+                    redacted: false,
+                    ext: BTreeMap::new(),
+                })
+            }
             gemini_ox::content::PartData::Text(text) => Ok(Part::Text {
                 text: text.to_string(),
-                ext: std::collections::BTreeMap::new(),
+                ext: signature_to_ext(signature),
             }),
             gemini_ox::content::PartData::FunctionCall(function_call) => Ok(Part::ToolUse {
                 id: function_call
@@ -148,26 +241,10 @@ impl TryFrom<GeminiPart> for Part {
                     .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
                 name: function_call.name,
                 args: function_call.args.unwrap_or_default(),
-                ext: std::collections::BTreeMap::new(),
+                ext: signature_to_ext(signature),
             }),
             gemini_ox::content::PartData::FunctionResponse(function_response) => {
-                let encoded_response = serde_json::to_string(&function_response.response)?;
-                let (decoded_name, parts) = decode_tool_result_parts(&encoded_response)?;
-                // Verify the decoded name matches the expected function name
-                if decoded_name != function_response.name {
-                    return Err(GenerateContentError::message_conversion(&format!(
-                        "Function name mismatch: expected '{}', got '{}'",
-                        function_response.name, decoded_name
-                    )));
-                }
-                Ok(Part::ToolResult {
-                    id: function_response
-                        .id
-                        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
-                    name: function_response.name,
-                    parts,
-                    ext: std::collections::BTreeMap::new(),
-                })
+                function_response_to_part(function_response)
             }
             gemini_ox::content::PartData::InlineData(blob) => Ok(Part::Blob {
                 data_ref: crate::content::part::DataRef::Base64 { data: blob.data },
@@ -234,6 +311,8 @@ pub(super) fn convert_response_to_stream_events(
         events.push(Ok(StreamEvent::StreamStop(StreamStop {
             finish_reason,
             usage: usage_metadata.clone().into(),
+            model_name: None,
+            vendor_name: None,
         })));
     }
 
@@ -245,31 +324,26 @@ impl TryFrom<GeminiPart> for StreamEvent {
 
     fn try_from(part: GeminiPart) -> Result<Self, Self::Error> {
         match part.data {
+            gemini_ox::content::PartData::Text(text) if part.thought == Some(true) => {
+                Ok(StreamEvent::ReasoningDelta(ReasoningDelta {
+                    text: text.to_string(),
+                    signature: part.thought_signature,
+                    ..ReasoningDelta::default()
+                }))
+            }
             gemini_ox::content::PartData::Text(text) => {
                 Ok(StreamEvent::TextDelta(text.to_string()))
             }
             gemini_ox::content::PartData::FunctionCall(function_call) => {
-                Ok(StreamEvent::ToolCall(function_call.into()))
-            }
-            gemini_ox::content::PartData::FunctionResponse(function_response) => {
-                let encoded_response = serde_json::to_string(&function_response.response)?;
-                let (decoded_name, parts) = decode_tool_result_parts(&encoded_response)?;
-                // Verify the decoded name matches the expected function name
-                if decoded_name != function_response.name {
-                    return Err(GenerateContentError::message_conversion(&format!(
-                        "Function name mismatch: expected '{}', got '{}'",
-                        function_response.name, decoded_name
-                    )));
+                let mut tool_use = crate::tool::ToolUse::from(function_call);
+                if part.thought_signature.is_some() {
+                    tool_use.ext = Some(signature_to_ext(part.thought_signature));
                 }
-                Ok(StreamEvent::ToolResult(Part::ToolResult {
-                    id: function_response
-                        .id
-                        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
-                    name: function_response.name,
-                    parts,
-                    ext: std::collections::BTreeMap::new(),
-                }))
+                Ok(StreamEvent::ToolCall(tool_use))
             }
+            gemini_ox::content::PartData::FunctionResponse(function_response) => Ok(
+                StreamEvent::ToolResult(function_response_to_part(function_response)?),
+            ),
             gemini_ox::content::PartData::InlineData(blob) => {
                 Ok(StreamEvent::ToolResult(Part::Blob {
                     data_ref: crate::content::part::DataRef::Base64 { data: blob.data },
@@ -324,9 +398,18 @@ pub(super) fn convert_request_to_gemini(
         .map(TryInto::try_into)
         .collect::<Result<Vec<GeminiContent>, _>>()?;
 
-    let tools = request
-        .tools
-        .map(|tools| tools.into_iter().map(|tool| tool.into()).collect());
+    let tools = request.tools.map(|tools| {
+        tools
+            .into_iter()
+            .filter(|tool| !tool.is_openai_tool())
+            .map(|tool| tool.into())
+            .collect()
+    });
+
+    let (generation_config, tool_config) = match request.generation_config {
+        Some(config) => apply_generation_config(config, generation_config, tool_config),
+        None => (generation_config, tool_config),
+    };
 
     Ok(GeminiGenerateContentRequest {
         model,
@@ -340,6 +423,55 @@ pub(super) fn convert_request_to_gemini(
     })
 }
 
+/// Layers the request's generation options over the model-level Gemini configuration.
+///
+/// Options set on the request take precedence over the model defaults.
+fn apply_generation_config(
+    config: AiOxGenerationConfig,
+    generation_config: Option<GenerationConfig>,
+    tool_config: Option<ToolConfig>,
+) -> (Option<GenerationConfig>, Option<ToolConfig>) {
+    let mut generation_config = generation_config.unwrap_or_default();
+    if let Some(temperature) = config.temperature {
+        generation_config.temperature = Some(temperature);
+    }
+    if let Some(top_p) = config.top_p {
+        generation_config.top_p = Some(top_p);
+    }
+    if let Some(top_k) = config.top_k {
+        generation_config.top_k = Some(u64::from(top_k));
+    }
+    if let Some(max_output_tokens) = config.max_output_tokens {
+        generation_config.max_output_tokens = Some(max_output_tokens);
+    }
+    if let Some(stop_sequences) = config.stop_sequences {
+        generation_config.stop_sequences = Some(stop_sequences);
+    }
+    if let Some(seed) = config.seed {
+        generation_config.seed = Some(seed);
+    }
+
+    let tool_config = match config.tool_choice {
+        Some(choice) => Some(ToolConfig::new().function_calling_config(choice.into())),
+        None => tool_config,
+    };
+
+    (Some(generation_config), tool_config)
+}
+
+impl From<ToolChoice> for FunctionCallingConfig {
+    fn from(choice: ToolChoice) -> Self {
+        match choice {
+            ToolChoice::Auto => Self::new().mode(FunctionCallingMode::Auto),
+            ToolChoice::None => Self::new().mode(FunctionCallingMode::None),
+            ToolChoice::Required => Self::new().mode(FunctionCallingMode::Any),
+            ToolChoice::Tool(name) => Self::new()
+                .mode(FunctionCallingMode::Any)
+                .allowed_function_names([name]),
+        }
+    }
+}
+
 pub(super) fn convert_gemini_response_to_ai_ox(
     response: GenerateContentResponse,
     model_name: String,
@@ -363,3 +495,48 @@ pub(super) fn convert_gemini_response_to_ai_ox(
         usage,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gemini_ox::content::{FunctionCall, PartData};
+    use serde_json::json;
+
+    #[test]
+    fn test_thought_parts_round_trip() {
+        let mut thought = GeminiPart::new_with_thought(PartData::Text("Checking.".into()), true);
+        thought.thought_signature = Some("sig-1".to_string());
+        let mut call = GeminiPart::new(PartData::FunctionCall(FunctionCall {
+            id: Some("call-1".to_string()),
+            name: "weather".to_string(),
+            args: Some(json!({})),
+        }));
+        call.thought_signature = Some("sig-2".to_string());
+
+        let parts = [thought.clone(), call.clone()]
+            .into_iter()
+            .map(Part::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(parts[0], Part::signed_reasoning("Checking.", "sig-1"));
+        assert!(
+            matches!(&parts[1], Part::ToolUse { ext, .. } if signature_from_ext(ext).as_deref() == Some("sig-2"))
+        );
+
+        let replayed = parts
+            .into_iter()
+            .map(GeminiPart::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(replayed, vec![thought, call]);
+    }
+
+    #[test]
+    fn test_thought_stream_event() {
+        let thought = GeminiPart::new_with_thought(PartData::Text("Hmm".into()), true);
+        assert_eq!(
+            StreamEvent::try_from(thought).unwrap(),
+            StreamEvent::ReasoningDelta(ReasoningDelta::text("Hmm"))
+        );
+    }
+}
@@ -92,6 +92,11 @@ impl From<GeminiError> for GenerateContentError {
                 GeminiRequestError::AuthenticationMissing => GenerateContentError::configuration(
                     "Gemini authentication is missing: no API key or OAuth token provided",
                 ),
+                error @ (GeminiRequestError::FileProcessingFailed { .. }
+                | GeminiRequestError::FileProcessingTimeout { .. }
+                | GeminiRequestError::BatchTimeout { .. }) => {
+                    GenerateContentError::provider_error("gemini", error.to_string())
+                }
             },
             GeminiError::MissingApiKey => {
                 GenerateContentError::configuration("Missing Gemini API key".to_string())
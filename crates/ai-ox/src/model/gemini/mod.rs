@@ -1,24 +1,32 @@
+mod cache;
 mod conversion;
 mod error;
+mod uploader;
 
 pub use error::GeminiError;
+pub use uploader::GeminiUploader;
 
 use crate::{
     ModelResponse,
     content::delta::StreamEvent,
     errors::GenerateContentError,
-    model::{Model, ModelInfo, ModelRequest, Provider, response::RawStructuredResponse},
+    model::{
+        Model, ModelInfo, ModelRequest, Provider, prepare_request, response::RawStructuredResponse,
+    },
+    provider::{Capabilities, ConversionPolicy},
     usage::Usage,
 };
 use async_stream::try_stream;
 use bon::Builder;
+use cache::PromptCache;
 use futures_util::{FutureExt, StreamExt, future::BoxFuture, stream::BoxStream};
 use gemini_ox::{
     Gemini,
     content::Content as GeminiContent,
-    generate_content::{GenerationConfig, SafetySettings},
+    generate_content::{GenerationConfig, SafetySettings, request::GenerateContentRequest},
     tool::config::ToolConfig,
 };
+use std::sync::Arc;
 
 /// Represents a model from the Google Gemini family.
 #[derive(Debug, Clone, Builder)]
@@ -36,6 +44,12 @@ pub struct GeminiModel {
     generation_config: Option<GenerationConfig>,
     #[builder(into)]
     cached_content: Option<String>,
+    /// Policy for content the provider can't represent exactly.
+    #[builder(default)]
+    conversion_policy: ConversionPolicy,
+    /// Cached contents created for requests with cache breakpoints.
+    #[builder(skip)]
+    prompt_cache: Arc<PromptCache>,
 }
 
 impl<S: gemini_model_builder::State> GeminiModelBuilder<S> {
@@ -43,6 +57,12 @@ impl<S: gemini_model_builder::State> GeminiModelBuilder<S> {
         self.client = Gemini::new(api_key);
         self
     }
+
+    /// Uses an existing client, for example one with a custom transport.
+    pub fn client(mut self, client: Gemini) -> Self {
+        self.client = client;
+        self
+    }
 }
 
 impl GeminiModel {
@@ -64,6 +84,8 @@ impl GeminiModel {
             safety_settings: None,
             generation_config: None,
             cached_content: None,
+            conversion_policy: ConversionPolicy::default(),
+            prompt_cache: Arc::default(),
         })
     }
 }
@@ -77,6 +99,14 @@ impl Model for GeminiModel {
         &self.model
     }
 
+    fn capabilities(&self) -> Option<Capabilities> {
+        Some(Capabilities::gemini())
+    }
+
+    fn conversion_policy(&self) -> &ConversionPolicy {
+        &self.conversion_policy
+    }
+
     /// Sends a request to the Gemini API and returns the response.
     ///
     /// This implementation will handle the conversion from the generic `ModelRequest`
@@ -87,15 +117,9 @@ impl Model for GeminiModel {
         request: ModelRequest,
     ) -> BoxFuture<'_, Result<ModelResponse, GenerateContentError>> {
         async move {
-            let gemini_request = conversion::convert_request_to_gemini(
-                request,
-                self.model.clone(),
-                self.system_instruction.clone(),
-                self.tool_config.clone(),
-                self.safety_settings.clone(),
-                self.generation_config.clone(),
-                self.cached_content.clone(),
-            )?;
+            let gemini_request = self
+                .gemini_request(request, self.generation_config.clone())
+                .await?;
             let response = gemini_request
                 .send(&self.client)
                 .await
@@ -112,30 +136,7 @@ impl Model for GeminiModel {
         &self,
         request: ModelRequest,
     ) -> BoxStream<'_, Result<StreamEvent, GenerateContentError>> {
-        let client = self.client.clone();
-
-        let stream = try_stream! {
-            let gemini_request = conversion::convert_request_to_gemini(
-                request,
-                self.model.clone(),
-                self.system_instruction.clone(),
-                self.tool_config.clone(),
-                self.safety_settings.clone(),
-                self.generation_config.clone(),
-                self.cached_content.clone(),
-            )?;
-            let mut response_stream = gemini_request.stream(&client);
-
-            while let Some(response) = response_stream.next().await {
-                let response = response.map_err(GeminiError::Api)?;
-                let events = conversion::convert_response_to_stream_events(response);
-                for event in events {
-                    yield event?;
-                }
-            }
-        };
-
-        Box::pin(stream)
+        self.stream_content(request, self.generation_config.clone())
     }
 
     fn request_structured_internal(
@@ -144,27 +145,10 @@ impl Model for GeminiModel {
         schema: String,
     ) -> BoxFuture<'_, Result<RawStructuredResponse, GenerateContentError>> {
         async move {
-            // Parse the schema and remove the $schema field if present (Gemini API doesn't accept it)
-            let mut schema_json: serde_json::Value = serde_json::from_str(&schema)
-                .map_err(|e| GeminiError::InvalidSchema(e.to_string()))?;
-            if let Some(obj) = schema_json.as_object_mut() {
-                obj.remove("$schema");
-            }
-
-            let generation_config = GenerationConfig::builder()
-                .response_mime_type("application/json")
-                .response_schema(schema_json)
-                .build();
-
-            let gemini_request = conversion::convert_request_to_gemini(
-                request,
-                self.model.clone(),
-                self.system_instruction.clone(),
-                self.tool_config.clone(),
-                self.safety_settings.clone(),
-                Some(generation_config),
-                self.cached_content.clone(),
-            )?;
+            let generation_config = json_generation_config(&schema)?;
+            let gemini_request = self
+                .gemini_request(request, Some(generation_config))
+                .await?;
             let response = gemini_request
                 .send(&self.client)
                 .await
@@ -196,6 +180,90 @@ impl Model for GeminiModel {
         }
         .boxed()
     }
+
+    /// Streams a response constrained to the JSON schema.
+    fn request_structured_stream(
+        &self,
+        request: ModelRequest,
+        schema: String,
+    ) -> BoxStream<'_, Result<StreamEvent, GenerateContentError>> {
+        match json_generation_config(&schema) {
+            Ok(config) => self.stream_content(request, Some(config)),
+            Err(e) => Box::pin(futures_util::stream::once(async move { Err(e.into()) })),
+        }
+    }
+}
+
+impl GeminiModel {
+    /// Converts `request` for the Gemini API using `generation_config`.
+    ///
+    /// When the request has cache breakpoints and the model was not given a
+    /// `cached_content` explicitly, the prefix ending at the last breakpoint is
+    /// served from a cached content managed by the model.
+    async fn gemini_request(
+        &self,
+        request: ModelRequest,
+        generation_config: Option<GenerationConfig>,
+    ) -> Result<GenerateContentRequest, GenerateContentError> {
+        let request = prepare_request(self, request).await?;
+        let breakpoints = request.cache.clone();
+        let mut gemini_request = conversion::convert_request_to_gemini(
+            request,
+            self.model.clone(),
+            self.system_instruction.clone(),
+            self.tool_config.clone(),
+            self.safety_settings.clone(),
+            generation_config,
+            self.cached_content.clone(),
+        )?;
+        if let Some(breakpoints) = breakpoints
+            && self.cached_content.is_none()
+        {
+            self.prompt_cache
+                .apply(&self.client, &mut gemini_request, &breakpoints)
+                .await;
+        }
+        Ok(gemini_request)
+    }
+
+    /// Streams generated content using `generation_config`.
+    fn stream_content(
+        &self,
+        request: ModelRequest,
+        generation_config: Option<GenerationConfig>,
+    ) -> BoxStream<'_, Result<StreamEvent, GenerateContentError>> {
+        let client = self.client.clone();
+
+        let stream = try_stream! {
+            let gemini_request = self.gemini_request(request, generation_config).await?;
+            let mut response_stream = gemini_request.stream(&client);
+
+            while let Some(response) = response_stream.next().await {
+                let response = response.map_err(GeminiError::Api)?;
+                let events = conversion::convert_response_to_stream_events(response);
+                for event in events {
+                    yield event?;
+                }
+            }
+        };
+
+        Box::pin(stream)
+    }
+}
+
+/// Builds a generation config requesting JSON output that matches `schema`.
+fn json_generation_config(schema: &str) -> Result<GenerationConfig, GeminiError> {
+    // Remove the $schema field if present (Gemini API doesn't accept it)
+    let mut schema: serde_json::Value =
+        serde_json::from_str(schema).map_err(|e| GeminiError::InvalidSchema(e.to_string()))?;
+    if let Some(obj) = schema.as_object_mut() {
+        obj.remove("$schema");
+    }
+
+    Ok(GenerationConfig::builder()
+        .response_mime_type("application/json")
+        .response_schema(schema)
+        .build())
 }
 
 #[cfg(test)]
@@ -282,6 +350,8 @@ mod tests {
                 messages,
                 system_message: None,
                 tools: None,
+                generation_config: None,
+                cache: None,
             })
             .await;
 
@@ -407,6 +477,8 @@ mod tests {
                 messages,
                 system_message: None,
                 tools: None,
+                generation_config: None,
+                cache: None,
             })
             .await;
 
@@ -445,6 +517,8 @@ mod tests {
             messages,
             system_message: None,
             tools: None,
+            generation_config: None,
+            cache: None,
         };
 
         let mut stream = model.request_stream(request);
@@ -471,6 +545,9 @@ mod tests {
                     content_received = true;
                     print!("{text}"); // Print the streaming text
                 }
+                StreamEvent::ReasoningDelta(_) => {
+                    // Reasoning is handled but doesn't affect this test logic
+                }
                 StreamEvent::ToolCall(_) => {
                     // Tool calls are handled but don't affect this test logic
                 }
@@ -567,6 +644,8 @@ Received {} events total",
                 messages: vec![message],
                 system_message: None,
                 tools: Some(vec![tool.clone()]), // Actually provide tools here!
+                generation_config: None,
+                cache: None,
             },
             "gemini-1.5-flash".to_string(),
             None, // system_instruction
@@ -607,4 +686,57 @@ Received {} events total",
             }
         }
     }
+
+    #[test]
+    fn test_generation_config_overrides_model_defaults() {
+        use crate::model::request::{GenerationConfig as AiOxGenerationConfig, ToolChoice};
+        use gemini_ox::tool::config::Mode;
+
+        let request = ModelRequest::builder()
+            .messages([Message::new(
+                MessageRole::User,
+                vec![Part::Text {
+                    text: "Hi".to_string(),
+                    ext: std::collections::BTreeMap::new(),
+                }],
+            )])
+            .generation_config(
+                AiOxGenerationConfig::builder()
+                    .temperature(0.2)
+                    .seed(42)
+                    .tool_choice(ToolChoice::Tool("lookup".to_string()))
+                    .build(),
+            )
+            .build();
+        let model_defaults = GenerationConfig::builder()
+            .temperature(1.0)
+            .max_output_tokens(512)
+            .build();
+
+        let gemini_request = conversion::convert_request_to_gemini(
+            request,
+            "gemini-2.0-flash".to_string(),
+            None,
+            None,
+            None,
+            Some(model_defaults),
+            None,
+        )
+        .unwrap();
+
+        let generation_config = gemini_request.generation_config.unwrap();
+        assert_eq!(generation_config.temperature, Some(0.2));
+        assert_eq!(generation_config.max_output_tokens, Some(512));
+        assert_eq!(generation_config.seed, Some(42));
+
+        let function_calling_config = gemini_request
+            .tool_config
+            .and_then(|config| config.function_calling_config)
+            .unwrap();
+        assert_eq!(function_calling_config.mode, Some(Mode::Any));
+        assert_eq!(
+            function_calling_config.allowed_function_names,
+            Some(vec!["lookup".to_string()])
+        );
+    }
 }
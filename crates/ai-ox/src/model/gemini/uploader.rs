@@ -0,0 +1,43 @@
+use std::{future::Future, pin::Pin};
+
+use gemini_ox::Gemini;
+
+use crate::provider::{UploadError, Uploader};
+
+/// Uploads blobs through the Gemini Files API.
+///
+/// Use it with [`ConversionPolicy::UploadAllowed`](crate::provider::ConversionPolicy)
+/// to send inline data that exceeds Gemini's request size limit as file URIs.
+#[derive(Debug, Clone)]
+pub struct GeminiUploader {
+    client: Gemini,
+}
+
+impl GeminiUploader {
+    /// Creates an uploader that uses the given Gemini client.
+    pub fn new(client: Gemini) -> Self {
+        Self { client }
+    }
+}
+
+impl Uploader for GeminiUploader {
+    fn upload(
+        &self,
+        data: Vec<u8>,
+        mime_type: String,
+        name: Option<String>,
+    ) -> Pin<Box<dyn Future<Output = Result<String, UploadError>> + Send>> {
+        let client = self.client.clone();
+        Box::pin(async move {
+            client
+                .upload_file()
+                .file_name(name.unwrap_or_else(|| "ai-ox-upload".to_string()))
+                .mime_type(mime_type)
+                .data(data)
+                .build()
+                .send()
+                .await
+                .map_err(|e| UploadError::Failed(e.to_string()))
+        })
+    }
+}
@@ -12,7 +12,7 @@ use crate::{
         part::Part,
     },
     errors::GenerateContentError,
-    model::ModelRequest,
+    model::{ModelRequest, options::GenerationOption},
     usage::Usage,
 };
 
@@ -25,6 +25,13 @@ pub fn convert_request_to_groq(
     system_instruction: Option<String>,
     _tool_choice: Option<ai_ox_common::openai_format::ToolChoice>,
 ) -> Result<ChatRequest, GenerateContentError> {
+    let config = request.generation_config.unwrap_or_default();
+    // Tool calling is not wired up for Groq yet, so a tool choice cannot be honoured.
+    config.reject(
+        "Groq",
+        &[GenerationOption::TopK, GenerationOption::ToolChoice],
+    )?;
+
     let mut groq_messages = Vec::new();
 
     // Add system instruction if provided
@@ -57,10 +64,17 @@ pub fn convert_request_to_groq(
     }
 
     // TODO: Add tool support later
-    Ok(ChatRequest::builder()
+    let mut chat_request = ChatRequest::builder()
         .model(model)
         .messages(groq_messages)
-        .build())
+        .build();
+    chat_request.temperature = config.temperature.map(|t| t as f32);
+    chat_request.top_p = config.top_p.map(|p| p as f32);
+    chat_request.max_completion_tokens = config.max_output_tokens;
+    chat_request.stop = config.stop_sequences;
+    chat_request.seed = config.seed;
+
+    Ok(chat_request)
 }
 
 /// Extract text content from content parts
@@ -179,6 +193,8 @@ pub fn convert_response_to_stream_events(
             events.push(Ok(StreamEvent::StreamStop(StreamStop {
                 usage,
                 finish_reason: reason,
+                model_name: None,
+                vendor_name: None,
             })));
         }
     }
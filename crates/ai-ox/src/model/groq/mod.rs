@@ -7,7 +7,10 @@ use crate::{
     ModelResponse,
     content::delta::StreamEvent,
     errors::GenerateContentError,
-    model::{Model, ModelInfo, ModelRequest, Provider, response::RawStructuredResponse},
+    model::{
+        Model, ModelInfo, ModelRequest, Provider, prepare_request, response::RawStructuredResponse,
+    },
+    provider::{Capabilities, ConversionPolicy},
     usage::Usage,
 };
 use ai_ox_common::openai_format::ToolChoice;
@@ -37,6 +40,9 @@ pub struct GroqModel {
     /// Tool choice configuration
     #[builder(default = default_tool_choice())]
     tool_choice: ToolChoice,
+    /// Policy for content the provider can't represent exactly.
+    #[builder(default)]
+    conversion_policy: ConversionPolicy,
 }
 
 impl<S: groq_model_builder::State> GroqModelBuilder<S> {
@@ -60,6 +66,7 @@ impl GroqModel {
             model: model.into(),
             system_instruction: None,
             tool_choice: default_tool_choice(),
+            conversion_policy: ConversionPolicy::default(),
         })
     }
 }
@@ -73,12 +80,21 @@ impl Model for GroqModel {
         &self.model
     }
 
+    fn capabilities(&self) -> Option<Capabilities> {
+        Some(Capabilities::groq())
+    }
+
+    fn conversion_policy(&self) -> &ConversionPolicy {
+        &self.conversion_policy
+    }
+
     /// Sends a request to the Groq API and returns the response.
     fn request(
         &self,
         request: ModelRequest,
     ) -> BoxFuture<'_, Result<ModelResponse, GenerateContentError>> {
         async move {
+            let request = prepare_request(self, request).await?;
             let groq_request = conversion::convert_request_to_groq(
                 request,
                 self.model.clone(),
@@ -103,6 +119,7 @@ impl Model for GroqModel {
         let client = self.client.clone();
 
         let stream = try_stream! {
+            let request = prepare_request(self, request).await?;
             let groq_request = conversion::convert_request_to_groq(
                 request,
                 self.model.clone(),
@@ -129,6 +146,7 @@ impl Model for GroqModel {
         schema: String,
     ) -> BoxFuture<'_, Result<RawStructuredResponse, GenerateContentError>> {
         async move {
+            let request = prepare_request(self, request).await?;
             // For Groq, we use response_format with json_schema type
             let mut groq_request = conversion::convert_request_to_groq(
                 request,
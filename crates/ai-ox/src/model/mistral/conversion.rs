@@ -21,7 +21,7 @@ use crate::{
         part::{DataRef, Part},
     },
     errors::GenerateContentError,
-    model::ModelRequest,
+    model::{ModelRequest, options::GenerationOption},
     tool::{ToolUse, decode_tool_result_parts, encode_tool_result_parts},
     usage::Usage,
 };
@@ -36,6 +36,9 @@ pub fn convert_request_to_mistral(
     system_instruction: Option<String>,
     tool_choice: Option<mistral_ox::tool::ToolChoice>,
 ) -> Result<ChatRequest, GenerateContentError> {
+    let config = request.generation_config.unwrap_or_default();
+    config.reject("Mistral", &[GenerationOption::TopK])?;
+
     let mut mistral_messages = Vec::new();
 
     // Add system instruction if provided
@@ -52,22 +55,29 @@ pub fn convert_request_to_mistral(
     }
 
     // Convert tools if present
-    if let Some(tools) = request.tools {
+    let mut chat_request = if let Some(tools) = request.tools {
         let common_tools = convert_tools_to_mistral(tools)?;
-        Ok(ChatRequest::builder()
+        ChatRequest::builder()
             .model(model)
             .messages(mistral_messages)
             .tools(common_tools)
-            // Omit tool_choice entirely: Mistral treats absence as 'auto'.
-            // Setting Some(Auto) with the shared OpenAI type serializes to null due to untagged enum,
-            // which Mistral rejects. Omitting avoids invalid null.
-            .build())
+            // Omit tool_choice unless requested: Mistral treats absence as 'auto'.
+            .build()
     } else {
-        Ok(ChatRequest::builder()
+        ChatRequest::builder()
             .model(model)
             .messages(mistral_messages)
-            .build())
-    }
+            .build()
+    };
+
+    chat_request.temperature = config.temperature.map(|t| t as f32);
+    chat_request.top_p = config.top_p.map(|p| p as f32);
+    chat_request.max_tokens = config.max_output_tokens;
+    chat_request.stop = config.stop_sequences;
+    chat_request.random_seed = config.seed;
+    chat_request.tool_choice = config.tool_choice.map(Into::into);
+
+    Ok(chat_request)
 }
 
 /// Convert from ai-ox Message to Mistral Message(s)
@@ -116,6 +126,8 @@ fn convert_message_to_mistral(
                             )));
                         }
                     }
+                    // Mistral cannot replay reasoning, so it is dropped.
+                    Part::Reasoning { .. } => {}
                     Part::ToolUse { .. } => {
                         return Err(GenerateContentError::message_conversion(
                             "Tool calls should not appear in user messages",
@@ -171,6 +183,8 @@ fn convert_message_to_mistral(
                     Part::Text { text, .. } => {
                         text_content.push_str(&text);
                     }
+                    // Mistral cannot replay reasoning, so it is dropped.
+                    Part::Reasoning { .. } => {}
                     Part::ToolUse { id, name, args, .. } => {
                         let args_str = serde_json::to_string(&args)
                             .map_err(|e| GenerateContentError::message_conversion(e.to_string()))?;
@@ -263,6 +277,10 @@ fn convert_tools_to_mistral(
             crate::tool::Tool::GeminiTool(_) => {
                 // Skip Gemini tools for Mistral
             }
+            #[cfg(feature = "openai")]
+            crate::tool::Tool::OpenAITool(_) => {
+                // Skip OpenAI built-in tools for Mistral
+            }
         }
     }
 
@@ -394,6 +412,8 @@ pub fn convert_response_to_stream_events(
                 events.push(Ok(StreamEvent::StreamStop(StreamStop {
                     usage,
                     finish_reason: FinishReason::Stop,
+                    model_name: None,
+                    vendor_name: None,
                 })));
             }
         }
@@ -787,4 +807,39 @@ mod tests {
                 .contains("Mistral does not support images in assistant messages")
         );
     }
+
+    #[test]
+    fn test_generation_config_mapping() {
+        use crate::model::request::{GenerationConfig, ToolChoice};
+
+        let request = ModelRequest::builder()
+            .messages([Message::new(MessageRole::User, vec![Part::text("Hi")])])
+            .generation_config(
+                GenerationConfig::builder()
+                    .temperature(0.3)
+                    .seed(11)
+                    .max_output_tokens(64)
+                    .tool_choice(ToolChoice::Required)
+                    .build(),
+            )
+            .build();
+
+        let chat_request =
+            convert_request_to_mistral(request, "mistral-small".into(), None, None).unwrap();
+        assert_eq!(chat_request.temperature, Some(0.3));
+        assert_eq!(chat_request.random_seed, Some(11));
+        assert_eq!(chat_request.max_tokens, Some(64));
+        assert_eq!(
+            serde_json::to_value(&chat_request.tool_choice).unwrap(),
+            json!("required")
+        );
+
+        let request = ModelRequest::builder()
+            .generation_config(GenerationConfig::builder().top_k(5).build())
+            .build();
+        assert!(matches!(
+            convert_request_to_mistral(request, "mistral-small".into(), None, None),
+            Err(GenerateContentError::UnsupportedFeature(_))
+        ));
+    }
 }
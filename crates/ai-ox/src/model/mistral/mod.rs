@@ -7,7 +7,10 @@ use crate::{
     ModelResponse,
     content::delta::StreamEvent,
     errors::GenerateContentError,
-    model::{Model, ModelInfo, ModelRequest, Provider, response::RawStructuredResponse},
+    model::{
+        Model, ModelInfo, ModelRequest, Provider, prepare_request, response::RawStructuredResponse,
+    },
+    provider::{Capabilities, ConversionPolicy},
     usage::Usage,
 };
 use async_stream::try_stream;
@@ -36,6 +39,9 @@ pub struct MistralModel {
     /// Tool choice configuration
     #[builder(default = default_tool_choice())]
     tool_choice: ToolChoice,
+    /// Policy for content the provider can't represent exactly.
+    #[builder(default)]
+    conversion_policy: ConversionPolicy,
 }
 
 impl<S: mistral_model_builder::State> MistralModelBuilder<S> {
@@ -59,6 +65,7 @@ impl MistralModel {
             model: model.into(),
             system_instruction: None,
             tool_choice: default_tool_choice(),
+            conversion_policy: ConversionPolicy::default(),
         })
     }
 }
@@ -72,12 +79,21 @@ impl Model for MistralModel {
         &self.model
     }
 
+    fn capabilities(&self) -> Option<Capabilities> {
+        Some(Capabilities::mistral())
+    }
+
+    fn conversion_policy(&self) -> &ConversionPolicy {
+        &self.conversion_policy
+    }
+
     /// Sends a request to the Mistral API and returns the response.
     fn request(
         &self,
         request: ModelRequest,
     ) -> BoxFuture<'_, Result<ModelResponse, GenerateContentError>> {
         async move {
+            let request = prepare_request(self, request).await?;
             let mistral_request = conversion::convert_request_to_mistral(
                 request,
                 self.model.clone(),
@@ -102,6 +118,7 @@ impl Model for MistralModel {
         let client = self.client.clone();
 
         let stream = try_stream! {
+            let request = prepare_request(self, request).await?;
             let mistral_request = conversion::convert_request_to_mistral(
                 request,
                 self.model.clone(),
@@ -128,6 +145,7 @@ impl Model for MistralModel {
         _schema: String,
     ) -> BoxFuture<'_, Result<RawStructuredResponse, GenerateContentError>> {
         async move {
+            let request = prepare_request(self, request).await?;
             // For Mistral, we use response_format with json_object type
             let mut mistral_request = conversion::convert_request_to_mistral(
                 request,
@@ -0,0 +1,514 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+
+use futures_util::{FutureExt, StreamExt, future::BoxFuture, stream::BoxStream};
+use serde_json::Value;
+
+use crate::{
+    ModelResponse,
+    agent::StreamAccumulator,
+    content::{
+        delta::{FinishReason, ReasoningDelta, StreamEvent, StreamStop},
+        message::{Message, MessageRole},
+        part::Part,
+    },
+    errors::GenerateContentError,
+    model::{Model, ModelInfo, Provider, request::ModelRequest, response::RawStructuredResponse},
+    provider::Capabilities,
+    tool::ToolUse,
+    usage::Usage,
+};
+
+/// Vendor name reported in responses from a [`MockModel`].
+const VENDOR_NAME: &str = "mock";
+
+/// Predicate choosing which requests a scripted reply answers.
+type Matcher = Arc<dyn Fn(&ModelRequest) -> bool + Send + Sync>;
+
+/// A scripted reply served by a [`MockModel`].
+#[derive(Debug)]
+pub enum MockReply {
+    /// A complete response.
+    ///
+    /// Streaming requests receive it as text, reasoning and tool call events
+    /// followed by its usage and a stop event.
+    Response(Box<ModelResponse>),
+    /// A sequence of stream events, optionally ending in an error.
+    ///
+    /// Non-streaming requests receive the events accumulated into a response,
+    /// or the first error among them.
+    Stream(Vec<Result<StreamEvent, GenerateContentError>>),
+    /// An error returned instead of a response.
+    Error(GenerateContentError),
+}
+
+impl MockReply {
+    /// Creates an assistant response with the given parts.
+    pub fn parts(parts: Vec<Part>) -> Self {
+        Self::Response(Box::new(ModelResponse {
+            message: Message::new(MessageRole::Assistant, parts),
+            model_name: String::new(),
+            usage: Usage::new(),
+            vendor_name: VENDOR_NAME.to_string(),
+        }))
+    }
+
+    /// Creates an assistant response containing `text`.
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::parts(vec![Part::text(text)])
+    }
+
+    /// Creates an assistant response containing `value` as JSON text.
+    ///
+    /// This is the reply structured requests expect.
+    pub fn json(value: Value) -> Self {
+        Self::text(value.to_string())
+    }
+
+    /// Creates an assistant response calling a single tool.
+    pub fn tool_call(id: impl Into<String>, name: impl Into<String>, args: Value) -> Self {
+        Self::parts(vec![Part::tool_use(id, name, args)])
+    }
+
+    /// Creates a stream yielding `events`.
+    pub fn stream(events: impl IntoIterator<Item = StreamEvent>) -> Self {
+        Self::Stream(events.into_iter().map(Ok).collect())
+    }
+
+    /// Creates a stream yielding `events` and then failing with `error`.
+    pub fn stream_error(
+        events: impl IntoIterator<Item = StreamEvent>,
+        error: GenerateContentError,
+    ) -> Self {
+        Self::Stream(events.into_iter().map(Ok).chain([Err(error)]).collect())
+    }
+
+    /// Converts this reply into a response from `model_name`.
+    fn into_response(self, model_name: &str) -> Result<ModelResponse, GenerateContentError> {
+        let mut response = match self {
+            Self::Response(response) => *response,
+            Self::Stream(events) => {
+                let mut accumulator = StreamAccumulator::new();
+                for event in events {
+                    accumulator.accumulate(&event?);
+                }
+                let usage = accumulator.get_usage();
+                let (message, _) = accumulator.finalize();
+                ModelResponse {
+                    message,
+                    model_name: String::new(),
+                    usage,
+                    vendor_name: VENDOR_NAME.to_string(),
+                }
+            }
+            Self::Error(error) => return Err(error),
+        };
+        if response.model_name.is_empty() {
+            response.model_name = model_name.to_string();
+        }
+        Ok(response)
+    }
+
+    /// Converts this reply into stream events.
+    fn into_events(self) -> Vec<Result<StreamEvent, GenerateContentError>> {
+        match self {
+            Self::Response(response) => response_events(*response).into_iter().map(Ok).collect(),
+            Self::Stream(events) => events,
+            Self::Error(error) => vec![Err(error)],
+        }
+    }
+}
+
+/// Splits a response into the events a provider would have streamed.
+fn response_events(response: ModelResponse) -> Vec<StreamEvent> {
+    let mut events = Vec::new();
+    let mut finish_reason = FinishReason::Stop;
+
+    for part in response.message.content {
+        match part {
+            Part::Text { text, .. } => events.push(StreamEvent::TextDelta(text)),
+            Part::Reasoning {
+                text,
+                signature,
+                redacted,
+                ext,
+            } => events.push(StreamEvent::ReasoningDelta(ReasoningDelta {
+                text,
+                signature,
+                redacted,
+                ext,
+            })),
+            Part::ToolUse {
+                id,
+                name,
+                args,
+                ext,
+            } => {
+                finish_reason = FinishReason::ToolCalls;
+                events.push(StreamEvent::ToolCall(ToolUse {
+                    id,
+                    name,
+                    args,
+                    ext: (!ext.is_empty()).then_some(ext),
+                }));
+            }
+            _ => {}
+        }
+    }
+
+    events.push(StreamEvent::Usage(response.usage.clone()));
+    events.push(StreamEvent::StreamStop(StreamStop {
+        finish_reason,
+        usage: response.usage,
+        model_name: None,
+        vendor_name: None,
+    }));
+    events
+}
+
+/// A reply waiting in a [`MockModel`]'s script.
+struct Scripted {
+    /// Requests this reply may answer; any request if `None`.
+    matcher: Option<Matcher>,
+    /// The reply to serve.
+    reply: MockReply,
+}
+
+/// Mutable state of a [`MockModel`].
+#[derive(Default)]
+struct MockState {
+    /// Replies not served yet, in the order they were scripted.
+    script: VecDeque<Scripted>,
+    /// Every request received, in order.
+    requests: Vec<ModelRequest>,
+}
+
+/// A scriptable in-memory model for testing code built on [`Model`].
+///
+/// Each request is answered with the first scripted reply whose matcher
+/// accepts it, and that reply is then removed from the script. Requests with
+/// no matching reply fail with a configuration error. Every request is
+/// recorded so tests can assert on what was sent.
+#[derive(Clone)]
+pub struct MockModel {
+    /// Name reported by the model and its responses.
+    name: String,
+    /// Delay before each reply is served.
+    latency: Option<Duration>,
+    /// Capabilities reported to planners and routers.
+    capabilities: Option<Capabilities>,
+    /// Script and recorded requests, shared between clones.
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockModel {
+    /// Creates a model named `name` with an empty script.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            latency: None,
+            capabilities: None,
+            state: Arc::default(),
+        }
+    }
+
+    /// Appends a reply for the next request that reaches it.
+    pub fn with_reply(self, reply: MockReply) -> Self {
+        self.push(None, reply);
+        self
+    }
+
+    /// Appends a reply for the next request accepted by `matcher`.
+    pub fn with_reply_when(
+        self,
+        matcher: impl Fn(&ModelRequest) -> bool + Send + Sync + 'static,
+        reply: MockReply,
+    ) -> Self {
+        self.push(Some(Arc::new(matcher)), reply);
+        self
+    }
+
+    /// Delays every reply by `latency`.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+
+    /// Reports `capabilities`, so requests are planned and routed as they
+    /// would be for a real provider.
+    pub fn with_capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.capabilities = Some(capabilities);
+        self
+    }
+
+    /// Appends a reply to the script of a model that is already in use.
+    pub fn push_reply(&self, reply: MockReply) {
+        self.push(None, reply);
+    }
+
+    /// Returns every request received so far, in order.
+    pub fn requests(&self) -> Vec<ModelRequest> {
+        self.lock().requests.clone()
+    }
+
+    /// Returns the most recent request, if any.
+    pub fn last_request(&self) -> Option<ModelRequest> {
+        self.lock().requests.last().cloned()
+    }
+
+    /// Returns the number of scripted replies not served yet.
+    pub fn remaining_replies(&self) -> usize {
+        self.lock().script.len()
+    }
+
+    /// Locks the state, recovering it if a test panicked while holding it.
+    fn lock(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn push(&self, matcher: Option<Matcher>, reply: MockReply) {
+        self.lock().script.push_back(Scripted { matcher, reply });
+    }
+
+    /// Records `request` and takes the reply scripted for it.
+    fn take_reply(&self, request: ModelRequest) -> MockReply {
+        let mut state = self.lock();
+        let index = state.script.iter().position(|scripted| {
+            scripted
+                .matcher
+                .as_ref()
+                .is_none_or(|matcher| matcher(&request))
+        });
+        state.requests.push(request);
+
+        match index.and_then(|index| state.script.remove(index)) {
+            Some(scripted) => scripted.reply,
+            None => MockReply::Error(GenerateContentError::configuration(format!(
+                "MockModel '{}' has no scripted reply for request {}",
+                self.name,
+                state.requests.len()
+            ))),
+        }
+    }
+
+    /// Waits for the configured latency, if any.
+    async fn delay(&self) {
+        if let Some(latency) = self.latency {
+            tokio::time::sleep(latency).await;
+        }
+    }
+}
+
+impl fmt::Debug for MockModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.lock();
+        f.debug_struct("MockModel")
+            .field("name", &self.name)
+            .field("latency", &self.latency)
+            .field("remaining_replies", &state.script.len())
+            .field("requests", &state.requests.len())
+            .finish()
+    }
+}
+
+impl Model for MockModel {
+    fn info(&self) -> ModelInfo<'_> {
+        ModelInfo(Provider::Mock, &self.name)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn capabilities(&self) -> Option<Capabilities> {
+        self.capabilities.clone()
+    }
+
+    fn request(
+        &self,
+        request: ModelRequest,
+    ) -> BoxFuture<'_, Result<ModelResponse, GenerateContentError>> {
+        let reply = self.take_reply(request);
+        async move {
+            self.delay().await;
+            reply.into_response(&self.name)
+        }
+        .boxed()
+    }
+
+    fn request_stream(
+        &self,
+        request: ModelRequest,
+    ) -> BoxStream<'_, Result<StreamEvent, GenerateContentError>> {
+        let events = self.take_reply(request).into_events();
+        async move {
+            self.delay().await;
+            futures_util::stream::iter(events)
+        }
+        .flatten_stream()
+        .boxed()
+    }
+
+    fn request_structured_internal(
+        &self,
+        request: ModelRequest,
+        _schema: String,
+    ) -> BoxFuture<'_, Result<RawStructuredResponse, GenerateContentError>> {
+        let reply = self.take_reply(request);
+        async move {
+            self.delay().await;
+            let response = reply.into_response(&self.name)?;
+            let text = response.to_string().unwrap_or_default();
+            let json = serde_json::from_str(&text)
+                .map_err(|e| GenerateContentError::response_parsing(e.to_string()))?;
+            Ok(RawStructuredResponse {
+                json,
+                vendor_name: response.vendor_name,
+                model_name: response.model_name,
+                usage: response.usage,
+            })
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        agent::Agent,
+        tool::{FunctionMetadata, Tool, ToolBox, ToolError},
+    };
+    use serde_json::json;
+    use std::time::Instant;
+
+    struct EchoTool;
+
+    impl ToolBox for EchoTool {
+        fn tools(&self) -> Vec<Tool> {
+            vec![Tool::FunctionDeclarations(vec![FunctionMetadata {
+                name: "echo".to_string(),
+                description: None,
+                parameters: json!({"type": "object"}),
+            }])]
+        }
+
+        fn invoke(&self, call: ToolUse) -> BoxFuture<'_, Result<Part, ToolError>> {
+            async move {
+                let text = call.args["text"].as_str().unwrap_or_default().to_string();
+                Ok(Part::tool_result(
+                    call.id,
+                    call.name,
+                    vec![Part::text(text)],
+                ))
+            }
+            .boxed()
+        }
+    }
+
+    fn request(text: &str) -> ModelRequest {
+        ModelRequest {
+            messages: vec![Message::new(MessageRole::User, vec![Part::text(text)])],
+            system_message: None,
+            tools: None,
+            generation_config: None,
+            cache: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_agent_tool_loop_is_recorded() {
+        let model = MockModel::new("mock")
+            .with_reply(MockReply::tool_call(
+                "call-1",
+                "echo",
+                json!({"text": "pong"}),
+            ))
+            .with_reply(MockReply::text("done"));
+        let agent = Agent::model(model.clone()).tools(EchoTool).build();
+
+        let response = agent
+            .run([Message::new(MessageRole::User, vec![Part::text("ping")])])
+            .await;
+        assert_eq!(response.unwrap().to_string().as_deref(), Some("done"));
+
+        let requests = model.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[1].messages.last().unwrap().content,
+            vec![Part::tool_result(
+                "call-1",
+                "echo",
+                vec![Part::text("pong")]
+            )]
+        );
+        assert_eq!(model.remaining_replies(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_matcher_chooses_reply() {
+        let model = MockModel::new("mock")
+            .with_reply_when(
+                |request| {
+                    request.messages.iter().flat_map(|m| &m.content).any(
+                        |part| matches!(part, Part::Text { text, .. } if text.contains("weather")),
+                    )
+                },
+                MockReply::text("sunny"),
+            )
+            .with_reply(MockReply::text("fallback"));
+
+        let first = model.request(request("hello")).await.unwrap();
+        let second = model.request(request("weather?")).await.unwrap();
+        assert_eq!(first.to_string().as_deref(), Some("fallback"));
+        assert_eq!(second.to_string().as_deref(), Some("sunny"));
+
+        let error = model.request(request("again")).await;
+        assert!(matches!(error, Err(GenerateContentError::Configuration(_))));
+    }
+
+    #[tokio::test]
+    async fn test_stream_replays_events_and_errors() {
+        let model = MockModel::new("mock")
+            .with_reply(MockReply::stream_error(
+                [StreamEvent::TextDelta("partial".to_string())],
+                GenerateContentError::provider_error("mock", "connection reset"),
+            ))
+            .with_reply(MockReply::text("whole"));
+
+        let events: Vec<_> = model.request_stream(request("a")).collect().await;
+        assert!(
+            matches!(&events[..], [Ok(StreamEvent::TextDelta(text)), Err(_)] if text == "partial")
+        );
+
+        let events: Vec<_> = model.request_stream(request("b")).collect().await;
+        assert!(matches!(
+            &events[..],
+            [
+                Ok(StreamEvent::TextDelta(text)),
+                Ok(StreamEvent::Usage(_)),
+                Ok(StreamEvent::StreamStop(StreamStop { finish_reason: FinishReason::Stop, .. })),
+            ] if text == "whole"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_structured_reply_and_latency() {
+        let model = MockModel::new("mock")
+            .with_latency(Duration::from_millis(20))
+            .with_reply(MockReply::json(json!({"answer": 42})));
+
+        let start = Instant::now();
+        let response = model
+            .request_structured_internal(request("question"), "{}".to_string())
+            .await
+            .unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        assert_eq!(response.json, json!({"answer": 42}));
+        assert_eq!(response.model_name, "mock");
+    }
+}
@@ -2,16 +2,39 @@
 pub mod anthropic;
 #[cfg(feature = "bedrock")]
 pub mod bedrock;
+pub mod cache;
 #[cfg(feature = "gemini")]
 pub mod gemini;
 #[cfg(feature = "groq")]
 pub mod groq;
 #[cfg(feature = "mistral")]
 pub mod mistral;
+#[cfg(any(test, feature = "testing"))]
+pub mod mock;
+#[cfg(feature = "openai")]
+pub mod openai;
 #[cfg(feature = "openrouter")]
 pub mod openrouter;
+#[cfg(any(
+    feature = "anthropic",
+    feature = "bedrock",
+    feature = "groq",
+    feature = "mistral",
+    feature = "openai"
+))]
+pub(crate) mod options;
+pub mod partial;
 pub mod request;
 pub mod response;
+pub mod router;
+pub mod structured;
+
+pub use cache::{CacheBreakpoints, CacheTtl};
+pub use partial::{Partial, StructuredStreamEvent, parse_partial_json};
+pub use router::{FallbackModel, RequestProfile, RouterModel};
+pub use structured::{
+    ToolStructuredModel, request_structured_via_tool, stream_structured_via_tool,
+};
 
 use futures_util::{future::BoxFuture, stream::BoxStream};
 use schemars::JsonSchema;
@@ -25,8 +48,12 @@ use crate::{
         request::ModelRequest,
         response::{ModelResponse, RawStructuredResponse},
     },
+    provider::{Capabilities, ConversionPlan, ConversionPolicy},
 };
 
+/// Policy used by models that don't carry their own.
+static STRICT_POLICY: ConversionPolicy = ConversionPolicy::Strict;
+
 /// Supported model providers.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString)]
 #[strum(serialize_all = "lowercase")]
@@ -43,6 +70,10 @@ pub enum Provider {
     Bedrock,
     #[cfg(feature = "anthropic")]
     Anthropic,
+    #[cfg(feature = "openai")]
+    OpenAI,
+    #[cfg(any(test, feature = "testing"))]
+    Mock,
 }
 
 /// Model information containing provider and model identifier.
@@ -76,6 +107,33 @@ pub trait Model: Send + Sync + 'static + std::fmt::Debug {
     /// A string slice containing the model name.
     fn name(&self) -> &str;
 
+    /// Returns the content capabilities of the provider behind this model.
+    ///
+    /// Models that return `None` are not planned: every request is sent as is.
+    fn capabilities(&self) -> Option<Capabilities> {
+        None
+    }
+
+    /// Returns the policy applied to content the provider can't represent.
+    fn conversion_policy(&self) -> &ConversionPolicy {
+        &STRICT_POLICY
+    }
+
+    /// Plans how `request` will be adapted to this model's provider without
+    /// sending it.
+    ///
+    /// The plan lists one action per top-level part along with any errors and
+    /// warnings, so callers can inspect what uploads or shadowing a request
+    /// would cause before calling [`Model::request`].
+    fn plan_conversion(&self, request: &ModelRequest) -> ConversionPlan {
+        match self.capabilities() {
+            Some(capabilities) => {
+                ConversionPlan::for_request(&capabilities, self.conversion_policy(), request)
+            }
+            None => ConversionPlan::new(self.name(), self.conversion_policy()),
+        }
+    }
+
     /// Sends a single, non-streaming request to the model.
     ///
     /// # Arguments
@@ -129,6 +187,24 @@ pub trait Model: Send + Sync + 'static + std::fmt::Debug {
         schema: String,
     ) -> BoxFuture<'_, Result<RawStructuredResponse, GenerateContentError>>;
 
+    /// Streams a response that conforms to a JSON schema.
+    ///
+    /// The returned events carry the JSON as [`StreamEvent::TextDelta`]s. The
+    /// default implementation adds the schema to the system message and calls
+    /// [`Model::request_stream`]; providers that can constrain streamed output
+    /// natively override it.
+    ///
+    /// Prefer the generic `stream_typed` helper method, which parses the
+    /// deltas as they arrive.
+    fn request_structured_stream(
+        &self,
+        mut request: ModelRequest,
+        schema: String,
+    ) -> BoxStream<'_, Result<StreamEvent, GenerateContentError>> {
+        partial::instruct_json(&mut request, &schema);
+        self.request_stream(request)
+    }
+
     /// Generates structured content that conforms to a specific schema.
     ///
     /// This is a high-level helper method that takes a collection of messages and returns
@@ -171,6 +247,8 @@ pub trait Model: Send + Sync + 'static + std::fmt::Debug {
             messages: msgs,
             system_message: None,
             tools: None,
+            generation_config: None,
+            cache: None,
         };
         let schema = serde_json::to_string(&schema_for!(O)).unwrap_or_default();
 
@@ -189,4 +267,75 @@ pub trait Model: Send + Sync + 'static + std::fmt::Debug {
         }
         .boxed()
     }
+
+    /// Streams structured content that conforms to the schema of `O`.
+    ///
+    /// The JSON is parsed tolerantly as it arrives: every time it describes a
+    /// more complete value, a [`StructuredStreamEvent::Partial`] snapshot is
+    /// yielded. Once the stream ends the complete JSON is deserialized and
+    /// yielded as [`StructuredStreamEvent::Complete`].
+    ///
+    /// Like `request_structured`, this method is only available on sized types.
+    fn stream_typed<O>(
+        &self,
+        messages: impl IntoIterator<Item = impl Into<Message>>,
+    ) -> BoxStream<'_, Result<StructuredStreamEvent<O>, GenerateContentError>>
+    where
+        O: DeserializeOwned + JsonSchema + Send + 'static,
+        Self: Sized,
+    {
+        let request = ModelRequest {
+            messages: messages.into_iter().map(Into::into).collect(),
+            system_message: None,
+            tools: None,
+            generation_config: None,
+            cache: None,
+        };
+        let schema = crate::tool::schema_for_type::<O>().to_string();
+
+        partial::typed_stream(
+            self.request_structured_stream(request, schema),
+            vendor_name(self),
+            self.name().to_string(),
+            |text| {
+                serde_json::from_str(text)
+                    .or_else(|e| match crate::agent::repair::extract_json(text) {
+                        Some(json) => serde_json::from_str(json),
+                        None => Err(e),
+                    })
+                    .map_err(|e| GenerateContentError::response_parsing(e.to_string()))
+            },
+        )
+    }
+}
+
+/// Returns the provider name of `model`, as reported in
+/// [`ModelResponse::vendor_name`].
+#[allow(
+    unreachable_code,
+    reason = "`Provider` has no variants when no provider feature is enabled"
+)]
+pub(crate) fn vendor_name<M: Model + ?Sized>(model: &M) -> String {
+    model.info().0.to_string()
+}
+
+/// Plans and executes the conversion of `request` with the model's policy.
+///
+/// Provider models call this before converting a request to their own format,
+/// so strict failures, uploads and shadowing happen before anything is sent.
+#[cfg(any(
+    feature = "anthropic",
+    feature = "bedrock",
+    feature = "gemini",
+    feature = "groq",
+    feature = "mistral",
+    feature = "openai",
+    feature = "openrouter"
+))]
+pub(crate) async fn prepare_request<M: Model + ?Sized>(
+    model: &M,
+    request: ModelRequest,
+) -> Result<ModelRequest, GenerateContentError> {
+    let plan = model.plan_conversion(&request);
+    Ok(plan.execute(model.conversion_policy(), request).await?)
 }
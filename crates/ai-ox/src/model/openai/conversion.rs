@@ -0,0 +1,423 @@
+use std::collections::BTreeMap;
+
+use ai_ox_common::openai_format::Message as OpenAIMessage;
+use openai_ox::{ChatCompletionChunk, ChatRequest, ChatResponse, StreamOptions};
+
+use crate::{
+    ModelResponse,
+    content::{
+        delta::{FinishReason, StreamEvent, StreamStop},
+        message::{Message, MessageRole},
+        part::Part,
+    },
+    conversion::model_request_to_openai_chat_request,
+    errors::GenerateContentError,
+    model::{ModelRequest, options::GenerationOption},
+    tool::ToolUse,
+    usage::{Modality, Usage},
+};
+
+use super::OpenAIError;
+
+/// Convert from ai-ox ModelRequest to OpenAI ChatRequest
+pub fn convert_request_to_openai(
+    mut request: ModelRequest,
+    model: &str,
+    system_instruction: Option<String>,
+) -> Result<ChatRequest, GenerateContentError> {
+    let config = request.generation_config.take().unwrap_or_default();
+    config.reject("OpenAI", &[GenerationOption::TopK])?;
+
+    let mut chat_request = model_request_to_openai_chat_request(&request, model)?;
+
+    // The request's own system message takes precedence over the model-level instruction
+    if request.system_message.is_none()
+        && let Some(system_instruction) = system_instruction
+    {
+        chat_request
+            .messages
+            .insert(0, OpenAIMessage::system(system_instruction));
+    }
+
+    chat_request.temperature = config.temperature.map(|t| t as f32);
+    chat_request.top_p = config.top_p.map(|p| p as f32);
+    chat_request.max_tokens = config.max_output_tokens;
+    chat_request.stop = config.stop_sequences;
+    chat_request.seed = config.seed.map(u64::from);
+    chat_request.tool_choice = config.tool_choice.map(Into::into);
+
+    Ok(chat_request)
+}
+
+/// Enable the trailing usage chunk on a streaming request
+pub fn enable_stream_usage(request: &mut ChatRequest) {
+    request.stream_options = Some(StreamOptions {
+        include_usage: true,
+    });
+}
+
+/// Convert OpenAI usage to ai-ox Usage
+pub fn convert_usage(usage: &openai_ox::Usage) -> Usage {
+    let mut result = Usage::new();
+    result.requests = 1;
+    result
+        .input_tokens_by_modality
+        .insert(Modality::Text, usage.prompt_tokens());
+    result
+        .output_tokens_by_modality
+        .insert(Modality::Text, usage.completion_tokens());
+    result.cache_read_tokens = usage
+        .prompt_tokens_details
+        .as_ref()
+        .and_then(|details| details.cached_tokens);
+    result.thoughts_tokens = usage
+        .completion_tokens_details
+        .as_ref()
+        .and_then(|details| details.reasoning_tokens);
+    result
+}
+
+/// Convert an OpenAI finish reason string to ai-ox FinishReason
+pub fn convert_finish_reason(reason: &str) -> FinishReason {
+    match reason {
+        "stop" => FinishReason::Stop,
+        "tool_calls" | "function_call" => FinishReason::ToolCalls,
+        "length" => FinishReason::Length,
+        "content_filter" => FinishReason::ContentFilter,
+        _ => FinishReason::Other,
+    }
+}
+
+fn parse_tool_arguments(arguments: &str) -> Result<serde_json::Value, GenerateContentError> {
+    if arguments.trim().is_empty() {
+        return Ok(serde_json::Value::Object(Default::default()));
+    }
+    serde_json::from_str(arguments).map_err(|e| {
+        OpenAIError::ResponseParsing(format!(
+            "Failed to parse tool call arguments: {}. Arguments: '{}'",
+            e, arguments
+        ))
+        .into()
+    })
+}
+
+/// Convert OpenAI ChatResponse to ai-ox ModelResponse
+pub fn convert_openai_response_to_ai_ox(
+    response: ChatResponse,
+    model_name: String,
+) -> Result<ModelResponse, GenerateContentError> {
+    let choice = response
+        .choices
+        .into_iter()
+        .next()
+        .ok_or_else(|| OpenAIError::ResponseParsing("No choices in response".to_string()))?;
+
+    let mut parts = Vec::new();
+
+    if let Some(text) = choice.message.content
+        && !text.is_empty()
+    {
+        parts.push(Part::Text {
+            text,
+            ext: BTreeMap::new(),
+        });
+    }
+
+    for tool_call in choice.message.tool_calls.unwrap_or_default() {
+        parts.push(Part::ToolUse {
+            id: tool_call.id,
+            name: tool_call.function.name,
+            args: parse_tool_arguments(&tool_call.function.arguments)?,
+            ext: BTreeMap::new(),
+        });
+    }
+
+    let message = Message {
+        role: MessageRole::Assistant,
+        content: parts,
+        timestamp: Some(chrono::Utc::now()),
+        ext: Some(BTreeMap::new()),
+    };
+
+    Ok(ModelResponse {
+        message,
+        usage: response
+            .usage
+            .as_ref()
+            .map(convert_usage)
+            .unwrap_or_default(),
+        model_name,
+        vendor_name: "openai".to_string(),
+    })
+}
+
+#[derive(Debug, Default)]
+struct PartialToolCall {
+    id: String,
+    name: String,
+    arguments: String,
+}
+
+/// Accumulates OpenAI streaming chunks into ai-ox stream events.
+///
+/// Tool call fragments are buffered by index and emitted once the choice finishes.
+/// The final `StreamStop` is deferred to [`OpenAIStreamProcessor::finish`] because
+/// OpenAI sends the usage chunk after the chunk carrying the finish reason.
+#[derive(Debug, Default)]
+pub struct OpenAIStreamProcessor {
+    tool_calls: BTreeMap<u32, PartialToolCall>,
+    finish_reason: Option<FinishReason>,
+    usage: Option<Usage>,
+}
+
+impl OpenAIStreamProcessor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Process a single chunk, returning the events it completes
+    pub fn process_chunk(
+        &mut self,
+        chunk: ChatCompletionChunk,
+    ) -> Vec<Result<StreamEvent, GenerateContentError>> {
+        let mut events = Vec::new();
+
+        if let Some(choice) = chunk.choices.into_iter().next() {
+            if let Some(content) = choice.delta.content
+                && !content.is_empty()
+            {
+                events.push(Ok(StreamEvent::TextDelta(content)));
+            }
+
+            for tool_call in choice.delta.tool_calls.unwrap_or_default() {
+                let partial = self.tool_calls.entry(tool_call.index).or_default();
+                if let Some(id) = tool_call.id {
+                    partial.id = id;
+                }
+                if let Some(function) = tool_call.function {
+                    if let Some(name) = function.name {
+                        partial.name.push_str(&name);
+                    }
+                    if let Some(arguments) = function.arguments {
+                        partial.arguments.push_str(&arguments);
+                    }
+                }
+            }
+
+            if let Some(finish_reason) = choice.finish_reason {
+                events.extend(self.flush_tool_calls());
+                self.finish_reason = Some(convert_finish_reason(&finish_reason));
+            }
+        }
+
+        if let Some(usage) = chunk.usage {
+            let usage = convert_usage(&usage);
+            events.push(Ok(StreamEvent::Usage(usage.clone())));
+            self.usage = Some(usage);
+        }
+
+        events
+    }
+
+    /// Finish the stream, emitting any pending tool calls and the final stop event
+    pub fn finish(mut self) -> Vec<Result<StreamEvent, GenerateContentError>> {
+        let mut events = self.flush_tool_calls();
+        events.push(Ok(StreamEvent::StreamStop(StreamStop {
+            finish_reason: self.finish_reason.unwrap_or(FinishReason::Stop),
+            usage: self.usage.unwrap_or_default(),
+            model_name: None,
+            vendor_name: None,
+        })));
+        events
+    }
+
+    fn flush_tool_calls(&mut self) -> Vec<Result<StreamEvent, GenerateContentError>> {
+        std::mem::take(&mut self.tool_calls)
+            .into_values()
+            .map(|partial| {
+                Ok(StreamEvent::ToolCall(ToolUse {
+                    id: partial.id,
+                    name: partial.name,
+                    args: parse_tool_arguments(&partial.arguments)?,
+                    ext: None,
+                }))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::request::{GenerationConfig, ToolChoice};
+    use serde_json::json;
+
+    fn chunk(value: serde_json::Value) -> ChatCompletionChunk {
+        let mut base = json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion.chunk",
+            "created": 0,
+            "model": "gpt-4o-mini",
+            "choices": []
+        });
+        base.as_object_mut()
+            .unwrap()
+            .extend(value.as_object().unwrap().clone());
+        serde_json::from_value(base).unwrap()
+    }
+
+    #[test]
+    fn test_usage_cost_bills_cached_and_reasoning_tokens_once() {
+        use crate::usage::ModelPrice;
+
+        let usage: openai_ox::Usage = serde_json::from_value(json!({
+            "prompt_tokens": 1_000_000,
+            "completion_tokens": 100_000,
+            "total_tokens": 1_100_000,
+            "prompt_tokens_details": { "cached_tokens": 400_000 },
+            "completion_tokens_details": { "reasoning_tokens": 60_000 }
+        }))
+        .unwrap();
+
+        let price = ModelPrice::new(2.0, 10.0)
+            .with_cache_read(0.5)
+            .with_reasoning(10.0);
+        let cost = price.cost(&convert_usage(&usage));
+
+        assert!((cost.input - 0.6 * 2.0).abs() < 1e-9);
+        assert!((cost.cache_read - 0.4 * 0.5).abs() < 1e-9);
+        assert!((cost.output - 0.04 * 10.0).abs() < 1e-9);
+        assert!((cost.reasoning - 0.06 * 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_generation_config_mapping() {
+        let request = ModelRequest::builder()
+            .messages([Message::new(MessageRole::User, vec![Part::text("Hi")])])
+            .generation_config(
+                GenerationConfig::builder()
+                    .temperature(0.5)
+                    .seed(7)
+                    .max_output_tokens(32)
+                    .stop_sequences(["END"])
+                    .tool_choice(ToolChoice::Tool("lookup".into()))
+                    .build(),
+            )
+            .build();
+
+        let chat_request =
+            convert_request_to_openai(request, "gpt-4o-mini", Some("Be brief".into())).unwrap();
+        assert_eq!(chat_request.model, "gpt-4o-mini");
+        assert_eq!(chat_request.messages.len(), 2);
+        assert_eq!(
+            chat_request.messages[0].content.as_deref(),
+            Some("Be brief")
+        );
+        assert_eq!(chat_request.temperature, Some(0.5));
+        assert_eq!(chat_request.seed, Some(7));
+        assert_eq!(chat_request.max_tokens, Some(32));
+        assert_eq!(chat_request.stop, Some(vec!["END".to_string()]));
+        assert_eq!(
+            serde_json::to_value(&chat_request.tool_choice).unwrap(),
+            json!({"type": "function", "function": {"name": "lookup"}})
+        );
+
+        let request = ModelRequest::builder()
+            .generation_config(GenerationConfig::builder().top_k(5).build())
+            .build();
+        assert!(matches!(
+            convert_request_to_openai(request, "gpt-4o-mini", None),
+            Err(GenerateContentError::UnsupportedFeature(_))
+        ));
+    }
+
+    #[test]
+    fn test_response_conversion() {
+        let response: ChatResponse = serde_json::from_value(json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 0,
+            "model": "gpt-4o-mini",
+            "choices": [{
+                "index": 0,
+                "message": {
+                    "role": "assistant",
+                    "content": "Checking.",
+                    "tool_calls": [{
+                        "id": "call_1",
+                        "type": "function",
+                        "function": {"name": "lookup", "arguments": "{\"q\":\"rust\"}"}
+                    }]
+                },
+                "finish_reason": "tool_calls"
+            }],
+            "usage": {
+                "prompt_tokens": 10,
+                "completion_tokens": 5,
+                "total_tokens": 15,
+                "prompt_tokens_details": {"cached_tokens": 4}
+            }
+        }))
+        .unwrap();
+
+        let response = convert_openai_response_to_ai_ox(response, "gpt-4o-mini".into()).unwrap();
+        assert_eq!(response.vendor_name, "openai");
+        assert_eq!(response.message.content.len(), 2);
+        match &response.message.content[1] {
+            Part::ToolUse { id, name, args, .. } => {
+                assert_eq!(id, "call_1");
+                assert_eq!(name, "lookup");
+                assert_eq!(args, &json!({"q": "rust"}));
+            }
+            other => panic!("Expected tool use, got {:?}", other),
+        }
+        assert_eq!(response.usage.input_tokens(), 10);
+        assert_eq!(response.usage.output_tokens(), 5);
+        assert_eq!(response.usage.cache_read_tokens, Some(4));
+    }
+
+    #[test]
+    fn test_stream_processor_accumulates_tool_calls() {
+        let mut processor = OpenAIStreamProcessor::new();
+        let mut events = Vec::new();
+
+        for value in [
+            json!({"choices": [{"index": 0, "delta": {"role": "assistant", "content": "Hi"}}]}),
+            json!({"choices": [{"index": 0, "delta": {"tool_calls": [{
+                "index": 0, "id": "call_1", "type": "function",
+                "function": {"name": "lookup", "arguments": ""}
+            }]}}]}),
+            json!({"choices": [{"index": 0, "delta": {"tool_calls": [{
+                "index": 0, "function": {"arguments": "{\"q\":"}
+            }]}}]}),
+            json!({"choices": [{"index": 0, "delta": {"tool_calls": [{
+                "index": 0, "function": {"arguments": "\"rust\"}"}
+            }]}}]}),
+            json!({"choices": [{"index": 0, "delta": {}, "finish_reason": "tool_calls"}]}),
+            json!({"usage": {"prompt_tokens": 3, "completion_tokens": 2, "total_tokens": 5}}),
+        ] {
+            events.extend(processor.process_chunk(chunk(value)));
+        }
+        events.extend(processor.finish());
+
+        let events: Vec<_> = events.into_iter().map(Result::unwrap).collect();
+        assert_eq!(events.len(), 4);
+        assert!(matches!(&events[0], StreamEvent::TextDelta(text) if text == "Hi"));
+        match &events[1] {
+            StreamEvent::ToolCall(tool_use) => {
+                assert_eq!(tool_use.id, "call_1");
+                assert_eq!(tool_use.name, "lookup");
+                assert_eq!(tool_use.args, json!({"q": "rust"}));
+            }
+            other => panic!("Expected tool call, got {:?}", other),
+        }
+        assert!(matches!(&events[2], StreamEvent::Usage(_)));
+        match &events[3] {
+            StreamEvent::StreamStop(stop) => {
+                assert_eq!(stop.finish_reason, FinishReason::ToolCalls);
+                assert_eq!(stop.usage.output_tokens(), 2);
+            }
+            other => panic!("Expected stream stop, got {:?}", other),
+        }
+    }
+}
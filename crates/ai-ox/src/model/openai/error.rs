@@ -0,0 +1,35 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum OpenAIError {
+    #[error("Missing API key")]
+    MissingApiKey,
+
+    #[error("OpenAI API error: {0}")]
+    Api(#[from] openai_ox::OpenAIRequestError),
+
+    #[error("Response parsing error: {0}")]
+    ResponseParsing(String),
+
+    #[error("Invalid schema: {0}")]
+    InvalidSchema(String),
+}
+
+impl From<OpenAIError> for crate::errors::GenerateContentError {
+    fn from(err: OpenAIError) -> Self {
+        match err {
+            OpenAIError::MissingApiKey => crate::errors::GenerateContentError::configuration(
+                "Missing OPENAI_API_KEY environment variable",
+            ),
+            OpenAIError::Api(api_err) => {
+                crate::errors::GenerateContentError::provider_error("openai", api_err.to_string())
+            }
+            OpenAIError::ResponseParsing(msg) => {
+                crate::errors::GenerateContentError::response_parsing(msg)
+            }
+            OpenAIError::InvalidSchema(msg) => {
+                crate::errors::GenerateContentError::configuration(msg)
+            }
+        }
+    }
+}
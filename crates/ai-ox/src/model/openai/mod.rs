@@ -0,0 +1,225 @@
+mod conversion;
+mod error;
+pub mod responses;
+
+pub use error::OpenAIError;
+pub use responses::OpenAIResponsesModel;
+
+use crate::{
+    ModelResponse,
+    content::delta::StreamEvent,
+    errors::GenerateContentError,
+    model::{
+        Model, ModelInfo, ModelRequest, Provider, prepare_request, response::RawStructuredResponse,
+    },
+    provider::{Capabilities, ConversionPolicy},
+    usage::Usage,
+};
+use async_stream::try_stream;
+use bon::Builder;
+use futures_util::{FutureExt, StreamExt, future::BoxFuture, stream::BoxStream};
+use openai_ox::OpenAI;
+use serde_json::{Value, json};
+
+/// Represents a model served by the OpenAI Chat Completions API.
+#[derive(Debug, Clone, Builder)]
+#[builder(state_mod(name = openai_model_builder))]
+pub struct OpenAIModel {
+    /// OpenAI client
+    client: OpenAI,
+    /// The specific model name (e.g., "gpt-4o-mini").
+    #[builder(into)]
+    model: String,
+    /// System instruction if provided
+    #[builder(into)]
+    system_instruction: Option<String>,
+    /// Policy for content the provider can't represent exactly.
+    #[builder(default)]
+    conversion_policy: ConversionPolicy,
+}
+
+impl<S: openai_model_builder::State> OpenAIModelBuilder<S>
+where
+    <S as openai_model_builder::State>::Client: openai_model_builder::IsUnset,
+{
+    pub fn api_key(
+        self,
+        api_key: impl Into<String>,
+    ) -> OpenAIModelBuilder<openai_model_builder::SetClient<S>> {
+        self.client(OpenAI::new(api_key))
+    }
+}
+
+impl OpenAIModel {
+    /// Create a new OpenAIModel from environment variables.
+    ///
+    /// This function reads the OPENAI_API_KEY from the environment and returns an error if missing.
+    pub async fn new(model: impl Into<String>) -> Result<Self, OpenAIError> {
+        let client = OpenAI::from_env().map_err(|_| OpenAIError::MissingApiKey)?;
+
+        Ok(Self {
+            client,
+            model: model.into(),
+            system_instruction: None,
+            conversion_policy: ConversionPolicy::default(),
+        })
+    }
+}
+
+impl Model for OpenAIModel {
+    fn info(&self) -> ModelInfo<'_> {
+        ModelInfo(Provider::OpenAI, &self.model)
+    }
+
+    fn name(&self) -> &str {
+        &self.model
+    }
+
+    fn capabilities(&self) -> Option<Capabilities> {
+        Some(Capabilities::openai())
+    }
+
+    fn conversion_policy(&self) -> &ConversionPolicy {
+        &self.conversion_policy
+    }
+
+    /// Sends a request to the OpenAI API and returns the response.
+    fn request(
+        &self,
+        request: ModelRequest,
+    ) -> BoxFuture<'_, Result<ModelResponse, GenerateContentError>> {
+        async move {
+            let request = prepare_request(self, request).await?;
+            let chat_request = conversion::convert_request_to_openai(
+                request,
+                &self.model,
+                self.system_instruction.clone(),
+            )?;
+            let response = self
+                .client
+                .send(&chat_request)
+                .await
+                .map_err(OpenAIError::Api)?;
+            conversion::convert_openai_response_to_ai_ox(response, self.model.clone())
+        }
+        .boxed()
+    }
+
+    /// Returns a stream of events for a streaming request.
+    fn request_stream(
+        &self,
+        request: ModelRequest,
+    ) -> BoxStream<'_, Result<StreamEvent, GenerateContentError>> {
+        self.stream_chat(request, None)
+    }
+
+    fn request_structured_internal(
+        &self,
+        request: ModelRequest,
+        schema: String,
+    ) -> BoxFuture<'_, Result<RawStructuredResponse, GenerateContentError>> {
+        async move {
+            let request = prepare_request(self, request).await?;
+            let mut chat_request = conversion::convert_request_to_openai(
+                request,
+                &self.model,
+                self.system_instruction.clone(),
+            )?;
+            chat_request.response_format = Some(json_schema_format(&schema)?);
+
+            let response = self
+                .client
+                .send(&chat_request)
+                .await
+                .map_err(OpenAIError::Api)?;
+
+            let content = response
+                .content()
+                .ok_or_else(|| OpenAIError::ResponseParsing("No response content".to_string()))?;
+
+            let json: Value = serde_json::from_str(content).map_err(|e| {
+                OpenAIError::ResponseParsing(format!(
+                    "Failed to parse JSON: {}. Response content: '{}'",
+                    e, content
+                ))
+            })?;
+
+            let usage = response
+                .usage
+                .as_ref()
+                .map(conversion::convert_usage)
+                .unwrap_or_else(Usage::new);
+
+            Ok(RawStructuredResponse {
+                json,
+                usage,
+                model_name: self.model.clone(),
+                vendor_name: "openai".to_string(),
+            })
+        }
+        .boxed()
+    }
+
+    /// Streams a response constrained to the JSON schema.
+    fn request_structured_stream(
+        &self,
+        request: ModelRequest,
+        schema: String,
+    ) -> BoxStream<'_, Result<StreamEvent, GenerateContentError>> {
+        match json_schema_format(&schema) {
+            Ok(format) => self.stream_chat(request, Some(format)),
+            Err(e) => Box::pin(futures_util::stream::once(async move { Err(e.into()) })),
+        }
+    }
+}
+
+impl OpenAIModel {
+    /// Streams a chat completion, optionally constrained to `response_format`.
+    fn stream_chat(
+        &self,
+        request: ModelRequest,
+        response_format: Option<Value>,
+    ) -> BoxStream<'_, Result<StreamEvent, GenerateContentError>> {
+        let client = self.client.clone();
+
+        let stream = try_stream! {
+            let request = prepare_request(self, request).await?;
+            let mut chat_request = conversion::convert_request_to_openai(
+                request,
+                &self.model,
+                self.system_instruction.clone(),
+            )?;
+            chat_request.response_format = response_format;
+            conversion::enable_stream_usage(&mut chat_request);
+
+            let mut response_stream = client.stream(&chat_request);
+            let mut processor = conversion::OpenAIStreamProcessor::new();
+
+            while let Some(chunk) = response_stream.next().await {
+                let chunk = chunk.map_err(OpenAIError::Api)?;
+                for event in processor.process_chunk(chunk) {
+                    yield event?;
+                }
+            }
+
+            for event in processor.finish() {
+                yield event?;
+            }
+        };
+
+        Box::pin(stream)
+    }
+}
+
+/// Builds the `response_format` requesting output that matches `schema`.
+fn json_schema_format(schema: &str) -> Result<Value, OpenAIError> {
+    let schema: Value =
+        serde_json::from_str(schema).map_err(|e| OpenAIError::InvalidSchema(e.to_string()))?;
+    Ok(json!({
+        "type": "json_schema",
+        "json_schema": {
+            "name": "Response",
+            "schema": schema
+        }
+    }))
+}
@@ -0,0 +1,800 @@
+use std::collections::BTreeMap;
+
+use openai_ox::{
+    InputContent, InputItem, ReasoningConfig, ReasoningSummary, ResponseOutputContent,
+    ResponseOutputItem, ResponsesInput, ResponsesRequest, ResponsesResponse, ResponsesStreamEvent,
+    ResponsesUsage,
+    responses::{ResponsesTool, ResponsesToolChoice},
+};
+
+use crate::{
+    ModelResponse,
+    content::{
+        delta::{FinishReason, ReasoningDelta, StreamEvent, StreamStop},
+        message::{Message, MessageRole},
+        part::{DataRef, Part},
+    },
+    errors::GenerateContentError,
+    model::{ModelRequest, options::GenerationOption, request::ToolChoice},
+    tool::{Tool, ToolUse, encode_tool_result_parts},
+    usage::{Modality, Usage},
+};
+
+use super::super::OpenAIError;
+
+/// Provider name used for opaque parts produced by the Responses API
+pub const OPAQUE_PROVIDER: &str = "openai";
+
+/// Opaque part kind for reasoning items
+///
+/// Reasoning is now returned as `Part::Reasoning`; opaque reasoning parts from
+/// older histories are still accepted as input.
+pub const REASONING_KIND: &str = "reasoning";
+
+/// Extension key holding the ID of the reasoning item a reasoning part came from
+pub const REASONING_ID_EXT: &str = "openai.reasoning_id";
+
+/// Convert from ai-ox ModelRequest to OpenAI ResponsesRequest
+pub fn convert_request_to_responses(
+    request: ModelRequest,
+    model: &str,
+    system_instruction: Option<String>,
+    reasoning: Option<ReasoningConfig>,
+) -> Result<ResponsesRequest, GenerateContentError> {
+    let config = request.generation_config.unwrap_or_default();
+    config.reject(
+        "OpenAI Responses",
+        &[
+            GenerationOption::TopK,
+            GenerationOption::StopSequences,
+            GenerationOption::Seed,
+        ],
+    )?;
+
+    let instructions = match request.system_message {
+        Some(message) => Some(collect_text(&message)?),
+        None => system_instruction,
+    };
+
+    let mut items = Vec::new();
+    for message in request.messages {
+        convert_message_to_items(message, &mut items)?;
+    }
+
+    let tools = request
+        .tools
+        .map(convert_tools)
+        .filter(|tools| !tools.is_empty());
+
+    let tool_choice = config.tool_choice.map(|choice| match choice {
+        ToolChoice::Auto => ResponsesToolChoice::from("auto"),
+        ToolChoice::None => ResponsesToolChoice::from("none"),
+        ToolChoice::Required => ResponsesToolChoice::from("required"),
+        ToolChoice::Tool(name) => ResponsesToolChoice::function(name),
+    });
+
+    // Without server-side storage, reasoning items can only be replayed in encrypted form.
+    let include = vec!["reasoning.encrypted_content".to_string()];
+
+    Ok(ResponsesRequest::builder()
+        .model(model)
+        .input(ResponsesInput::Items(items))
+        .maybe_instructions(instructions)
+        .maybe_reasoning(reasoning)
+        .maybe_tools(tools)
+        .maybe_tool_choice(tool_choice)
+        .include(include)
+        .store(false)
+        .maybe_max_output_tokens(config.max_output_tokens)
+        .maybe_temperature(config.temperature.map(|t| t as f32))
+        .maybe_top_p(config.top_p.map(|p| p as f32))
+        .build())
+}
+
+fn collect_text(message: &Message) -> Result<String, GenerateContentError> {
+    message
+        .content
+        .iter()
+        .map(|part| match part {
+            Part::Text { text, .. } => Ok(text.as_str()),
+            _ => Err(GenerateContentError::unsupported_feature(
+                "Non-text system parts cannot be converted to OpenAI Responses instructions",
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(|texts| texts.join("\n"))
+}
+
+/// Convert one ai-ox message into Responses input items.
+///
+/// Text and images are grouped into message items; tool calls, tool results and
+/// reasoning become standalone items in their original order.
+fn convert_message_to_items(
+    message: Message,
+    items: &mut Vec<InputItem>,
+) -> Result<(), GenerateContentError> {
+    let role = match message.role {
+        MessageRole::User => "user",
+        MessageRole::Assistant => "assistant",
+        MessageRole::System => "system",
+        MessageRole::Unknown(role) => {
+            return Err(GenerateContentError::message_conversion(format!(
+                "Unknown role: {}",
+                role
+            )));
+        }
+    };
+
+    let mut content = Vec::new();
+    let flush = |content: &mut Vec<InputContent>, items: &mut Vec<InputItem>| {
+        if !content.is_empty() {
+            items.push(InputItem::Message {
+                role: role.to_string(),
+                content: std::mem::take(content),
+            });
+        }
+    };
+
+    for part in message.content {
+        match part {
+            Part::Text { text, .. } => {
+                if role == "assistant" {
+                    content.push(InputContent::OutputText { text });
+                } else {
+                    content.push(InputContent::InputText { text });
+                }
+            }
+            Part::Blob {
+                data_ref,
+                mime_type,
+                ..
+            } => {
+                if !mime_type.starts_with("image/") {
+                    return Err(GenerateContentError::message_conversion(format!(
+                        "OpenAI Responses does not support {} content in messages",
+                        mime_type
+                    )));
+                }
+                let image_url = match data_ref {
+                    DataRef::Uri { uri } => uri,
+                    DataRef::Base64 { data } => format!("data:{};base64,{}", mime_type, data),
+                };
+                content.push(InputContent::InputImage {
+                    image_url,
+                    detail: None,
+                });
+            }
+            Part::ToolUse { id, name, args, .. } => {
+                flush(&mut content, items);
+                let arguments = serde_json::to_string(&args)
+                    .map_err(|e| GenerateContentError::message_conversion(e.to_string()))?;
+                items.push(InputItem::FunctionCall {
+                    call_id: id,
+                    name,
+                    arguments,
+                });
+            }
+            Part::ToolResult {
+                id, name, parts, ..
+            } => {
+                flush(&mut content, items);
+                items.push(InputItem::FunctionCallOutput {
+                    call_id: id,
+                    output: encode_tool_result_parts(&name, &parts)?,
+                });
+            }
+            Part::Reasoning {
+                text,
+                signature,
+                ext,
+                ..
+            } => {
+                // Reasoning without an item ID came from another provider, and
+                // without encrypted content the unstored item can't be resolved
+                // by ID; neither can be replayed.
+                let Some(id) = ext.get(REASONING_ID_EXT).and_then(|id| id.as_str()) else {
+                    continue;
+                };
+                if signature.is_none() {
+                    continue;
+                }
+                flush(&mut content, items);
+                let summary = if text.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![ReasoningSummary::SummaryText { text }]
+                };
+                items.push(InputItem::Reasoning {
+                    id: id.to_string(),
+                    summary,
+                    encrypted_content: signature,
+                });
+            }
+            Part::Opaque {
+                provider,
+                kind,
+                payload,
+                ..
+            } => {
+                if provider != OPAQUE_PROVIDER || kind != REASONING_KIND {
+                    return Err(GenerateContentError::message_conversion(format!(
+                        "Cannot convert opaque content from provider '{}' of type '{}' to OpenAI Responses format",
+                        provider, kind
+                    )));
+                }
+                let item = serde_json::from_value(payload).map_err(|e| {
+                    GenerateContentError::message_conversion(format!(
+                        "Invalid OpenAI reasoning payload: {}",
+                        e
+                    ))
+                })?;
+                if let InputItem::Reasoning {
+                    encrypted_content: None,
+                    ..
+                } = item
+                {
+                    continue;
+                }
+                flush(&mut content, items);
+                items.push(item);
+            }
+        }
+    }
+
+    flush(&mut content, items);
+    Ok(())
+}
+
+/// Function declarations become function tools and built-in tools are passed
+/// through; Gemini tools are skipped.
+fn convert_tools(tools: Vec<Tool>) -> Vec<ResponsesTool> {
+    tools
+        .into_iter()
+        .flat_map(|tool| match tool {
+            Tool::FunctionDeclarations(functions) => functions
+                .into_iter()
+                .map(|function| ResponsesTool {
+                    tool_type: "function".to_string(),
+                    name: function.name,
+                    description: function.description,
+                    format: None,
+                    parameters: Some(function.parameters),
+                    options: Default::default(),
+                })
+                .collect(),
+            Tool::OpenAITool(tool) => vec![tool],
+            #[cfg(feature = "gemini")]
+            Tool::GeminiTool(_) => Vec::new(),
+        })
+        .collect()
+}
+
+/// Convert Responses usage to ai-ox Usage
+pub fn convert_usage(usage: &ResponsesUsage) -> Usage {
+    let mut result = Usage::new();
+    result.requests = 1;
+    result
+        .input_tokens_by_modality
+        .insert(Modality::Text, usage.input_tokens as u64);
+    result
+        .output_tokens_by_modality
+        .insert(Modality::Text, usage.output_tokens as u64);
+    result.cache_read_tokens = usage
+        .input_tokens_details
+        .as_ref()
+        .and_then(|details| details.cached_tokens)
+        .map(u64::from);
+    result.thoughts_tokens = usage
+        .output_tokens_details
+        .as_ref()
+        .and_then(|details| details.reasoning_tokens)
+        .or(usage.reasoning_tokens)
+        .map(u64::from);
+    result
+}
+
+fn reasoning_id_ext(id: String) -> BTreeMap<String, serde_json::Value> {
+    BTreeMap::from([(REASONING_ID_EXT.to_string(), id.into())])
+}
+
+/// Convert a reasoning output item to a reasoning part that can be replayed as input
+fn reasoning_to_part(
+    id: String,
+    summary: Vec<serde_json::Value>,
+    encrypted_content: Option<String>,
+) -> Part {
+    let text = summary
+        .iter()
+        .filter_map(|value| value.get("text")?.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    Part::Reasoning {
+        text,
+        signature: encrypted_content,
+        redacted: false,
+        ext: reasoning_id_ext(id),
+    }
+}
+
+fn parse_arguments(arguments: &str) -> Result<serde_json::Value, GenerateContentError> {
+    if arguments.trim().is_empty() {
+        return Ok(serde_json::Value::Object(Default::default()));
+    }
+    serde_json::from_str(arguments).map_err(|e| {
+        OpenAIError::ResponseParsing(format!(
+            "Failed to parse function call arguments: {}. Arguments: '{}'",
+            e, arguments
+        ))
+        .into()
+    })
+}
+
+/// Derive the finish reason from the final state of a response
+pub fn convert_finish_reason(response: &ResponsesResponse) -> FinishReason {
+    let reason = response
+        .incomplete_details
+        .as_ref()
+        .and_then(|details| details.reason.as_deref());
+    match reason {
+        Some("max_output_tokens") => FinishReason::Length,
+        Some("content_filter") => FinishReason::ContentFilter,
+        Some(_) => FinishReason::Other,
+        None if response
+            .output
+            .iter()
+            .any(|item| matches!(item, ResponseOutputItem::FunctionCall { .. })) =>
+        {
+            FinishReason::ToolCalls
+        }
+        None => FinishReason::Stop,
+    }
+}
+
+/// Convert OpenAI ResponsesResponse to ai-ox ModelResponse
+pub fn convert_response_to_ai_ox(
+    response: ResponsesResponse,
+    model_name: String,
+) -> Result<ModelResponse, GenerateContentError> {
+    if let Some(error) = response.error {
+        return Err(GenerateContentError::provider_error(
+            "openai",
+            format!("{}: {}", error.code, error.message),
+        ));
+    }
+
+    let mut parts = Vec::new();
+
+    for item in response.output {
+        match item {
+            ResponseOutputItem::Reasoning {
+                id,
+                summary,
+                encrypted_content,
+                ..
+            } => parts.push(reasoning_to_part(id, summary, encrypted_content)),
+            ResponseOutputItem::Message { content, .. } => {
+                for content in content {
+                    match content {
+                        ResponseOutputContent::Text { text, .. } => parts.push(Part::Text {
+                            text,
+                            ext: BTreeMap::new(),
+                        }),
+                        ResponseOutputContent::Refusal { refusal } => parts.push(Part::Text {
+                            text: refusal,
+                            ext: BTreeMap::from([("refusal".to_string(), true.into())]),
+                        }),
+                    }
+                }
+            }
+            ResponseOutputItem::FunctionCall {
+                call_id,
+                name,
+                arguments,
+                ..
+            } => parts.push(Part::ToolUse {
+                id: call_id,
+                name,
+                args: parse_arguments(&arguments)?,
+                ext: BTreeMap::new(),
+            }),
+            _ => {}
+        }
+    }
+
+    let message = Message {
+        role: MessageRole::Assistant,
+        content: parts,
+        timestamp: Some(chrono::Utc::now()),
+        ext: Some(BTreeMap::new()),
+    };
+
+    Ok(ModelResponse {
+        message,
+        usage: response
+            .usage
+            .as_ref()
+            .map(convert_usage)
+            .unwrap_or_default(),
+        model_name,
+        vendor_name: "openai".to_string(),
+    })
+}
+
+/// Convert a single Responses stream event to ai-ox stream events
+pub fn convert_stream_event(
+    event: ResponsesStreamEvent,
+) -> Vec<Result<StreamEvent, GenerateContentError>> {
+    match event {
+        ResponsesStreamEvent::OutputTextDelta { delta, .. } if !delta.is_empty() => {
+            vec![Ok(StreamEvent::TextDelta(delta))]
+        }
+        ResponsesStreamEvent::ReasoningSummaryTextDelta { delta, .. } if !delta.is_empty() => {
+            vec![Ok(StreamEvent::ReasoningDelta(ReasoningDelta::text(delta)))]
+        }
+        // The summary was streamed already; completing the item attaches its
+        // ID and encrypted content so it can be replayed.
+        ResponsesStreamEvent::OutputItemDone {
+            item:
+                ResponseOutputItem::Reasoning {
+                    id,
+                    encrypted_content,
+                    ..
+                },
+            ..
+        } => vec![Ok(StreamEvent::ReasoningDelta(ReasoningDelta {
+            signature: encrypted_content,
+            ext: reasoning_id_ext(id),
+            ..ReasoningDelta::default()
+        }))],
+        ResponsesStreamEvent::OutputItemDone {
+            item:
+                ResponseOutputItem::FunctionCall {
+                    call_id,
+                    name,
+                    arguments,
+                    ..
+                },
+            ..
+        } => vec![parse_arguments(&arguments).map(|args| {
+            StreamEvent::ToolCall(ToolUse {
+                id: call_id,
+                name,
+                args,
+                ext: None,
+            })
+        })],
+        ResponsesStreamEvent::Completed { response }
+        | ResponsesStreamEvent::Incomplete { response } => {
+            let usage = response
+                .usage
+                .as_ref()
+                .map(convert_usage)
+                .unwrap_or_default();
+            vec![
+                Ok(StreamEvent::Usage(usage.clone())),
+                Ok(StreamEvent::StreamStop(StreamStop {
+                    finish_reason: convert_finish_reason(&response),
+                    usage,
+                    model_name: None,
+                    vendor_name: None,
+                })),
+            ]
+        }
+        ResponsesStreamEvent::Failed { response } => {
+            let message = response
+                .error
+                .map(|error| format!("{}: {}", error.code, error.message))
+                .unwrap_or_else(|| "Response failed".to_string());
+            vec![Err(GenerateContentError::provider_error("openai", message))]
+        }
+        ResponsesStreamEvent::Error { message, .. } => {
+            vec![Err(GenerateContentError::provider_error("openai", message))]
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::request::GenerationConfig;
+    use serde_json::json;
+
+    fn response(output: serde_json::Value) -> ResponsesResponse {
+        serde_json::from_value(json!({
+            "id": "resp_1",
+            "created_at": 0,
+            "model": "o4-mini",
+            "object": "response",
+            "output": output,
+            "parallel_tool_calls": true,
+            "tools": [],
+            "status": "completed",
+            "usage": {
+                "input_tokens": 12,
+                "output_tokens": 30,
+                "total_tokens": 42,
+                "input_tokens_details": {"cached_tokens": 2},
+                "output_tokens_details": {"reasoning_tokens": 20}
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_request_items_mapping() {
+        let request = ModelRequest::builder()
+            .messages([
+                Message::new(MessageRole::User, vec![Part::text("Weather?")]),
+                Message::new(
+                    MessageRole::Assistant,
+                    vec![Part::ToolUse {
+                        id: "call_1".into(),
+                        name: "weather".into(),
+                        args: json!({"city": "Oslo"}),
+                        ext: BTreeMap::new(),
+                    }],
+                ),
+                Message::new(
+                    MessageRole::User,
+                    vec![Part::ToolResult {
+                        id: "call_1".into(),
+                        name: "weather".into(),
+                        parts: vec![Part::text("Sunny")],
+                        ext: BTreeMap::new(),
+                    }],
+                ),
+            ])
+            .generation_config(GenerationConfig::builder().max_output_tokens(100).build())
+            .build();
+
+        let responses_request =
+            convert_request_to_responses(request, "o4-mini", Some("Be brief".into()), None)
+                .unwrap();
+        assert_eq!(responses_request.instructions.as_deref(), Some("Be brief"));
+        assert_eq!(responses_request.max_output_tokens, Some(100));
+        assert_eq!(responses_request.store, Some(false));
+        assert_eq!(
+            responses_request.include,
+            Some(vec!["reasoning.encrypted_content".to_string()])
+        );
+
+        let ResponsesInput::Items(items) = responses_request.input else {
+            panic!("Expected typed input items");
+        };
+        assert_eq!(items.len(), 3);
+        assert_eq!(
+            items[0],
+            InputItem::Message {
+                role: "user".into(),
+                content: vec![InputContent::InputText {
+                    text: "Weather?".into()
+                }],
+            }
+        );
+        assert!(
+            matches!(&items[1], InputItem::FunctionCall { call_id, name, .. } if call_id == "call_1" && name == "weather")
+        );
+        assert!(
+            matches!(&items[2], InputItem::FunctionCallOutput { call_id, .. } if call_id == "call_1")
+        );
+
+        let request = ModelRequest::builder()
+            .generation_config(GenerationConfig::builder().seed(1).build())
+            .build();
+        assert!(matches!(
+            convert_request_to_responses(request, "o4-mini", None, None),
+            Err(GenerateContentError::UnsupportedFeature(_))
+        ));
+    }
+
+    #[test]
+    fn test_tools_and_tool_choice_mapping() {
+        let request = ModelRequest::builder()
+            .tool(Tool::FunctionDeclarations(vec![
+                crate::tool::FunctionMetadata {
+                    name: "weather".into(),
+                    description: None,
+                    parameters: json!({"type": "object"}),
+                },
+            ]))
+            .tool(Tool::OpenAITool(
+                ResponsesTool::built_in("file_search").with_option("vector_store_ids", ["vs_1"]),
+            ))
+            .generation_config(
+                GenerationConfig::builder()
+                    .tool_choice(ToolChoice::Tool("weather".into()))
+                    .build(),
+            )
+            .build();
+
+        let responses_request =
+            convert_request_to_responses(request, "o4-mini", None, None).unwrap();
+        let json = serde_json::to_value(&responses_request).unwrap();
+
+        assert_eq!(
+            json["tools"],
+            json!([
+                {"type": "function", "name": "weather", "parameters": {"type": "object"}},
+                {"type": "file_search", "vector_store_ids": ["vs_1"]}
+            ])
+        );
+        assert_eq!(
+            json["tool_choice"],
+            json!({"type": "function", "name": "weather"})
+        );
+    }
+
+    #[test]
+    fn test_reasoning_round_trip() {
+        let response = response(json!([
+            {
+                "type": "reasoning",
+                "id": "rs_1",
+                "summary": [{"type": "summary_text", "text": "Adding numbers."}],
+                "encrypted_content": "gAAAA"
+            },
+            {
+                "type": "message",
+                "id": "msg_1",
+                "role": "assistant",
+                "status": "completed",
+                "content": [{"type": "output_text", "text": "4", "annotations": []}]
+            }
+        ]));
+
+        let model_response = convert_response_to_ai_ox(response, "o4-mini".into()).unwrap();
+        assert_eq!(model_response.usage.thoughts_tokens, Some(20));
+        assert_eq!(model_response.usage.cache_read_tokens, Some(2));
+        assert_eq!(model_response.message.content.len(), 2);
+        assert!(matches!(
+            &model_response.message.content[0],
+            Part::Reasoning { text, signature, ext, .. }
+                if text == "Adding numbers."
+                    && signature.as_deref() == Some("gAAAA")
+                    && ext[REASONING_ID_EXT] == "rs_1"
+        ));
+
+        let request = ModelRequest::builder()
+            .messages([model_response.message])
+            .build();
+        let responses_request = convert_request_to_responses(
+            request,
+            "o4-mini",
+            None,
+            Some(ReasoningConfig::with_effort("low")),
+        )
+        .unwrap();
+        assert_eq!(
+            responses_request.include,
+            Some(vec!["reasoning.encrypted_content".to_string()])
+        );
+
+        let ResponsesInput::Items(items) = responses_request.input else {
+            panic!("Expected typed input items");
+        };
+        assert_eq!(
+            items[0],
+            InputItem::Reasoning {
+                id: "rs_1".into(),
+                summary: vec![ReasoningSummary::SummaryText {
+                    text: "Adding numbers.".into()
+                }],
+                encrypted_content: Some("gAAAA".into()),
+            }
+        );
+        assert_eq!(
+            items[1],
+            InputItem::Message {
+                role: "assistant".into(),
+                content: vec![InputContent::OutputText { text: "4".into() }],
+            }
+        );
+    }
+
+    #[test]
+    fn test_reasoning_without_encrypted_content_is_not_replayed() {
+        let response = response(json!([
+            {
+                "type": "reasoning",
+                "id": "rs_1",
+                "summary": [{"type": "summary_text", "text": "Adding numbers."}]
+            },
+            {
+                "type": "message",
+                "id": "msg_1",
+                "role": "assistant",
+                "status": "completed",
+                "content": [{"type": "output_text", "text": "4", "annotations": []}]
+            }
+        ]));
+        let model_response = convert_response_to_ai_ox(response, "o4-mini".into()).unwrap();
+
+        let request = ModelRequest::builder()
+            .messages([model_response.message])
+            .build();
+        let responses_request =
+            convert_request_to_responses(request, "o4-mini", None, None).unwrap();
+
+        let ResponsesInput::Items(items) = responses_request.input else {
+            panic!("Expected typed input items");
+        };
+        assert_eq!(
+            items,
+            vec![InputItem::Message {
+                role: "assistant".into(),
+                content: vec![InputContent::OutputText { text: "4".into() }],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_reasoning_stream_events() {
+        let events: Vec<ResponsesStreamEvent> = serde_json::from_value(json!([
+            {"type": "response.reasoning_summary_text.delta", "item_id": "rs_1", "output_index": 0, "delta": "Adding"},
+            {"type": "response.output_item.done", "output_index": 0, "item": {
+                "type": "reasoning", "id": "rs_1",
+                "summary": [{"type": "summary_text", "text": "Adding"}],
+                "encrypted_content": "gAAAA"
+            }},
+        ]))
+        .unwrap();
+
+        let converted: Vec<_> = events
+            .into_iter()
+            .flat_map(convert_stream_event)
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(
+            converted,
+            vec![
+                StreamEvent::ReasoningDelta(ReasoningDelta::text("Adding")),
+                StreamEvent::ReasoningDelta(ReasoningDelta {
+                    signature: Some("gAAAA".into()),
+                    ext: reasoning_id_ext("rs_1".into()),
+                    ..ReasoningDelta::default()
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stream_events() {
+        let events: Vec<ResponsesStreamEvent> = serde_json::from_value(json!([
+            {"type": "response.created", "response": {}},
+            {"type": "response.output_text.delta", "item_id": "msg_1", "output_index": 0, "content_index": 0, "delta": "Hi"},
+            {"type": "response.output_item.done", "output_index": 1, "item": {
+                "type": "function_call", "id": "fc_1", "call_id": "call_1",
+                "name": "weather", "arguments": "{\"city\":\"Oslo\"}", "status": "completed"
+            }},
+        ]))
+        .unwrap();
+
+        let converted: Vec<_> = events
+            .into_iter()
+            .flat_map(convert_stream_event)
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(converted.len(), 2);
+        assert!(matches!(&converted[0], StreamEvent::TextDelta(text) if text == "Hi"));
+        assert!(
+            matches!(&converted[1], StreamEvent::ToolCall(tool_use) if tool_use.id == "call_1" && tool_use.args == json!({"city": "Oslo"}))
+        );
+
+        let completed = ResponsesStreamEvent::Completed {
+            response: Box::new(response(json!([{
+                "type": "function_call", "id": "fc_1", "call_id": "call_1",
+                "name": "weather", "arguments": "{}"
+            }]))),
+        };
+        let converted: Vec<_> = convert_stream_event(completed)
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert!(matches!(&converted[0], StreamEvent::Usage(usage) if usage.output_tokens() == 30));
+        assert!(
+            matches!(&converted[1], StreamEvent::StreamStop(stop) if stop.finish_reason == FinishReason::ToolCalls)
+        );
+    }
+}
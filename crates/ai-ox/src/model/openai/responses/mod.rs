@@ -0,0 +1,191 @@
+mod conversion;
+
+pub use conversion::{OPAQUE_PROVIDER, REASONING_KIND};
+
+use crate::{
+    ModelResponse,
+    content::delta::StreamEvent,
+    errors::GenerateContentError,
+    model::{
+        Model, ModelInfo, ModelRequest, Provider, prepare_request, response::RawStructuredResponse,
+    },
+    provider::{Capabilities, ConversionPolicy},
+};
+use async_stream::try_stream;
+use bon::Builder;
+use futures_util::{FutureExt, StreamExt, future::BoxFuture, stream::BoxStream};
+use openai_ox::{OpenAI, ReasoningConfig, TextConfig};
+use serde_json::Value;
+
+use super::OpenAIError;
+
+/// Represents a model served by the OpenAI Responses API.
+///
+/// Reasoning items returned by the model are surfaced as
+/// [`crate::content::part::Part::Reasoning`] parts, carrying the summary text, the
+/// encrypted content as their signature and the item ID in `ext`. They are replayed
+/// when the conversation is sent back, so reasoning continuity survives multi-turn
+/// exchanges without server-side storage.
+///
+/// Built-in tools such as web search are passed as [`crate::tool::Tool::OpenAITool`].
+#[derive(Debug, Clone, Builder)]
+#[builder(state_mod(name = openai_responses_model_builder))]
+pub struct OpenAIResponsesModel {
+    /// OpenAI client
+    client: OpenAI,
+    /// The specific model name (e.g., "o4-mini").
+    #[builder(into)]
+    model: String,
+    /// System instruction if provided
+    #[builder(into)]
+    system_instruction: Option<String>,
+    /// Reasoning configuration for reasoning models
+    reasoning: Option<ReasoningConfig>,
+    /// Policy for content the provider can't represent exactly.
+    #[builder(default)]
+    conversion_policy: ConversionPolicy,
+}
+
+impl<S: openai_responses_model_builder::State> OpenAIResponsesModelBuilder<S>
+where
+    <S as openai_responses_model_builder::State>::Client: openai_responses_model_builder::IsUnset,
+{
+    pub fn api_key(
+        self,
+        api_key: impl Into<String>,
+    ) -> OpenAIResponsesModelBuilder<openai_responses_model_builder::SetClient<S>> {
+        self.client(OpenAI::new(api_key))
+    }
+}
+
+impl OpenAIResponsesModel {
+    /// Create a new OpenAIResponsesModel from environment variables.
+    ///
+    /// This function reads the OPENAI_API_KEY from the environment and returns an error if missing.
+    pub async fn new(model: impl Into<String>) -> Result<Self, OpenAIError> {
+        let client = OpenAI::from_env().map_err(|_| OpenAIError::MissingApiKey)?;
+
+        Ok(Self {
+            client,
+            model: model.into(),
+            system_instruction: None,
+            reasoning: None,
+            conversion_policy: ConversionPolicy::default(),
+        })
+    }
+}
+
+impl Model for OpenAIResponsesModel {
+    fn info(&self) -> ModelInfo<'_> {
+        ModelInfo(Provider::OpenAI, &self.model)
+    }
+
+    fn name(&self) -> &str {
+        &self.model
+    }
+
+    fn capabilities(&self) -> Option<Capabilities> {
+        Some(Capabilities::openai())
+    }
+
+    fn conversion_policy(&self) -> &ConversionPolicy {
+        &self.conversion_policy
+    }
+
+    /// Sends a request to the OpenAI Responses API and returns the response.
+    fn request(
+        &self,
+        request: ModelRequest,
+    ) -> BoxFuture<'_, Result<ModelResponse, GenerateContentError>> {
+        async move {
+            let request = prepare_request(self, request).await?;
+            let responses_request = conversion::convert_request_to_responses(
+                request,
+                &self.model,
+                self.system_instruction.clone(),
+                self.reasoning.clone(),
+            )?;
+            let response = self
+                .client
+                .send_responses(&responses_request)
+                .await
+                .map_err(OpenAIError::Api)?;
+            conversion::convert_response_to_ai_ox(response, self.model.clone())
+        }
+        .boxed()
+    }
+
+    /// Returns a stream of events for a streaming request.
+    fn request_stream(
+        &self,
+        request: ModelRequest,
+    ) -> BoxStream<'_, Result<StreamEvent, GenerateContentError>> {
+        let client = self.client.clone();
+
+        let stream = try_stream! {
+            let request = prepare_request(self, request).await?;
+            let responses_request = conversion::convert_request_to_responses(
+                request,
+                &self.model,
+                self.system_instruction.clone(),
+                self.reasoning.clone(),
+            )?;
+            let mut event_stream = client.stream_responses(&responses_request);
+
+            while let Some(event) = event_stream.next().await {
+                let event = event.map_err(OpenAIError::Api)?;
+                for event in conversion::convert_stream_event(event) {
+                    yield event?;
+                }
+            }
+        };
+
+        Box::pin(stream)
+    }
+
+    fn request_structured_internal(
+        &self,
+        request: ModelRequest,
+        schema: String,
+    ) -> BoxFuture<'_, Result<RawStructuredResponse, GenerateContentError>> {
+        async move {
+            let request = prepare_request(self, request).await?;
+            let schema_value: Value = serde_json::from_str(&schema)
+                .map_err(|e| OpenAIError::InvalidSchema(e.to_string()))?;
+
+            let mut responses_request = conversion::convert_request_to_responses(
+                request,
+                &self.model,
+                self.system_instruction.clone(),
+                self.reasoning.clone(),
+            )?;
+            responses_request.text = Some(TextConfig::json_schema("Response", schema_value));
+
+            let response = self
+                .client
+                .send_responses(&responses_request)
+                .await
+                .map_err(OpenAIError::Api)?;
+
+            let response = conversion::convert_response_to_ai_ox(response, self.model.clone())?;
+            let text = response
+                .to_string()
+                .ok_or_else(|| OpenAIError::ResponseParsing("No response content".to_string()))?;
+
+            let json: Value = serde_json::from_str(&text).map_err(|e| {
+                OpenAIError::ResponseParsing(format!(
+                    "Failed to parse JSON: {}. Response content: '{}'",
+                    e, text
+                ))
+            })?;
+
+            Ok(RawStructuredResponse {
+                json,
+                usage: response.usage,
+                model_name: response.model_name,
+                vendor_name: response.vendor_name,
+            })
+        }
+        .boxed()
+    }
+}
@@ -4,15 +4,17 @@ use openrouter_ox::{
         SystemMessage, ToolMessage, UserMessage,
     },
     response::{
-        FinishReason as OpenRouterFinishReason, FunctionCall, ToolCall as OpenRouterToolCall,
+        FinishReason as OpenRouterFinishReason, FunctionCall, ReasoningDetail,
+        ToolCall as OpenRouterToolCall,
     },
     tool::FunctionMetadata,
 };
 use serde_json::Value;
+use std::collections::BTreeMap;
 
 use crate::{
     content::{
-        delta::FinishReason,
+        delta::{FinishReason, ReasoningDelta},
         message::{Message, MessageRole},
         part::Part,
     },
@@ -33,6 +35,90 @@ pub fn convert_finish_reason(reason: OpenRouterFinishReason) -> FinishReason {
     }
 }
 
+/// Extension keys preserving the metadata of OpenRouter reasoning details
+const REASONING_TYPE_EXT: &str = "openrouter.reasoning_type";
+const REASONING_FORMAT_EXT: &str = "openrouter.reasoning_format";
+const REASONING_ID_EXT: &str = "openrouter.reasoning_id";
+
+/// OpenRouter reasoning detail types
+const TEXT_REASONING: &str = "reasoning.text";
+const SUMMARY_REASONING: &str = "reasoning.summary";
+const ENCRYPTED_REASONING: &str = "reasoning.encrypted";
+
+fn reasoning_detail_ext(detail: &ReasoningDetail) -> BTreeMap<String, Value> {
+    let mut ext = BTreeMap::from([(
+        REASONING_TYPE_EXT.to_string(),
+        Value::from(detail.detail_type.clone()),
+    )]);
+    if let Some(format) = &detail.format {
+        ext.insert(REASONING_FORMAT_EXT.to_string(), format.clone().into());
+    }
+    if let Some(id) = &detail.id {
+        ext.insert(REASONING_ID_EXT.to_string(), id.clone().into());
+    }
+    ext
+}
+
+/// Convert an OpenRouter reasoning detail to an ai-ox reasoning part
+pub fn reasoning_detail_to_part(detail: ReasoningDetail) -> Part {
+    let ext = reasoning_detail_ext(&detail);
+    Part::Reasoning {
+        redacted: detail.detail_type == ENCRYPTED_REASONING,
+        text: detail.text.or(detail.summary).unwrap_or_default(),
+        signature: detail.signature.or(detail.data),
+        ext,
+    }
+}
+
+/// Convert a streamed OpenRouter reasoning detail to an ai-ox reasoning delta
+///
+/// Detail metadata is only attached once the block is complete, so partial
+/// text deltas keep extending the same reasoning block.
+pub fn reasoning_detail_to_delta(detail: ReasoningDetail) -> ReasoningDelta {
+    let complete = detail.signature.is_some() || detail.data.is_some();
+    ReasoningDelta {
+        ext: if complete {
+            reasoning_detail_ext(&detail)
+        } else {
+            BTreeMap::new()
+        },
+        redacted: detail.detail_type == ENCRYPTED_REASONING,
+        text: detail.text.or(detail.summary).unwrap_or_default(),
+        signature: detail.signature.or(detail.data),
+    }
+}
+
+/// Convert an ai-ox reasoning part back to the OpenRouter reasoning detail it came from
+fn reasoning_part_to_detail(
+    text: String,
+    signature: Option<String>,
+    redacted: bool,
+    ext: &BTreeMap<String, Value>,
+) -> ReasoningDetail {
+    let ext_str = |key: &str| ext.get(key).and_then(Value::as_str).map(str::to_string);
+    let detail_type = if redacted {
+        ENCRYPTED_REASONING.to_string()
+    } else {
+        ext_str(REASONING_TYPE_EXT).unwrap_or_else(|| TEXT_REASONING.to_string())
+    };
+    let (text, summary, data, signature) = match detail_type.as_str() {
+        ENCRYPTED_REASONING => (None, None, signature, None),
+        SUMMARY_REASONING => (None, Some(text), None, signature),
+        _ => (Some(text), None, None, signature),
+    };
+
+    ReasoningDetail {
+        detail_type,
+        text,
+        summary,
+        data,
+        signature,
+        id: ext_str(REASONING_ID_EXT),
+        format: ext_str(REASONING_FORMAT_EXT),
+        index: None,
+    }
+}
+
 /// Build OpenRouter messages from ai-ox ModelRequest
 pub fn build_openrouter_messages(
     request: &ModelRequest,
@@ -83,6 +169,8 @@ pub fn convert_tools_to_openrouter(
                             .collect(),
                         #[cfg(feature = "gemini")]
                         Tool::GeminiTool(_) => Vec::new(),
+                        #[cfg(feature = "openai")]
+                        Tool::OpenAITool(_) => Vec::new(),
                     }
                 })
                 .collect();
@@ -200,10 +288,18 @@ pub fn convert_message_to_openrouter(
             let mut text_parts = Vec::new();
             let mut tool_calls = Vec::new();
             let mut tool_results = Vec::new();
+            let mut reasoning_details = Vec::new();
 
             for part in message.content {
                 match part {
                     Part::Text { text, .. } => text_parts.push(text),
+                    Part::Reasoning {
+                        text,
+                        signature,
+                        redacted,
+                        ext,
+                    } => reasoning_details
+                        .push(reasoning_part_to_detail(text, signature, redacted, &ext)),
                     Part::ToolUse { id, name, args, .. } => {
                         // Convert to proper OpenRouter tool call
                         let tool_call = OpenRouterToolCall {
@@ -249,7 +345,7 @@ pub fn convert_message_to_openrouter(
             let mut messages = Vec::new();
 
             // Create assistant message (if there's content to add)
-            if !text_parts.is_empty() || !tool_calls.is_empty() {
+            if !text_parts.is_empty() || !tool_calls.is_empty() || !reasoning_details.is_empty() {
                 let mut assistant_msg = if text_parts.is_empty() {
                     AssistantMessage::text("")
                 } else {
@@ -260,6 +356,10 @@ pub fn convert_message_to_openrouter(
                     assistant_msg.tool_calls = Some(tool_calls);
                 }
 
+                if !reasoning_details.is_empty() {
+                    assistant_msg.reasoning_details = Some(reasoning_details);
+                }
+
                 messages.push(OpenRouterMessage::Assistant(assistant_msg));
             }
 
@@ -328,6 +428,14 @@ impl From<OpenRouterMessage> for Message {
             OpenRouterMessage::Assistant(assistant_msg) => {
                 let mut parts = Vec::new();
 
+                // Structured details carry signatures, so prefer them over plain text
+                match assistant_msg.reasoning_details {
+                    Some(details) => {
+                        parts.extend(details.into_iter().map(reasoning_detail_to_part))
+                    }
+                    None => parts.extend(assistant_msg.reasoning.map(Part::reasoning)),
+                }
+
                 // Add text content
                 let text = assistant_msg
                     .content
@@ -597,6 +705,76 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_reasoning_details_round_trip() {
+        use openrouter_ox::message::AssistantMessage;
+
+        let mut assistant = AssistantMessage::text("Answer");
+        assistant.reasoning = Some("Thinking it over".to_string());
+        assistant.reasoning_details = Some(vec![
+            ReasoningDetail {
+                detail_type: TEXT_REASONING.to_string(),
+                text: Some("Thinking it over".to_string()),
+                summary: None,
+                data: None,
+                signature: Some("sig".to_string()),
+                id: None,
+                format: Some("anthropic-claude-v1".to_string()),
+                index: Some(0),
+            },
+            ReasoningDetail {
+                detail_type: ENCRYPTED_REASONING.to_string(),
+                text: None,
+                summary: None,
+                data: Some("opaque".to_string()),
+                signature: None,
+                id: Some("rs_1".to_string()),
+                format: Some("openai-responses-v1".to_string()),
+                index: Some(1),
+            },
+        ]);
+
+        let message: Message = OpenRouterMessage::Assistant(assistant).into();
+        assert_eq!(message.content.len(), 3);
+        match &message.content[0] {
+            Part::Reasoning {
+                text, signature, ..
+            } => {
+                assert_eq!(text, "Thinking it over");
+                assert_eq!(signature.as_deref(), Some("sig"));
+            }
+            _ => panic!("Expected reasoning part"),
+        }
+        assert!(matches!(
+            &message.content[1],
+            Part::Reasoning { redacted: true, .. }
+        ));
+
+        let converted = convert_message_to_openrouter(message, "test-model").unwrap();
+        let OpenRouterMessage::Assistant(assistant) = &converted[0] else {
+            panic!("Expected Assistant message");
+        };
+        let details = assistant.reasoning_details.as_ref().unwrap();
+        assert_eq!(details[0].text.as_deref(), Some("Thinking it over"));
+        assert_eq!(details[0].signature.as_deref(), Some("sig"));
+        assert_eq!(details[0].format.as_deref(), Some("anthropic-claude-v1"));
+        assert_eq!(details[1].detail_type, ENCRYPTED_REASONING);
+        assert_eq!(details[1].data.as_deref(), Some("opaque"));
+        assert_eq!(details[1].id.as_deref(), Some("rs_1"));
+    }
+
+    #[test]
+    fn test_reasoning_without_details() {
+        use openrouter_ox::message::AssistantMessage;
+
+        let mut assistant = AssistantMessage::text("Answer");
+        assistant.reasoning = Some("Plain reasoning".to_string());
+
+        let message: Message = OpenRouterMessage::Assistant(assistant).into();
+        assert_eq!(message.content[0], Part::reasoning("Plain reasoning"));
+        assert_eq!(message.content[1], Part::text("Answer"));
+    }
+
     #[test]
     fn test_assistant_tool_call_conversion() {
         let message = Message {
@@ -1124,6 +1302,8 @@ mod tests {
             messages: vec![user_message],
             system_message: None,
             tools: Some(vec![knowledge_search_tool]),
+            generation_config: None,
+            cache: None,
         };
 
         println!("Step 1: Making initial request with tool...");
@@ -1200,6 +1380,8 @@ mod tests {
             messages: messages_with_result,
             system_message: None,
             tools: Some(vec![]),
+            generation_config: None,
+            cache: None,
         };
 
         println!("Step 3: Sending tool result back to OpenRouter...");
@@ -69,9 +69,12 @@ impl From<OpenRouterError> for GenerateContentError {
                         event_error
                     ))
                 }
-                OpenRouterRequestError::RateLimit => GenerateContentError::provider_error(
+                OpenRouterRequestError::RateLimit(info) => GenerateContentError::provider_error(
                     "openrouter",
-                    "Rate limit exceeded".to_string(),
+                    match info.wait_time() {
+                        Some(wait) => format!("Rate limit exceeded, retry after {wait:?}"),
+                        None => "Rate limit exceeded".to_string(),
+                    },
                 ),
                 OpenRouterRequestError::UrlBuildError(url_error) => {
                     GenerateContentError::configuration(format!(
@@ -17,13 +17,14 @@ use ai_ox_common::openai_format::{
 };
 
 use crate::{
-    content::delta::StreamEvent,
+    content::delta::{ReasoningDelta, StreamEvent},
     errors::GenerateContentError,
     model::{
-        Model, ModelInfo, Provider,
+        Model, ModelInfo, Provider, prepare_request,
         request::ModelRequest,
         response::{ModelResponse, RawStructuredResponse},
     },
+    provider::{Capabilities, ConversionPolicy},
     tool::ToolUse,
 };
 use std::collections::HashMap;
@@ -58,6 +59,22 @@ impl OpenRouterStreamProcessor {
 
         // Process each choice in the chunk
         for choice in chunk.choices {
+            // Handle reasoning, preferring structured details that carry signatures
+            match choice.delta.reasoning_details {
+                Some(details) if !details.is_empty() => {
+                    events.extend(details.into_iter().map(|detail| {
+                        StreamEvent::ReasoningDelta(conversion::reasoning_detail_to_delta(detail))
+                    }));
+                }
+                _ => {
+                    if let Some(reasoning) = choice.delta.reasoning
+                        && !reasoning.is_empty()
+                    {
+                        events.push(StreamEvent::ReasoningDelta(ReasoningDelta::text(reasoning)));
+                    }
+                }
+            }
+
             // Handle text content
             if let Some(content) = choice.delta.content {
                 if !content.is_empty() {
@@ -164,11 +181,15 @@ impl OpenRouterStreamProcessor {
                     events.push(StreamEvent::StreamStop(crate::content::delta::StreamStop {
                         finish_reason: conversion::convert_finish_reason(finish_reason),
                         usage,
+                        model_name: None,
+                        vendor_name: None,
                     }));
                 } else {
                     events.push(StreamEvent::StreamStop(crate::content::delta::StreamStop {
                         finish_reason: conversion::convert_finish_reason(finish_reason),
                         usage: crate::usage::Usage::default(),
+                        model_name: None,
+                        vendor_name: None,
                     }));
                 }
             }
@@ -186,6 +207,9 @@ pub struct OpenRouterModel {
     model: String,
     #[builder(default = default_tool_choice())]
     tool_choice: OaiToolChoice,
+    /// Policy for content the provider can't represent exactly.
+    #[builder(default)]
+    conversion_policy: ConversionPolicy,
 }
 
 /// Returns the default tool choice for OpenRouter models.
@@ -218,6 +242,7 @@ impl OpenRouterModel {
             model: model_name,
             client,
             tool_choice: default_tool_choice(),
+            conversion_policy: ConversionPolicy::default(),
         })
     }
 
@@ -230,6 +255,11 @@ impl OpenRouterModel {
     ) -> Result<OpenRouterRequest, OpenRouterError> {
         // Convert messages using the conversion module
         let messages = conversion::build_openrouter_messages(&request, model)?;
+        let config = request.generation_config.clone().unwrap_or_default();
+        let tool_choice = config
+            .tool_choice
+            .map(Into::into)
+            .unwrap_or_else(|| tool_choice.clone());
 
         // Convert tools using the conversion module
         let tools = conversion::convert_tools_to_openrouter(request.tools)?;
@@ -252,7 +282,7 @@ impl OpenRouterModel {
                 .model(model)
                 .messages(messages)
                 .tools(openrouter_tools)
-                .tool_choice(tool_choice.clone())
+                .tool_choice(tool_choice)
                 .build()
         } else {
             OpenRouterRequest::builder()
@@ -261,6 +291,14 @@ impl OpenRouterModel {
                 .build()
         };
 
+        // Apply generation parameters
+        request.temperature = config.temperature;
+        request.top_p = config.top_p;
+        request.top_k = config.top_k;
+        request.max_tokens = config.max_output_tokens;
+        request.stop = config.stop_sequences;
+        request.seed = config.seed;
+
         // Set response_format if provided
         if let Some(format) = response_format {
             request.response_format = Some(format);
@@ -279,11 +317,20 @@ impl Model for OpenRouterModel {
         &self.model
     }
 
+    fn capabilities(&self) -> Option<Capabilities> {
+        Some(Capabilities::openrouter())
+    }
+
+    fn conversion_policy(&self) -> &ConversionPolicy {
+        &self.conversion_policy
+    }
+
     fn request(
         &self,
         request: ModelRequest,
     ) -> BoxFuture<'_, Result<ModelResponse, GenerateContentError>> {
         async move {
+            let request = prepare_request(self, request).await?;
             // Build the request using the helper function
             let chat_request =
                 Self::build_openrouter_request(request, &self.model, &self.tool_choice, None)?;
@@ -326,6 +373,7 @@ impl Model for OpenRouterModel {
         let tool_choice = self.tool_choice.clone();
 
         let stream = try_stream! {
+            let request = prepare_request(self, request).await?;
             // Build the request using the helper function
             let chat_request = Self::build_openrouter_request(request, &model_name, &tool_choice, None)?;
 
@@ -360,6 +408,7 @@ impl Model for OpenRouterModel {
         schema: String,
     ) -> BoxFuture<'_, Result<RawStructuredResponse, GenerateContentError>> {
         async move {
+            let request = prepare_request(self, request).await?;
             // Parse and validate the schema
             let schema_value: Value = serde_json::from_str(&schema)
                 .map_err(|e| OpenRouterError::InvalidSchema(e.to_string()))?;
@@ -0,0 +1,54 @@
+use crate::{errors::GenerateContentError, model::request::GenerationConfig};
+
+/// Settings of [`GenerationConfig`] that some providers cannot honour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GenerationOption {
+    TopK,
+    StopSequences,
+    Seed,
+    ToolChoice,
+}
+
+impl GenerationOption {
+    /// The name of the corresponding `GenerationConfig` field.
+    fn name(self) -> &'static str {
+        match self {
+            Self::TopK => "top_k",
+            Self::StopSequences => "stop_sequences",
+            Self::Seed => "seed",
+            Self::ToolChoice => "tool_choice",
+        }
+    }
+}
+
+impl GenerationConfig {
+    /// The options that are set in this config.
+    fn set_options(&self) -> impl Iterator<Item = GenerationOption> {
+        [
+            (self.top_k.is_some(), GenerationOption::TopK),
+            (
+                self.stop_sequences.is_some(),
+                GenerationOption::StopSequences,
+            ),
+            (self.seed.is_some(), GenerationOption::Seed),
+            (self.tool_choice.is_some(), GenerationOption::ToolChoice),
+        ]
+        .into_iter()
+        .filter_map(|(is_set, option)| is_set.then_some(option))
+    }
+
+    /// Returns an `UnsupportedFeature` error if any of the given options is set.
+    pub(crate) fn reject(
+        &self,
+        provider: &str,
+        options: &[GenerationOption],
+    ) -> Result<(), GenerateContentError> {
+        match self.set_options().find(|option| options.contains(option)) {
+            Some(option) => Err(GenerateContentError::unsupported_feature(format!(
+                "{provider} does not support the `{}` generation option",
+                option.name()
+            ))),
+            None => Ok(()),
+        }
+    }
+}
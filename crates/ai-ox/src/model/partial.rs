@@ -0,0 +1,339 @@
+use std::marker::PhantomData;
+
+use async_stream::try_stream;
+use futures_util::{StreamExt, stream::BoxStream};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{
+    StructuredResponse,
+    content::{
+        delta::StreamEvent,
+        message::{Message, MessageRole},
+        part::Part,
+    },
+    errors::GenerateContentError,
+    model::request::ModelRequest,
+    usage::Usage,
+};
+
+/// Parses the JSON document at the start of `text`, which may be truncated.
+///
+/// Open strings, arrays and objects are closed, and trailing tokens that can't
+/// be completed yet (a half-written key or literal) are dropped, so the result
+/// is the most complete value the text describes so far. A trailing number is
+/// kept as written, although more digits may still follow. Any prose or
+/// code fence before the first `{` or `[` is skipped. Returns `None` until an
+/// object or array has started.
+pub fn parse_partial_json(text: &str) -> Option<Value> {
+    let text = &text[text.find(['{', '['])?..];
+    if let Ok(value) = serde_json::from_str(text) {
+        return Some(value);
+    }
+
+    // Positions where the text can be cut and closed, with the containers
+    // still open at that point.
+    let mut cuts: Vec<(usize, Vec<u8>)> = Vec::new();
+    let mut open = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, byte) in text.bytes().enumerate() {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => {
+                    in_string = false;
+                    cuts.push((i + 1, open.clone()));
+                }
+                _ => {}
+            }
+            continue;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'{' | b'[' => {
+                open.push(byte);
+                cuts.push((i + 1, open.clone()));
+            }
+            b'}' | b']' => {
+                open.pop();
+                cuts.push((i + 1, open.clone()));
+                if open.is_empty() {
+                    break;
+                }
+            }
+            b',' => cuts.push((i, open.clone())),
+            _ => {}
+        }
+    }
+
+    let mut tail = text.to_string();
+    if in_string {
+        if escaped {
+            tail.pop();
+        }
+        tail.push('"');
+    }
+
+    std::iter::once(close(tail, &open))
+        .chain(
+            cuts.iter()
+                .rev()
+                .map(|(end, open)| close(text[..*end].to_string(), open)),
+        )
+        .find_map(|candidate| serde_json::from_str(&candidate).ok())
+}
+
+/// Appends the closing brackets for every container in `open`.
+fn close(mut text: String, open: &[u8]) -> String {
+    text.extend(
+        open.iter()
+            .rev()
+            .map(|&byte| if byte == b'{' { '}' } else { ']' }),
+    );
+    text
+}
+
+/// An incomplete structured value of type `O`.
+///
+/// Wraps a snapshot produced by [`parse_partial_json`]. Fields that have not
+/// arrived yet are missing, and the last string or number may still be growing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Partial<O> {
+    value: Value,
+    _type: PhantomData<fn() -> O>,
+}
+
+impl<O> Partial<O> {
+    /// Wraps a snapshot of the value.
+    pub fn new(value: Value) -> Self {
+        Self {
+            value,
+            _type: PhantomData,
+        }
+    }
+
+    /// Returns the snapshot as JSON.
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// Consumes the view, returning the snapshot as JSON.
+    pub fn into_value(self) -> Value {
+        self.value
+    }
+
+    /// Returns the field `name` if it has started to arrive and deserializes
+    /// as `T`.
+    pub fn field<T: DeserializeOwned>(&self, name: &str) -> Option<T> {
+        T::deserialize(self.value.get(name)?).ok()
+    }
+}
+
+impl<O: DeserializeOwned> Partial<O> {
+    /// Deserializes the snapshot as `O`.
+    ///
+    /// Succeeds once every required field is present, which may be before
+    /// the value is complete.
+    pub fn try_parse(&self) -> Option<O> {
+        O::deserialize(&self.value).ok()
+    }
+}
+
+/// An event in a typed response stream.
+#[derive(Debug, Clone)]
+pub enum StructuredStreamEvent<O> {
+    /// A more complete snapshot of the value than the previous one.
+    Partial(Partial<O>),
+    /// The final, validated value. This is the terminal event.
+    Complete(Box<StructuredResponse<O>>),
+}
+
+/// Turns a stream of JSON text into snapshots followed by the value `finish`
+/// parses from the complete text.
+pub(crate) fn typed_stream<'a, O, E>(
+    mut events: BoxStream<'a, Result<StreamEvent, GenerateContentError>>,
+    vendor_name: String,
+    model_name: String,
+    finish: impl FnOnce(&str) -> Result<O, E> + Send + 'a,
+) -> BoxStream<'a, Result<StructuredStreamEvent<O>, E>>
+where
+    O: Send + 'a,
+    E: From<GenerateContentError> + Send + 'a,
+{
+    try_stream! {
+        let mut text = String::new();
+        let mut snapshot = None;
+        let mut usage = None;
+        let mut stop_usage = None;
+
+        while let Some(event) = events.next().await {
+            match event? {
+                StreamEvent::TextDelta(delta) => {
+                    text.push_str(&delta);
+                    let value = parse_partial_json(&text);
+                    if value.is_some() && value != snapshot {
+                        snapshot = value;
+                        yield StructuredStreamEvent::Partial(Partial::new(snapshot.clone().unwrap()));
+                    }
+                }
+                StreamEvent::Usage(event_usage) => usage = Some(event_usage),
+                StreamEvent::StreamStop(stop) => stop_usage = Some(stop.usage),
+                _ => {}
+            }
+        }
+
+        let data = finish(&text)?;
+        yield StructuredStreamEvent::Complete(Box::new(StructuredResponse {
+            data,
+            vendor_name,
+            model_name,
+            usage: usage.or(stop_usage).unwrap_or_else(Usage::new),
+        }));
+    }
+    .boxed()
+}
+
+/// Appends an instruction to respond with JSON matching `schema` to the
+/// request's system message.
+///
+/// Used by models that can't constrain streamed output natively.
+pub(crate) fn instruct_json(request: &mut ModelRequest, schema: &str) {
+    let instruction = Part::text(format!(
+        "Respond only with a JSON value that conforms to this JSON schema, \
+         without code fences or commentary:\n{schema}"
+    ));
+    match &mut request.system_message {
+        Some(message) => message.content.push(instruction),
+        None => {
+            request.system_message = Some(Message::new(MessageRole::System, vec![instruction]));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_partial_json_is_closed() {
+        let cases = [
+            (r#"{"title": "Hel"#, json!({"title": "Hel"})),
+            (r#"{"title": "Hello", "ta"#, json!({"title": "Hello"})),
+            (r#"{"a": 1, "b": tr"#, json!({"a": 1})),
+            (r#"{"a": 12"#, json!({"a": 12})),
+            (
+                r#"{"items": [{"n": 1}, {"n": "#,
+                json!({"items": [{"n": 1}, {}]}),
+            ),
+            (r#"{"s": "line\"#, json!({"s": "line"})),
+            ("```json\n{\"done\": true}\n```", json!({"done": true})),
+            ("[1, 2, ", json!([1, 2])),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(parse_partial_json(text), Some(expected), "{text}");
+        }
+        assert_eq!(parse_partial_json("Sure, here"), None);
+    }
+
+    #[derive(Debug, serde::Deserialize, schemars::JsonSchema, PartialEq)]
+    struct Story {
+        title: String,
+        body: Option<String>,
+    }
+
+    #[tokio::test]
+    async fn test_typed_stream_yields_growing_snapshots() {
+        let chunks = [r#"{"ti"#, r#"tle": "Ru"#, r#"st", "#, r#""body": "ok"}"#];
+        let events = futures_util::stream::iter(
+            chunks
+                .map(|chunk| Ok(StreamEvent::TextDelta(chunk.to_string())))
+                .into_iter()
+                .chain([Ok(StreamEvent::Usage(Usage::new()))]),
+        )
+        .boxed();
+
+        let events: Vec<_> = typed_stream(events, "test".into(), "model".into(), |text| {
+            serde_json::from_str::<Story>(text)
+                .map_err(|e| GenerateContentError::response_parsing(e.to_string()))
+        })
+        .collect()
+        .await;
+
+        let snapshots: Vec<Value> = events
+            .iter()
+            .filter_map(|event| match event {
+                Ok(StructuredStreamEvent::Partial(partial)) => Some(partial.value().clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            snapshots,
+            [
+                json!({}),
+                json!({"title": "Ru"}),
+                json!({"title": "Rust"}),
+                json!({"title": "Rust", "body": "ok"}),
+            ]
+        );
+
+        let Some(Ok(StructuredStreamEvent::Partial(partial))) = events.get(1) else {
+            panic!("expected a snapshot");
+        };
+        let partial: &Partial<Story> = partial;
+        assert_eq!(partial.field::<String>("title").as_deref(), Some("Ru"));
+        assert_eq!(partial.try_parse().map(|story| story.body), Some(None));
+
+        let Some(Ok(StructuredStreamEvent::Complete(response))) = events.last() else {
+            panic!("expected the final value");
+        };
+        assert_eq!(
+            response.data,
+            Story {
+                title: "Rust".into(),
+                body: Some("ok".into())
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_agent_streams_and_validates_typed_output() {
+        use crate::{
+            agent::{Agent, error::AgentError},
+            model::mock::{MockModel, MockReply},
+        };
+
+        let chunks = ["```json\n{\"title\": ", "\"Rust\"", "}\n```"];
+        let model = MockModel::new("mock")
+            .with_reply(MockReply::stream(
+                chunks.map(|chunk| StreamEvent::TextDelta(chunk.to_string())),
+            ))
+            .with_reply(MockReply::text(r#"{"title": 7}"#));
+        let agent = Agent::model(model.clone()).build();
+        let ask = || [Message::new(MessageRole::User, vec![Part::text("story")])];
+
+        let events: Vec<_> = agent.stream_typed::<Story>(ask()).collect().await;
+        assert!(matches!(
+            &events[..],
+            [
+                Ok(StructuredStreamEvent::Partial(_)),
+                Ok(StructuredStreamEvent::Partial(_)),
+                Ok(StructuredStreamEvent::Complete(response)),
+            ] if response.data.title == "Rust" && response.vendor_name == "mock"
+        ));
+
+        let system = model.last_request().unwrap().system_message.unwrap();
+        assert!(
+            matches!(&system.content[..], [Part::Text { text, .. }] if text.contains("\"title\""))
+        );
+
+        let events: Vec<_> = agent.stream_typed::<Story>(ask()).collect().await;
+        assert!(matches!(
+            events.last(),
+            Some(Err(AgentError::SchemaValidationFailed { .. }))
+        ));
+    }
+}
@@ -5,8 +5,9 @@
 //! request struct provides a stable, vendor-agnostic API.
 
 use bon::Builder;
+use serde::{Deserialize, Serialize};
 
-use crate::{content::Message, tool::Tool};
+use crate::{content::Message, model::cache::CacheBreakpoints, tool::Tool};
 
 /// Represents a single, canonical request to a large language model.
 ///
@@ -24,6 +25,10 @@ pub struct ModelRequest {
     /// An optional system instruction to guide the model's behavior.
     #[builder(into)]
     pub system_message: Option<Message>,
+    /// Optional sampling and output parameters for this request.
+    pub generation_config: Option<GenerationConfig>,
+    /// Parts of the prompt that providers may cache.
+    pub cache: Option<CacheBreakpoints>,
 }
 
 impl<S: model_request_builder::State> ModelRequestBuilder<S> {
@@ -51,6 +56,57 @@ where
             messages: messages.into_iter().map(Into::into).collect(),
             system_message: None,
             tools: None,
+            generation_config: None,
+            cache: None,
         }
     }
 }
+
+/// Controls whether and how the model may call tools.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolChoice {
+    /// The model decides whether to call a tool.
+    Auto,
+    /// The model must not call any tool.
+    None,
+    /// The model must call at least one tool.
+    Required,
+    /// The model must call the tool with the given name.
+    Tool(String),
+}
+
+impl From<ToolChoice> for ai_ox_common::openai_format::ToolChoice {
+    fn from(choice: ToolChoice) -> Self {
+        match choice {
+            ToolChoice::Auto => Self::Auto,
+            ToolChoice::None => Self::None,
+            ToolChoice::Required => Self::Required,
+            ToolChoice::Tool(name) => Self::function(name),
+        }
+    }
+}
+
+/// Provider-neutral generation parameters.
+///
+/// Every field is optional; unset fields leave the provider's default in place.
+/// Models that cannot honour a set field return
+/// [`GenerateContentError::UnsupportedFeature`] instead of silently ignoring it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Builder)]
+pub struct GenerationConfig {
+    /// Sampling temperature.
+    pub temperature: Option<f64>,
+    /// Nucleus sampling probability mass.
+    pub top_p: Option<f64>,
+    /// Number of most likely tokens to sample from.
+    pub top_k: Option<u32>,
+    /// Maximum number of tokens to generate.
+    pub max_output_tokens: Option<u32>,
+    /// Sequences that stop generation when produced.
+    #[builder(with = |v: impl IntoIterator<Item = impl Into<String>>| v.into_iter().map(Into::into).collect())]
+    pub stop_sequences: Option<Vec<String>>,
+    /// Seed for deterministic sampling.
+    pub seed: Option<u32>,
+    /// How the model should use the provided tools.
+    pub tool_choice: Option<ToolChoice>,
+}
@@ -0,0 +1,474 @@
+use std::{collections::BTreeSet, fmt, sync::Arc};
+
+use async_stream::stream;
+use futures_util::{FutureExt, StreamExt, future::BoxFuture, stream::BoxStream};
+
+use crate::{
+    ModelResponse,
+    content::{delta::StreamEvent, part::Part, tokens::estimate_tokens},
+    errors::GenerateContentError,
+    model::{
+        Model, ModelInfo, request::ModelRequest, response::RawStructuredResponse, vendor_name,
+    },
+    provider::Capabilities,
+};
+
+/// A model that tries several models in order.
+///
+/// Each request goes to the first model. When it fails with a
+/// [retryable](GenerateContentError::is_retryable) error, the same request is
+/// sent to the next model, and so on; the last model's error is returned if
+/// all of them fail. Streams only fall back if the error arrives before the
+/// first event. Responses keep the `model_name` and `vendor_name` of the model
+/// that actually answered.
+#[derive(Debug, Clone)]
+pub struct FallbackModel {
+    models: Vec<Arc<dyn Model>>,
+}
+
+impl FallbackModel {
+    /// Creates a fallback chain starting with `primary`.
+    pub fn new(primary: Arc<dyn Model>) -> Self {
+        Self {
+            models: vec![primary],
+        }
+    }
+
+    /// Adds a model to try after the existing ones.
+    pub fn with_fallback(mut self, model: Arc<dyn Model>) -> Self {
+        self.models.push(model);
+        self
+    }
+
+    /// Returns the models in the order they are tried.
+    pub fn models(&self) -> &[Arc<dyn Model>] {
+        &self.models
+    }
+}
+
+impl Model for FallbackModel {
+    /// Returns the info of the primary model.
+    fn info(&self) -> ModelInfo<'_> {
+        self.models[0].info()
+    }
+
+    /// Returns the name of the primary model.
+    fn name(&self) -> &str {
+        self.models[0].name()
+    }
+
+    fn request(
+        &self,
+        request: ModelRequest,
+    ) -> BoxFuture<'_, Result<ModelResponse, GenerateContentError>> {
+        request_in_order(self.models.iter().collect(), request).boxed()
+    }
+
+    fn request_stream(
+        &self,
+        request: ModelRequest,
+    ) -> BoxStream<'_, Result<StreamEvent, GenerateContentError>> {
+        stream_in_order(self.models.iter().collect(), request, None)
+    }
+
+    fn request_structured_internal(
+        &self,
+        request: ModelRequest,
+        schema: String,
+    ) -> BoxFuture<'_, Result<RawStructuredResponse, GenerateContentError>> {
+        structured_in_order(self.models.iter().collect(), request, schema).boxed()
+    }
+
+    fn request_structured_stream(
+        &self,
+        request: ModelRequest,
+        schema: String,
+    ) -> BoxStream<'_, Result<StreamEvent, GenerateContentError>> {
+        stream_in_order(self.models.iter().collect(), request, Some(schema))
+    }
+}
+
+/// What a [`RouterModel`] knows about a request when choosing a model.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestProfile {
+    /// Estimated prompt size, see [`estimate_tokens`].
+    pub estimated_tokens: u64,
+    /// MIME types of every blob in the request, including tool results.
+    pub mime_types: BTreeSet<String>,
+    /// Whether the request declares tools or contains tool calls or results.
+    pub uses_tools: bool,
+}
+
+impl RequestProfile {
+    /// Profiles a request.
+    pub fn from_request(request: &ModelRequest) -> Self {
+        let messages: Vec<_> = request
+            .system_message
+            .iter()
+            .chain(&request.messages)
+            .cloned()
+            .collect();
+
+        let mut profile = Self {
+            estimated_tokens: estimate_tokens(&messages),
+            mime_types: BTreeSet::new(),
+            uses_tools: request
+                .tools
+                .as_ref()
+                .is_some_and(|tools| !tools.is_empty()),
+        };
+        for part in messages.iter().flat_map(|message| &message.content) {
+            profile.visit(part);
+        }
+        profile
+    }
+
+    fn visit(&mut self, part: &Part) {
+        match part {
+            Part::Blob { mime_type, .. } => {
+                self.mime_types.insert(mime_type.clone());
+            }
+            Part::ToolUse { .. } => self.uses_tools = true,
+            Part::ToolResult { parts, .. } => {
+                self.uses_tools = true;
+                for part in parts {
+                    self.visit(part);
+                }
+            }
+            Part::Text { .. } | Part::Reasoning { .. } | Part::Opaque { .. } => {}
+        }
+    }
+
+    /// Returns `true` if the request contains images.
+    pub fn has_images(&self) -> bool {
+        self.mime_types
+            .iter()
+            .any(|mime| mime.starts_with("image/"))
+    }
+
+    /// Returns `true` if the request contains audio.
+    pub fn has_audio(&self) -> bool {
+        self.mime_types
+            .iter()
+            .any(|mime| mime.starts_with("audio/"))
+    }
+
+    /// Returns `true` if a provider with these capabilities can accept the
+    /// request's content and tools.
+    pub fn fits(&self, capabilities: &Capabilities) -> bool {
+        (!self.uses_tools || capabilities.supports_tool_use)
+            && self
+                .mime_types
+                .iter()
+                .all(|mime| capabilities.supports_mime(mime))
+    }
+}
+
+/// Decides whether a model may serve a request.
+pub type RoutePredicate = dyn Fn(&RequestProfile, &dyn Model) -> bool + Send + Sync;
+
+/// A model that routes each request to the models able to serve it.
+///
+/// The request is profiled and every model is checked with the route
+/// predicate, in order. The request goes to the first matching model and falls
+/// back to the next matching ones like [`FallbackModel`]. By default a model
+/// matches when [`RequestProfile::fits`] its [`Model::capabilities`]; models
+/// without known capabilities always match.
+#[derive(Clone)]
+pub struct RouterModel {
+    models: Vec<Arc<dyn Model>>,
+    predicate: Arc<RoutePredicate>,
+}
+
+impl fmt::Debug for RouterModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RouterModel")
+            .field("models", &self.models)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RouterModel {
+    /// Creates a router whose first candidate is `primary`.
+    pub fn new(primary: Arc<dyn Model>) -> Self {
+        Self {
+            models: vec![primary],
+            predicate: Arc::new(|profile, model| {
+                model
+                    .capabilities()
+                    .is_none_or(|capabilities| profile.fits(&capabilities))
+            }),
+        }
+    }
+
+    /// Adds a candidate model after the existing ones.
+    pub fn with_model(mut self, model: Arc<dyn Model>) -> Self {
+        self.models.push(model);
+        self
+    }
+
+    /// Replaces the route predicate.
+    ///
+    /// The predicate receives the request profile and a candidate model, and
+    /// can combine [`RequestProfile::fits`] with its own rules, such as
+    /// reserving a large-context model for long prompts.
+    pub fn with_predicate(
+        mut self,
+        predicate: impl Fn(&RequestProfile, &dyn Model) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.predicate = Arc::new(predicate);
+        self
+    }
+
+    /// Returns the models that may serve `request`, in the order they are tried.
+    pub fn route(&self, request: &ModelRequest) -> Vec<&Arc<dyn Model>> {
+        let profile = RequestProfile::from_request(request);
+        self.models
+            .iter()
+            .filter(|model| (self.predicate)(&profile, model.as_ref()))
+            .collect()
+    }
+}
+
+impl Model for RouterModel {
+    /// Returns the info of the first candidate model.
+    fn info(&self) -> ModelInfo<'_> {
+        self.models[0].info()
+    }
+
+    /// Returns the name of the first candidate model.
+    fn name(&self) -> &str {
+        self.models[0].name()
+    }
+
+    fn request(
+        &self,
+        request: ModelRequest,
+    ) -> BoxFuture<'_, Result<ModelResponse, GenerateContentError>> {
+        request_in_order(self.route(&request), request).boxed()
+    }
+
+    fn request_stream(
+        &self,
+        request: ModelRequest,
+    ) -> BoxStream<'_, Result<StreamEvent, GenerateContentError>> {
+        stream_in_order(self.route(&request), request, None)
+    }
+
+    fn request_structured_internal(
+        &self,
+        request: ModelRequest,
+        schema: String,
+    ) -> BoxFuture<'_, Result<RawStructuredResponse, GenerateContentError>> {
+        structured_in_order(self.route(&request), request, schema).boxed()
+    }
+
+    fn request_structured_stream(
+        &self,
+        request: ModelRequest,
+        schema: String,
+    ) -> BoxStream<'_, Result<StreamEvent, GenerateContentError>> {
+        stream_in_order(self.route(&request), request, Some(schema))
+    }
+}
+
+fn no_model_error() -> GenerateContentError {
+    GenerateContentError::unsupported_feature("No model is able to serve this request")
+}
+
+async fn request_in_order(
+    models: Vec<&Arc<dyn Model>>,
+    request: ModelRequest,
+) -> Result<ModelResponse, GenerateContentError> {
+    let mut last_error = None;
+    for model in models {
+        match model.request(request.clone()).await {
+            Ok(response) => return Ok(response),
+            Err(error) if error.is_retryable() => last_error = Some(error),
+            Err(error) => return Err(error),
+        }
+    }
+    Err(last_error.unwrap_or_else(no_model_error))
+}
+
+async fn structured_in_order(
+    models: Vec<&Arc<dyn Model>>,
+    request: ModelRequest,
+    schema: String,
+) -> Result<RawStructuredResponse, GenerateContentError> {
+    let mut last_error = None;
+    for model in models {
+        match model
+            .request_structured_internal(request.clone(), schema.clone())
+            .await
+        {
+            Ok(response) => return Ok(response),
+            Err(error) if error.is_retryable() => last_error = Some(error),
+            Err(error) => return Err(error),
+        }
+    }
+    Err(last_error.unwrap_or_else(no_model_error))
+}
+
+/// Records `model` as the model that served a stream on its stop event.
+fn served_by(
+    event: Result<StreamEvent, GenerateContentError>,
+    model: &dyn Model,
+) -> Result<StreamEvent, GenerateContentError> {
+    match event {
+        Ok(StreamEvent::StreamStop(mut stop)) if stop.model_name.is_none() => {
+            stop.vendor_name = Some(vendor_name(model));
+            stop.model_name = Some(model.name().to_string());
+            Ok(StreamEvent::StreamStop(stop))
+        }
+        other => other,
+    }
+}
+
+fn stream_in_order(
+    models: Vec<&Arc<dyn Model>>,
+    request: ModelRequest,
+    schema: Option<String>,
+) -> BoxStream<'_, Result<StreamEvent, GenerateContentError>> {
+    stream! {
+        let mut last_error = None;
+        for model in models {
+            let mut events = match &schema {
+                Some(schema) => model.request_structured_stream(request.clone(), schema.clone()),
+                None => model.request_stream(request.clone()),
+            };
+            match events.next().await {
+                Some(Err(error)) if error.is_retryable() => last_error = Some(error),
+                Some(first) => {
+                    yield served_by(first, model.as_ref());
+                    while let Some(event) = events.next().await {
+                        yield served_by(event, model.as_ref());
+                    }
+                    return;
+                }
+                None => return,
+            }
+        }
+        yield Err(last_error.unwrap_or_else(no_model_error));
+    }
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        content::message::{Message, MessageRole},
+        content::part::DataRef,
+        model::mock::{MockModel, MockReply},
+    };
+
+    fn rate_limited() -> MockReply {
+        MockReply::Error(GenerateContentError::provider_error(
+            "mock",
+            "Rate limit exceeded",
+        ))
+    }
+
+    /// A model answering `replies` requests with its own name.
+    fn answering(name: &str, replies: usize) -> MockModel {
+        (0..replies).fold(MockModel::new(name), |model, _| {
+            model.with_reply(MockReply::text(name))
+        })
+    }
+
+    fn request(parts: Vec<Part>) -> ModelRequest {
+        ModelRequest {
+            messages: vec![Message::new(MessageRole::User, parts)],
+            system_message: None,
+            tools: None,
+            generation_config: None,
+            cache: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fallback_reports_answering_model() {
+        let primary = MockModel::new("primary")
+            .with_reply(rate_limited())
+            .with_reply(rate_limited());
+        let model =
+            FallbackModel::new(Arc::new(primary)).with_fallback(Arc::new(answering("backup", 2)));
+
+        let response = model
+            .request(request(vec![Part::text("hi")]))
+            .await
+            .unwrap();
+        assert_eq!(response.model_name, "backup");
+
+        let events: Vec<_> = model
+            .request_stream(request(vec![Part::text("hi")]))
+            .collect()
+            .await;
+        assert!(matches!(&events[0], Ok(StreamEvent::TextDelta(name)) if name == "backup"));
+        assert!(matches!(
+            events.last(),
+            Some(Ok(StreamEvent::StreamStop(stop))) if stop.model_name.as_deref() == Some("backup")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fallback_stops_on_non_retryable_error() {
+        let backup = MockModel::new("backup");
+        let primary = MockModel::new("primary").with_reply(MockReply::Error(
+            GenerateContentError::configuration("missing key"),
+        ));
+        let model = FallbackModel::new(Arc::new(primary)).with_fallback(Arc::new(backup.clone()));
+
+        let error = model.request(request(vec![Part::text("hi")])).await;
+        assert!(matches!(error, Err(GenerateContentError::Configuration(_))));
+        assert!(backup.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_router_skips_models_without_image_support() {
+        let model = RouterModel::new(Arc::new(
+            answering("text-only", 1).with_capabilities(Capabilities::groq()),
+        ))
+        .with_model(Arc::new(
+            answering("vision", 1).with_capabilities(Capabilities::anthropic()),
+        ));
+
+        let image = Part::Blob {
+            data_ref: DataRef::uri("https://example.com/cat.png"),
+            mime_type: "image/png".to_string(),
+            name: None,
+            description: None,
+            ext: Default::default(),
+        };
+        let response = model.request(request(vec![image])).await.unwrap();
+        assert_eq!(response.model_name, "vision");
+
+        let response = model
+            .request(request(vec![Part::text("hi")]))
+            .await
+            .unwrap();
+        assert_eq!(response.model_name, "text-only");
+    }
+
+    #[tokio::test]
+    async fn test_router_custom_predicate() {
+        let model = RouterModel::new(Arc::new(answering("small", 1)))
+            .with_model(Arc::new(answering("large", 1)))
+            .with_predicate(|profile, model| {
+                (profile.estimated_tokens > 10) == (model.name() == "large")
+            });
+
+        let short = model
+            .request(request(vec![Part::text("hi")]))
+            .await
+            .unwrap();
+        let long = model
+            .request(request(vec![Part::text("a".repeat(100))]))
+            .await
+            .unwrap();
+
+        assert_eq!(short.model_name, "small");
+        assert_eq!(long.model_name, "large");
+    }
+}
@@ -0,0 +1,349 @@
+use std::sync::Arc;
+
+use futures_util::{
+    FutureExt, StreamExt, future,
+    future::BoxFuture,
+    stream::{self, BoxStream},
+};
+use serde_json::Value;
+
+use crate::{
+    ModelResponse,
+    content::{delta::StreamEvent, part::Part},
+    errors::GenerateContentError,
+    model::{
+        Model, ModelInfo,
+        request::{ModelRequest, ToolChoice},
+        response::RawStructuredResponse,
+    },
+    provider::{Capabilities, ConversionPlan, ConversionPolicy},
+    tool::{FunctionMetadata, Tool},
+};
+
+/// Name of the synthetic tool used to force structured output.
+pub const STRUCTURED_OUTPUT_TOOL: &str = "json_data";
+
+/// Replaces the request's tools with a single tool whose input schema is
+/// `schema` and requires the model to call it.
+fn force_structured_tool(
+    request: &mut ModelRequest,
+    schema: &str,
+) -> Result<(), GenerateContentError> {
+    let parameters: Value = serde_json::from_str(schema).map_err(|e| {
+        GenerateContentError::configuration(format!(
+            "Invalid JSON schema for structured output: {e}"
+        ))
+    })?;
+
+    request.tools = Some(vec![Tool::FunctionDeclarations(vec![FunctionMetadata {
+        name: STRUCTURED_OUTPUT_TOOL.to_string(),
+        description: Some("Respond with data matching the JSON schema.".to_string()),
+        parameters,
+    }])]);
+    request
+        .generation_config
+        .get_or_insert_default()
+        .tool_choice = Some(ToolChoice::Tool(STRUCTURED_OUTPUT_TOOL.to_string()));
+    Ok(())
+}
+
+/// Requests structured output by forcing a call to a synthetic tool.
+///
+/// The request's tools are replaced by a single tool whose input schema is
+/// `schema`, and the model is required to call it. The arguments of that call
+/// are returned as the structured response. This works with any model that
+/// supports forced tool calls, including those without native JSON schema
+/// output.
+pub async fn request_structured_via_tool<M: Model + ?Sized>(
+    model: &M,
+    mut request: ModelRequest,
+    schema: String,
+) -> Result<RawStructuredResponse, GenerateContentError> {
+    force_structured_tool(&mut request, &schema)?;
+
+    let response = model.request(request).await?;
+    let args = response
+        .message
+        .content
+        .into_iter()
+        .find_map(|part| match part {
+            Part::ToolUse { name, args, .. } if name == STRUCTURED_OUTPUT_TOOL => Some(args),
+            _ => None,
+        })
+        .ok_or_else(|| {
+            GenerateContentError::response_parsing(format!(
+                "Model did not call the `{STRUCTURED_OUTPUT_TOOL}` tool"
+            ))
+        })?;
+
+    Ok(RawStructuredResponse {
+        json: decode_args(args)?,
+        vendor_name: response.vendor_name,
+        model_name: response.model_name,
+        usage: response.usage,
+    })
+}
+
+/// Streams structured output by forcing a call to a synthetic tool.
+///
+/// The request is forced like in [`request_structured_via_tool`]. The
+/// arguments of the call are yielded as [`StreamEvent::TextDelta`]s, whether
+/// the provider streams them in chunks or delivers the complete call; any
+/// free text the model produces alongside is dropped.
+pub fn stream_structured_via_tool<'a, M: Model + ?Sized>(
+    model: &'a M,
+    mut request: ModelRequest,
+    schema: String,
+) -> BoxStream<'a, Result<StreamEvent, GenerateContentError>> {
+    if let Err(error) = force_structured_tool(&mut request, &schema) {
+        return stream::once(async move { Err(error) }).boxed();
+    }
+
+    // Index of the structured tool call among the streamed chunks, and
+    // whether any of its arguments arrived that way.
+    let mut tool_index = None;
+    let mut streamed_args = false;
+    model
+        .request_stream(request)
+        .filter_map(move |event| {
+            let event = match event {
+                Ok(StreamEvent::MessageDelta(delta)) => {
+                    let mut args = String::new();
+                    for chunk in delta.tool_call_chunks {
+                        if chunk.name.as_deref() == Some(STRUCTURED_OUTPUT_TOOL) {
+                            tool_index = Some(chunk.index);
+                        }
+                        if tool_index == Some(chunk.index)
+                            && let Some(delta) = chunk.args_delta
+                        {
+                            args.push_str(&delta);
+                        }
+                    }
+                    streamed_args |= !args.is_empty();
+                    (!args.is_empty()).then_some(Ok(StreamEvent::TextDelta(args)))
+                }
+                Ok(StreamEvent::TextDelta(_)) => None,
+                Ok(StreamEvent::ToolCall(call)) if call.name == STRUCTURED_OUTPUT_TOOL => {
+                    (!streamed_args).then(|| {
+                        let text = match call.args {
+                            Value::String(text) => text,
+                            args => args.to_string(),
+                        };
+                        Ok(StreamEvent::TextDelta(text))
+                    })
+                }
+                event => Some(event),
+            };
+            future::ready(event)
+        })
+        .boxed()
+}
+
+/// Decodes the arguments of the structured output tool call.
+fn decode_args(args: Value) -> Result<Value, GenerateContentError> {
+    // Some providers hand back the arguments as an encoded JSON string.
+    match args {
+        Value::String(text) => serde_json::from_str(&text)
+            .map_err(|e| GenerateContentError::response_parsing(e.to_string())),
+        args => Ok(args),
+    }
+}
+
+/// A model that produces structured output by forcing a tool call.
+///
+/// Wraps a model that supports tools but not native JSON schema output, or
+/// whose native support is unreliable. Structured requests go through
+/// [`request_structured_via_tool`] and [`stream_structured_via_tool`];
+/// everything else is passed to the wrapped
+/// model unchanged.
+#[derive(Debug, Clone)]
+pub struct ToolStructuredModel {
+    inner: Arc<dyn Model>,
+}
+
+impl ToolStructuredModel {
+    /// Wraps `inner`.
+    pub fn new(inner: Arc<dyn Model>) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the wrapped model.
+    pub fn inner(&self) -> &Arc<dyn Model> {
+        &self.inner
+    }
+}
+
+impl Model for ToolStructuredModel {
+    fn info(&self) -> ModelInfo<'_> {
+        self.inner.info()
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn capabilities(&self) -> Option<Capabilities> {
+        self.inner.capabilities()
+    }
+
+    fn conversion_policy(&self) -> &ConversionPolicy {
+        self.inner.conversion_policy()
+    }
+
+    fn plan_conversion(&self, request: &ModelRequest) -> ConversionPlan {
+        self.inner.plan_conversion(request)
+    }
+
+    fn request(
+        &self,
+        request: ModelRequest,
+    ) -> BoxFuture<'_, Result<ModelResponse, GenerateContentError>> {
+        self.inner.request(request)
+    }
+
+    fn request_stream(
+        &self,
+        request: ModelRequest,
+    ) -> BoxStream<'_, Result<StreamEvent, GenerateContentError>> {
+        self.inner.request_stream(request)
+    }
+
+    fn request_structured_internal(
+        &self,
+        request: ModelRequest,
+        schema: String,
+    ) -> BoxFuture<'_, Result<RawStructuredResponse, GenerateContentError>> {
+        request_structured_via_tool(self.inner.as_ref(), request, schema).boxed()
+    }
+
+    fn request_structured_stream(
+        &self,
+        request: ModelRequest,
+        schema: String,
+    ) -> BoxStream<'_, Result<StreamEvent, GenerateContentError>> {
+        stream_structured_via_tool(self.inner.as_ref(), request, schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        content::{
+            delta::{FinishReason, MessageDelta, StreamStop, ToolCallChunk},
+            message::{Message, MessageRole},
+        },
+        model::mock::{MockModel, MockReply},
+        usage::Usage,
+    };
+    use serde_json::json;
+
+    fn request() -> ModelRequest {
+        ModelRequest::from([Message::new(MessageRole::User, vec![Part::text("hi")])])
+    }
+
+    fn structured_call(args: Value) -> MockReply {
+        MockReply::tool_call("call-1", STRUCTURED_OUTPUT_TOOL, args)
+    }
+
+    /// Concatenates the text deltas of a structured stream.
+    async fn streamed_text(model: &ToolStructuredModel, schema: &Value) -> String {
+        model
+            .request_structured_stream(request(), schema.to_string())
+            .map(|event| match event.unwrap() {
+                StreamEvent::TextDelta(text) => text,
+                _ => String::new(),
+            })
+            .collect()
+            .await
+    }
+
+    fn assert_forced(request: &ModelRequest, schema: &Value) {
+        let Some([Tool::FunctionDeclarations(functions)]) = request.tools.as_deref() else {
+            panic!("expected a single synthetic tool, got {:?}", request.tools);
+        };
+        assert_eq!(functions[0].name, STRUCTURED_OUTPUT_TOOL);
+        assert_eq!(&functions[0].parameters, schema);
+        assert_eq!(
+            request.generation_config.as_ref().unwrap().tool_choice,
+            Some(ToolChoice::Tool(STRUCTURED_OUTPUT_TOOL.to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_forced_tool_arguments_are_returned() {
+        let inner = MockModel::new("tool-only").with_reply(structured_call(json!({"answer": 42})));
+        let model = ToolStructuredModel::new(Arc::new(inner.clone()));
+        let schema = json!({"type": "object", "properties": {"answer": {"type": "integer"}}});
+
+        let response = model
+            .request_structured_internal(request(), schema.to_string())
+            .await
+            .unwrap();
+        assert_eq!(response.json, json!({"answer": 42}));
+        assert_eq!(response.model_name, "tool-only");
+        assert_forced(&inner.last_request().unwrap(), &schema);
+    }
+
+    #[tokio::test]
+    async fn test_string_arguments_are_decoded() {
+        let model =
+            MockModel::new("tool-only").with_reply(structured_call(json!(r#"{"answer": 7}"#)));
+        let response = request_structured_via_tool(&model, request(), "{}".to_string())
+            .await
+            .unwrap();
+        assert_eq!(response.json, json!({"answer": 7}));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_schema_is_rejected() {
+        let model = MockModel::new("tool-only");
+        let error = request_structured_via_tool(&model, request(), "not json".to_string()).await;
+        assert!(matches!(error, Err(GenerateContentError::Configuration(_))));
+        assert!(model.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_stream_forces_the_tool_and_yields_its_arguments() {
+        let inner = MockModel::new("tool-only").with_reply(structured_call(json!({"answer": 42})));
+        let model = ToolStructuredModel::new(Arc::new(inner.clone()));
+        let schema = json!({"type": "object"});
+
+        let text = streamed_text(&model, &schema).await;
+        assert_eq!(
+            serde_json::from_str::<Value>(&text).unwrap(),
+            json!({"answer": 42})
+        );
+        assert_forced(&inner.last_request().unwrap(), &schema);
+    }
+
+    #[tokio::test]
+    async fn test_stream_yields_argument_chunks_as_they_arrive() {
+        let chunk = |id: Option<&str>, args: &str| {
+            StreamEvent::MessageDelta(MessageDelta {
+                tool_call_chunks: vec![ToolCallChunk {
+                    index: 0,
+                    id: id.map(str::to_string),
+                    name: id.map(|_| STRUCTURED_OUTPUT_TOOL.to_string()),
+                    args_delta: Some(args.to_string()),
+                }],
+                ..MessageDelta::default()
+            })
+        };
+        let inner = MockModel::new("tool-only").with_reply(MockReply::stream([
+            StreamEvent::TextDelta("Here you go: ".to_string()),
+            chunk(Some("call-1"), r#"{"answer""#),
+            chunk(None, ": 42}"),
+            StreamEvent::StreamStop(StreamStop {
+                finish_reason: FinishReason::ToolCalls,
+                usage: Usage::new(),
+                model_name: None,
+                vendor_name: None,
+            }),
+        ]));
+        let model = ToolStructuredModel::new(Arc::new(inner));
+
+        let text = streamed_text(&model, &json!({"type": "object"})).await;
+        assert_eq!(text, r#"{"answer": 42}"#);
+    }
+}
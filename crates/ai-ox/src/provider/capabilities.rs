@@ -49,6 +49,7 @@ impl Capabilities {
     pub fn anthropic() -> Self {
         let mut caps = Self::new("anthropic");
         caps.supports_base64_blob_input = true;
+        caps.supports_blob_uri_input = true; // Images uploaded through the Files API
         caps.supports_images = true;
         caps.supports_tool_use = true;
         caps.supports_tool_result_parts = false; // Only supports text/image in tool results
@@ -98,6 +99,7 @@ impl Capabilities {
             .iter()
             .map(|s| s.to_string())
             .collect();
+        caps.max_base64_size = Some(20 * 1024 * 1024); // Inline data limit, larger files need the Files API
         caps
     }
 
@@ -107,9 +109,10 @@ impl Capabilities {
         caps.supports_base64_blob_input = false; // Mistral doesn't handle base64 well
         caps.supports_blob_uri_input = true;
         caps.supports_images = true; // Via Pixtral models
+        caps.supports_audio = true; // Via Voxtral models
         caps.supports_tool_use = true;
         caps.supports_tool_result_parts = false;
-        caps.allowed_mime_inputs = ["image/jpeg", "image/png"]
+        caps.allowed_mime_inputs = ["image/jpeg", "image/png", "audio/*"]
             .iter()
             .map(|s| s.to_string())
             .collect();
@@ -132,6 +135,41 @@ impl Capabilities {
         caps
     }
 
+    /// Groq capabilities (text-only chat completions)
+    pub fn groq() -> Self {
+        let mut caps = Self::new("groq");
+        caps.supports_tool_use = true;
+        caps.supports_tool_result_parts = false;
+        caps
+    }
+
+    /// AWS Bedrock capabilities (Converse API)
+    pub fn bedrock() -> Self {
+        let mut caps = Self::new("bedrock");
+        caps.supports_base64_blob_input = true;
+        caps.supports_blob_uri_input = true; // Documents via file:// URIs
+        caps.supports_images = true;
+        caps.supports_files = true;
+        caps.supports_tool_use = true;
+        caps.supports_tool_result_parts = true;
+        caps.allowed_mime_inputs = [
+            "image/jpeg",
+            "image/png",
+            "image/gif",
+            "image/webp",
+            "application/pdf",
+            "text/plain",
+            "text/csv",
+            "text/html",
+            "text/markdown",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        caps.max_base64_size = Some(3_750_000); // Converse image limit
+        caps
+    }
+
     /// Check if a specific MIME type is supported
     pub fn supports_mime(&self, mime_type: &str) -> bool {
         // Check exact match
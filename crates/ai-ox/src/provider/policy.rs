@@ -1,5 +1,19 @@
+use base64::prelude::*;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use super::Capabilities;
+use crate::{
+    content::{
+        message::Message,
+        part::{DataRef, Part},
+    },
+    model::request::ModelRequest,
+};
+
+/// Extension key under which shadowed content keeps its original part.
+pub const SHADOW_EXT: &str = "ai_ox.shadow";
 
 /// Trait for services that can upload binary data and return a URI
 pub trait Uploader: Send + Sync + std::fmt::Debug {
@@ -23,23 +37,19 @@ pub enum UploadError {
 }
 
 /// Policy for handling content that a provider doesn't support
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum ConversionPolicy {
     /// Fail with an error if any content can't be represented exactly (default)
     Strict,
 
     /// Allow uploading base64 data to get URIs when provider doesn't support base64
-    UploadAllowed {
-        uploader: std::sync::Arc<MockUploader>,
-    },
+    UploadAllowed { uploader: Arc<dyn Uploader> },
 
     /// Allow storing original content in metadata when provider can't handle it
     ShadowAllowed,
 
     /// Both upload and shadow are allowed
-    Combined {
-        uploader: std::sync::Arc<MockUploader>,
-    },
+    Combined { uploader: Arc<dyn Uploader> },
 }
 
 impl Default for ConversionPolicy {
@@ -48,6 +58,21 @@ impl Default for ConversionPolicy {
     }
 }
 
+impl ConversionPolicy {
+    /// Returns the uploader if this policy allows uploads
+    pub fn uploader(&self) -> Option<&Arc<dyn Uploader>> {
+        match self {
+            Self::UploadAllowed { uploader } | Self::Combined { uploader } => Some(uploader),
+            Self::Strict | Self::ShadowAllowed => None,
+        }
+    }
+
+    /// Check if this policy allows shadowing content into metadata
+    pub fn allows_shadow(&self) -> bool {
+        matches!(self, Self::ShadowAllowed | Self::Combined { .. })
+    }
+}
+
 /// Describes a transformation needed during conversion
 #[derive(Debug, Clone)]
 pub enum TransformAction {
@@ -71,7 +96,7 @@ pub enum TransformAction {
 }
 
 /// Plan for converting a message to a specific provider
-#[derive(Default)]
+#[derive(Debug, Clone, Default)]
 pub struct ConversionPlan {
     /// Provider this plan is for
     pub provider_name: String,
@@ -136,6 +161,239 @@ impl ConversionPlan {
     pub fn add_action(&mut self, action: TransformAction) {
         self.part_actions.push(action);
     }
+
+    /// Plan the conversion of a request for a provider with the given capabilities.
+    ///
+    /// Parts are indexed in request order: the system message first, then every
+    /// message. Only top-level parts are planned; parts nested in tool results
+    /// are encoded by each provider's conversion.
+    pub fn for_request(
+        capabilities: &Capabilities,
+        policy: &ConversionPolicy,
+        request: &ModelRequest,
+    ) -> Self {
+        let mut plan = Self::new(&capabilities.provider_name, policy);
+        for (index, part) in request_parts(request).enumerate() {
+            let action = plan.plan_part(capabilities, policy, index, part);
+            plan.add_action(action);
+        }
+        plan
+    }
+
+    fn plan_part(
+        &mut self,
+        capabilities: &Capabilities,
+        policy: &ConversionPolicy,
+        index: usize,
+        part: &Part,
+    ) -> TransformAction {
+        let provider = capabilities.provider_name.clone();
+
+        let error = match part {
+            Part::ToolUse { .. } | Part::ToolResult { .. } if !capabilities.supports_tool_use => {
+                ConversionError::UnsupportedContent {
+                    part_index: index,
+                    part_type: part_type(part),
+                    provider,
+                    reason: "tool use is not supported".to_string(),
+                }
+            }
+            Part::Blob {
+                data_ref,
+                mime_type,
+                ..
+            } => {
+                if !capabilities.supports_mime(mime_type) {
+                    ConversionError::UnsupportedMimeType {
+                        mime_type: mime_type.clone(),
+                        provider,
+                    }
+                } else {
+                    match data_ref {
+                        DataRef::Uri { .. } if capabilities.supports_blob_uri_input => {
+                            return TransformAction::PassThrough;
+                        }
+                        DataRef::Uri { .. } => ConversionError::UnsupportedContent {
+                            part_index: index,
+                            part_type: part_type(part),
+                            provider,
+                            reason: "URI references are not supported".to_string(),
+                        },
+                        DataRef::Base64 { .. } => {
+                            let size = data_ref.base64_size().unwrap_or_default();
+                            if capabilities.can_accept_base64(size) {
+                                return TransformAction::PassThrough;
+                            }
+                            if capabilities.supports_blob_uri_input && policy.uploader().is_some() {
+                                return TransformAction::UploadBase64 {
+                                    original_size: size,
+                                    mime_type: mime_type.clone(),
+                                };
+                            }
+                            match capabilities.max_base64_size {
+                                Some(max_size) if capabilities.supports_base64_blob_input => {
+                                    ConversionError::Base64TooLarge {
+                                        size,
+                                        max_size,
+                                        provider,
+                                    }
+                                }
+                                _ if capabilities.supports_blob_uri_input => {
+                                    ConversionError::NoUploaderAvailable
+                                }
+                                _ => ConversionError::MissingRequiredFeature {
+                                    provider,
+                                    required_feature: "base64 blob input".to_string(),
+                                },
+                            }
+                        }
+                    }
+                }
+            }
+            _ => return TransformAction::PassThrough,
+        };
+
+        if policy.allows_shadow() {
+            let original_type = part_type(part);
+            self.add_warning(format!(
+                "Part {index} ({original_type}) replaced with a text placeholder: {error}"
+            ));
+            if let Ok(original) = serde_json::to_value(part) {
+                self.shadow_metadata
+                    .insert(format!("part_{index}"), original);
+            }
+            TransformAction::Shadow {
+                original_type,
+                simplified_to: "text".to_string(),
+            }
+        } else {
+            self.add_error(error);
+            TransformAction::PassThrough
+        }
+    }
+
+    /// Execute this plan against the request it was built from.
+    ///
+    /// Fails with the first planning error, so a strict plan with errors never
+    /// reaches the provider. Uploads run sequentially through the policy's uploader.
+    pub async fn execute(
+        &self,
+        policy: &ConversionPolicy,
+        mut request: ModelRequest,
+    ) -> Result<ModelRequest, ConversionError> {
+        if let Some(error) = self.errors.first() {
+            return Err(error.clone());
+        }
+        if self
+            .part_actions
+            .iter()
+            .all(|action| matches!(action, TransformAction::PassThrough))
+        {
+            return Ok(request);
+        }
+
+        let mut index = 0;
+        if let Some(system_message) = request.system_message.as_mut() {
+            self.execute_message(policy, system_message, &mut index)
+                .await?;
+        }
+        for message in &mut request.messages {
+            self.execute_message(policy, message, &mut index).await?;
+        }
+        Ok(request)
+    }
+
+    async fn execute_message(
+        &self,
+        policy: &ConversionPolicy,
+        message: &mut Message,
+        index: &mut usize,
+    ) -> Result<(), ConversionError> {
+        let parts = std::mem::take(&mut message.content);
+        for part in parts {
+            let action = self
+                .part_actions
+                .get(*index)
+                .unwrap_or(&TransformAction::PassThrough);
+            *index += 1;
+
+            match action {
+                TransformAction::PassThrough => message.content.push(part),
+                TransformAction::UploadBase64 { .. } => {
+                    message.content.push(upload_part(policy, part).await?);
+                }
+                TransformAction::Shadow { original_type, .. } => {
+                    message
+                        .content
+                        .push(shadow_part(part, original_type, &self.provider_name));
+                }
+                TransformAction::Omit { .. } => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Iterates over the top-level parts of a request in plan order.
+fn request_parts(request: &ModelRequest) -> impl Iterator<Item = &Part> {
+    request
+        .system_message
+        .iter()
+        .chain(request.messages.iter())
+        .flat_map(|message| message.content.iter())
+}
+
+fn part_type(part: &Part) -> String {
+    match part {
+        Part::Text { .. } => "text".to_string(),
+        Part::Blob { mime_type, .. } => mime_type.clone(),
+        Part::ToolUse { .. } => "tool_use".to_string(),
+        Part::ToolResult { .. } => "tool_result".to_string(),
+        Part::Reasoning { .. } => "reasoning".to_string(),
+        Part::Opaque { kind, .. } => kind.clone(),
+    }
+}
+
+async fn upload_part(policy: &ConversionPolicy, part: Part) -> Result<Part, ConversionError> {
+    let uploader = policy
+        .uploader()
+        .ok_or(ConversionError::NoUploaderAvailable)?;
+    match part {
+        Part::Blob {
+            data_ref: DataRef::Base64 { data },
+            mime_type,
+            name,
+            description,
+            ext,
+        } => {
+            let bytes = BASE64_STANDARD
+                .decode(&data)
+                .map_err(|e| ConversionError::UploadFailed(format!("Invalid base64 data: {e}")))?;
+            let uri = uploader
+                .upload(bytes, mime_type.clone(), name.clone())
+                .await
+                .map_err(|e| ConversionError::UploadFailed(e.to_string()))?;
+            Ok(Part::Blob {
+                data_ref: DataRef::Uri { uri },
+                mime_type,
+                name,
+                description,
+                ext,
+            })
+        }
+        other => Ok(other),
+    }
+}
+
+fn shadow_part(part: Part, original_type: &str, provider: &str) -> Part {
+    let mut ext = BTreeMap::new();
+    if let Ok(original) = serde_json::to_value(&part) {
+        ext.insert(SHADOW_EXT.to_string(), original);
+    }
+    Part::Text {
+        text: format!("[{original_type} content omitted: not supported by {provider}]"),
+        ext,
+    }
 }
 
 /// Error that occurs during conversion planning or execution
@@ -170,6 +428,9 @@ pub enum ConversionError {
 
     #[error("Shadow metadata required but provider doesn't support metadata passthrough")]
     NoShadowSupport,
+
+    #[error("Upload failed: {0}")]
+    UploadFailed(String),
 }
 
 /// Mock uploader for testing
@@ -233,4 +494,82 @@ mod tests {
         let policy = ConversionPolicy::default();
         matches!(policy, ConversionPolicy::Strict);
     }
+    fn request_with(part: Part) -> ModelRequest {
+        ModelRequest {
+            messages: vec![Message::new(
+                crate::content::message::MessageRole::User,
+                vec![Part::text("look"), part],
+            )],
+            system_message: None,
+            tools: None,
+            generation_config: None,
+            cache: None,
+        }
+    }
+
+    fn base64_blob(mime_type: &str) -> Part {
+        Part::Blob {
+            data_ref: DataRef::base64(BASE64_STANDARD.encode(b"binary")),
+            mime_type: mime_type.to_string(),
+            name: Some("clip".to_string()),
+            description: None,
+            ext: BTreeMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_strict_plan_fails_without_uploader() {
+        let request = request_with(base64_blob("audio/wav"));
+        let policy = ConversionPolicy::Strict;
+        let plan = ConversionPlan::for_request(&Capabilities::mistral(), &policy, &request);
+
+        assert!(plan.has_errors());
+        assert!(matches!(
+            plan.execute(&policy, request).await,
+            Err(ConversionError::NoUploaderAvailable)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_upload_replaces_base64_with_uri() {
+        let request = request_with(base64_blob("audio/wav"));
+        let policy = ConversionPolicy::UploadAllowed {
+            uploader: Arc::new(MockUploader::new()),
+        };
+        let plan = ConversionPlan::for_request(&Capabilities::mistral(), &policy, &request);
+
+        assert!(plan.is_lossless());
+        assert!(matches!(
+            plan.part_actions[1],
+            TransformAction::UploadBase64 {
+                original_size: 6,
+                ..
+            }
+        ));
+
+        let request = plan.execute(&policy, request).await.unwrap();
+        assert!(matches!(
+            &request.messages[0].content[1],
+            Part::Blob { data_ref: DataRef::Uri { uri }, .. }
+                if uri == "https://mock-storage.example.com/files/clip.wav"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_shadow_keeps_original_in_ext() {
+        let blob = base64_blob("audio/wav");
+        let request = request_with(blob.clone());
+        let policy = ConversionPolicy::ShadowAllowed;
+        let plan = ConversionPlan::for_request(&Capabilities::anthropic(), &policy, &request);
+
+        assert!(!plan.has_errors());
+        assert_eq!(plan.warnings.len(), 1);
+        assert!(plan.shadow_metadata.contains_key("part_1"));
+
+        let request = plan.execute(&policy, request).await.unwrap();
+        let Part::Text { ext, .. } = &request.messages[0].content[1] else {
+            panic!("Expected shadowed text part");
+        };
+        assert_eq!(ext[SHADOW_EXT], serde_json::to_value(&blob).unwrap());
+    }
 }
@@ -39,30 +39,47 @@ pub fn mistral_stt_with_key(model: &str, api_key: &str) -> Result<Arc<dyn Speech
 }
 
 /// Convenience function to create a Gemini STT provider
-/// TODO: Implement Gemini STT provider (currently requires ALSA for audio examples)
-// #[cfg(feature = "gemini")]
-// pub fn gemini_stt(model: &str) -> Result<Arc<dyn SpeechToText>, SttError> {
-//     super::providers::gemini::GeminiStt::builder()
-//         .model(model)
-//         .api_key_from_env("GEMINI_API_KEY")?
-//         .build()
-// }
+#[cfg(feature = "gemini")]
+pub fn gemini_stt(model: &str) -> Result<Arc<dyn SpeechToText>, SttError> {
+    super::providers::gemini::GeminiStt::builder()
+        .model(model)
+        .api_key_from_env("GEMINI_API_KEY")?
+        .build()
+}
 
 /// Convenience function to create a Gemini STT provider with custom API key
-/// TODO: Implement Gemini STT provider (currently requires ALSA for audio examples)
-// #[cfg(feature = "gemini")]
-// pub fn gemini_stt_with_key(model: &str, api_key: &str) -> Result<Arc<dyn SpeechToText>, SttError> {
-//     super::providers::gemini::GeminiStt::builder()
-//         .model(model)
-//         .api_key(api_key)
-//         .build()
-// }
+#[cfg(feature = "gemini")]
+pub fn gemini_stt_with_key(model: &str, api_key: &str) -> Result<Arc<dyn SpeechToText>, SttError> {
+    super::providers::gemini::GeminiStt::builder()
+        .model(model)
+        .api_key(api_key)
+        .build()
+}
+
+/// Convenience function to create an OpenAI STT provider
+#[cfg(feature = "openai")]
+pub fn openai_stt(model: &str) -> Result<Arc<dyn SpeechToText>, SttError> {
+    super::providers::openai::OpenAISttProvider::builder()
+        .model(model)
+        .api_key_from_env("OPENAI_API_KEY")?
+        .build()
+}
+
+/// Convenience function to create an OpenAI STT provider with custom API key
+#[cfg(feature = "openai")]
+pub fn openai_stt_with_key(model: &str, api_key: &str) -> Result<Arc<dyn SpeechToText>, SttError> {
+    super::providers::openai::OpenAISttProvider::builder()
+        .model(model)
+        .api_key(api_key)
+        .build()
+}
 
 /// Auto-detect and create the best available STT provider
 /// Auto-detection preference order:
 /// 1. Groq (fastest, good quality)
 /// 2. Mistral (good balance)  
-/// 3. Gemini (feature-rich)
+/// 3. OpenAI (translation to English)
+/// 4. Gemini (feature-rich)
 pub fn auto_stt() -> Result<Arc<dyn SpeechToText>, SttError> {
     #[cfg(feature = "groq")]
     if std::env::var("GROQ_API_KEY").is_ok() {
@@ -74,11 +91,15 @@ pub fn auto_stt() -> Result<Arc<dyn SpeechToText>, SttError> {
         return mistral_stt("voxtral-large-24-05");
     }
 
-    // TODO: Implement Gemini STT provider (currently requires ALSA for audio examples)
-    // #[cfg(feature = "gemini")]
-    // if std::env::var("GEMINI_API_KEY").is_ok() {
-    //     return gemini_stt("gemini-1.5-flash");
-    // }
+    #[cfg(feature = "openai")]
+    if std::env::var("OPENAI_API_KEY").is_ok() {
+        return openai_stt("whisper-1");
+    }
+
+    #[cfg(feature = "gemini")]
+    if std::env::var("GEMINI_API_KEY").is_ok() {
+        return gemini_stt("gemini-2.5-flash");
+    }
 
     Err(SttError::MissingApiKey)
 }
@@ -102,12 +123,14 @@ mod tests {
         // When no API keys are available, should return MissingApiKey
         let original_groq = std::env::var("GROQ_API_KEY");
         let original_mistral = std::env::var("MISTRAL_API_KEY");
+        let original_openai = std::env::var("OPENAI_API_KEY");
         let original_gemini = std::env::var("GEMINI_API_KEY");
 
         // Remove all keys temporarily
         unsafe {
             std::env::remove_var("GROQ_API_KEY");
             std::env::remove_var("MISTRAL_API_KEY");
+            std::env::remove_var("OPENAI_API_KEY");
             std::env::remove_var("GEMINI_API_KEY");
         }
 
@@ -122,6 +145,9 @@ mod tests {
             if let Ok(key) = original_mistral {
                 std::env::set_var("MISTRAL_API_KEY", key);
             }
+            if let Ok(key) = original_openai {
+                std::env::set_var("OPENAI_API_KEY", key);
+            }
             if let Ok(key) = original_gemini {
                 std::env::set_var("GEMINI_API_KEY", key);
             }
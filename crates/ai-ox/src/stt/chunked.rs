@@ -0,0 +1,501 @@
+//! Transcribing recordings longer than a provider accepts.
+//!
+//! [`ChunkedTranscriber`] splits uncompressed audio in pauses with
+//! [`split_on_silence`], transcribes the chunks concurrently and merges them
+//! back into one response on the timeline of the whole recording.
+
+use bon::Builder;
+use bytes::Bytes;
+use futures_util::{
+    FutureExt, StreamExt, TryStreamExt,
+    future::BoxFuture,
+    stream::{self, BoxStream},
+};
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::{
+    AudioFormat, AudioSource, SpeechToText, StreamingTranscriptionRequest, SttError, SttInfo,
+    SttModel, SttUsage, TranscriptionEvent, TranscriptionRequest, TranscriptionResponse,
+    chunking::{
+        PcmChunk, PcmFormat, SilenceChunking, encode_wav, parse_wav_header, split_on_silence,
+    },
+};
+
+/// Size of the header [`encode_wav`] writes
+const WAV_HEADER_SIZE: usize = 44;
+
+/// Most words looked for twice where two chunks overlap
+const MAX_OVERLAP_WORDS: usize = 32;
+
+/// Decodes compressed audio to PCM so that it can be split into chunks
+pub trait AudioDecoder: Send + Sync + std::fmt::Debug {
+    /// Decodes `data`, returning its PCM layout and interleaved samples
+    fn decode(&self, data: &[u8], format: AudioFormat) -> Result<(PcmFormat, Vec<u8>), SttError>;
+}
+
+/// Transcribes recordings of any length with a provider that limits the size
+/// or duration of its input
+///
+/// Audio longer than a chunk is split in pauses, and each chunk is sent to
+/// `stt` as a WAV file, up to `max_concurrency` at a time. Chunks are no
+/// longer than the model's [`SttModel::max_duration`], `chunking.max_chunk`
+/// or what fits in `max_chunk_bytes`. Where a chunk has to be cut outside a
+/// pause, the next one starts `overlap` earlier and the words heard twice are
+/// dropped when merging.
+///
+/// WAV input is split directly; other formats are only split if a `decoder`
+/// is given, and are otherwise passed to `stt` whole.
+#[derive(Debug, Clone, Builder)]
+pub struct ChunkedTranscriber {
+    /// Provider transcribing each chunk
+    stt: Arc<dyn SpeechToText>,
+
+    /// How audio is split; pauses must be `silence_threshold` quiet
+    #[builder(default = SilenceChunking {
+        max_chunk: Duration::from_secs(10 * 60),
+        ..SilenceChunking::default()
+    })]
+    chunking: SilenceChunking,
+
+    /// Audio repeated at the start of a chunk that was cut outside a pause
+    #[builder(default = Duration::from_secs(2))]
+    overlap: Duration,
+
+    /// Most chunks being transcribed at once
+    #[builder(default = 4)]
+    max_concurrency: usize,
+
+    /// Largest WAV file to send for one chunk
+    #[builder(default = 25 * 1024 * 1024)]
+    max_chunk_bytes: usize,
+
+    /// Decoder for compressed formats
+    decoder: Option<Arc<dyn AudioDecoder>>,
+}
+
+impl ChunkedTranscriber {
+    /// PCM audio of `source`, or None if it can't be split
+    fn decode(&self, source: &AudioSource) -> Result<Option<(PcmFormat, Vec<u8>)>, SttError> {
+        let format = source.format().unwrap_or(AudioFormat::Unknown);
+        let data = match source {
+            AudioSource::Bytes { data, .. } => Cow::Borrowed(data.as_slice()),
+            AudioSource::File(path) => Cow::Owned(std::fs::read(path).map_err(|e| {
+                SttError::InvalidConfig(format!("Failed to read file {:?}: {}", path, e))
+            })?),
+            AudioSource::Base64 { data, .. } => {
+                use base64::Engine;
+                Cow::Owned(
+                    base64::engine::general_purpose::STANDARD
+                        .decode(data)
+                        .map_err(|e| {
+                            SttError::InvalidAudioData(format!("Invalid base64: {}", e))
+                        })?,
+                )
+            }
+            AudioSource::Url(_) | AudioSource::RecordingId(_) => return Ok(None),
+        };
+
+        if format == AudioFormat::Wav || data.starts_with(b"RIFF") {
+            let (pcm, start) = parse_wav_header(&data)?.ok_or_else(|| {
+                SttError::InvalidAudioData("WAV file ends in its header".to_string())
+            })?;
+            return Ok(Some((pcm, data[start..].to_vec())));
+        }
+        match &self.decoder {
+            Some(decoder) => decoder.decode(&data, format).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Longest chunk the model accepts, in a WAV file of at most
+    /// `max_chunk_bytes`
+    async fn max_chunk(&self, format: PcmFormat) -> Duration {
+        // Models the provider can't list impose no limit of their own
+        let models = self.stt.available_models().await.unwrap_or_default();
+        let model_limit = models
+            .iter()
+            .find(|model| model.id == self.stt.model())
+            .and_then(|model| model.max_duration);
+
+        let bytes_per_second = format.sample_rate as usize * 2 * usize::from(format.channels);
+        let size_limit = Duration::from_secs_f64(
+            self.max_chunk_bytes.saturating_sub(WAV_HEADER_SIZE) as f64 / bytes_per_second as f64,
+        );
+
+        let limit = self.chunking.max_chunk.min(size_limit);
+        model_limit.map_or(limit, |model_limit| limit.min(model_limit))
+    }
+}
+
+impl SpeechToText for ChunkedTranscriber {
+    fn info(&self) -> SttInfo<'_> {
+        self.stt.info()
+    }
+
+    fn model(&self) -> &str {
+        self.stt.model()
+    }
+
+    fn transcribe(
+        &self,
+        request: TranscriptionRequest,
+    ) -> BoxFuture<'_, Result<TranscriptionResponse, SttError>> {
+        async move {
+            let Some((format, pcm)) = self.decode(&request.audio)? else {
+                return self.stt.transcribe(request).await;
+            };
+            let config = SilenceChunking {
+                max_chunk: self.max_chunk(format).await,
+                ..self.chunking
+            };
+            let chunks = split_on_silence(&config, format, &pcm, self.overlap);
+            if chunks.len() <= 1 {
+                return self.stt.transcribe(request).await;
+            }
+
+            let template = TranscriptionRequest {
+                audio: AudioSource::default(),
+                ..request
+            };
+            let requests: Vec<_> = chunks
+                .iter()
+                .map(|chunk| {
+                    let mut request = template.clone();
+                    request.audio = AudioSource::from_bytes_with_name(
+                        encode_wav(format, &chunk.data),
+                        AudioFormat::Wav,
+                        format!("chunk-{}.wav", chunk.start.as_millis()),
+                    );
+                    self.stt.transcribe(request)
+                })
+                .collect();
+            let responses: Vec<TranscriptionResponse> = stream::iter(requests)
+                .buffered(self.max_concurrency.max(1))
+                .try_collect()
+                .await?;
+
+            let duration = format.duration_of((pcm.len() / format.frame_size()) as u64);
+            Ok(merge(&chunks, responses, duration))
+        }
+        .boxed()
+    }
+
+    fn transcribe_stream(
+        &self,
+        request: StreamingTranscriptionRequest,
+        audio: BoxStream<'static, Bytes>,
+    ) -> BoxStream<'_, Result<TranscriptionEvent, SttError>> {
+        self.stt.transcribe_stream(request, audio)
+    }
+
+    fn available_models(&self) -> BoxFuture<'_, Result<Vec<SttModel>, SttError>> {
+        self.stt.available_models()
+    }
+
+    fn supports_format(&self, format: AudioFormat) -> bool {
+        self.stt.supports_format(format)
+    }
+}
+
+/// Combines the transcriptions of consecutive chunks into one, on the
+/// timeline of the whole recording
+fn merge(
+    chunks: &[PcmChunk],
+    responses: Vec<TranscriptionResponse>,
+    duration: Duration,
+) -> TranscriptionResponse {
+    let mut merged = TranscriptionResponse::simple(
+        String::new(),
+        responses[0].provider.clone(),
+        responses[0].model.clone(),
+    );
+    let mut text: Vec<String> = Vec::new();
+    let mut processing_time = None;
+    let mut cost = None;
+    let mut previous_end = Duration::ZERO;
+
+    for (chunk, response) in chunks.iter().zip(responses) {
+        let overlaps = chunk.start < previous_end;
+        previous_end = chunk.start + chunk.duration;
+
+        let duplicated = if overlaps {
+            duplicated_words(
+                text.iter().map(String::as_str),
+                response.text.split_whitespace(),
+            )
+        } else {
+            0
+        };
+        // Providers split words differently from the text, so the timed words
+        // are matched on their own.
+        let duplicated_timed = if overlaps {
+            duplicated_words(
+                merged.words.iter().map(|word| word.text.as_str()),
+                response.words.iter().map(|word| word.text.as_str()),
+            )
+        } else {
+            0
+        };
+
+        text.extend(
+            response
+                .text
+                .split_whitespace()
+                .skip(duplicated)
+                .map(str::to_string),
+        );
+
+        let mut skip = duplicated;
+        for mut segment in response.segments {
+            let words: Vec<&str> = segment.text.split_whitespace().collect();
+            if skip >= words.len() {
+                skip -= words.len();
+                continue;
+            }
+            if skip > 0 {
+                segment.text = words[skip..].join(" ");
+                skip = 0;
+            }
+            segment.start += chunk.start;
+            segment.end += chunk.start;
+            segment.id = Some(merged.segments.len() as u32);
+            merged.segments.push(segment);
+        }
+        merged.words.extend(
+            response
+                .words
+                .into_iter()
+                .skip(duplicated_timed)
+                .map(|mut word| {
+                    word.start += chunk.start;
+                    word.end += chunk.start;
+                    word
+                }),
+        );
+
+        merged.language = merged.language.or(response.language);
+        if let Some(time) = response.usage.processing_time {
+            processing_time = Some(processing_time.unwrap_or_default() + time);
+        }
+        if let Some(cost_estimate) = response.usage.cost_estimate {
+            cost = Some(cost.unwrap_or_default() + cost_estimate);
+        }
+    }
+
+    merged.text = text.join(" ");
+    merged.duration = Some(duration);
+    merged.usage = SttUsage {
+        processing_time,
+        cost_estimate: cost,
+        segments_processed: merged.segments.len() as u32,
+        characters_transcribed: merged.text.len() as u32,
+        ..SttUsage::new(duration)
+    };
+    merged
+}
+
+/// Number of words at the start of `next` that repeat the end of `previous`
+fn duplicated_words<'a>(
+    previous: impl DoubleEndedIterator<Item = &'a str>,
+    next: impl Iterator<Item = &'a str>,
+) -> usize {
+    let normalize = |word: &str| {
+        word.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect::<String>()
+    };
+    let next: Vec<String> = next.take(MAX_OVERLAP_WORDS).map(normalize).collect();
+    let mut previous: Vec<String> = previous
+        .rev()
+        .take(MAX_OVERLAP_WORDS)
+        .map(normalize)
+        .collect();
+    previous.reverse();
+
+    (1..=next.len().min(previous.len()))
+        .rev()
+        .find(|&count| previous[previous.len() - count..] == next[..count])
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stt::{
+        OutputFormat, Segment, Word, chunking::tests::FORMAT, chunking::tests::tone, mock::MockStt,
+    };
+
+    /// A transcription of `text` in one segment, with every word timed
+    fn reply(text: &str) -> TranscriptionResponse {
+        let mut response =
+            TranscriptionResponse::simple(text.to_string(), "mock".to_string(), "mock".to_string());
+        response.segments.push(Segment::new(
+            text.to_string(),
+            Duration::from_millis(100),
+            Duration::from_millis(900),
+        ));
+        response.words = text
+            .split_whitespace()
+            .map(|word| {
+                let at = Duration::from_millis(100);
+                Word::new(word.to_string(), at, at)
+            })
+            .collect();
+        response
+    }
+
+    /// Transcribes with `replies` in order, accepting up to two seconds of
+    /// audio at a time
+    fn scripted(replies: &[&str]) -> MockStt {
+        replies.iter().fold(
+            MockStt::new("mock").with_max_duration(Duration::from_secs(2)),
+            |stt, text| stt.with_response(reply(text)),
+        )
+    }
+
+    /// Length in milliseconds of the WAV file sent with each request
+    fn lengths(stt: &MockStt) -> Vec<u128> {
+        stt.requests()
+            .into_iter()
+            .filter_map(|request| match request.audio {
+                AudioSource::Bytes {
+                    data,
+                    format: AudioFormat::Wav,
+                    ..
+                } => {
+                    let (format, start) =
+                        parse_wav_header(&data).unwrap().expect("a whole WAV file");
+                    let frames = ((data.len() - start) / format.frame_size()) as u64;
+                    Some(format.duration_of(frames).as_millis())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_long_audio_is_transcribed_in_chunks() {
+        let stt = scripted(&["One two.", "three four", "Four, five six."]);
+        let transcriber = ChunkedTranscriber::builder()
+            .stt(Arc::new(stt.clone()))
+            .overlap(Duration::from_millis(250))
+            .max_concurrency(2)
+            .build();
+        let audio = [tone(1300, true), tone(200, false), tone(2900, true)].concat();
+        let request = TranscriptionRequest::builder()
+            .audio(AudioSource::from_bytes(
+                encode_wav(FORMAT, &audio),
+                AudioFormat::Wav,
+            ))
+            .output_format(OutputFormat::Verbose)
+            .build();
+
+        let response = transcriber.transcribe(request).await.unwrap();
+
+        // The model's two second limit is enforced
+        assert_eq!(lengths(&stt), [1465, 1975, 1210]);
+        assert_eq!(response.text, "One two. three four five six.");
+        assert_eq!(response.duration, Some(Duration::from_millis(4400)));
+
+        let segments: Vec<_> = response
+            .segments
+            .iter()
+            .map(|segment| (segment.text.as_str(), segment.start.as_millis()))
+            .collect();
+        assert_eq!(
+            segments,
+            [("One two.", 100), ("three four", 1565), ("five six.", 3290)]
+        );
+        assert_eq!(response.segments[2].id, Some(2));
+        assert_eq!(response.words.len(), 6);
+        assert_eq!(response.words[4].text, "five");
+    }
+
+    #[tokio::test]
+    async fn test_short_and_compressed_audio_pass_through() {
+        let stt = scripted(&["hello", "world"]);
+        let transcriber = ChunkedTranscriber::builder()
+            .stt(Arc::new(stt.clone()))
+            .build();
+
+        let wav = encode_wav(FORMAT, &tone(500, true));
+        let request = TranscriptionRequest::builder()
+            .audio(AudioSource::from_bytes(wav, AudioFormat::Wav))
+            .build();
+        assert_eq!(transcriber.transcribe(request).await.unwrap().text, "hello");
+
+        // Without a decoder MP3 can't be split, so it is sent as is
+        let request = TranscriptionRequest::builder()
+            .audio(AudioSource::from_bytes(
+                b"ID3\x03 not split".to_vec(),
+                AudioFormat::Mp3,
+            ))
+            .build();
+        assert_eq!(transcriber.transcribe(request).await.unwrap().text, "world");
+        assert_eq!(lengths(&stt), [500]);
+    }
+
+    #[test]
+    fn test_duplicated_words() {
+        let previous = ["so", "we", "went", "to", "the"];
+        let next = |text: &'static str| text.split_whitespace();
+        assert_eq!(
+            duplicated_words(previous.into_iter(), next("To the, market")),
+            2
+        );
+        assert_eq!(duplicated_words(previous.into_iter(), next("market")), 0);
+    }
+
+    fn chunk(start_ms: u64, duration_ms: u64) -> PcmChunk {
+        PcmChunk {
+            start: Duration::from_millis(start_ms),
+            duration: Duration::from_millis(duration_ms),
+            data: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_timed_words_are_deduplicated_on_their_own() {
+        // The provider times "twenty-one" as two words
+        let mut first = reply("we saw twenty-one");
+        first.words = ["we", "saw", "twenty", "one"]
+            .map(|word| Word::new(word.to_string(), Duration::ZERO, Duration::ZERO))
+            .to_vec();
+        let mut second = reply("twenty-one dogs");
+        second.words = ["twenty", "one", "dogs"]
+            .map(|word| Word::new(word.to_string(), Duration::ZERO, Duration::ZERO))
+            .to_vec();
+
+        let chunks = [chunk(0, 1000), chunk(800, 1000)];
+        let merged = merge(&chunks, vec![first, second], Duration::from_millis(1800));
+
+        assert_eq!(merged.text, "we saw twenty-one dogs");
+        let words: Vec<_> = merged.words.iter().map(|word| word.text.as_str()).collect();
+        assert_eq!(words, ["we", "saw", "twenty", "one", "dogs"]);
+    }
+
+    #[test]
+    fn test_usage_totals_skip_chunks_without_usage() {
+        let mut first = reply("one");
+        first.usage.processing_time = Some(Duration::from_millis(300));
+        first.usage.cost_estimate = Some(0.5);
+        let mut third = reply("three");
+        third.usage.processing_time = Some(Duration::from_millis(200));
+
+        let chunks = [chunk(0, 1000), chunk(1000, 1000), chunk(2000, 1000)];
+        let merged = merge(
+            &chunks,
+            vec![first, reply("two"), third],
+            Duration::from_secs(3),
+        );
+
+        assert_eq!(
+            merged.usage.processing_time,
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(merged.usage.cost_estimate, Some(0.5));
+    }
+}
@@ -0,0 +1,526 @@
+//! Splitting PCM audio into utterances on silence.
+//!
+//! Used to transcribe live audio with providers that only accept whole files:
+//! each utterance is sent as its own WAV file and its timestamps are shifted to
+//! where it started in the stream. Recordings longer than a provider accepts
+//! are split the same way by [`split_on_silence`].
+
+use std::time::Duration;
+
+use super::{SttError, streaming::AudioEncoding, streaming::AudioStreamConfig};
+
+/// How audio is split into chunks on silence
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SilenceChunking {
+    /// RMS level, relative to full scale, below which audio counts as silence
+    pub silence_threshold: f32,
+    /// How long silence must last to end an utterance
+    pub min_silence: Duration,
+    /// Longest chunk to transcribe; longer speech is cut without waiting for a
+    /// pause
+    pub max_chunk: Duration,
+    /// Length of the windows the level is measured over
+    pub window: Duration,
+}
+
+impl Default for SilenceChunking {
+    fn default() -> Self {
+        Self {
+            silence_threshold: 0.01,
+            min_silence: Duration::from_millis(500),
+            max_chunk: Duration::from_secs(30),
+            window: Duration::from_millis(30),
+        }
+    }
+}
+
+/// Layout of uncompressed interleaved PCM audio
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PcmFormat {
+    /// Sample rate in Hz
+    pub sample_rate: u32,
+    /// Number of interleaved channels
+    pub channels: u16,
+    /// Sample encoding, either [`AudioEncoding::Linear16`] or
+    /// [`AudioEncoding::Float32`]
+    pub encoding: AudioEncoding,
+}
+
+impl PcmFormat {
+    /// The PCM layout described by `config`, if its encoding is uncompressed
+    pub fn from_config(config: &AudioStreamConfig) -> Option<Self> {
+        let format = Self {
+            sample_rate: config.sample_rate,
+            channels: u16::from(config.channels),
+            encoding: config.encoding,
+        };
+        format.is_valid().then_some(format)
+    }
+
+    fn is_valid(&self) -> bool {
+        matches!(
+            self.encoding,
+            AudioEncoding::Linear16 | AudioEncoding::Float32
+        ) && self.sample_rate > 0
+            && self.channels > 0
+    }
+
+    fn bytes_per_sample(&self) -> usize {
+        match self.encoding {
+            AudioEncoding::Float32 => 4,
+            _ => 2,
+        }
+    }
+
+    /// Size in bytes of one sample for every channel
+    pub fn frame_size(&self) -> usize {
+        self.bytes_per_sample() * usize::from(self.channels)
+    }
+
+    /// Number of whole frames in `duration`
+    fn frames_in(&self, duration: Duration) -> u64 {
+        (duration.as_secs_f64() * f64::from(self.sample_rate)) as u64
+    }
+
+    /// Time taken to play `frames` frames
+    pub fn duration_of(&self, frames: u64) -> Duration {
+        Duration::from_secs_f64(frames as f64 / f64::from(self.sample_rate))
+    }
+
+    /// RMS level of `data` relative to full scale
+    fn level(&self, data: &[u8]) -> f32 {
+        let samples: Vec<f32> = match self.encoding {
+            AudioEncoding::Float32 => data
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect(),
+            _ => data
+                .chunks_exact(2)
+                .map(|b| f32::from(i16::from_le_bytes([b[0], b[1]])) / 32768.0)
+                .collect(),
+        };
+        if samples.is_empty() {
+            return 0.0;
+        }
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+}
+
+/// A stretch of audio containing speech
+#[derive(Debug, Clone, PartialEq)]
+pub struct PcmChunk {
+    /// Where the chunk starts in the whole audio
+    pub start: Duration,
+    /// Length of the chunk
+    pub duration: Duration,
+    /// Raw PCM data
+    pub data: Vec<u8>,
+}
+
+/// What [`SilenceChunker`] found in the audio fed to it
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChunkEvent {
+    /// Speech started at this position
+    SpeechStart(Duration),
+    /// Speech ended at this position
+    SpeechEnd(Duration),
+    /// A chunk is ready to be transcribed
+    Chunk(PcmChunk),
+}
+
+/// Incrementally splits PCM audio into chunks of speech
+#[derive(Debug)]
+pub struct SilenceChunker {
+    config: SilenceChunking,
+    format: PcmFormat,
+    /// Bytes not yet measured because they don't fill a window
+    pending: Vec<u8>,
+    /// Audio of the chunk being collected
+    chunk: Vec<u8>,
+    /// Frame at which the current chunk starts
+    chunk_start: u64,
+    /// Number of frames measured so far
+    position: u64,
+    /// Whether the current chunk contains speech
+    has_speech: bool,
+    /// Whether speech is ongoing
+    in_speech: bool,
+    /// Frames of silence since speech was last heard
+    silent_run: u64,
+}
+
+impl SilenceChunker {
+    pub fn new(config: SilenceChunking, format: PcmFormat) -> Self {
+        Self {
+            config,
+            format,
+            pending: Vec::new(),
+            chunk: Vec::new(),
+            chunk_start: 0,
+            position: 0,
+            has_speech: false,
+            in_speech: false,
+            silent_run: 0,
+        }
+    }
+
+    /// Feeds more audio, returning what it completed
+    pub fn push(&mut self, data: &[u8]) -> Vec<ChunkEvent> {
+        let window =
+            self.format.frames_in(self.config.window).max(1) as usize * self.format.frame_size();
+        self.pending.extend_from_slice(data);
+        let pending = std::mem::take(&mut self.pending);
+
+        let mut events = Vec::new();
+        let mut windows = pending.chunks_exact(window);
+        for window in windows.by_ref() {
+            self.measure(window, &mut events);
+        }
+        self.pending = windows.remainder().to_vec();
+        events
+    }
+
+    /// Ends the audio, returning the last chunk if it contains speech
+    pub fn finish(&mut self) -> Vec<ChunkEvent> {
+        let mut events = Vec::new();
+        let pending = std::mem::take(&mut self.pending);
+        let whole = pending.len() - pending.len() % self.format.frame_size();
+        if whole > 0 {
+            self.measure(&pending[..whole], &mut events);
+        }
+        if self.in_speech {
+            let end = self.position - self.silent_run;
+            events.push(ChunkEvent::SpeechEnd(self.format.duration_of(end)));
+            self.in_speech = false;
+            self.silent_run = 0;
+        }
+        events.extend(self.cut().map(ChunkEvent::Chunk));
+        events
+    }
+
+    fn measure(&mut self, window: &[u8], events: &mut Vec<ChunkEvent>) {
+        let frames = (window.len() / self.format.frame_size()) as u64;
+        let silent = self.format.level(window) < self.config.silence_threshold;
+
+        if !silent {
+            if !self.in_speech {
+                let start = self.format.duration_of(self.position);
+                events.push(ChunkEvent::SpeechStart(start));
+                self.in_speech = true;
+            }
+            self.has_speech = true;
+            self.silent_run = 0;
+        } else if self.in_speech {
+            self.silent_run += frames;
+        }
+        self.chunk.extend_from_slice(window);
+        self.position += frames;
+
+        if !self.has_speech {
+            // Keep only enough leading silence to not clip the first word.
+            let keep =
+                self.format.frames_in(self.config.min_silence) as usize * self.format.frame_size();
+            if self.chunk.len() > keep {
+                let dropped = self.chunk.len() - keep;
+                self.chunk.drain(..dropped);
+                self.chunk_start += (dropped / self.format.frame_size()) as u64;
+            }
+        }
+
+        if self.in_speech && self.silent_run >= self.format.frames_in(self.config.min_silence) {
+            let end = self.position - self.silent_run;
+            events.push(ChunkEvent::SpeechEnd(self.format.duration_of(end)));
+            self.in_speech = false;
+            self.silent_run = 0;
+            events.extend(self.cut().map(ChunkEvent::Chunk));
+        } else if self.has_speech
+            && self.position - self.chunk_start >= self.format.frames_in(self.config.max_chunk)
+        {
+            events.extend(self.cut().map(ChunkEvent::Chunk));
+        }
+    }
+
+    /// Starts a new chunk, returning the current one if it contains speech
+    fn cut(&mut self) -> Option<PcmChunk> {
+        let data = std::mem::take(&mut self.chunk);
+        let start = std::mem::replace(&mut self.chunk_start, self.position);
+        let has_speech =
+            std::mem::replace(&mut self.has_speech, self.in_speech && self.silent_run == 0);
+        has_speech.then(|| PcmChunk {
+            start: self.format.duration_of(start),
+            duration: self.format.duration_of(self.position - start),
+            data,
+        })
+    }
+}
+
+/// Splits a whole recording into chunks no longer than `config.max_chunk`
+///
+/// Each chunk is cut in the last silent window of its second half, or in the
+/// quietest one if none is silent. A cut outside silence may fall inside a
+/// word, so the next chunk then starts `overlap` earlier to hear it whole;
+/// the overlap is capped at a quarter of `max_chunk`.
+pub fn split_on_silence(
+    config: &SilenceChunking,
+    format: PcmFormat,
+    data: &[u8],
+    overlap: Duration,
+) -> Vec<PcmChunk> {
+    let frame_size = format.frame_size();
+    let total = (data.len() / frame_size) as u64;
+    let max = format.frames_in(config.max_chunk).max(4);
+    let window = format.frames_in(config.window).clamp(1, max / 2);
+    let overlap = format.frames_in(overlap).min(max / 4);
+    let frames = |from: u64, to: u64| &data[from as usize * frame_size..to as usize * frame_size];
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    loop {
+        let mut next = None;
+        let end = if total - start <= max {
+            total
+        } else {
+            let levels: Vec<(u64, f32)> = (start + max / 2..=start + max - window)
+                .step_by(window as usize)
+                .map(|at| (at, format.level(frames(at, at + window))))
+                .collect();
+            let silent = levels
+                .iter()
+                .rev()
+                .find(|(_, level)| *level < config.silence_threshold);
+            let (at, _) = silent.copied().unwrap_or_else(|| {
+                levels
+                    .iter()
+                    .copied()
+                    .min_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)))
+                    .unwrap_or((start + max - window, 0.0))
+            });
+            let cut = at + window / 2;
+            next = Some(if silent.is_some() { cut } else { cut - overlap });
+            cut
+        };
+
+        chunks.push(PcmChunk {
+            start: format.duration_of(start),
+            duration: format.duration_of(end - start),
+            data: frames(start, end).to_vec(),
+        });
+        match next {
+            Some(next) => start = next,
+            None => return chunks,
+        }
+    }
+}
+
+/// Finds the PCM layout and the start of the sample data in a WAV file
+///
+/// Returns `Ok(None)` if `data` ends before the sample data starts.
+///
+/// # Errors
+///
+/// Returns [`SttError::UnsupportedFormat`] if `data` is a WAV file whose
+/// samples aren't 16-bit integers or 32-bit floats, and
+/// [`SttError::InvalidAudioData`] if it isn't a WAV file.
+pub fn parse_wav_header(data: &[u8]) -> Result<Option<(PcmFormat, usize)>, SttError> {
+    if data.len() < 12 {
+        return Ok(None);
+    }
+    if &data[..4] != b"RIFF" || &data[8..12] != b"WAVE" {
+        return Err(SttError::InvalidAudioData("not a WAV file".to_string()));
+    }
+
+    let u16_at = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let u32_at =
+        |at: usize| u32::from_le_bytes([data[at], data[at + 1], data[at + 2], data[at + 3]]);
+
+    let mut format = None;
+    let mut offset = 12;
+    while offset + 8 <= data.len() {
+        let id = &data[offset..offset + 4];
+        let size = u32_at(offset + 4) as usize;
+        let body = offset + 8;
+
+        if id == b"data" {
+            let format = format.ok_or_else(|| {
+                SttError::InvalidAudioData("WAV data chunk before fmt chunk".to_string())
+            })?;
+            return Ok(Some((format, body)));
+        }
+        if body + size > data.len() {
+            return Ok(None);
+        }
+        if id == b"fmt " {
+            if size < 16 {
+                return Err(SttError::InvalidAudioData(
+                    "truncated WAV fmt chunk".to_string(),
+                ));
+            }
+            let mut tag = u16_at(body);
+            if tag == 0xFFFE && size >= 26 {
+                // WAVE_FORMAT_EXTENSIBLE keeps the real tag in its sub-format GUID.
+                tag = u16_at(body + 24);
+            }
+            let bits = u16_at(body + 14);
+            let encoding = match (tag, bits) {
+                (1, 16) => AudioEncoding::Linear16,
+                (3, 32) => AudioEncoding::Float32,
+                _ => {
+                    return Err(SttError::UnsupportedFormat(format!(
+                        "WAV with format tag {tag} and {bits}-bit samples"
+                    )));
+                }
+            };
+            let parsed = PcmFormat {
+                sample_rate: u32_at(body + 4),
+                channels: u16_at(body + 2),
+                encoding,
+            };
+            if !parsed.is_valid() {
+                return Err(SttError::InvalidAudioData(
+                    "invalid WAV fmt chunk".to_string(),
+                ));
+            }
+            format = Some(parsed);
+        }
+        offset = body + size + size % 2;
+    }
+    Ok(None)
+}
+
+/// Wraps PCM data in a 16-bit WAV file
+pub fn encode_wav(format: PcmFormat, data: &[u8]) -> Vec<u8> {
+    let samples: Vec<u8> = match format.encoding {
+        AudioEncoding::Float32 => data
+            .chunks_exact(4)
+            .flat_map(|b| {
+                let sample = f32::from_le_bytes([b[0], b[1], b[2], b[3]]).clamp(-1.0, 1.0);
+                ((sample * f32::from(i16::MAX)) as i16).to_le_bytes()
+            })
+            .collect(),
+        _ => data[..data.len() - data.len() % 2].to_vec(),
+    };
+    let block_align = 2 * format.channels;
+    let byte_rate = format.sample_rate * u32::from(block_align);
+    let data_len = samples.len() as u32;
+
+    let mut wav = Vec::with_capacity(44 + samples.len());
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&format.channels.to_le_bytes());
+    wav.extend_from_slice(&format.sample_rate.to_le_bytes());
+    wav.extend_from_slice(&byte_rate.to_le_bytes());
+    wav.extend_from_slice(&block_align.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    wav.extend_from_slice(&samples);
+    wav
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) const FORMAT: PcmFormat = PcmFormat {
+        sample_rate: 1000,
+        channels: 1,
+        encoding: AudioEncoding::Linear16,
+    };
+
+    /// `millis` of 1 kHz mono 16-bit audio, a square wave if `loud`
+    pub(crate) fn tone(millis: usize, loud: bool) -> Vec<u8> {
+        (0..millis)
+            .flat_map(|i| {
+                let sample: i16 = if loud && i % 2 == 0 {
+                    8000
+                } else if loud {
+                    -8000
+                } else {
+                    0
+                };
+                sample.to_le_bytes()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_chunks_are_cut_on_silence() {
+        let mut chunker = SilenceChunker::new(SilenceChunking::default(), FORMAT);
+        let audio = [
+            tone(1000, false),
+            tone(600, true),
+            tone(900, false),
+            tone(300, true),
+        ]
+        .concat();
+
+        let mut events = Vec::new();
+        for piece in audio.chunks(77) {
+            events.extend(chunker.push(piece));
+        }
+        events.extend(chunker.finish());
+
+        let ms = Duration::from_millis;
+        let chunks: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                ChunkEvent::Chunk(chunk) => Some((chunk.start, chunk.duration)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(chunks, [(ms(490), ms(1640)), (ms(2130), ms(670))]);
+        assert_eq!(events[0], ChunkEvent::SpeechStart(ms(990)));
+        assert_eq!(events[1], ChunkEvent::SpeechEnd(ms(1620)));
+        assert!(events.contains(&ChunkEvent::SpeechEnd(ms(2800))));
+    }
+
+    #[test]
+    fn test_long_speech_is_cut_at_max_chunk() {
+        let config = SilenceChunking {
+            max_chunk: Duration::from_secs(1),
+            ..SilenceChunking::default()
+        };
+        let mut chunker = SilenceChunker::new(config, FORMAT);
+        let mut events = chunker.push(&tone(2500, true));
+        events.extend(chunker.finish());
+
+        let durations: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                ChunkEvent::Chunk(chunk) => Some(chunk.duration.as_millis()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(durations, [1020, 1020, 460]);
+    }
+
+    #[test]
+    fn test_recordings_are_split_in_pauses() {
+        let config = SilenceChunking {
+            max_chunk: Duration::from_secs(2),
+            ..SilenceChunking::default()
+        };
+        let audio = [tone(1300, true), tone(200, false), tone(2900, true)].concat();
+
+        let chunks: Vec<_> = split_on_silence(&config, FORMAT, &audio, Duration::from_millis(250))
+            .into_iter()
+            .map(|chunk| (chunk.start.as_millis(), chunk.duration.as_millis()))
+            .collect();
+        // The first cut falls in the pause; the second has to cut the tone, so
+        // the third chunk overlaps it.
+        assert_eq!(chunks, [(0, 1465), (1465, 1975), (3190, 1210)]);
+    }
+
+    #[test]
+    fn test_wav_header_round_trip() {
+        let wav = encode_wav(FORMAT, &tone(10, true));
+
+        assert_eq!(parse_wav_header(&wav[..30]).unwrap(), None);
+        assert_eq!(parse_wav_header(&wav).unwrap(), Some((FORMAT, 44)));
+        assert_eq!(&wav[44..], tone(10, true));
+        assert!(parse_wav_header(b"ID3\x03 not a wav file").is_err());
+    }
+}
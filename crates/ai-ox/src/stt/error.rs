@@ -76,9 +76,9 @@ pub enum SttError {
 
 // Conversion implementations for provider-specific errors
 
-#[cfg(feature = "groq")]
-impl From<groq_ox::GroqRequestError> for SttError {
-    fn from(error: groq_ox::GroqRequestError) -> Self {
+// Groq, Mistral and OpenAI all report errors as `ProviderError`
+impl From<ProviderError> for SttError {
+    fn from(error: ProviderError) -> Self {
         SttError::ProviderError(error)
     }
 }
@@ -90,4 +90,11 @@ impl From<crate::errors::GenerateContentError> for SttError {
     }
 }
 
+#[cfg(feature = "gemini")]
+impl From<gemini_ox::GeminiRequestError> for SttError {
+    fn from(error: gemini_ox::GeminiRequestError) -> Self {
+        SttError::Provider(Box::new(error))
+    }
+}
+
 // Note: reqwest errors are handled through provider-specific error types
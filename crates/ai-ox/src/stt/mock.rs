@@ -0,0 +1,130 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+
+use futures_util::{FutureExt, future::BoxFuture};
+
+use crate::stt::{
+    AudioFormat, SpeechToText, SttError, SttInfo, SttModel, TranscriptionRequest,
+    TranscriptionResponse,
+};
+
+/// Provider name reported by a [`MockStt`] and its transcriptions
+const PROVIDER_NAME: &str = "mock";
+
+/// A speech-to-text provider that serves scripted transcriptions, for tests
+///
+/// Each request is answered with the next scripted reply, in order. Requests
+/// with no reply left fail with a configuration error. Every request is
+/// recorded so tests can assert on what was sent.
+#[derive(Debug, Clone)]
+pub struct MockStt {
+    /// Model reported by the provider and its transcriptions
+    model: String,
+    /// Longest audio the model reports accepting
+    max_duration: Option<Duration>,
+    /// Script and recorded requests, shared between clones
+    state: Arc<Mutex<MockState>>,
+}
+
+/// Scripted replies and recorded requests of a [`MockStt`]
+#[derive(Debug, Default)]
+struct MockState {
+    replies: VecDeque<Result<TranscriptionResponse, SttError>>,
+    requests: Vec<TranscriptionRequest>,
+}
+
+impl MockStt {
+    /// Create a provider for `model` with an empty script
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            max_duration: None,
+            state: Arc::default(),
+        }
+    }
+
+    /// Append a transcription of `text` without timestamps
+    pub fn with_text(self, text: impl Into<String>) -> Self {
+        let response = TranscriptionResponse::simple(
+            text.into(),
+            PROVIDER_NAME.to_string(),
+            self.model.clone(),
+        );
+        self.with_response(response)
+    }
+
+    /// Append a transcription
+    pub fn with_response(self, response: TranscriptionResponse) -> Self {
+        self.lock().replies.push_back(Ok(response));
+        self
+    }
+
+    /// Append an error returned instead of a transcription
+    pub fn with_error(self, error: SttError) -> Self {
+        self.lock().replies.push_back(Err(error));
+        self
+    }
+
+    /// Report `max_duration` as the longest audio the model accepts
+    pub fn with_max_duration(mut self, max_duration: Duration) -> Self {
+        self.max_duration = Some(max_duration);
+        self
+    }
+
+    /// Every request received so far, in order
+    pub fn requests(&self) -> Vec<TranscriptionRequest> {
+        self.lock().requests.clone()
+    }
+
+    /// Number of scripted replies not served yet
+    pub fn remaining_replies(&self) -> usize {
+        self.lock().replies.len()
+    }
+
+    /// Lock the state, recovering it if a test panicked while holding it
+    fn lock(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl SpeechToText for MockStt {
+    fn info(&self) -> SttInfo<'_> {
+        SttInfo::new(PROVIDER_NAME, &self.model)
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn transcribe(
+        &self,
+        request: TranscriptionRequest,
+    ) -> BoxFuture<'_, Result<TranscriptionResponse, SttError>> {
+        let mut state = self.lock();
+        state.requests.push(request);
+        let reply = state.replies.pop_front().unwrap_or_else(|| {
+            Err(SttError::InvalidConfig(format!(
+                "MockStt '{}' has no scripted reply for request {}",
+                self.model,
+                state.requests.len()
+            )))
+        });
+        async move { reply }.boxed()
+    }
+
+    fn available_models(&self) -> BoxFuture<'_, Result<Vec<SttModel>, SttError>> {
+        let model = SttModel {
+            id: self.model.clone(),
+            name: self.model.clone(),
+            description: None,
+            supported_formats: vec![AudioFormat::Mp3, AudioFormat::Wav, AudioFormat::Flac],
+            max_duration: self.max_duration,
+            supports_streaming: false,
+            supported_languages: Vec::new(),
+        };
+        async move { Ok(vec![model]) }.boxed()
+    }
+}
@@ -1,15 +1,21 @@
 pub mod builder;
+pub mod chunked;
+pub mod chunking;
 pub mod error;
+#[cfg(any(test, feature = "testing"))]
+pub mod mock;
 pub mod providers;
 pub mod request;
 pub mod response;
 pub mod streaming;
 
+pub use chunked::{AudioDecoder, ChunkedTranscriber};
 pub use error::SttError;
 pub use request::{
     AudioFormat, AudioSource, OutputFormat, TimestampGranularity, TranscriptionRequest,
 };
 pub use response::{Alternative, Segment, SttUsage, TranscriptionResponse, Word};
+pub use streaming::{AudioStreamConfig, StreamingTranscriptionRequest, TranscriptionEvent};
 
 #[cfg(feature = "groq")]
 pub use builder::groq_stt;
@@ -17,11 +23,14 @@ pub use builder::groq_stt;
 #[cfg(feature = "mistral")]
 pub use builder::mistral_stt;
 
-// TODO: Implement Gemini STT provider (currently requires ALSA for audio examples)
-// #[cfg(feature = "gemini")]
-// pub use builder::gemini_stt;
+#[cfg(feature = "gemini")]
+pub use builder::gemini_stt;
 
-use futures_util::future::BoxFuture;
+#[cfg(feature = "openai")]
+pub use builder::openai_stt;
+
+use bytes::Bytes;
+use futures_util::{future::BoxFuture, stream::BoxStream};
 use std::sync::Arc;
 
 /// Provider information containing provider name and model identifier
@@ -95,6 +104,29 @@ pub trait SpeechToText: Send + Sync + 'static + std::fmt::Debug {
         request: TranscriptionRequest,
     ) -> BoxFuture<'_, Result<TranscriptionResponse, SttError>>;
 
+    /// Transcribes audio while it is still being captured
+    ///
+    /// Events are yielded as speech is recognised, ending with
+    /// [`TranscriptionEvent::EndOfStream`] once `audio` ends.
+    ///
+    /// The default implementation is for providers without native streaming:
+    /// it splits uncompressed audio into utterances on silence, as configured
+    /// by [`StreamingTranscriptionRequest::silence_chunking`], transcribes each
+    /// with [`transcribe`](Self::transcribe) and shifts the timestamps of its
+    /// segments to where it started in the stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - Configuration of the audio and the transcription
+    /// * `audio` - Audio as it arrives, raw or in a WAV container
+    fn transcribe_stream(
+        &self,
+        request: StreamingTranscriptionRequest,
+        audio: BoxStream<'static, Bytes>,
+    ) -> BoxStream<'_, Result<TranscriptionEvent, SttError>> {
+        streaming::transcribe_in_chunks(self, request, audio)
+    }
+
     /// Lists available models/engines for this provider
     ///
     /// # Returns
@@ -128,6 +160,14 @@ impl<T: SpeechToText + ?Sized> SpeechToText for Arc<T> {
         self.as_ref().transcribe(request)
     }
 
+    fn transcribe_stream(
+        &self,
+        request: StreamingTranscriptionRequest,
+        audio: BoxStream<'static, Bytes>,
+    ) -> BoxStream<'_, Result<TranscriptionEvent, SttError>> {
+        self.as_ref().transcribe_stream(request, audio)
+    }
+
     fn available_models(&self) -> BoxFuture<'_, Result<Vec<SttModel>, SttError>> {
         self.as_ref().available_models()
     }
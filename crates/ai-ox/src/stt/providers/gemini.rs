@@ -0,0 +1,448 @@
+use futures_util::{FutureExt, future::BoxFuture};
+use serde::Deserialize;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+use crate::stt::{
+    AudioFormat, AudioSource, OutputFormat, Segment, SpeechToText, SttError, SttInfo, SttModel,
+    SttUsage, TimestampGranularity, TranscriptionRequest, TranscriptionResponse, Word,
+};
+
+use gemini_ox::{
+    content::{Blob, Content, FileData, Part, PartData, Role},
+    generate_content::{GenerationConfig, request::GenerateContentRequest},
+};
+
+/// Gemini STT provider implementation, transcribing audio passed to a
+/// multimodal model
+#[derive(Debug, Clone)]
+pub struct GeminiStt {
+    client: gemini_ox::Gemini,
+    model: String,
+}
+
+/// Builder for GeminiStt
+pub struct GeminiSttBuilder {
+    model: Option<String>,
+    api_key: Option<String>,
+}
+
+impl GeminiSttBuilder {
+    pub fn new() -> Self {
+        Self {
+            model: None,
+            api_key: None,
+        }
+    }
+
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    pub fn api_key_from_env(mut self, env_var: &str) -> Result<Self, SttError> {
+        let key = std::env::var(env_var).map_err(|_| SttError::MissingApiKey)?;
+        self.api_key = Some(key);
+        Ok(self)
+    }
+
+    pub fn build(self) -> Result<Arc<dyn SpeechToText>, SttError> {
+        let api_key = self.api_key.ok_or(SttError::MissingApiKey)?;
+        let model = self.model.unwrap_or_else(|| "gemini-2.5-flash".to_string());
+
+        let client = gemini_ox::Gemini::new(api_key);
+        Ok(Arc::new(GeminiStt::new(client, model)))
+    }
+}
+
+impl Default for GeminiSttBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Transcript Gemini is asked to return when timestamps are requested
+#[derive(Debug, Deserialize)]
+struct GeminiTranscript {
+    #[serde(default)]
+    language: Option<String>,
+    #[serde(default)]
+    segments: Vec<GeminiSegment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiSegment {
+    start: f64,
+    end: f64,
+    text: String,
+    #[serde(default)]
+    words: Vec<GeminiWord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiWord {
+    word: String,
+    start: f64,
+    end: f64,
+}
+
+impl GeminiStt {
+    /// Create a new Gemini STT provider
+    pub fn new(client: gemini_ox::Gemini, model: String) -> Self {
+        Self { client, model }
+    }
+
+    /// Create a builder for GeminiStt
+    pub fn builder() -> GeminiSttBuilder {
+        GeminiSttBuilder::new()
+    }
+
+    /// Convert unified audio source to a Gemini part
+    ///
+    /// URLs must point to files uploaded through the Gemini Files API.
+    fn convert_audio_source(&self, source: AudioSource) -> Result<Part, SttError> {
+        let mime_type = |format: Option<AudioFormat>| match format {
+            Some(AudioFormat::Unknown) | None => Err(SttError::UnsupportedFormat(
+                "Gemini needs the audio format to be known".to_string(),
+            )),
+            Some(format) => Ok(format.mime_type()),
+        };
+        let format = source.format();
+        let data = match source {
+            AudioSource::Bytes { data, .. } => {
+                use base64::Engine;
+                PartData::from(Blob::new(
+                    mime_type(format)?,
+                    base64::engine::general_purpose::STANDARD.encode(data),
+                ))
+            }
+            AudioSource::File(path) => {
+                use base64::Engine;
+                let data = std::fs::read(&path).map_err(|e| {
+                    SttError::InvalidConfig(format!("Failed to read file {:?}: {}", path, e))
+                })?;
+                PartData::from(Blob::new(
+                    mime_type(format)?,
+                    base64::engine::general_purpose::STANDARD.encode(data),
+                ))
+            }
+            AudioSource::Base64 { data, .. } => PartData::from(Blob::new(mime_type(format)?, data)),
+            AudioSource::Url(uri) => {
+                PartData::from(FileData::new_with_optional_mime_type(uri, None::<String>))
+            }
+            AudioSource::RecordingId(_) => {
+                return Err(SttError::InvalidConfig(
+                    "Recording ID sources not supported by Gemini".to_string(),
+                ));
+            }
+        };
+        Ok(Part::new(data))
+    }
+
+    /// Instructions telling the model what to do with the audio
+    fn instructions(request: &TranscriptionRequest) -> String {
+        let mut instructions = if request.translate {
+            "Translate the speech in this audio into English.".to_string()
+        } else {
+            "Transcribe the speech in this audio verbatim, in the language it is spoken."
+                .to_string()
+        };
+        if let Some(language) = &request.language {
+            instructions.push_str(&format!(" The speech is in the language `{language}`."));
+        }
+        match Self::timestamps(request) {
+            TimestampGranularity::None => {
+                instructions.push_str(" Reply with the text only.");
+            }
+            TimestampGranularity::Segment => instructions.push_str(
+                " Split the text into segments at sentence boundaries, giving the start and \
+                 end of each in seconds from the beginning of the audio, and report the \
+                 ISO-639-1 code of the spoken language.",
+            ),
+            TimestampGranularity::Word => instructions.push_str(
+                " Split the text into segments at sentence boundaries, giving the start and \
+                 end of each segment and each of its words in seconds from the beginning of \
+                 the audio, and report the ISO-639-1 code of the spoken language.",
+            ),
+        }
+        if let Some(prompt) = &request.prompt {
+            instructions.push_str(&format!("\n\nContext for the transcription: {prompt}"));
+        }
+        instructions
+    }
+
+    /// Timestamps to ask for, with verbose output implying segments
+    fn timestamps(request: &TranscriptionRequest) -> TimestampGranularity {
+        match (request.timestamps, request.output_format) {
+            (TimestampGranularity::None, OutputFormat::Verbose) => TimestampGranularity::Segment,
+            (timestamps, _) => timestamps,
+        }
+    }
+
+    /// JSON schema of [`GeminiTranscript`]
+    fn transcript_schema(words: bool) -> serde_json::Value {
+        let timed = |text_field: &str| {
+            serde_json::json!({
+                "type": "OBJECT",
+                "properties": {
+                    text_field: {"type": "STRING"},
+                    "start": {"type": "NUMBER"},
+                    "end": {"type": "NUMBER"},
+                },
+                "required": [text_field, "start", "end"],
+            })
+        };
+        let mut segment = timed("text");
+        if words {
+            segment["properties"]["words"] = serde_json::json!({
+                "type": "ARRAY",
+                "items": timed("word"),
+            });
+        }
+        serde_json::json!({
+            "type": "OBJECT",
+            "properties": {
+                "language": {"type": "STRING"},
+                "segments": {"type": "ARRAY", "items": segment},
+            },
+            "required": ["segments"],
+        })
+    }
+
+    /// Convert unified request to Gemini format
+    fn convert_request(
+        &self,
+        request: TranscriptionRequest,
+    ) -> Result<GenerateContentRequest, SttError> {
+        let instructions = Self::instructions(&request);
+        let timestamps = Self::timestamps(&request);
+        let audio = self.convert_audio_source(request.audio)?;
+
+        let generation_config = GenerationConfig::builder()
+            .temperature(f64::from(request.temperature))
+            .maybe_response_mime_type(
+                (timestamps != TimestampGranularity::None).then_some("application/json"),
+            )
+            .maybe_response_schema(
+                (timestamps != TimestampGranularity::None)
+                    .then(|| Self::transcript_schema(timestamps == TimestampGranularity::Word)),
+            )
+            .build();
+
+        Ok(GenerateContentRequest::builder()
+            .model(self.model.clone())
+            .content(Content::new(
+                Role::User,
+                [Part::new(PartData::from(instructions)), audio],
+            ))
+            .generation_config(generation_config)
+            .build())
+    }
+
+    /// Convert Gemini response to unified format
+    fn convert_response(
+        &self,
+        gemini_response: gemini_ox::generate_content::response::GenerateContentResponse,
+        timestamps: TimestampGranularity,
+    ) -> Result<TranscriptionResponse, SttError> {
+        let text: String = gemini_response
+            .last_content()
+            .into_iter()
+            .flat_map(|content| content.parts())
+            .filter(|part| !part.thought.unwrap_or(false))
+            .filter_map(|part| part.as_text().map(|text| text.to_string()))
+            .collect();
+
+        if timestamps == TimestampGranularity::None {
+            return Ok(TranscriptionResponse::simple(
+                text.trim().to_string(),
+                "gemini".to_string(),
+                self.model.clone(),
+            ));
+        }
+
+        let transcript: GeminiTranscript = serde_json::from_str(&text).map_err(|e| {
+            SttError::TranscriptionFailed(format!("Gemini returned an invalid transcript: {e}"))
+        })?;
+        let seconds = |secs: f64| Duration::from_secs_f64(secs.max(0.0));
+
+        let mut response = TranscriptionResponse::simple(
+            transcript
+                .segments
+                .iter()
+                .map(|segment| segment.text.trim())
+                .collect::<Vec<_>>()
+                .join(" "),
+            "gemini".to_string(),
+            self.model.clone(),
+        );
+        response.language = transcript.language;
+        response.duration = transcript
+            .segments
+            .last()
+            .map(|segment| seconds(segment.end));
+        for (id, segment) in transcript.segments.into_iter().enumerate() {
+            response.words.extend(
+                segment
+                    .words
+                    .into_iter()
+                    .map(|word| Word::new(word.word, seconds(word.start), seconds(word.end))),
+            );
+            response.segments.push(
+                Segment::new(segment.text, seconds(segment.start), seconds(segment.end))
+                    .with_id(id as u32),
+            );
+        }
+
+        // Set usage information
+        if let Some(duration) = response.duration {
+            response.usage = SttUsage::new(duration);
+            response.usage.characters_transcribed = response.text.len() as u32;
+        }
+
+        Ok(response)
+    }
+}
+
+/// Formats Gemini accepts as audio input
+const GEMINI_FORMATS: [AudioFormat; 6] = [
+    AudioFormat::Mp3,
+    AudioFormat::Wav,
+    AudioFormat::Flac,
+    AudioFormat::Ogg,
+    AudioFormat::Aac,
+    AudioFormat::M4a,
+];
+
+/// Available Gemini STT models with metadata
+static GEMINI_MODELS: LazyLock<Vec<SttModel>> = LazyLock::new(|| {
+    [
+        (
+            "gemini-2.5-flash",
+            "Gemini 2.5 Flash",
+            "Fast multimodal model with approximate timestamps",
+        ),
+        (
+            "gemini-2.5-pro",
+            "Gemini 2.5 Pro",
+            "Most capable multimodal model with approximate timestamps",
+        ),
+    ]
+    .into_iter()
+    .map(|(id, name, description)| SttModel {
+        id: id.to_string(),
+        name: name.to_string(),
+        description: Some(description.to_string()),
+        supported_formats: GEMINI_FORMATS.to_vec(),
+        max_duration: Some(Duration::from_secs(9 * 60 * 60 + 30 * 60)), // 9.5 hours per prompt
+        supports_streaming: false,
+        supported_languages: Vec::new(),
+    })
+    .collect()
+});
+
+impl SpeechToText for GeminiStt {
+    fn info(&self) -> SttInfo<'_> {
+        SttInfo::new("gemini", &self.model)
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn transcribe(
+        &self,
+        request: TranscriptionRequest,
+    ) -> BoxFuture<'_, Result<TranscriptionResponse, SttError>> {
+        async move {
+            let timestamps = Self::timestamps(&request);
+            let gemini_request = self.convert_request(request)?;
+            let gemini_response = gemini_request.send(&self.client).await?;
+            self.convert_response(gemini_response, timestamps)
+        }
+        .boxed()
+    }
+
+    fn available_models(&self) -> BoxFuture<'_, Result<Vec<SttModel>, SttError>> {
+        async move { Ok(GEMINI_MODELS.clone()) }.boxed()
+    }
+
+    fn supports_format(&self, format: AudioFormat) -> bool {
+        GEMINI_FORMATS.contains(&format)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider() -> GeminiStt {
+        GeminiStt::new(
+            gemini_ox::Gemini::new("test-key"),
+            "gemini-2.5-flash".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_request_conversion() {
+        let request = TranscriptionRequest::builder()
+            .audio(AudioSource::from_bytes(vec![1, 2, 3], AudioFormat::Wav))
+            .timestamps(TimestampGranularity::Word)
+            .translate(true)
+            .build();
+        let converted = provider().convert_request(request).unwrap();
+
+        let parts = converted.contents[0].parts();
+        let instructions = parts[0].as_text().unwrap().to_string();
+        assert!(instructions.starts_with("Translate the speech in this audio into English."));
+        assert_eq!(parts[1].as_inline_data().unwrap().mime_type, "audio/wav");
+
+        let config = converted.generation_config.unwrap();
+        assert_eq!(
+            config.response_mime_type.as_deref(),
+            Some("application/json")
+        );
+        let schema = config.response_schema.unwrap();
+        assert!(schema["properties"]["segments"]["items"]["properties"]["words"].is_object());
+
+        let unknown = TranscriptionRequest::builder()
+            .audio(AudioSource::from_bytes(vec![1, 2, 3], AudioFormat::Unknown))
+            .build();
+        assert!(matches!(
+            provider().convert_request(unknown),
+            Err(SttError::UnsupportedFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_timestamped_response_conversion() {
+        let transcript = serde_json::json!({
+            "language": "en",
+            "segments": [
+                {"text": "Hello there.", "start": 0.2, "end": 1.1,
+                 "words": [{"word": "Hello", "start": 0.2, "end": 0.6},
+                           {"word": "there.", "start": 0.6, "end": 1.1}]},
+                {"text": "How are you?", "start": 1.4, "end": 2.3, "words": []}
+            ]
+        });
+        let gemini_response = serde_json::from_value(serde_json::json!({
+            "candidates": [{"content": {"role": "model", "parts": [{"text": transcript.to_string()}]}}]
+        }))
+        .unwrap();
+
+        let response = provider()
+            .convert_response(gemini_response, TimestampGranularity::Word)
+            .unwrap();
+
+        assert_eq!(response.text, "Hello there. How are you?");
+        assert_eq!(response.language.as_deref(), Some("en"));
+        assert_eq!(response.segments[1].start, Duration::from_millis(1400));
+        assert_eq!(response.words.len(), 2);
+        assert_eq!(response.duration, Some(Duration::from_millis(2300)));
+    }
+}
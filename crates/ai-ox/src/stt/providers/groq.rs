@@ -101,6 +101,11 @@ impl GroqStt {
         &self,
         request: TranscriptionRequest,
     ) -> Result<groq_ox::audio::TranscriptionRequest, SttError> {
+        if request.translate {
+            return Err(SttError::InvalidConfig(
+                "Translation is not supported by Groq".to_string(),
+            ));
+        }
         let audio_data = self.convert_audio_source(request.audio)?;
 
         // Convert output format
@@ -289,6 +294,7 @@ impl SpeechToText for GroqStt {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use base64::Engine;
 
     #[test]
     fn test_audio_source_conversion() {
@@ -102,6 +102,11 @@ impl MistralStt {
         &self,
         request: TranscriptionRequest,
     ) -> Result<mistral_ox::audio::TranscriptionRequest, SttError> {
+        if request.translate {
+            return Err(SttError::InvalidConfig(
+                "Translation is not supported by Mistral".to_string(),
+            ));
+        }
         let audio_data = self.convert_audio_source(request.audio)?;
 
         // Convert output format
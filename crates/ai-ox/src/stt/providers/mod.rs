@@ -4,6 +4,8 @@ pub mod groq;
 #[cfg(feature = "mistral")]
 pub mod mistral;
 
-// TODO: Implement Gemini STT provider (requires ALSA dependencies)
-// #[cfg(feature = "gemini")]
-// pub mod gemini;
+#[cfg(feature = "gemini")]
+pub mod gemini;
+
+#[cfg(feature = "openai")]
+pub mod openai;
@@ -0,0 +1,372 @@
+use futures_util::{FutureExt, future::BoxFuture};
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+use crate::stt::{
+    AudioFormat, AudioSource, OutputFormat, Segment, SpeechToText, SttError, SttInfo, SttModel,
+    SttUsage, TimestampGranularity, TranscriptionRequest, TranscriptionResponse, Word,
+};
+
+/// OpenAI STT provider implementation using Whisper and GPT-4o transcription models
+#[derive(Debug, Clone)]
+pub struct OpenAISttProvider {
+    client: openai_ox::OpenAI,
+    model: String,
+}
+
+/// Builder for OpenAISttProvider
+pub struct OpenAISttProviderBuilder {
+    model: Option<String>,
+    api_key: Option<String>,
+}
+
+impl OpenAISttProviderBuilder {
+    pub fn new() -> Self {
+        Self {
+            model: None,
+            api_key: None,
+        }
+    }
+
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    pub fn api_key_from_env(mut self, env_var: &str) -> Result<Self, SttError> {
+        let key = std::env::var(env_var).map_err(|_| SttError::MissingApiKey)?;
+        self.api_key = Some(key);
+        Ok(self)
+    }
+
+    pub fn build(self) -> Result<Arc<dyn SpeechToText>, SttError> {
+        let api_key = self.api_key.ok_or(SttError::MissingApiKey)?;
+        let model = self.model.unwrap_or_else(|| "whisper-1".to_string());
+
+        let client = openai_ox::OpenAI::new(api_key);
+        Ok(Arc::new(OpenAISttProvider::new(client, model)))
+    }
+}
+
+impl Default for OpenAISttProviderBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OpenAISttProvider {
+    /// Create a new OpenAI STT provider
+    pub fn new(client: openai_ox::OpenAI, model: String) -> Self {
+        Self { client, model }
+    }
+
+    /// Create a builder for OpenAISttProvider
+    pub fn builder() -> OpenAISttProviderBuilder {
+        OpenAISttProviderBuilder::new()
+    }
+
+    /// Convert unified audio source to bytes and the filename OpenAI detects
+    /// the format from
+    fn convert_audio_source(&self, source: AudioSource) -> Result<(Vec<u8>, String), SttError> {
+        let default_name = |format: Option<AudioFormat>| {
+            let extension = format
+                .and_then(|format| format.extensions().first().copied())
+                .unwrap_or("wav");
+            format!("audio.{extension}")
+        };
+        match source {
+            AudioSource::Bytes {
+                data,
+                format,
+                filename,
+            } => Ok((data, filename.unwrap_or_else(|| default_name(Some(format))))),
+            AudioSource::File(path) => {
+                let data = std::fs::read(&path).map_err(|e| {
+                    SttError::InvalidConfig(format!("Failed to read file {:?}: {}", path, e))
+                })?;
+                let filename = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| default_name(None));
+                Ok((data, filename))
+            }
+            AudioSource::Base64 { data, format } => {
+                use base64::Engine;
+                let data = base64::engine::general_purpose::STANDARD
+                    .decode(data)
+                    .map_err(|e| SttError::InvalidAudioData(format!("Invalid base64: {}", e)))?;
+                Ok((data, default_name(Some(format))))
+            }
+            AudioSource::Url(_) => Err(SttError::InvalidConfig(
+                "URL audio sources not supported by OpenAI".to_string(),
+            )),
+            AudioSource::RecordingId(_) => Err(SttError::InvalidConfig(
+                "Recording ID sources not supported by OpenAI".to_string(),
+            )),
+        }
+    }
+
+    /// Convert unified request to OpenAI format
+    /// Whether the model returns verbose JSON with timestamps
+    ///
+    /// The GPT-4o transcription models only return text and reject
+    /// `verbose_json` and timestamp granularities.
+    fn supports_verbose_json(&self) -> bool {
+        self.model.starts_with("whisper")
+    }
+
+    fn convert_request(
+        &self,
+        request: TranscriptionRequest,
+    ) -> Result<openai_ox::request::AudioRequest, SttError> {
+        let (file, filename) = self.convert_audio_source(request.audio)?;
+
+        // Plain text responses aren't JSON, so even simple output asks for JSON.
+        // Only Whisper returns verbose JSON; other models fall back to text.
+        let timestamps = self.supports_verbose_json();
+        let verbose = timestamps
+            && (request.output_format == OutputFormat::Verbose
+                || request.timestamps != TimestampGranularity::None);
+        let response_format = if verbose { "verbose_json" } else { "json" };
+
+        // Translations don't take timestamp granularities or a source language
+        let timestamp_granularities = match request.timestamps {
+            _ if request.translate || !timestamps => None,
+            TimestampGranularity::None => None,
+            TimestampGranularity::Segment => Some(vec!["segment".to_string()]),
+            TimestampGranularity::Word => Some(vec!["segment".to_string(), "word".to_string()]),
+        };
+
+        Ok(openai_ox::request::AudioRequest {
+            file,
+            filename,
+            model: self.model.clone(),
+            language: request.language.filter(|_| !request.translate),
+            prompt: request.prompt,
+            response_format: Some(response_format.to_string()),
+            temperature: if request.temperature > 0.0 {
+                Some(request.temperature)
+            } else {
+                None
+            },
+            timestamp_granularities,
+        })
+    }
+
+    /// Convert OpenAI response to unified format
+    fn convert_response(
+        &self,
+        openai_response: openai_ox::response::AudioResponse,
+    ) -> TranscriptionResponse {
+        let mut response = TranscriptionResponse::simple(
+            openai_response.text,
+            "openai".to_string(),
+            self.model.clone(),
+        );
+
+        response.language = openai_response.language;
+        response.duration = openai_response.duration.map(Duration::from_secs_f64);
+
+        // Convert segments if available
+        if let Some(openai_segments) = openai_response.segments {
+            response.segments = openai_segments
+                .into_iter()
+                .map(|seg| {
+                    Segment::new(
+                        seg.text,
+                        Duration::from_secs_f64(seg.start),
+                        Duration::from_secs_f64(seg.end),
+                    )
+                    .with_id(seg.id)
+                    // Use logprob-based confidence approximation
+                    .with_confidence(seg.avg_logprob.exp().clamp(0.0, 1.0) as f32)
+                })
+                .collect();
+        }
+
+        // Convert words if available
+        if let Some(openai_words) = openai_response.words {
+            response.words = openai_words
+                .into_iter()
+                .map(|word| {
+                    Word::new(
+                        word.word,
+                        Duration::from_secs_f64(word.start),
+                        Duration::from_secs_f64(word.end),
+                    )
+                })
+                .collect();
+        }
+
+        // Set usage information
+        if let Some(duration) = response.duration {
+            response.usage = SttUsage::new(duration);
+            response.usage.characters_transcribed = response.text.len() as u32;
+        }
+
+        response
+    }
+}
+
+/// Formats accepted by every OpenAI transcription model
+const OPENAI_FORMATS: [AudioFormat; 6] = [
+    AudioFormat::Mp3,
+    AudioFormat::Wav,
+    AudioFormat::Flac,
+    AudioFormat::M4a,
+    AudioFormat::Ogg,
+    AudioFormat::WebM,
+];
+
+/// Available OpenAI STT models with metadata
+static OPENAI_MODELS: LazyLock<Vec<SttModel>> = LazyLock::new(|| {
+    [
+        (
+            "whisper-1",
+            "Whisper",
+            "Whisper V2 with timestamps and translation to English",
+        ),
+        (
+            "gpt-4o-transcribe",
+            "GPT-4o Transcribe",
+            "Most accurate OpenAI transcription model, text output only",
+        ),
+        (
+            "gpt-4o-mini-transcribe",
+            "GPT-4o mini Transcribe",
+            "Faster and cheaper GPT-4o transcription, text output only",
+        ),
+    ]
+    .into_iter()
+    .map(|(id, name, description)| SttModel {
+        id: id.to_string(),
+        name: name.to_string(),
+        description: Some(description.to_string()),
+        supported_formats: OPENAI_FORMATS.to_vec(),
+        max_duration: None, // Limited by the 25 MB upload size instead
+        supports_streaming: false,
+        supported_languages: Vec::new(),
+    })
+    .collect()
+});
+
+impl SpeechToText for OpenAISttProvider {
+    fn info(&self) -> SttInfo<'_> {
+        SttInfo::new("openai", &self.model)
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn transcribe(
+        &self,
+        request: TranscriptionRequest,
+    ) -> BoxFuture<'_, Result<TranscriptionResponse, SttError>> {
+        async move {
+            let translate = request.translate;
+            let openai_request = self.convert_request(request)?;
+            let openai_response = if translate {
+                self.client.create_translation(&openai_request).await?
+            } else {
+                self.client.create_transcription(&openai_request).await?
+            };
+            Ok(self.convert_response(openai_response))
+        }
+        .boxed()
+    }
+
+    fn available_models(&self) -> BoxFuture<'_, Result<Vec<SttModel>, SttError>> {
+        async move { Ok(OPENAI_MODELS.clone()) }.boxed()
+    }
+
+    fn supports_format(&self, format: AudioFormat) -> bool {
+        OPENAI_FORMATS.contains(&format)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider() -> OpenAISttProvider {
+        OpenAISttProvider::new(openai_ox::OpenAI::new("test-key"), "whisper-1".to_string())
+    }
+
+    #[test]
+    fn test_request_conversion() {
+        let request = TranscriptionRequest::builder()
+            .audio(AudioSource::from_bytes(vec![1, 2, 3], AudioFormat::Flac))
+            .language("de")
+            .timestamps(TimestampGranularity::Word)
+            .build();
+        let converted = provider().convert_request(request).unwrap();
+
+        assert_eq!(converted.filename, "audio.flac");
+        assert_eq!(converted.language.as_deref(), Some("de"));
+        assert_eq!(converted.response_format.as_deref(), Some("verbose_json"));
+        assert_eq!(
+            converted.timestamp_granularities,
+            Some(vec!["segment".to_string(), "word".to_string()])
+        );
+
+        let request = TranscriptionRequest::builder()
+            .audio(AudioSource::from_bytes(vec![1, 2, 3], AudioFormat::Mp3))
+            .language("de")
+            .timestamps(TimestampGranularity::Segment)
+            .translate(true)
+            .build();
+        let converted = provider().convert_request(request).unwrap();
+
+        assert_eq!(converted.language, None);
+        assert_eq!(converted.timestamp_granularities, None);
+    }
+
+    #[test]
+    fn test_text_only_models_skip_timestamps() {
+        let provider = OpenAISttProvider::new(
+            openai_ox::OpenAI::new("test-key"),
+            "gpt-4o-transcribe".to_string(),
+        );
+        let request = TranscriptionRequest::builder()
+            .audio(AudioSource::from_bytes(vec![1, 2, 3], AudioFormat::Mp3))
+            .output_format(OutputFormat::Verbose)
+            .timestamps(TimestampGranularity::Segment)
+            .build();
+        let converted = provider.convert_request(request).unwrap();
+
+        assert_eq!(converted.response_format.as_deref(), Some("json"));
+        assert_eq!(converted.timestamp_granularities, None);
+    }
+
+    #[test]
+    fn test_verbose_response_conversion() {
+        let openai_response: openai_ox::response::AudioResponse =
+            serde_json::from_value(serde_json::json!({
+                "text": "Hello there.",
+                "language": "english",
+                "duration": 1.5,
+                "segments": [{
+                    "id": 0, "seek": 0, "start": 0.0, "end": 1.5, "text": "Hello there.",
+                    "tokens": [50364], "temperature": 0.0, "avg_logprob": -0.2,
+                    "compression_ratio": 0.8, "no_speech_prob": 0.01
+                }],
+                "words": [
+                    {"word": "Hello", "start": 0.1, "end": 0.5},
+                    {"word": "there", "start": 0.6, "end": 1.1}
+                ]
+            }))
+            .unwrap();
+        let response = provider().convert_response(openai_response);
+
+        assert_eq!(response.provider, "openai");
+        assert_eq!(response.segments[0].end, Duration::from_millis(1500));
+        assert_eq!(response.words[1].text, "there");
+        assert_eq!(response.usage.audio_duration, Duration::from_millis(1500));
+    }
+}
@@ -29,6 +29,11 @@ pub struct TranscriptionRequest {
     #[builder(default = TimestampGranularity::None)]
     pub timestamps: TimestampGranularity,
 
+    /// Translate the speech into English instead of transcribing it in the
+    /// language it is spoken
+    #[builder(default)]
+    pub translate: bool,
+
     /// Provider-specific options
     #[builder(default)]
     pub vendor_options: HashMap<String, serde_json::Value>,
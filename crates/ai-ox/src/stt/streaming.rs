@@ -1,8 +1,18 @@
+use async_stream::try_stream;
 use bon::Builder;
+use bytes::Bytes;
+use futures_util::{StreamExt, stream::BoxStream};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-use super::response::{Segment, Word};
+use super::{
+    AudioFormat, AudioSource, OutputFormat, SpeechToText, SttError, TimestampGranularity,
+    TranscriptionRequest, TranscriptionResponse,
+    chunking::{
+        ChunkEvent, PcmFormat, SilenceChunker, SilenceChunking, encode_wav, parse_wav_header,
+    },
+    response::{Segment, Word},
+};
 
 /// Request for streaming audio transcription
 #[derive(Debug, Clone, Builder)]
@@ -34,6 +44,11 @@ pub struct StreamingTranscriptionRequest {
     #[builder(default = 2)]
     pub max_speakers: u8,
 
+    /// How audio is split into utterances by providers without native
+    /// streaming
+    #[builder(default)]
+    pub silence_chunking: SilenceChunking,
+
     /// Provider-specific options
     #[builder(default)]
     pub vendor_options: std::collections::HashMap<String, serde_json::Value>,
@@ -292,3 +307,257 @@ impl TranscriptionEvent {
 
 // Note: Use StreamingTranscriptionRequest::builder() directly for creating instances
 // Example: StreamingTranscriptionRequest::builder().audio_config(config).build()
+
+/// Streams transcription events for `audio` using single-shot transcription.
+///
+/// Uncompressed audio, raw or in a WAV container, is split into utterances on
+/// silence and each utterance is transcribed as soon as it ends, with its
+/// segment and word timestamps shifted to where it started in the stream.
+/// Compressed audio can't be split and is transcribed once the stream ends.
+pub(crate) fn transcribe_in_chunks<'a, S>(
+    stt: &'a S,
+    request: StreamingTranscriptionRequest,
+    mut audio: BoxStream<'static, Bytes>,
+) -> BoxStream<'a, Result<TranscriptionEvent, SttError>>
+where
+    S: SpeechToText + ?Sized,
+{
+    Box::pin(try_stream! {
+        let mut head = Vec::new();
+        while head.len() < 12 {
+            match audio.next().await {
+                Some(bytes) => head.extend_from_slice(&bytes),
+                None => break,
+            }
+        }
+
+        let mut format = PcmFormat::from_config(&request.audio_config);
+        if head.starts_with(b"RIFF") {
+            loop {
+                if let Some((wav_format, data_start)) = parse_wav_header(&head)? {
+                    format = Some(wav_format);
+                    head.drain(..data_start);
+                    break;
+                }
+                match audio.next().await {
+                    Some(bytes) => head.extend_from_slice(&bytes),
+                    None => Err(SttError::InvalidAudioData("WAV stream ended in its header".to_string()))?,
+                }
+            }
+        }
+
+        let mut segment_id = 0;
+        let Some(format) = format else {
+            while let Some(bytes) = audio.next().await {
+                head.extend_from_slice(&bytes);
+            }
+            if !head.is_empty() {
+                let source = AudioSource::from_bytes(head, request.audio_config.format);
+                let response = stt.transcribe(chunk_request(&request, source)).await?;
+                for event in stitch(response, Duration::ZERO, &mut segment_id) {
+                    yield event;
+                }
+            }
+            yield TranscriptionEvent::end_of_stream();
+            return;
+        };
+
+        let mut chunker = SilenceChunker::new(request.silence_chunking, format);
+        let mut events = chunker.push(&head);
+        let mut finished = false;
+        loop {
+            for event in events.drain(..) {
+                match event {
+                    ChunkEvent::SpeechStart(at) => {
+                        yield TranscriptionEvent::speech_start(at);
+                    }
+                    ChunkEvent::SpeechEnd(at) => {
+                        yield TranscriptionEvent::speech_end(at);
+                    }
+                    ChunkEvent::Chunk(chunk) => {
+                        let source = AudioSource::from_bytes_with_name(
+                            encode_wav(format, &chunk.data),
+                            AudioFormat::Wav,
+                            format!("chunk-{}.wav", chunk.start.as_millis()),
+                        );
+                        let response = stt.transcribe(chunk_request(&request, source)).await?;
+                        for event in stitch(response, chunk.start, &mut segment_id) {
+                            yield event;
+                        }
+                    }
+                }
+            }
+            if finished {
+                break;
+            }
+            match audio.next().await {
+                Some(bytes) => events = chunker.push(&bytes),
+                None => {
+                    events = chunker.finish();
+                    finished = true;
+                }
+            }
+        }
+        yield TranscriptionEvent::end_of_stream();
+    })
+}
+
+/// Single-shot request transcribing one chunk of a stream
+fn chunk_request(
+    request: &StreamingTranscriptionRequest,
+    audio: AudioSource,
+) -> TranscriptionRequest {
+    TranscriptionRequest::builder()
+        .audio(audio)
+        .maybe_language(request.language.clone())
+        .maybe_prompt(request.prompt.clone())
+        .temperature(request.temperature)
+        .output_format(OutputFormat::Verbose)
+        .timestamps(TimestampGranularity::Segment)
+        .vendor_options(request.vendor_options.clone())
+        .build()
+}
+
+/// Final events for the transcription of a chunk starting at `offset`
+fn stitch(
+    response: TranscriptionResponse,
+    offset: Duration,
+    next_id: &mut u32,
+) -> Vec<TranscriptionEvent> {
+    let mut segments = response.segments;
+    if segments.is_empty() {
+        let end = offset + response.duration.unwrap_or_default();
+        segments.push(Segment::new(response.text, Duration::ZERO, end - offset));
+    }
+
+    let mut words = response.words.into_iter().peekable();
+    let last = segments.len() - 1;
+    let mut events = Vec::new();
+    for (index, mut segment) in segments.into_iter().enumerate() {
+        let segment_words: Vec<Word> =
+            std::iter::from_fn(|| words.next_if(|word| index == last || word.start < segment.end))
+                .map(|mut word| {
+                    word.start += offset;
+                    word.end += offset;
+                    word
+                })
+                .collect();
+        if segment.text.trim().is_empty() {
+            continue;
+        }
+
+        segment.start += offset;
+        segment.end += offset;
+        segment.id = Some(*next_id);
+        events.push(TranscriptionEvent::final_with_words(
+            segment,
+            *next_id,
+            segment_words,
+        ));
+        *next_id += 1;
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stt::{SttInfo, SttModel, chunking::tests::tone};
+    use futures_util::{FutureExt, future::BoxFuture};
+
+    /// Transcribes every chunk as one word spoken 100 to 400 ms into it
+    #[derive(Debug)]
+    struct OneWord;
+
+    impl SpeechToText for OneWord {
+        fn info(&self) -> SttInfo<'_> {
+            SttInfo::new("test", "one-word")
+        }
+
+        fn model(&self) -> &str {
+            "one-word"
+        }
+
+        fn transcribe(
+            &self,
+            request: TranscriptionRequest,
+        ) -> BoxFuture<'_, Result<TranscriptionResponse, SttError>> {
+            async move {
+                let AudioSource::Bytes { data, .. } = request.audio else {
+                    unreachable!("chunks are sent as bytes");
+                };
+                assert!(parse_wav_header(&data)?.is_some());
+
+                let (start, end) = (Duration::from_millis(100), Duration::from_millis(400));
+                let mut response = TranscriptionResponse::simple(
+                    "hello".to_string(),
+                    "test".to_string(),
+                    "one-word".to_string(),
+                );
+                response.segments = vec![Segment::new("hello".to_string(), start, end)];
+                response.words = vec![Word::new("hello".to_string(), start, end)];
+                Ok(response)
+            }
+            .boxed()
+        }
+
+        fn available_models(&self) -> BoxFuture<'_, Result<Vec<SttModel>, SttError>> {
+            async { Ok(Vec::new()) }.boxed()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fallback_stitches_chunk_timestamps() {
+        let config = AudioStreamConfig {
+            sample_rate: 1000,
+            ..AudioStreamConfig::wav_16khz_mono()
+        };
+        let audio = [
+            tone(1000, false),
+            tone(600, true),
+            tone(900, false),
+            tone(300, true),
+        ]
+        .concat();
+        let audio = futures_util::stream::iter(
+            audio
+                .chunks(200)
+                .map(Bytes::copy_from_slice)
+                .collect::<Vec<_>>(),
+        )
+        .boxed();
+        let request = StreamingTranscriptionRequest::builder()
+            .audio_config(config)
+            .build();
+
+        let events: Vec<_> = OneWord
+            .transcribe_stream(request, audio)
+            .map(|event| event.expect("transcription should succeed"))
+            .collect()
+            .await;
+
+        let finals: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                TranscriptionEvent::Final {
+                    segment,
+                    segment_id,
+                    words,
+                    ..
+                } => Some((
+                    *segment_id,
+                    segment.start.as_millis(),
+                    words[0].end.as_millis(),
+                )),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(finals, [(0, 590, 890), (1, 2230, 2530)]);
+        assert!(matches!(events[0], TranscriptionEvent::SpeechStart { .. }));
+        assert!(matches!(events[1], TranscriptionEvent::SpeechEnd { .. }));
+        assert!(matches!(
+            events.last(),
+            Some(TranscriptionEvent::EndOfStream)
+        ));
+    }
+}
@@ -58,6 +58,26 @@ pub enum ToolError {
 }
 
 impl ToolError {
+    /// Returns `true` if the tool itself failed while running, which may
+    /// succeed when retried.
+    pub fn is_execution(&self) -> bool {
+        matches!(self, Self::Execution { .. })
+    }
+
+    /// Returns `true` for failures inside the tool-handling framework.
+    pub fn is_internal(&self) -> bool {
+        matches!(self, Self::Internal { .. })
+    }
+
+    /// Formats the error together with its source, suitable for reporting
+    /// back to the model.
+    pub fn report(&self) -> String {
+        match StdError::source(self) {
+            Some(source) => format!("{self}: {source}"),
+            None => self.to_string(),
+        }
+    }
+
     /// Creates an internal error, capturing the context and the source error.
     pub fn internal(
         context: impl Into<String>,
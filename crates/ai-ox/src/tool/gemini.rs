@@ -19,6 +19,9 @@ impl From<AiOxTool> for GeminiTool {
                 Self::FunctionDeclarations(gemini_functions)
             }
             AiOxTool::GeminiTool(gemini_tool) => gemini_tool,
+            // Gemini has no equivalent of OpenAI's built-in tools
+            #[cfg(feature = "openai")]
+            AiOxTool::OpenAITool(_) => Self::FunctionDeclarations(Vec::new()),
         }
     }
 }
@@ -39,13 +39,29 @@ pub enum Tool {
     /// Vendor-specific tool with opaque metadata
     #[cfg(feature = "gemini")]
     GeminiTool(gemini_ox::tool::Tool),
+    /// Built-in tool of the OpenAI Responses API, such as web search
+    #[cfg(feature = "openai")]
+    #[serde(rename = "openai_tool")]
+    OpenAITool(openai_ox::responses::ResponsesTool),
+}
+
+impl Tool {
+    /// Returns true for built-in tools of the OpenAI Responses API.
+    #[cfg(feature = "gemini")]
+    pub(crate) fn is_openai_tool(&self) -> bool {
+        match self {
+            #[cfg(feature = "openai")]
+            Tool::OpenAITool(_) => true,
+            _ => false,
+        }
+    }
 }
 
 /// Trait for objects that provide tool functionality.
 ///
 /// This trait allows objects to expose their available tools and handle
-/// tool invocations in a standardized way. Any additional safety or approval
-/// flows should now be implemented by library consumers around these calls.
+/// tool invocations in a standardized way. Approval and redaction flows for
+/// agent-driven calls belong in an [`crate::agent::interceptor::ToolInterceptor`].
 pub trait ToolBox: Send + Sync + 'static {
     /// Returns the list of tools provided by this toolbox.
     fn tools(&self) -> Vec<Tool>;
@@ -62,6 +78,8 @@ pub trait ToolBox: Send + Sync + 'static {
             Tool::FunctionDeclarations(functions) => functions.iter().any(|func| func.name == name),
             #[cfg(feature = "gemini")]
             Tool::GeminiTool(_) => false,
+            #[cfg(feature = "openai")]
+            Tool::OpenAITool(_) => false,
         })
     }
 }
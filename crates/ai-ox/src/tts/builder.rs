@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use super::{TextToSpeech, TtsError};
+
+/// Convenience function to create a Groq TTS provider
+#[cfg(feature = "groq")]
+pub fn groq_tts(model: &str) -> Result<Arc<dyn TextToSpeech>, TtsError> {
+    super::providers::groq::GroqTts::builder()
+        .model(model)
+        .api_key_from_env("GROQ_API_KEY")?
+        .build()
+}
+
+/// Convenience function to create a Groq TTS provider with custom API key
+#[cfg(feature = "groq")]
+pub fn groq_tts_with_key(model: &str, api_key: &str) -> Result<Arc<dyn TextToSpeech>, TtsError> {
+    super::providers::groq::GroqTts::builder()
+        .model(model)
+        .api_key(api_key)
+        .build()
+}
+
+/// Convenience function to create an OpenAI TTS provider
+#[cfg(feature = "openai")]
+pub fn openai_tts(model: &str) -> Result<Arc<dyn TextToSpeech>, TtsError> {
+    super::providers::openai::OpenAITts::builder()
+        .model(model)
+        .api_key_from_env("OPENAI_API_KEY")?
+        .build()
+}
+
+/// Convenience function to create an OpenAI TTS provider with custom API key
+#[cfg(feature = "openai")]
+pub fn openai_tts_with_key(model: &str, api_key: &str) -> Result<Arc<dyn TextToSpeech>, TtsError> {
+    super::providers::openai::OpenAITts::builder()
+        .model(model)
+        .api_key(api_key)
+        .build()
+}
+
+/// Convenience function to create a Gemini TTS provider
+#[cfg(feature = "gemini")]
+pub fn gemini_tts(model: &str) -> Result<Arc<dyn TextToSpeech>, TtsError> {
+    super::providers::gemini::GeminiTts::builder()
+        .model(model)
+        .api_key_from_env("GEMINI_API_KEY")?
+        .build()
+}
+
+/// Convenience function to create a Gemini TTS provider with custom API key
+#[cfg(feature = "gemini")]
+pub fn gemini_tts_with_key(model: &str, api_key: &str) -> Result<Arc<dyn TextToSpeech>, TtsError> {
+    super::providers::gemini::GeminiTts::builder()
+        .model(model)
+        .api_key(api_key)
+        .build()
+}
+
+/// Auto-detect and create the best available TTS provider
+/// Auto-detection preference order:
+/// 1. OpenAI (every output format, streaming)
+/// 2. Gemini (expressive, PCM and WAV only)
+/// 3. Groq (fast)
+pub fn auto_tts() -> Result<Arc<dyn TextToSpeech>, TtsError> {
+    #[cfg(feature = "openai")]
+    if std::env::var("OPENAI_API_KEY").is_ok() {
+        return openai_tts("gpt-4o-mini-tts");
+    }
+
+    #[cfg(feature = "gemini")]
+    if std::env::var("GEMINI_API_KEY").is_ok() {
+        return gemini_tts("gemini-2.5-flash-preview-tts");
+    }
+
+    #[cfg(feature = "groq")]
+    if std::env::var("GROQ_API_KEY").is_ok() {
+        return groq_tts("playai-tts");
+    }
+
+    Err(TtsError::MissingApiKey)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_convenience() {
+        // Test that convenience functions work
+        #[cfg(feature = "openai")]
+        {
+            let result = openai_tts_with_key("tts-1", "test-key");
+            assert!(result.is_ok());
+        }
+    }
+
+    #[test]
+    fn test_auto_detection_returns_error_when_no_keys() {
+        // When no API keys are available, should return MissingApiKey
+        let original_openai = std::env::var("OPENAI_API_KEY");
+        let original_gemini = std::env::var("GEMINI_API_KEY");
+        let original_groq = std::env::var("GROQ_API_KEY");
+
+        // Remove all keys temporarily
+        unsafe {
+            std::env::remove_var("OPENAI_API_KEY");
+            std::env::remove_var("GEMINI_API_KEY");
+            std::env::remove_var("GROQ_API_KEY");
+        }
+
+        let result = auto_tts();
+        assert!(matches!(result, Err(TtsError::MissingApiKey)));
+
+        // Restore original values
+        unsafe {
+            if let Ok(key) = original_openai {
+                std::env::set_var("OPENAI_API_KEY", key);
+            }
+            if let Ok(key) = original_gemini {
+                std::env::set_var("GEMINI_API_KEY", key);
+            }
+            if let Ok(key) = original_groq {
+                std::env::set_var("GROQ_API_KEY", key);
+            }
+        }
+    }
+}
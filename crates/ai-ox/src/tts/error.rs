@@ -0,0 +1,63 @@
+use thiserror::Error;
+
+use ai_ox_common::error::ProviderError;
+
+/// Errors that can occur during text-to-speech operations
+#[derive(Debug, Error)]
+pub enum TtsError {
+    /// Provider-specific error
+    #[error("Provider error: {0}")]
+    Provider(Box<dyn std::error::Error + Send + Sync>),
+
+    /// Unsupported output audio format
+    #[error("Unsupported audio format: {0}")]
+    UnsupportedFormat(String),
+
+    /// Synthesis failed
+    #[error("Synthesis failed: {0}")]
+    SynthesisFailed(String),
+
+    /// Missing API key
+    #[error("Missing API key")]
+    MissingApiKey,
+
+    /// Invalid configuration
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+
+    /// Voice not offered by the provider
+    #[error("Voice not found: {0}")]
+    VoiceNotFound(String),
+
+    /// Network/IO error
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// JSON serialization/deserialization error
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// Invalid audio data returned by the provider
+    #[error("Invalid audio data: {0}")]
+    InvalidAudioData(String),
+
+    /// Provider error
+    #[error("Provider error: {0}")]
+    ProviderError(ProviderError),
+}
+
+// Conversion implementations for provider-specific errors
+
+// Groq and OpenAI both report errors as `ProviderError`
+impl From<ProviderError> for TtsError {
+    fn from(error: ProviderError) -> Self {
+        TtsError::ProviderError(error)
+    }
+}
+
+#[cfg(feature = "gemini")]
+impl From<gemini_ox::GeminiRequestError> for TtsError {
+    fn from(error: gemini_ox::GeminiRequestError) -> Self {
+        TtsError::Provider(Box::new(error))
+    }
+}
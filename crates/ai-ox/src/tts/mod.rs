@@ -0,0 +1,141 @@
+pub mod builder;
+pub mod error;
+pub mod providers;
+pub mod request;
+pub mod response;
+
+pub use error::TtsError;
+pub use request::{SpeechFormat, SynthesisRequest};
+pub use response::{SynthesisResponse, TtsUsage, Voice};
+
+#[cfg(feature = "groq")]
+pub use builder::groq_tts;
+
+#[cfg(feature = "openai")]
+pub use builder::openai_tts;
+
+#[cfg(feature = "gemini")]
+pub use builder::gemini_tts;
+
+use bytes::Bytes;
+use futures_util::{FutureExt, StreamExt, future::BoxFuture, stream::BoxStream};
+use std::sync::Arc;
+
+/// Provider information containing provider name and model identifier
+#[derive(Debug, Clone)]
+pub struct TtsInfo<'a> {
+    pub provider: &'a str,
+    pub model: &'a str,
+}
+
+impl<'a> TtsInfo<'a> {
+    pub fn new(provider: &'a str, model: &'a str) -> Self {
+        Self { provider, model }
+    }
+}
+
+impl<'a> std::fmt::Display for TtsInfo<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.provider, self.model)
+    }
+}
+
+/// The primary trait for Text-to-Speech providers
+///
+/// This trait provides a standardized interface for synthesizing speech across
+/// different TTS providers. It supports both single-shot and streaming synthesis.
+pub trait TextToSpeech: Send + Sync + 'static + std::fmt::Debug {
+    /// Returns the provider information containing provider and model identifier
+    ///
+    /// # Returns
+    ///
+    /// A `TtsInfo` containing the provider and model identifier
+    fn info(&self) -> TtsInfo<'_>;
+
+    /// Returns the model name
+    ///
+    /// # Returns
+    ///
+    /// A string slice containing the model name
+    fn model(&self) -> &str;
+
+    /// Synthesizes speech for the whole of a text
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - A SynthesisRequest containing text and configuration
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the `SynthesisResponse` or a `TtsError`
+    fn synthesize(
+        &self,
+        request: SynthesisRequest,
+    ) -> BoxFuture<'_, Result<SynthesisResponse, TtsError>>;
+
+    /// Synthesizes speech, yielding audio in the requested format as it is
+    /// generated
+    ///
+    /// Formats a provider can't stream, such as WAV whose header needs the
+    /// length of the audio, arrive as one chunk once synthesis is done. The
+    /// default implementation is for providers without native streaming: it
+    /// yields the result of [`synthesize`](Self::synthesize) as one chunk.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - A SynthesisRequest containing text and configuration
+    fn synthesize_stream(
+        &self,
+        request: SynthesisRequest,
+    ) -> BoxStream<'_, Result<Bytes, TtsError>> {
+        self.synthesize(request)
+            .map(|response| response.map(|response| response.audio))
+            .into_stream()
+            .boxed()
+    }
+
+    /// Lists the voices available with this provider's model
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either a list of `Voice` or a `TtsError`
+    fn available_voices(&self) -> BoxFuture<'_, Result<Vec<Voice>, TtsError>>;
+
+    /// Checks if this provider can generate audio in a given format
+    fn supports_format(&self, format: SpeechFormat) -> bool {
+        // Default implementation - providers can override
+        matches!(format, SpeechFormat::Mp3 | SpeechFormat::Wav)
+    }
+}
+
+impl<T: TextToSpeech + ?Sized> TextToSpeech for Arc<T> {
+    fn info(&self) -> TtsInfo<'_> {
+        self.as_ref().info()
+    }
+
+    fn model(&self) -> &str {
+        self.as_ref().model()
+    }
+
+    fn synthesize(
+        &self,
+        request: SynthesisRequest,
+    ) -> BoxFuture<'_, Result<SynthesisResponse, TtsError>> {
+        self.as_ref().synthesize(request)
+    }
+
+    fn synthesize_stream(
+        &self,
+        request: SynthesisRequest,
+    ) -> BoxStream<'_, Result<Bytes, TtsError>> {
+        self.as_ref().synthesize_stream(request)
+    }
+
+    fn available_voices(&self) -> BoxFuture<'_, Result<Vec<Voice>, TtsError>> {
+        self.as_ref().available_voices()
+    }
+
+    fn supports_format(&self, format: SpeechFormat) -> bool {
+        self.as_ref().supports_format(format)
+    }
+}
@@ -0,0 +1,362 @@
+use base64::Engine;
+use bytes::Bytes;
+use futures_util::{FutureExt, StreamExt, future::BoxFuture, stream::BoxStream};
+use std::sync::{Arc, LazyLock};
+
+use crate::stt::chunking::encode_wav;
+use crate::tts::{
+    SpeechFormat, SynthesisRequest, SynthesisResponse, TextToSpeech, TtsError, TtsInfo, TtsUsage,
+    Voice, response::PCM_FORMAT,
+};
+
+use gemini_ox::{
+    content::{Content, Role},
+    generate_content::{
+        GenerationConfig, PrebuiltVoiceConfig, SpeechConfig, VoiceConfig,
+        request::GenerateContentRequest, response::GenerateContentResponse,
+    },
+};
+
+/// Gemini TTS provider implementation using the native audio output of the
+/// TTS models
+///
+/// Gemini generates 24 kHz 16-bit mono PCM, which is returned as is or wrapped
+/// in a WAV file.
+#[derive(Debug, Clone)]
+pub struct GeminiTts {
+    client: gemini_ox::Gemini,
+    model: String,
+}
+
+/// Builder for GeminiTts
+pub struct GeminiTtsBuilder {
+    model: Option<String>,
+    api_key: Option<String>,
+}
+
+impl GeminiTtsBuilder {
+    pub fn new() -> Self {
+        Self {
+            model: None,
+            api_key: None,
+        }
+    }
+
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    pub fn api_key_from_env(mut self, env_var: &str) -> Result<Self, TtsError> {
+        let key = std::env::var(env_var).map_err(|_| TtsError::MissingApiKey)?;
+        self.api_key = Some(key);
+        Ok(self)
+    }
+
+    pub fn build(self) -> Result<Arc<dyn TextToSpeech>, TtsError> {
+        let api_key = self.api_key.ok_or(TtsError::MissingApiKey)?;
+        let model = self
+            .model
+            .unwrap_or_else(|| "gemini-2.5-flash-preview-tts".to_string());
+
+        let client = gemini_ox::Gemini::new(api_key);
+        Ok(Arc::new(GeminiTts::new(client, model)))
+    }
+}
+
+impl Default for GeminiTtsBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GeminiTts {
+    /// Create a new Gemini TTS provider
+    pub fn new(client: gemini_ox::Gemini, model: String) -> Self {
+        Self { client, model }
+    }
+
+    /// Create a builder for GeminiTts
+    pub fn builder() -> GeminiTtsBuilder {
+        GeminiTtsBuilder::new()
+    }
+
+    /// Convert unified request to Gemini format
+    ///
+    /// Gemini takes the delivery, speed included, as a natural-language
+    /// direction ahead of the text.
+    fn convert_request(
+        &self,
+        request: &SynthesisRequest,
+    ) -> Result<GenerateContentRequest, TtsError> {
+        if !self.supports_format(request.format) {
+            return Err(TtsError::UnsupportedFormat(format!(
+                "{:?} is not supported by Gemini",
+                request.format
+            )));
+        }
+
+        let speed = request
+            .speed
+            .filter(|speed| *speed != 1.0)
+            .map(|speed| format!("at {speed} times normal speed"));
+        let direction = match (&request.instructions, speed) {
+            (Some(instructions), Some(speed)) => Some(format!("{instructions}, {speed}")),
+            (Some(instructions), None) => Some(instructions.clone()),
+            (None, Some(speed)) => Some(format!("Say {speed}")),
+            (None, None) => None,
+        };
+        let text = match direction {
+            Some(direction) => format!("{direction}: {}", request.text),
+            None => request.text.clone(),
+        };
+
+        let voice = request.voice.clone().unwrap_or_else(|| "Kore".to_string());
+        let speech_config = SpeechConfig {
+            voice_config: Some(VoiceConfig {
+                prebuilt_voice_config: Some(PrebuiltVoiceConfig {
+                    voice_name: Some(voice),
+                }),
+            }),
+        };
+
+        Ok(GenerateContentRequest::builder()
+            .model(self.model.clone())
+            .content(Content::new(Role::User, [text]))
+            .generation_config(
+                GenerationConfig::builder()
+                    .response_modalities(["AUDIO"])
+                    .speech_config(speech_config)
+                    .build(),
+            )
+            .build())
+    }
+
+    /// Decode the PCM audio in a Gemini response
+    fn response_audio(response: &GenerateContentResponse) -> Result<Vec<u8>, TtsError> {
+        let mut audio = Vec::new();
+        for part in response
+            .candidates
+            .iter()
+            .flat_map(|candidate| candidate.content.parts())
+        {
+            if let Some(blob) = part.as_inline_data() {
+                audio.extend(
+                    base64::engine::general_purpose::STANDARD
+                        .decode(&blob.data)
+                        .map_err(|e| {
+                            TtsError::InvalidAudioData(format!("Invalid base64: {}", e))
+                        })?,
+                );
+            }
+        }
+        Ok(audio)
+    }
+
+    /// Convert Gemini response to unified format
+    fn convert_response(
+        &self,
+        gemini_response: GenerateContentResponse,
+        request: &SynthesisRequest,
+        voice: String,
+    ) -> Result<SynthesisResponse, TtsError> {
+        let pcm = Self::response_audio(&gemini_response)?;
+        if pcm.is_empty() {
+            return Err(TtsError::SynthesisFailed(
+                "Gemini returned no audio".to_string(),
+            ));
+        }
+        let audio = match request.format {
+            SpeechFormat::Wav => encode_wav(PCM_FORMAT, &pcm),
+            _ => pcm,
+        };
+
+        let mut response = SynthesisResponse::new(
+            audio.into(),
+            request.format,
+            voice,
+            "gemini".to_string(),
+            self.model.clone(),
+        );
+        response.usage = TtsUsage::new(request.text.chars().count() as u32);
+        if let Some(usage) = gemini_response.usage_metadata {
+            response.usage.input_tokens = Some(usage.prompt_token_count);
+            response.usage.output_tokens = usage.candidates_token_count;
+        }
+        Ok(response)
+    }
+}
+
+/// Prebuilt Gemini voices with the style Google describes them by
+static GEMINI_VOICES: LazyLock<Vec<Voice>> = LazyLock::new(|| {
+    [
+        ("Zephyr", "Bright"),
+        ("Puck", "Upbeat"),
+        ("Charon", "Informative"),
+        ("Kore", "Firm"),
+        ("Fenrir", "Excitable"),
+        ("Leda", "Youthful"),
+        ("Orus", "Firm"),
+        ("Aoede", "Breezy"),
+        ("Callirrhoe", "Easy-going"),
+        ("Autonoe", "Bright"),
+        ("Enceladus", "Breathy"),
+        ("Iapetus", "Clear"),
+        ("Umbriel", "Easy-going"),
+        ("Algieba", "Smooth"),
+        ("Despina", "Smooth"),
+        ("Erinome", "Clear"),
+        ("Algenib", "Gravelly"),
+        ("Rasalgethi", "Informative"),
+        ("Laomedeia", "Upbeat"),
+        ("Achernar", "Soft"),
+        ("Alnilam", "Firm"),
+        ("Schedar", "Even"),
+        ("Gacrux", "Mature"),
+        ("Pulcherrima", "Forward"),
+        ("Achird", "Friendly"),
+        ("Zubenelgenubi", "Casual"),
+        ("Vindemiatrix", "Gentle"),
+        ("Sadachbia", "Lively"),
+        ("Sadaltager", "Knowledgeable"),
+        ("Sulafat", "Warm"),
+    ]
+    .into_iter()
+    .map(|(name, style)| Voice::new(name, name).with_description(style))
+    .collect()
+});
+
+impl TextToSpeech for GeminiTts {
+    fn info(&self) -> TtsInfo<'_> {
+        TtsInfo::new("gemini", &self.model)
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn synthesize(
+        &self,
+        request: SynthesisRequest,
+    ) -> BoxFuture<'_, Result<SynthesisResponse, TtsError>> {
+        async move {
+            let gemini_request = self.convert_request(&request)?;
+            let gemini_response = gemini_request.send(&self.client).await?;
+            let voice = request.voice.clone().unwrap_or_else(|| "Kore".to_string());
+            self.convert_response(gemini_response, &request, voice)
+        }
+        .boxed()
+    }
+
+    /// Streams PCM as Gemini generates it; WAV needs the length of the audio
+    /// up front, so it is yielded in one chunk once synthesis is done
+    fn synthesize_stream(
+        &self,
+        request: SynthesisRequest,
+    ) -> BoxStream<'_, Result<Bytes, TtsError>> {
+        if request.format != SpeechFormat::Pcm {
+            return self
+                .synthesize(request)
+                .map(|response| response.map(|response| response.audio))
+                .into_stream()
+                .boxed();
+        }
+        match self.convert_request(&request) {
+            Ok(gemini_request) => gemini_request
+                .stream(&self.client)
+                .map(|response| Ok(Bytes::from(Self::response_audio(&response?)?)))
+                .filter(|chunk| {
+                    futures_util::future::ready(!matches!(chunk, Ok(audio) if audio.is_empty()))
+                })
+                .boxed(),
+            Err(error) => futures_util::stream::once(async move { Err(error) }).boxed(),
+        }
+    }
+
+    fn available_voices(&self) -> BoxFuture<'_, Result<Vec<Voice>, TtsError>> {
+        async move { Ok(GEMINI_VOICES.clone()) }.boxed()
+    }
+
+    fn supports_format(&self, format: SpeechFormat) -> bool {
+        matches!(format, SpeechFormat::Pcm | SpeechFormat::Wav)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider() -> GeminiTts {
+        GeminiTts::new(
+            gemini_ox::Gemini::new("test-key"),
+            "gemini-2.5-flash-preview-tts".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_request_conversion() {
+        let request = SynthesisRequest::builder()
+            .text("Have a wonderful day!")
+            .voice("Puck")
+            .instructions("Say cheerfully")
+            .speed(1.5)
+            .build();
+        let converted = provider().convert_request(&request).unwrap();
+
+        let text = converted.contents[0].parts()[0]
+            .as_text()
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            text,
+            "Say cheerfully, at 1.5 times normal speed: Have a wonderful day!"
+        );
+        let config = converted.generation_config.unwrap();
+        assert_eq!(config.response_modalities, Some(vec!["AUDIO".to_string()]));
+        let voice = config.speech_config.unwrap().voice_config.unwrap();
+        assert_eq!(
+            voice.prebuilt_voice_config.unwrap().voice_name.as_deref(),
+            Some("Puck")
+        );
+
+        let mp3 = SynthesisRequest::builder()
+            .text("Hi")
+            .format(SpeechFormat::Mp3)
+            .build();
+        assert!(matches!(
+            provider().convert_request(&mp3),
+            Err(TtsError::UnsupportedFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_response_is_wrapped_in_wav() {
+        let pcm = vec![0u8; 4800];
+        let gemini_response = serde_json::from_value(serde_json::json!({
+            "candidates": [{"content": {"role": "model", "parts": [{"inlineData": {
+                "mimeType": "audio/L16;codec=pcm;rate=24000",
+                "data": base64::engine::general_purpose::STANDARD.encode(&pcm),
+            }}]}}],
+            "usageMetadata": {"promptTokenCount": 9, "candidatesTokenCount": 3, "totalTokenCount": 12}
+        }))
+        .unwrap();
+        let request = SynthesisRequest::builder().text("Hi").build();
+
+        let response = provider()
+            .convert_response(gemini_response, &request, "Kore".to_string())
+            .unwrap();
+
+        assert!(response.audio.starts_with(b"RIFF"));
+        assert_eq!(response.audio.len(), 44 + pcm.len());
+        assert_eq!(
+            response.duration,
+            Some(std::time::Duration::from_millis(100))
+        );
+        assert_eq!(response.usage.output_tokens, Some(3));
+    }
+}
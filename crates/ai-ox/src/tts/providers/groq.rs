@@ -0,0 +1,209 @@
+use futures_util::{FutureExt, future::BoxFuture};
+use std::sync::{Arc, LazyLock};
+
+use crate::tts::{
+    SpeechFormat, SynthesisRequest, SynthesisResponse, TextToSpeech, TtsError, TtsInfo, TtsUsage,
+    Voice,
+};
+
+use groq_ox::audio::speech::{AudioFormat as GroqAudioFormat, SpeechRequest};
+
+/// Groq TTS provider implementation using PlayAI voices
+#[derive(Debug, Clone)]
+pub struct GroqTts {
+    client: groq_ox::Groq,
+    model: String,
+}
+
+/// Builder for GroqTts
+pub struct GroqTtsBuilder {
+    model: Option<String>,
+    api_key: Option<String>,
+}
+
+impl GroqTtsBuilder {
+    pub fn new() -> Self {
+        Self {
+            model: None,
+            api_key: None,
+        }
+    }
+
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    pub fn api_key_from_env(mut self, env_var: &str) -> Result<Self, TtsError> {
+        let key = std::env::var(env_var).map_err(|_| TtsError::MissingApiKey)?;
+        self.api_key = Some(key);
+        Ok(self)
+    }
+
+    pub fn build(self) -> Result<Arc<dyn TextToSpeech>, TtsError> {
+        let api_key = self.api_key.ok_or(TtsError::MissingApiKey)?;
+        let model = self.model.unwrap_or_else(|| "playai-tts".to_string());
+
+        let client = groq_ox::Groq::new(&api_key);
+        Ok(Arc::new(GroqTts::new(client, model)))
+    }
+}
+
+impl Default for GroqTtsBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GroqTts {
+    /// Create a new Groq TTS provider
+    pub fn new(client: groq_ox::Groq, model: String) -> Self {
+        Self { client, model }
+    }
+
+    /// Create a builder for GroqTts
+    pub fn builder() -> GroqTtsBuilder {
+        GroqTtsBuilder::new()
+    }
+
+    /// Voices spoken by this provider's model
+    fn voices(&self) -> &'static [Voice] {
+        if self.model.ends_with("-arabic") {
+            &GROQ_ARABIC_VOICES
+        } else {
+            &GROQ_ENGLISH_VOICES
+        }
+    }
+
+    /// Convert unified request to Groq format
+    fn convert_request(&self, request: SynthesisRequest) -> Result<SpeechRequest, TtsError> {
+        let response_format = match request.format {
+            SpeechFormat::Mp3 => GroqAudioFormat::Mp3,
+            SpeechFormat::Flac => GroqAudioFormat::Flac,
+            SpeechFormat::Wav => GroqAudioFormat::Wav,
+            format => {
+                return Err(TtsError::UnsupportedFormat(format!(
+                    "{format:?} is not supported by Groq"
+                )));
+            }
+        };
+        if request.instructions.is_some() {
+            return Err(TtsError::InvalidConfig(
+                "Instructions are not supported by Groq".to_string(),
+            ));
+        }
+        let voice = request.voice.unwrap_or_else(|| self.voices()[0].id.clone());
+
+        Ok(SpeechRequest {
+            model: self.model.clone(),
+            input: request.text,
+            voice,
+            response_format: Some(response_format),
+            speed: request.speed,
+        })
+    }
+}
+
+/// Formats Groq can generate
+const GROQ_FORMATS: [SpeechFormat; 3] = [SpeechFormat::Wav, SpeechFormat::Mp3, SpeechFormat::Flac];
+
+/// Voices of `playai-tts`
+static GROQ_ENGLISH_VOICES: LazyLock<Vec<Voice>> = LazyLock::new(|| {
+    [
+        "Fritz", "Arista", "Atlas", "Basil", "Briggs", "Calum", "Celeste", "Cheyenne", "Chip",
+        "Cillian", "Deedee", "Gail", "Indigo", "Mamaw", "Mason", "Mikail", "Mitch", "Quinn",
+        "Thunder",
+    ]
+    .into_iter()
+    .map(|name| Voice::new(format!("{name}-PlayAI"), name).with_languages(["en"]))
+    .collect()
+});
+
+/// Voices of `playai-tts-arabic`
+static GROQ_ARABIC_VOICES: LazyLock<Vec<Voice>> = LazyLock::new(|| {
+    ["Ahmad", "Amira", "Khalid", "Nasser"]
+        .into_iter()
+        .map(|name| Voice::new(format!("{name}-PlayAI"), name).with_languages(["ar"]))
+        .collect()
+});
+
+impl TextToSpeech for GroqTts {
+    fn info(&self) -> TtsInfo<'_> {
+        TtsInfo::new("groq", &self.model)
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn synthesize(
+        &self,
+        request: SynthesisRequest,
+    ) -> BoxFuture<'_, Result<SynthesisResponse, TtsError>> {
+        async move {
+            let format = request.format;
+            let characters = request.text.chars().count() as u32;
+            let groq_request = self.convert_request(request)?;
+            let groq_response = self.client.speech(&groq_request).await?;
+
+            let mut response = SynthesisResponse::new(
+                groq_response.audio.into(),
+                format,
+                groq_request.voice,
+                "groq".to_string(),
+                self.model.clone(),
+            );
+            response.usage = TtsUsage::new(characters);
+            Ok(response)
+        }
+        .boxed()
+    }
+
+    fn available_voices(&self) -> BoxFuture<'_, Result<Vec<Voice>, TtsError>> {
+        async move { Ok(self.voices().to_vec()) }.boxed()
+    }
+
+    fn supports_format(&self, format: SpeechFormat) -> bool {
+        GROQ_FORMATS.contains(&format)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(model: &str) -> GroqTts {
+        GroqTts::new(groq_ox::Groq::new("test-key"), model.to_string())
+    }
+
+    #[test]
+    fn test_request_conversion() {
+        let request = SynthesisRequest::builder().text("Hello").build();
+        let converted = provider("playai-tts").convert_request(request).unwrap();
+        assert_eq!(converted.voice, "Fritz-PlayAI");
+        assert!(matches!(
+            converted.response_format,
+            Some(GroqAudioFormat::Wav)
+        ));
+
+        let request = SynthesisRequest::builder()
+            .text("مرحبا")
+            .format(SpeechFormat::Pcm)
+            .build();
+        assert!(matches!(
+            provider("playai-tts-arabic").convert_request(request),
+            Err(TtsError::UnsupportedFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_voices_follow_model() {
+        assert_eq!(provider("playai-tts-arabic").voices()[0].id, "Ahmad-PlayAI");
+        assert_eq!(provider("playai-tts").voices().len(), 19);
+    }
+}
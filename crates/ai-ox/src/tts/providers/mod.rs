@@ -0,0 +1,8 @@
+#[cfg(feature = "groq")]
+pub mod groq;
+
+#[cfg(feature = "openai")]
+pub mod openai;
+
+#[cfg(feature = "gemini")]
+pub mod gemini;
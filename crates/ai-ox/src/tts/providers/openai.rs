@@ -0,0 +1,222 @@
+use bytes::Bytes;
+use futures_util::{FutureExt, StreamExt, future::BoxFuture, stream::BoxStream};
+use std::sync::Arc;
+
+use crate::tts::{
+    SpeechFormat, SynthesisRequest, SynthesisResponse, TextToSpeech, TtsError, TtsInfo, TtsUsage,
+    Voice,
+};
+
+/// OpenAI TTS provider implementation
+#[derive(Debug, Clone)]
+pub struct OpenAITts {
+    client: openai_ox::OpenAI,
+    model: String,
+}
+
+/// Builder for OpenAITts
+pub struct OpenAITtsBuilder {
+    model: Option<String>,
+    api_key: Option<String>,
+}
+
+impl OpenAITtsBuilder {
+    pub fn new() -> Self {
+        Self {
+            model: None,
+            api_key: None,
+        }
+    }
+
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    pub fn api_key_from_env(mut self, env_var: &str) -> Result<Self, TtsError> {
+        let key = std::env::var(env_var).map_err(|_| TtsError::MissingApiKey)?;
+        self.api_key = Some(key);
+        Ok(self)
+    }
+
+    pub fn build(self) -> Result<Arc<dyn TextToSpeech>, TtsError> {
+        let api_key = self.api_key.ok_or(TtsError::MissingApiKey)?;
+        let model = self.model.unwrap_or_else(|| "gpt-4o-mini-tts".to_string());
+
+        let client = openai_ox::OpenAI::new(api_key);
+        Ok(Arc::new(OpenAITts::new(client, model)))
+    }
+}
+
+impl Default for OpenAITtsBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OpenAITts {
+    /// Create a new OpenAI TTS provider
+    pub fn new(client: openai_ox::OpenAI, model: String) -> Self {
+        Self { client, model }
+    }
+
+    /// Create a builder for OpenAITts
+    pub fn builder() -> OpenAITtsBuilder {
+        OpenAITtsBuilder::new()
+    }
+
+    /// Whether this is one of the older models, without the newer voices or
+    /// instructions
+    fn is_legacy_model(&self) -> bool {
+        self.model.starts_with("tts-1")
+    }
+
+    /// Convert unified request to OpenAI format
+    fn convert_request(
+        &self,
+        request: SynthesisRequest,
+    ) -> Result<openai_ox::SpeechRequest, TtsError> {
+        if request.instructions.is_some() && self.is_legacy_model() {
+            return Err(TtsError::InvalidConfig(format!(
+                "Instructions are not supported by {}",
+                self.model
+            )));
+        }
+
+        Ok(openai_ox::SpeechRequest {
+            model: self.model.clone(),
+            input: request.text,
+            voice: request.voice.unwrap_or_else(|| "alloy".to_string()),
+            instructions: request.instructions,
+            response_format: Some(request.format.extension().to_string()),
+            speed: request.speed,
+        })
+    }
+}
+
+/// Voices of every OpenAI TTS model
+const OPENAI_VOICES: [&str; 9] = [
+    "alloy", "ash", "coral", "echo", "fable", "nova", "onyx", "sage", "shimmer",
+];
+
+/// Voices only spoken by `gpt-4o-mini-tts`
+const OPENAI_NEW_VOICES: [&str; 4] = ["ballad", "verse", "marin", "cedar"];
+
+impl TextToSpeech for OpenAITts {
+    fn info(&self) -> TtsInfo<'_> {
+        TtsInfo::new("openai", &self.model)
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn synthesize(
+        &self,
+        request: SynthesisRequest,
+    ) -> BoxFuture<'_, Result<SynthesisResponse, TtsError>> {
+        async move {
+            let format = request.format;
+            let characters = request.text.chars().count() as u32;
+            let openai_request = self.convert_request(request)?;
+            let audio = self.client.create_speech(&openai_request).await?;
+
+            let mut response = SynthesisResponse::new(
+                audio,
+                format,
+                openai_request.voice,
+                "openai".to_string(),
+                self.model.clone(),
+            );
+            response.usage = TtsUsage::new(characters);
+            Ok(response)
+        }
+        .boxed()
+    }
+
+    fn synthesize_stream(
+        &self,
+        request: SynthesisRequest,
+    ) -> BoxStream<'_, Result<Bytes, TtsError>> {
+        match self.convert_request(request) {
+            Ok(openai_request) => self
+                .client
+                .stream_speech(&openai_request)
+                .map(|chunk| chunk.map_err(TtsError::from))
+                .boxed(),
+            Err(error) => futures_util::stream::once(async move { Err(error) }).boxed(),
+        }
+    }
+
+    fn available_voices(&self) -> BoxFuture<'_, Result<Vec<Voice>, TtsError>> {
+        async move {
+            let new_voices = if self.is_legacy_model() {
+                &[][..]
+            } else {
+                &OPENAI_NEW_VOICES[..]
+            };
+            Ok(OPENAI_VOICES
+                .iter()
+                .chain(new_voices)
+                .map(|id| {
+                    let mut name = id.to_string();
+                    name[..1].make_ascii_uppercase();
+                    Voice::new(*id, name)
+                })
+                .collect())
+        }
+        .boxed()
+    }
+
+    fn supports_format(&self, _format: SpeechFormat) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(model: &str) -> OpenAITts {
+        OpenAITts::new(openai_ox::OpenAI::new("test-key"), model.to_string())
+    }
+
+    #[test]
+    fn test_request_conversion() {
+        let request = SynthesisRequest::builder()
+            .text("Hello")
+            .format(SpeechFormat::Opus)
+            .speed(1.25)
+            .instructions("Whisper it")
+            .build();
+        let converted = provider("gpt-4o-mini-tts")
+            .convert_request(request.clone())
+            .unwrap();
+
+        assert_eq!(converted.voice, "alloy");
+        assert_eq!(converted.response_format.as_deref(), Some("opus"));
+        assert_eq!(converted.instructions.as_deref(), Some("Whisper it"));
+        assert!(matches!(
+            provider("tts-1-hd").convert_request(request),
+            Err(TtsError::InvalidConfig(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_voices_follow_model() {
+        let voices = provider("tts-1").available_voices().await.unwrap();
+        assert_eq!(voices.len(), 9);
+        assert_eq!(voices[0], Voice::new("alloy", "Alloy"));
+
+        let voices = provider("gpt-4o-mini-tts")
+            .available_voices()
+            .await
+            .unwrap();
+        assert!(voices.iter().any(|voice| voice.id == "ballad"));
+    }
+}
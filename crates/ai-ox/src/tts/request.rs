@@ -0,0 +1,68 @@
+use bon::Builder;
+use serde::{Deserialize, Serialize};
+
+/// Request for speech synthesis
+#[derive(Debug, Clone, Builder)]
+pub struct SynthesisRequest {
+    /// Text to speak
+    #[builder(into)]
+    pub text: String,
+
+    /// Voice to speak with - None for the provider's default voice
+    #[builder(into)]
+    pub voice: Option<String>,
+
+    /// Format of the generated audio
+    #[builder(default = SpeechFormat::Wav)]
+    pub format: SpeechFormat,
+
+    /// Speaking rate, 1.0 being normal speed
+    pub speed: Option<f32>,
+
+    /// Instructions for the tone and delivery, for models that take them
+    #[builder(into)]
+    pub instructions: Option<String>,
+}
+
+/// Format of synthesized audio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpeechFormat {
+    Mp3,
+    Opus,
+    Aac,
+    Flac,
+    Wav,
+    /// Raw 16-bit signed little-endian mono samples at
+    /// [`PCM_SAMPLE_RATE`](Self::PCM_SAMPLE_RATE)
+    Pcm,
+}
+
+impl SpeechFormat {
+    /// Sample rate of [`SpeechFormat::Pcm`] audio
+    pub const PCM_SAMPLE_RATE: u32 = 24_000;
+
+    /// Get the MIME type for this format
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            SpeechFormat::Mp3 => "audio/mpeg",
+            SpeechFormat::Opus => "audio/ogg",
+            SpeechFormat::Aac => "audio/aac",
+            SpeechFormat::Flac => "audio/flac",
+            SpeechFormat::Wav => "audio/wav",
+            SpeechFormat::Pcm => "audio/pcm",
+        }
+    }
+
+    /// Get the file extension for this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SpeechFormat::Mp3 => "mp3",
+            SpeechFormat::Opus => "opus",
+            SpeechFormat::Aac => "aac",
+            SpeechFormat::Flac => "flac",
+            SpeechFormat::Wav => "wav",
+            SpeechFormat::Pcm => "pcm",
+        }
+    }
+}
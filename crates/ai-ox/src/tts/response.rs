@@ -0,0 +1,160 @@
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use super::SpeechFormat;
+use crate::stt::chunking::{PcmFormat, parse_wav_header};
+use crate::stt::streaming::AudioEncoding;
+
+/// Response from speech synthesis
+#[derive(Debug, Clone)]
+pub struct SynthesisResponse {
+    /// Generated audio
+    pub audio: Bytes,
+
+    /// Format of the generated audio
+    pub format: SpeechFormat,
+
+    /// Length of the generated audio, if it can be told without decoding it
+    pub duration: Option<Duration>,
+
+    /// Voice the audio was spoken with
+    pub voice: String,
+
+    /// Provider name for reference
+    pub provider: String,
+
+    /// Model used for synthesis
+    pub model: String,
+
+    /// Usage statistics
+    pub usage: TtsUsage,
+
+    /// Additional provider-specific metadata
+    pub metadata: serde_json::Value,
+}
+
+impl SynthesisResponse {
+    /// Create a response for `audio`, working out its duration for
+    /// uncompressed formats
+    pub fn new(
+        audio: Bytes,
+        format: SpeechFormat,
+        voice: String,
+        provider: String,
+        model: String,
+    ) -> Self {
+        Self {
+            duration: uncompressed_duration(&audio, format),
+            audio,
+            format,
+            voice,
+            provider,
+            model,
+            usage: TtsUsage::default(),
+            metadata: serde_json::Value::Null,
+        }
+    }
+}
+
+/// Usage statistics for speech synthesis
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TtsUsage {
+    /// Number of characters synthesized
+    pub characters: u32,
+    /// Input tokens, for token-billed models
+    pub input_tokens: Option<u64>,
+    /// Output tokens, for token-billed models
+    pub output_tokens: Option<u64>,
+}
+
+impl TtsUsage {
+    pub fn new(characters: u32) -> Self {
+        Self {
+            characters,
+            ..Self::default()
+        }
+    }
+}
+
+/// Voice offered by a TTS provider
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Voice {
+    /// Voice identifier, as passed in [`SynthesisRequest::voice`](super::SynthesisRequest::voice)
+    pub id: String,
+    /// Human-readable name
+    pub name: String,
+    /// Short description of how the voice sounds
+    pub description: Option<String>,
+    /// Languages the voice speaks (ISO-639-1 codes), empty if not restricted
+    #[serde(default)]
+    pub languages: Vec<String>,
+}
+
+impl Voice {
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            description: None,
+            languages: Vec::new(),
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn with_languages(
+        mut self,
+        languages: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.languages = languages.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// Layout of [`SpeechFormat::Pcm`] audio
+pub(crate) const PCM_FORMAT: PcmFormat = PcmFormat {
+    sample_rate: SpeechFormat::PCM_SAMPLE_RATE,
+    channels: 1,
+    encoding: AudioEncoding::Linear16,
+};
+
+/// Duration of PCM or WAV audio, None for compressed formats
+fn uncompressed_duration(audio: &[u8], format: SpeechFormat) -> Option<Duration> {
+    let (pcm, samples) = match format {
+        SpeechFormat::Pcm => (PCM_FORMAT, audio),
+        SpeechFormat::Wav => {
+            let (pcm, start) = parse_wav_header(audio).ok()??;
+            (pcm, &audio[start..])
+        }
+        _ => return None,
+    };
+    Some(pcm.duration_of((samples.len() / pcm.frame_size()) as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stt::chunking::encode_wav;
+
+    #[test]
+    fn test_duration_of_uncompressed_audio() {
+        let pcm = Bytes::from(vec![0u8; 48_000]);
+        let duration = |audio: Bytes, format| {
+            SynthesisResponse::new(audio, format, "v".into(), "p".into(), "m".into()).duration
+        };
+
+        assert_eq!(
+            duration(pcm.clone(), SpeechFormat::Pcm),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            duration(encode_wav(PCM_FORMAT, &pcm).into(), SpeechFormat::Wav),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(duration(pcm, SpeechFormat::Mp3), None);
+    }
+}
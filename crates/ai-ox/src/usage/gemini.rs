@@ -8,9 +8,12 @@ impl From<UsageMetadata> for Usage {
         // The prompt_token_count in gemini is a u32, not an option.
         input_tokens_by_modality.insert(Modality::Text, meta.prompt_token_count as u64);
 
+        // Gemini reports thought tokens separately, ai-ox counts them as output.
         let mut output_tokens_by_modality = std::collections::HashMap::new();
-        if let Some(tokens) = meta.candidates_token_count {
-            output_tokens_by_modality.insert(Modality::Text, tokens as u64);
+        if meta.candidates_token_count.is_some() || meta.thoughts_token_count.is_some() {
+            let tokens =
+                meta.candidates_token_count.unwrap_or(0) + meta.thoughts_token_count.unwrap_or(0);
+            output_tokens_by_modality.insert(Modality::Text, tokens);
         }
 
         let mut cache_tokens_by_modality = std::collections::HashMap::new();
@@ -40,3 +43,29 @@ impl From<UsageMetadata> for Usage {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::usage::ModelPrice;
+
+    #[test]
+    fn test_thought_tokens_count_as_output() {
+        let usage = Usage::from(UsageMetadata {
+            prompt_token_count: 1_000_000,
+            cached_content_token_count: Some(250_000),
+            candidates_token_count: Some(40_000),
+            thoughts_token_count: Some(60_000),
+            ..Default::default()
+        });
+        assert_eq!(usage.output_tokens(), 100_000);
+
+        let price = ModelPrice::new(0.3, 2.5).with_cache_read(0.075);
+        let cost = price.cost(&usage);
+
+        assert!((cost.input - 0.75 * 0.3).abs() < 1e-9);
+        assert!((cost.cache_read - 0.25 * 0.075).abs() < 1e-9);
+        assert!((cost.output - 0.04 * 2.5).abs() < 1e-9);
+        assert!((cost.reasoning - 0.06 * 2.5).abs() < 1e-9);
+    }
+}
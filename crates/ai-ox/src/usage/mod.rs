@@ -1,5 +1,8 @@
 #[cfg(feature = "gemini")]
 pub mod gemini;
+pub mod pricing;
+
+pub use pricing::{CostBreakdown, ModelPrice, PriceTable, PricingError};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -34,6 +37,11 @@ fn add_modality_maps(lhs: &mut HashMap<Modality, u64>, rhs: &HashMap<Modality, u
 /// Simple, clean usage tracking for AI model interactions.
 ///
 /// Following the principle: store only essential data, calculate the rest.
+///
+/// Every provider conversion reports usage in the same shape: input tokens
+/// include the tokens read from or written to the prompt cache, and output
+/// tokens include the reasoning tokens. The cache and thought counts break
+/// those totals down rather than adding to them.
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Usage {
     /// Number of requests made to the model
@@ -54,13 +62,13 @@ pub struct Usage {
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     #[serde(default)]
     pub tool_tokens_by_modality: HashMap<Modality, u64>,
-    /// Tokens from cached content (when caching is used)
+    /// Input tokens read from the prompt cache (when caching is used)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cache_read_tokens: Option<u64>,
-    /// Tokens from cache creation (when content is first cached)
+    /// Input tokens written to the prompt cache (when content is first cached)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cache_creation_tokens: Option<u64>,
-    /// Thoughts tokens (for thinking models)
+    /// Output tokens spent on reasoning (for thinking models)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub thoughts_tokens: Option<u64>,
     /// Additional provider-specific usage details
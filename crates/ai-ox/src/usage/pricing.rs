@@ -0,0 +1,382 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::{Add, AddAssign};
+use std::path::Path;
+use thiserror::Error;
+
+use super::{Modality, Usage};
+
+/// Number of tokens a rate in a [`ModelPrice`] is quoted for.
+const TOKENS_PER_RATE: f64 = 1_000_000.0;
+
+/// Errors that can occur when loading a [`PriceTable`].
+#[derive(Debug, Error)]
+pub enum PricingError {
+    /// The price table file could not be read.
+    #[error("Failed to read price table: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The price table is not valid JSON.
+    #[error("Invalid JSON price table: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// The price table is not valid TOML.
+    #[cfg(feature = "toml")]
+    #[error("Invalid TOML price table: {0}")]
+    Toml(#[from] toml::de::Error),
+
+    /// The price table file has an extension that cannot be parsed.
+    #[error("Unsupported price table format: {0}")]
+    UnsupportedFormat(String),
+}
+
+/// Rates for a single model, quoted per million tokens.
+///
+/// Rates are in whatever currency the table is written in. Optional rates fall
+/// back to the base input or output rate when unset, and per-modality rates
+/// override the base rate for tokens of that modality.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    /// Rate for input tokens.
+    pub input: f64,
+    /// Rate for output tokens.
+    pub output: f64,
+    /// Rate for tokens read from the prompt cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_read: Option<f64>,
+    /// Rate for tokens written to the prompt cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write: Option<f64>,
+    /// Rate for reasoning (thought) tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<f64>,
+    /// Input rates for specific modalities.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub input_by_modality: HashMap<Modality, f64>,
+    /// Output rates for specific modalities.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub output_by_modality: HashMap<Modality, f64>,
+}
+
+impl ModelPrice {
+    /// Creates a price with the given input and output rates.
+    pub fn new(input: f64, output: f64) -> Self {
+        Self {
+            input,
+            output,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the rate for tokens read from the prompt cache.
+    pub fn with_cache_read(mut self, rate: f64) -> Self {
+        self.cache_read = Some(rate);
+        self
+    }
+
+    /// Sets the rate for tokens written to the prompt cache.
+    pub fn with_cache_write(mut self, rate: f64) -> Self {
+        self.cache_write = Some(rate);
+        self
+    }
+
+    /// Sets the rate for reasoning tokens.
+    pub fn with_reasoning(mut self, rate: f64) -> Self {
+        self.reasoning = Some(rate);
+        self
+    }
+
+    /// Sets the input rate for a modality.
+    pub fn with_input_modality(mut self, modality: Modality, rate: f64) -> Self {
+        self.input_by_modality.insert(modality, rate);
+        self
+    }
+
+    /// Sets the output rate for a modality.
+    pub fn with_output_modality(mut self, modality: Modality, rate: f64) -> Self {
+        self.output_by_modality.insert(modality, rate);
+        self
+    }
+
+    /// Calculates the cost of `usage` at these rates.
+    ///
+    /// Following the [`Usage`] convention, cached tokens are part of the text
+    /// input tokens and reasoning tokens part of the text output tokens. They
+    /// are billed at the cache and reasoning rates instead of the base rates.
+    pub fn cost(&self, usage: &Usage) -> CostBreakdown {
+        let cache_read = usage.cache_read_tokens.unwrap_or(0);
+        let cache_write = usage.cache_creation_tokens.unwrap_or(0);
+        let cached = cache_read + cache_write;
+        let thoughts = usage.thoughts_tokens.unwrap_or(0);
+
+        let input = usage
+            .input_tokens_by_modality
+            .iter()
+            .map(|(modality, tokens)| {
+                let tokens = match modality {
+                    Modality::Text => tokens.saturating_sub(cached),
+                    _ => *tokens,
+                };
+                priced(
+                    tokens,
+                    rate_for(&self.input_by_modality, modality, self.input),
+                )
+            })
+            .sum();
+        let output = usage
+            .output_tokens_by_modality
+            .iter()
+            .map(|(modality, tokens)| {
+                let tokens = match modality {
+                    Modality::Text => tokens.saturating_sub(thoughts),
+                    _ => *tokens,
+                };
+                priced(
+                    tokens,
+                    rate_for(&self.output_by_modality, modality, self.output),
+                )
+            })
+            .sum();
+
+        CostBreakdown {
+            input,
+            output,
+            reasoning: priced(thoughts, self.reasoning.unwrap_or(self.output)),
+            cache_read: priced(cache_read, self.cache_read.unwrap_or(self.input)),
+            cache_write: priced(cache_write, self.cache_write.unwrap_or(self.input)),
+        }
+    }
+}
+
+fn rate_for(rates: &HashMap<Modality, f64>, modality: &Modality, default: f64) -> f64 {
+    rates.get(modality).copied().unwrap_or(default)
+}
+
+fn priced(tokens: u64, rate: f64) -> f64 {
+    tokens as f64 * rate / TOKENS_PER_RATE
+}
+
+/// An itemized cost calculated by [`Usage::cost`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CostBreakdown {
+    /// Cost of uncached input tokens.
+    pub input: f64,
+    /// Cost of output tokens other than reasoning tokens.
+    pub output: f64,
+    /// Cost of reasoning tokens.
+    pub reasoning: f64,
+    /// Cost of tokens read from the prompt cache.
+    pub cache_read: f64,
+    /// Cost of tokens written to the prompt cache.
+    pub cache_write: f64,
+}
+
+impl CostBreakdown {
+    /// Returns the sum of all items.
+    pub fn total(&self) -> f64 {
+        self.input + self.output + self.reasoning + self.cache_read + self.cache_write
+    }
+}
+
+impl Add for CostBreakdown {
+    type Output = CostBreakdown;
+
+    fn add(mut self, other: CostBreakdown) -> CostBreakdown {
+        self += other;
+        self
+    }
+}
+
+impl AddAssign for CostBreakdown {
+    fn add_assign(&mut self, rhs: Self) {
+        self.input += rhs.input;
+        self.output += rhs.output;
+        self.reasoning += rhs.reasoning;
+        self.cache_read += rhs.cache_read;
+        self.cache_write += rhs.cache_write;
+    }
+}
+
+/// Model prices keyed by provider and model name.
+///
+/// Providers are named as in [`ModelResponse::vendor_name`](crate::ModelResponse),
+/// e.g. `"openai"` or `"anthropic"`. Tables serialize as a map of providers to
+/// maps of models:
+///
+/// ```json
+/// { "openai": { "gpt-4o": { "input": 2.5, "output": 10.0, "cache_read": 1.25 } } }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PriceTable {
+    providers: HashMap<String, HashMap<String, ModelPrice>>,
+}
+
+impl PriceTable {
+    /// Creates an empty price table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds or replaces the price of a model.
+    pub fn insert(
+        &mut self,
+        provider: impl Into<String>,
+        model: impl Into<String>,
+        price: ModelPrice,
+    ) {
+        self.providers
+            .entry(provider.into())
+            .or_default()
+            .insert(model.into(), price);
+    }
+
+    /// Adds or replaces the price of a model, returning the table.
+    pub fn with_price(
+        mut self,
+        provider: impl Into<String>,
+        model: impl Into<String>,
+        price: ModelPrice,
+    ) -> Self {
+        self.insert(provider, model, price);
+        self
+    }
+
+    /// Returns the price of a model if the table has one.
+    pub fn get(&self, provider: &str, model: &str) -> Option<&ModelPrice> {
+        self.providers.get(provider)?.get(model)
+    }
+
+    /// Parses a price table from JSON.
+    pub fn from_json(json: &str) -> Result<Self, PricingError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Parses a price table from TOML.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(toml: &str) -> Result<Self, PricingError> {
+        Ok(toml::from_str(toml)?)
+    }
+
+    /// Loads a price table from a `.json` or, with the `toml` feature, a
+    /// `.toml` file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PricingError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)?;
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => Self::from_json(&contents),
+            #[cfg(feature = "toml")]
+            Some("toml") => Self::from_toml(&contents),
+            _ => Err(PricingError::UnsupportedFormat(path.display().to_string())),
+        }
+    }
+}
+
+impl Usage {
+    /// Calculates the cost of this usage for a model in `prices`.
+    ///
+    /// Returns `None` if the table has no price for the model.
+    pub fn cost(&self, prices: &PriceTable, provider: &str, model: &str) -> Option<CostBreakdown> {
+        prices.get(provider, model).map(|price| price.cost(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    fn usage() -> Usage {
+        let mut usage = Usage::new();
+        usage.requests = 1;
+        usage
+            .input_tokens_by_modality
+            .insert(Modality::Text, 1_000_000);
+        usage
+            .input_tokens_by_modality
+            .insert(Modality::Image, 1_000);
+        usage
+            .output_tokens_by_modality
+            .insert(Modality::Text, 100_000);
+        usage.cache_read_tokens = Some(200_000);
+        usage.thoughts_tokens = Some(10_000);
+        usage
+    }
+
+    #[test]
+    fn test_itemized_cost() {
+        let prices = PriceTable::new().with_price(
+            "openai",
+            "gpt-4o",
+            ModelPrice::new(2.0, 10.0)
+                .with_cache_read(0.5)
+                .with_reasoning(20.0)
+                .with_input_modality(Modality::Image, 5.0),
+        );
+
+        let cost = usage().cost(&prices, "openai", "gpt-4o").unwrap();
+
+        assert_close(cost.input, 0.8 * 2.0 + 0.001 * 5.0);
+        assert_close(cost.output, 0.09 * 10.0);
+        assert_close(cost.reasoning, 0.01 * 20.0);
+        assert_close(cost.cache_read, 0.2 * 0.5);
+        assert_close(cost.cache_write, 0.0);
+        assert_close(cost.total(), 1.605 + 0.9 + 0.2 + 0.1);
+        assert!(usage().cost(&prices, "openai", "gpt-5").is_none());
+    }
+
+    #[test]
+    fn test_unset_rates_fall_back() {
+        let cost = ModelPrice::new(1.0, 4.0).cost(&usage());
+
+        assert_close(cost.input, 0.8 + 0.001);
+        assert_close(cost.reasoning, 0.01 * 4.0);
+        assert_close(cost.cache_read, 0.2);
+    }
+
+    #[test]
+    fn test_price_table_from_json() {
+        let prices = PriceTable::from_json(
+            r#"{
+                "anthropic": {
+                    "claude-sonnet-4": {
+                        "input": 3.0,
+                        "output": 15.0,
+                        "cache_read": 0.3,
+                        "cache_write": 3.75,
+                        "input_by_modality": { "image": 4.0 }
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let price = prices.get("anthropic", "claude-sonnet-4").unwrap();
+        assert_eq!(price.cache_write, Some(3.75));
+        assert_eq!(price.input_by_modality[&Modality::Image], 4.0);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_price_table_from_toml() {
+        let prices = PriceTable::from_toml(
+            r#"
+            [google."gemini-2.5-flash"]
+            input = 0.3
+            output = 2.5
+            input_by_modality = { audio = 1.0 }
+            "#,
+        )
+        .unwrap();
+
+        let price = prices.get("google", "gemini-2.5-flash").unwrap();
+        assert_eq!(price.output, 2.5);
+        assert_eq!(price.input_by_modality[&Modality::Audio], 1.0);
+    }
+}
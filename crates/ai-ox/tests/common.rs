@@ -25,12 +25,16 @@ use ai_ox::model::mistral::MistralModel;
 #[cfg(feature = "anthropic")]
 use ai_ox::model::anthropic::AnthropicModel;
 
+#[cfg(feature = "openai")]
+use ai_ox::model::openai::OpenAIModel;
+
 // Model version constants for consistent testing
 const BEDROCK_MODEL: &str = "anthropic.claude-3-haiku-20240307-v1:0";
 const GEMINI_MODEL: &str = "gemini-2.5-flash";
 const OPENROUTER_MODEL: &str = "openai/gpt-4o-mini";
 const MISTRAL_MODEL: &str = "mistral-small-latest";
 const ANTHROPIC_MODEL: &str = "claude-3-haiku-20240307";
+const OPENAI_MODEL: &str = "gpt-4o-mini";
 
 /// Helper function to initialize a provider model with graceful error handling
 async fn try_init_provider<T, E, F, Fut>(
@@ -149,13 +153,25 @@ pub async fn get_available_models() -> Vec<Box<dyn Model>> {
         }
     }
 
+    #[cfg(feature = "openai")]
+    {
+        if let Some(model) = try_init_provider("OPENAI_API_KEY", "OpenAI", || {
+            OpenAIModel::new(OPENAI_MODEL.to_string())
+        })
+        .await
+        {
+            models.push(model);
+        }
+    }
+
     // This is to satisfy the compiler in case no features are enabled.
     #[cfg(not(any(
         feature = "bedrock",
         feature = "gemini",
         feature = "openrouter",
         feature = "mistral",
-        feature = "anthropic"
+        feature = "anthropic",
+        feature = "openai"
     )))]
     {
         println!("⚠️ No provider features enabled. All compliance tests will be skipped.");
@@ -167,7 +183,8 @@ pub async fn get_available_models() -> Vec<Box<dyn Model>> {
             feature = "gemini",
             feature = "openrouter",
             feature = "mistral",
-            feature = "anthropic"
+            feature = "anthropic",
+            feature = "openai"
         ))
     {
         // This case can happen when features are enabled but API keys are not set
@@ -183,6 +200,8 @@ pub async fn get_available_models() -> Vec<Box<dyn Model>> {
         println!("   - MISTRAL_API_KEY for Mistral");
         #[cfg(feature = "anthropic")]
         println!("   - ANTHROPIC_API_KEY for Anthropic");
+        #[cfg(feature = "openai")]
+        println!("   - OPENAI_API_KEY for OpenAI");
     }
 
     models
@@ -43,6 +43,8 @@ async fn test_all_providers_simple_request() {
             messages: vec![message],
             system_message: None,
             tools: None,
+            generation_config: None,
+            cache: None,
         };
 
         let result = model.request(request).await;
@@ -120,6 +122,8 @@ async fn test_all_providers_simple_streaming() {
             messages: vec![message],
             system_message: None,
             tools: None,
+            generation_config: None,
+            cache: None,
         };
 
         let mut stream = model.request_stream(request);
@@ -223,6 +227,8 @@ async fn test_all_providers_tool_use() {
             messages: vec![message],
             system_message: None,
             tools: Some(vec![weather_tool.clone()]),
+            generation_config: None,
+            cache: None,
         };
 
         let result = model.request(request).await;
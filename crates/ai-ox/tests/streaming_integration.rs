@@ -123,6 +123,7 @@ async fn test_all_providers_streaming_with_tools() {
                         println!("⚠ Unexpected tool execution: {}", tool_call.name);
                     }
                 }
+                AgentEvent::ToolApprovalRequested(_) | AgentEvent::ToolDenied { .. } => {}
                 AgentEvent::ToolResult(messages) => {
                     // Look for tool result information in the messages
                     for message in messages {
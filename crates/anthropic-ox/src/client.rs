@@ -1,3 +1,4 @@
+use ai_ox_common::Transport;
 use bon::Builder;
 use core::fmt;
 use futures_util::StreamExt;
@@ -8,7 +9,7 @@ use leaky_bucket::RateLimiter;
 use std::sync::Arc;
 
 use crate::{
-    error::{self, AnthropicRequestError},
+    error::AnthropicRequestError,
     internal::{AnthropicRequestHelper, Endpoint, HttpMethod},
     request,
     response::{self, StreamEvent},
@@ -41,6 +42,10 @@ const BATCHES_URL: &str = "v1/message_batches";
 const FILES_URL: &str = "v1/files";
 const ADMIN_ORGANIZATIONS_URL: &str = "v1/organizations";
 const API_VERSION: &str = "2023-06-01";
+const BETA_HEADER: &str = "anthropic-beta";
+
+/// Beta required by the Files API and by messages that reference uploaded files.
+pub const FILES_API_BETA: &str = "files-api-2025-04-14";
 
 /// A struct to configure beta features for the Anthropic API.
 #[derive(Clone, Default, Debug)]
@@ -51,6 +56,8 @@ pub struct BetaFeatures {
     pub interleaved_thinking: bool,
     /// Enables the computer use tool.
     pub computer_use: bool,
+    /// Enables referencing files uploaded through the Files API.
+    pub files_api: bool,
 }
 
 #[derive(Clone, Default, Builder)]
@@ -69,6 +76,9 @@ pub struct Anthropic {
     pub(crate) api_version: String,
     #[builder(default)]
     pub(crate) headers: std::collections::HashMap<String, String>,
+    /// Live, recording or replaying transport for API requests
+    #[builder(default)]
+    pub(crate) transport: Transport,
 }
 
 impl Anthropic {
@@ -81,6 +91,7 @@ impl Anthropic {
             &self.oauth_token,
             &self.api_version,
             &self.headers,
+            &self.transport,
         )
     }
 
@@ -145,6 +156,7 @@ impl Anthropic {
             base_url: BASE_URL.to_string(),
             api_version: API_VERSION.to_string(),
             headers: std::collections::HashMap::new(),
+            transport: Transport::default(),
         }
     }
 
@@ -160,21 +172,32 @@ impl Anthropic {
     }
 
     /// Enables beta features for the client.
+    ///
+    /// Betas already enabled on the client are kept.
     pub fn with_beta_features(mut self, features: BetaFeatures) -> Self {
-        let mut beta_headers = Vec::new();
         if features.fine_grained_tool_streaming {
-            beta_headers.push("fine-grained-tool-streaming-2025-05-14");
+            self = self.with_beta("fine-grained-tool-streaming-2025-05-14");
         }
         if features.interleaved_thinking {
-            beta_headers.push("interleaved-thinking-2025-05-14");
+            self = self.with_beta("interleaved-thinking-2025-05-14");
         }
         if features.computer_use {
-            beta_headers.push("computer-use-2025-01-24");
+            self = self.with_beta("computer-use-2025-01-24");
         }
+        if features.files_api {
+            self = self.with_beta(FILES_API_BETA);
+        }
+        self
+    }
 
-        if !beta_headers.is_empty() {
-            self.headers
-                .insert("anthropic-beta".to_string(), beta_headers.join(","));
+    /// Adds a beta to the `anthropic-beta` header sent with every request.
+    pub fn with_beta(mut self, beta: &str) -> Self {
+        let header = self.headers.entry(BETA_HEADER.to_string()).or_default();
+        if !header.split(',').any(|enabled| enabled.trim() == beta) {
+            if !header.is_empty() {
+                header.push(',');
+            }
+            header.push_str(beta);
         }
         self
     }
@@ -315,41 +338,15 @@ impl Anthropic {
         &self,
         request: &FileUploadRequest,
     ) -> Result<FileInfo, AnthropicRequestError> {
-        let url = format!("{}/{}", self.base_url, FILES_URL);
-
         let part = reqwest::multipart::Part::bytes(request.content.clone())
             .file_name(request.filename.clone())
             .mime_str(&request.mime_type)?;
-
         let form = reqwest::multipart::Form::new().part("file", part);
 
-        let mut req = self.client.post(&url).multipart(form);
-
-        if let Some(oauth_token) = &self.oauth_token {
-            req = req.header("authorization", format!("Bearer {}", oauth_token));
-        } else if let Some(api_key) = &self.api_key {
-            req = req.header("x-api-key", api_key);
-        } else {
-            return Err(AnthropicRequestError::AuthenticationMissing);
-        }
-
-        let mut req_with_headers = req
-            .header("anthropic-version", &self.api_version)
-            .header("anthropic-beta", "files-api-2025-04-14");
-
-        for (key, value) in &self.headers {
-            req_with_headers = req_with_headers.header(key, value);
-        }
-
-        let res = req_with_headers.send().await?;
-
-        if res.status().is_success() {
-            Ok(res.json::<FileInfo>().await?)
-        } else {
-            let status = res.status();
-            let bytes = res.bytes().await?;
-            Err(error::parse_error_response(status, bytes))
-        }
+        let endpoint = Endpoint::new(FILES_URL, HttpMethod::Post).with_beta(FILES_API_BETA);
+        self.request_helper()?
+            .request_multipart(&endpoint, form)
+            .await
     }
 
     /// Lists the files in the workspace.
@@ -372,7 +369,7 @@ impl Anthropic {
         }
 
         let endpoint = Endpoint::new(FILES_URL, HttpMethod::Get)
-            .with_beta("files-api-2025-04-14")
+            .with_beta(FILES_API_BETA)
             .with_query_params(query_params);
         self.api_request(endpoint).await
     }
@@ -381,7 +378,7 @@ impl Anthropic {
     #[cfg(feature = "files")]
     pub async fn get_file(&self, file_id: &str) -> Result<FileInfo, AnthropicRequestError> {
         let endpoint = Endpoint::new(format!("{}/{}", FILES_URL, file_id), HttpMethod::Get)
-            .with_beta("files-api-2025-04-14");
+            .with_beta(FILES_API_BETA);
         self.api_request(endpoint).await
     }
 
@@ -389,7 +386,7 @@ impl Anthropic {
     #[cfg(feature = "files")]
     pub async fn delete_file(&self, file_id: &str) -> Result<(), AnthropicRequestError> {
         let endpoint = Endpoint::new(format!("{}/{}", FILES_URL, file_id), HttpMethod::Delete)
-            .with_beta("files-api-2025-04-14");
+            .with_beta(FILES_API_BETA);
         self.api_delete(endpoint).await
     }
 
@@ -403,7 +400,7 @@ impl Anthropic {
             format!("{}/{}/content", FILES_URL, file_id),
             HttpMethod::Get,
         )
-        .with_beta("files-api-2025-04-14");
+        .with_beta(FILES_API_BETA);
         self.api_request_bytes(endpoint).await
     }
 }
@@ -622,6 +619,7 @@ impl fmt::Debug for Anthropic {
             .field("base_url", &self.base_url)
             .field("api_version", &self.api_version)
             .field("headers", &self.headers)
+            .field("transport", &self.transport)
             .finish_non_exhaustive()
     }
 }
@@ -1,6 +1,6 @@
-use crate::error::{self, AnthropicRequestError};
+use crate::error::AnthropicRequestError;
 use ai_ox_common::{
-    CommonRequestError,
+    CommonRequestError, Transport,
     request_builder::{AuthMethod, Endpoint as CommonEndpoint, RequestBuilder, RequestConfig},
 };
 use async_stream::try_stream;
@@ -65,6 +65,7 @@ impl AnthropicRequestHelper {
         oauth_token: &Option<String>,
         api_version: &str,
         headers: &HashMap<String, String>,
+        transport: &Transport,
     ) -> Result<Self, AnthropicRequestError> {
         let auth_method = if let Some(token) = oauth_token {
             AuthMethod::OAuth {
@@ -82,7 +83,8 @@ impl AnthropicRequestHelper {
 
         let mut config = RequestConfig::new(base_url.to_string())
             .with_auth(auth_method)
-            .with_header("anthropic-version", api_version.to_string());
+            .with_header("anthropic-version", api_version.to_string())
+            .with_transport(transport.clone());
 
         for (key, value) in headers {
             config = config.with_header(key.clone(), value.clone());
@@ -141,6 +143,20 @@ impl AnthropicRequestHelper {
             .map_err(AnthropicRequestError::from)
     }
 
+    pub async fn request_multipart<T>(
+        &self,
+        endpoint: &Endpoint,
+        form: reqwest::multipart::Form,
+    ) -> Result<T, AnthropicRequestError>
+    where
+        T: DeserializeOwned,
+    {
+        self.builder()
+            .request_multipart::<T>(&self.endpoint(endpoint), form)
+            .await
+            .map_err(AnthropicRequestError::from)
+    }
+
     pub fn stream<T, B>(
         &self,
         endpoint: &Endpoint,
@@ -164,40 +180,20 @@ impl AnthropicRequestHelper {
     where
         T: DeserializeOwned + Send + 'static,
     {
-        let client = self.client.clone();
-        let config = self.config.clone();
-        let common_endpoint = self.endpoint(endpoint);
+        let mut byte_stream = self
+            .builder()
+            .stream_bytes::<()>(&self.endpoint(endpoint), None);
 
         Box::pin(try_stream! {
-            let req = RequestBuilder::new(client.clone(), config.clone()).build_request(&common_endpoint)?;
-            let response = req.send().await?;
-            let status = response.status();
-
-            if !status.is_success() {
-                let bytes = response.bytes().await?;
-                Err(error::parse_error_response(status, bytes))?;
-            } else {
-                let mut byte_stream = response.bytes_stream();
-                let mut buffer = Vec::new();
+            let mut buffer = Vec::new();
 
-                while let Some(chunk_result) = byte_stream.next().await {
-                    let chunk = chunk_result?;
-                    buffer.extend_from_slice(&chunk);
+            while let Some(chunk_result) = byte_stream.next().await {
+                let chunk = chunk_result?;
+                buffer.extend_from_slice(&chunk);
 
-                    while let Some(pos) = buffer.iter().position(|&b| b == b'\n') {
-                        let line_bytes = buffer.drain(..=pos).collect::<Vec<u8>>();
-                        let line = String::from_utf8(line_bytes)
-                            .map_err(|e| AnthropicRequestError::InvalidEventData(e.to_string()))?;
-                        if !line.trim().is_empty() {
-                            let result: T = serde_json::from_str(line.trim())
-                                .map_err(AnthropicRequestError::SerdeError)?;
-                            yield result;
-                        }
-                    }
-                }
-
-                if !buffer.is_empty() {
-                    let line = String::from_utf8(buffer)
+                while let Some(pos) = buffer.iter().position(|&b| b == b'\n') {
+                    let line_bytes = buffer.drain(..=pos).collect::<Vec<u8>>();
+                    let line = String::from_utf8(line_bytes)
                         .map_err(|e| AnthropicRequestError::InvalidEventData(e.to_string()))?;
                     if !line.trim().is_empty() {
                         let result: T = serde_json::from_str(line.trim())
@@ -206,6 +202,16 @@ impl AnthropicRequestHelper {
                     }
                 }
             }
+
+            if !buffer.is_empty() {
+                let line = String::from_utf8(buffer)
+                    .map_err(|e| AnthropicRequestError::InvalidEventData(e.to_string()))?;
+                if !line.trim().is_empty() {
+                    let result: T = serde_json::from_str(line.trim())
+                        .map_err(AnthropicRequestError::SerdeError)?;
+                    yield result;
+                }
+            }
         })
     }
 }
@@ -235,7 +241,7 @@ impl From<CommonRequestError> for AnthropicRequestError {
                     code,
                 }
             }
-            CommonRequestError::RateLimit => AnthropicRequestError::RateLimit,
+            CommonRequestError::RateLimit(_) => AnthropicRequestError::RateLimit,
             CommonRequestError::AuthenticationMissing => {
                 AnthropicRequestError::AuthenticationMissing
             }
@@ -20,6 +20,10 @@ pub enum Role {
 pub enum ImageSource {
     #[serde(rename = "base64")]
     Base64 { media_type: String, data: String },
+    /// A file uploaded through the Files API. Requests that use it must send
+    /// the `files-api-2025-04-14` beta header.
+    #[serde(rename = "file")]
+    File { file_id: String },
 }
 
 impl ImageSource {
@@ -49,6 +53,7 @@ impl fmt::Display for ImageSource {
                 };
                 write!(f, "Base64 ({}, {})", media_type, truncated_data)
             }
+            ImageSource::File { file_id } => write!(f, "File ({file_id})"),
         }
     }
 }
@@ -66,6 +71,9 @@ pub enum Content {
     ToolResult(ToolResult),
     #[serde(rename = "thinking")]
     Thinking(ThinkingContent),
+    /// Thinking that was flagged by safety systems and is returned encrypted.
+    #[serde(rename = "redacted_thinking")]
+    RedactedThinking { data: String },
     #[serde(rename = "search_result")]
     SearchResult(SearchResult),
 }
@@ -98,6 +106,25 @@ pub struct Citations {
 pub struct CacheControl {
     #[serde(rename = "type")]
     pub cache_type: String,
+    /// How long the cached prefix lives, e.g. "5m" or "1h".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<String>,
+}
+
+impl CacheControl {
+    /// Creates an ephemeral cache breakpoint with the default lifetime.
+    pub fn ephemeral() -> Self {
+        Self {
+            cache_type: "ephemeral".to_string(),
+            ttl: None,
+        }
+    }
+
+    /// Sets how long the cached prefix lives.
+    pub fn with_ttl(mut self, ttl: impl Into<String>) -> Self {
+        self.ttl = Some(ttl.into());
+        self
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -109,6 +136,7 @@ pub struct Text {
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct ThinkingContent {
+    #[serde(rename = "thinking", alias = "text")]
     pub text: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub signature: Option<String>,
@@ -165,10 +193,14 @@ pub enum ContentBlock {
         input: serde_json::Value,
     },
     Thinking {
+        #[serde(rename = "thinking", alias = "text")]
         text: String,
         #[serde(skip_serializing_if = "Option::is_none")]
         signature: Option<String>,
     },
+    RedactedThinking {
+        data: String,
+    },
 }
 
 impl From<String> for Content {
@@ -219,6 +251,7 @@ impl fmt::Display for Content {
                     thinking.text.clone()
                 }
             ),
+            Content::RedactedThinking { .. } => write!(f, "[Redacted Thinking]"),
             Content::SearchResult(result) => write!(f, "[Search Result: {}]", result.title),
         }
     }
@@ -688,9 +721,7 @@ mod tests {
         // Ensure cache_control is preserved during serialization
         let original = Content::Text(Text {
             text: "Test text".to_string(),
-            cache_control: Some(CacheControl {
-                cache_type: "ephemeral".to_string(),
-            }),
+            cache_control: Some(CacheControl::ephemeral()),
         });
 
         let json = serde_json::to_string(&original).unwrap();
@@ -929,6 +960,7 @@ mod tests {
                                 "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mP8/5+hHgAHggJ/PchI7wAAAABJRU5ErkJggg=="
                             );
                         }
+                        other => panic!("Expected Base64 source, got {other}"),
                     },
                     _ => panic!("Expected Image content"),
                 }
@@ -1159,6 +1191,7 @@ mod tests {
                             assert_eq!(media_type, "image/jpeg");
                             assert_eq!(data, "/9j/4AAQSkZJRgABAQEAYABgAAD//2Q=");
                         }
+                        other => panic!("Expected Base64 source, got {other}"),
                     },
                     _ => panic!("Expected Image at index 1"),
                 }
@@ -1262,13 +1295,26 @@ mod tests {
         let content = Content::Thinking(ThinkingContent::new("Reasoning...".to_string()));
 
         let json = serde_json::to_string(&content).unwrap();
-        let expected = r#"{"type":"thinking","text":"Reasoning..."}"#;
+        let expected = r#"{"type":"thinking","thinking":"Reasoning..."}"#;
         assert_eq!(json, expected);
 
         let deserialized: Content = serde_json::from_str(&json).unwrap();
         assert_eq!(content, deserialized);
     }
 
+    #[test]
+    fn test_redacted_thinking_round_trip() {
+        let json = r#"{"type":"redacted_thinking","data":"EmwKAhgBEgy3va3pzix"}"#;
+        let content: Content = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            content,
+            Content::RedactedThinking {
+                data: "EmwKAhgBEgy3va3pzix".to_string()
+            }
+        );
+        assert_eq!(serde_json::to_string(&content).unwrap(), json);
+    }
+
     #[test]
     fn test_thinking_content_display() {
         let content = Content::Thinking(ThinkingContent::new(
@@ -19,6 +19,12 @@ pub struct Usage {
     pub output_tokens: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub thinking_tokens: Option<u32>,
+    /// Input tokens written to the prompt cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_creation_input_tokens: Option<u32>,
+    /// Input tokens read from the prompt cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_read_input_tokens: Option<u32>,
 }
 
 impl From<Usage> for ai_ox_common::usage::TokenUsage {
@@ -30,8 +36,8 @@ impl From<Usage> for ai_ox_common::usage::TokenUsage {
                 (Some(i), Some(o)) => Some((i + o) as u64),
                 _ => None,
             },
-            cache_creation_tokens: None,
-            cache_read_tokens: None,
+            cache_creation_tokens: usage.cache_creation_input_tokens.map(|t| t as u64),
+            cache_read_tokens: usage.cache_read_input_tokens.map(|t| t as u64),
             reasoning_tokens: usage.thinking_tokens.map(|t| t as u64),
             tool_prompt_tokens: None,
             thoughts_tokens: None,
@@ -185,9 +191,19 @@ pub enum StreamEvent {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ContentBlockDelta {
-    TextDelta { text: String },
-    InputJsonDelta { partial_json: String },
-    ThinkingDelta { text: String },
+    TextDelta {
+        text: String,
+    },
+    InputJsonDelta {
+        partial_json: String,
+    },
+    ThinkingDelta {
+        #[serde(rename = "thinking", alias = "text")]
+        text: String,
+    },
+    SignatureDelta {
+        signature: String,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -223,6 +239,8 @@ mod tests {
                 input_tokens: Some(10),
                 output_tokens: Some(20),
                 thinking_tokens: None,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
             },
         }
     }
@@ -277,11 +295,20 @@ mod tests {
         };
 
         let json = serde_json::to_string(&delta).unwrap();
-        let expected = r#"{"type":"thinking_delta","text":"More reasoning..."}"#;
+        let expected = r#"{"type":"thinking_delta","thinking":"More reasoning..."}"#;
         assert_eq!(json, expected);
 
         let deserialized: ContentBlockDelta = serde_json::from_str(&json).unwrap();
         assert_eq!(delta, deserialized);
+
+        let signature: ContentBlockDelta =
+            serde_json::from_str(r#"{"type":"signature_delta","signature":"sig"}"#).unwrap();
+        assert_eq!(
+            signature,
+            ContentBlockDelta::SignatureDelta {
+                signature: "sig".to_string()
+            }
+        );
     }
 
     #[test]
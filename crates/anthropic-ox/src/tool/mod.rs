@@ -1,7 +1,8 @@
 use async_trait::async_trait;
+use schemars::{JsonSchema, r#gen::SchemaSettings};
 use serde::{Deserialize, Deserializer, Serialize};
-use serde_json::Value;
-use std::{collections::HashMap, fmt, sync::Arc};
+use serde_json::{Value, json};
+use std::{collections::BTreeMap, fmt, sync::Arc};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -9,6 +10,7 @@ pub enum ToolChoice {
     Auto,
     Any,
     Tool { name: String },
+    None,
 }
 
 /// Represents a tool that can be used by the model.
@@ -33,6 +35,9 @@ pub struct CustomTool {
     pub description: String,
     /// The input schema for the tool.
     pub input_schema: serde_json::Value,
+    /// Cache control settings for the tool definitions up to this one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<crate::message::CacheControl>,
 }
 
 fn default_tool_type() -> String {
@@ -46,6 +51,7 @@ impl CustomTool {
             name,
             description,
             input_schema: serde_json::json!({}),
+            cache_control: None,
         }
     }
 
@@ -267,26 +273,59 @@ impl fmt::Display for ToolResult {
 }
 
 /// Trait for tools that can be called by the AI
+///
+/// The input schema sent to the model is generated from [`ToolTrait::Input`].
+/// String outputs are returned to the model verbatim; any other output is
+/// serialized as JSON.
 #[async_trait]
 pub trait ToolTrait: Send + Sync + Clone {
-    type Input: for<'de> Deserialize<'de> + Send;
+    type Input: for<'de> Deserialize<'de> + JsonSchema + Send;
     type Output: Serialize + Send;
     type Error: fmt::Display + Send;
 
     fn name(&self) -> String;
+
+    /// A description that tells the model when and how to use the tool.
+    fn description(&self) -> String {
+        String::new()
+    }
+
     async fn invoke(&self, input: Self::Input) -> Result<Self::Output, Self::Error>;
+
+    /// The tool definition sent to the API.
+    fn definition(&self) -> CustomTool {
+        CustomTool::new(self.name(), self.description()).with_schema(input_schema::<Self::Input>())
+    }
+}
+
+/// Generate an inline JSON schema suitable for a tool's `input_schema`.
+#[must_use]
+pub fn input_schema<T: JsonSchema>() -> Value {
+    let settings = SchemaSettings::draft07().with(|s| {
+        s.inline_subschemas = true;
+        s.meta_schema = None;
+    });
+    let root_schema = settings.into_generator().into_root_schema_for::<T>();
+    let mut schema = serde_json::to_value(root_schema).unwrap_or_else(|_| json!({}));
+    if let Some(obj) = schema.as_object_mut() {
+        obj.remove("title");
+    }
+    schema
 }
 
 /// A collection of tools that can be invoked
+///
+/// Tools are keyed by name; adding a tool with an existing name replaces it.
 #[derive(Clone, Default)]
 pub struct ToolBox {
-    tools: HashMap<String, Arc<dyn ToolInvoker>>,
+    /// Registered tools keyed by name
+    tools: BTreeMap<String, Arc<dyn ToolInvoker>>,
 }
 
 impl std::fmt::Debug for ToolBox {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ToolBox")
-            .field("tools", &format!("{} tools", self.tools.len()))
+            .field("tools", &self.tools.keys().collect::<Vec<_>>())
             .finish()
     }
 }
@@ -296,38 +335,191 @@ impl ToolBox {
         Self::default()
     }
 
-    pub fn add<T: ToolTrait + 'static>(&self, _tool: T) {
-        // This is a simplified version - in reality you'd need to store the tools
-        // and provide a way to invoke them
+    pub fn add<T: ToolTrait + 'static>(&mut self, tool: T) {
+        let definition = tool.definition();
+        self.tools.insert(
+            definition.name.clone(),
+            Arc::new(TypedTool { tool, definition }),
+        );
+    }
+
+    #[must_use]
+    pub fn with_tool<T: ToolTrait + 'static>(mut self, tool: T) -> Self {
+        self.add(tool);
+        self
     }
 
     pub fn is_empty(&self) -> bool {
         self.tools.is_empty()
     }
 
-    pub fn get(&self, name: &str) -> Option<&Arc<dyn ToolInvoker>> {
-        self.tools.get(name)
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tools.len()
     }
 
+    /// Get the definition of a registered tool
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&CustomTool> {
+        self.tools.get(name).map(|tool| tool.definition())
+    }
+
+    /// Definitions of all registered tools, ordered by name
+    #[must_use]
+    pub fn tools(&self) -> Vec<Tool> {
+        self.tools
+            .values()
+            .map(|tool| Tool::Custom(tool.definition().clone()))
+            .collect()
+    }
+
+    /// Dispatch a tool call to the matching tool
+    ///
+    /// Unknown tools, invalid input and tool errors are reported to the model
+    /// as [`ToolResult::error`] rather than failing the tool loop.
     pub async fn invoke(&self, tool_use: ToolUse) -> ToolResult {
-        // Simplified implementation
-        ToolResult::text(tool_use.id, "Tool invocation not implemented".to_string())
+        let Some(tool) = self.tools.get(&tool_use.name) else {
+            return ToolResult::error(tool_use.id, format!("Unknown tool: {}", tool_use.name));
+        };
+        match tool.invoke(tool_use.input).await {
+            Ok(Value::String(text)) => ToolResult::text(tool_use.id, text),
+            Ok(output) => ToolResult::text(tool_use.id, output.to_string()),
+            Err(error) => ToolResult::error(tool_use.id, error),
+        }
+    }
+
+    /// Dispatch several tool calls concurrently, keeping their order
+    pub async fn invoke_all(
+        &self,
+        tool_uses: impl IntoIterator<Item = ToolUse>,
+    ) -> Vec<ToolResult> {
+        futures_util::future::join_all(tool_uses.into_iter().map(|tool_use| self.invoke(tool_use)))
+            .await
     }
 }
 
-// Helper trait for type erasure
+/// Type-erased tool stored in a [`ToolBox`]
 #[async_trait]
 trait ToolInvoker: Send + Sync {
+    /// Definition of the tool sent to the API
+    fn definition(&self) -> &CustomTool;
+    /// Deserialize the input, run the tool and serialize its output
     async fn invoke(&self, input: Value) -> Result<Value, String>;
 }
 
+/// A [`ToolTrait`] implementation together with its cached definition
+struct TypedTool<T> {
+    /// The tool implementation
+    tool: T,
+    /// Definition generated when the tool was added
+    definition: CustomTool,
+}
+
+#[async_trait]
+impl<T: ToolTrait + 'static> ToolInvoker for TypedTool<T> {
+    fn definition(&self) -> &CustomTool {
+        &self.definition
+    }
+
+    async fn invoke(&self, input: Value) -> Result<Value, String> {
+        let input = serde_json::from_value(input)
+            .map_err(|e| format!("Invalid input for tool {}: {e}", self.definition.name))?;
+        let output = self.tool.invoke(input).await.map_err(|e| e.to_string())?;
+        serde_json::to_value(output).map_err(|e| {
+            format!(
+                "Failed to serialize output of tool {}: {e}",
+                self.definition.name
+            )
+        })
+    }
+}
+
 impl Serialize for ToolBox {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        // Serialize as empty array for now
-        let tools: Vec<Tool> = vec![];
-        tools.serialize(serializer)
+        self.tools().serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone)]
+    struct Adder;
+
+    #[derive(Deserialize, JsonSchema)]
+    struct AddInput {
+        /// First operand
+        a: i64,
+        /// Second operand
+        b: i64,
+    }
+
+    #[async_trait]
+    impl ToolTrait for Adder {
+        type Input = AddInput;
+        type Output = i64;
+        type Error = String;
+
+        fn name(&self) -> String {
+            "add".to_string()
+        }
+
+        fn description(&self) -> String {
+            "Add two integers".to_string()
+        }
+
+        async fn invoke(&self, input: AddInput) -> Result<i64, String> {
+            input
+                .a
+                .checked_add(input.b)
+                .ok_or_else(|| "overflow".to_string())
+        }
+    }
+
+    fn call(name: &str, input: Value) -> ToolUse {
+        ToolUse::new("toolu_1".to_string(), name.to_string(), input)
+    }
+
+    #[test]
+    fn test_toolbox_serializes_definitions() {
+        let toolbox = ToolBox::new().with_tool(Adder);
+
+        let json = serde_json::to_value(&toolbox).unwrap();
+        assert_eq!(json[0]["name"], "add");
+        assert_eq!(json[0]["description"], "Add two integers");
+        assert_eq!(json[0]["input_schema"]["type"], "object");
+        assert_eq!(json[0]["input_schema"]["required"], json!(["a", "b"]));
+    }
+
+    #[tokio::test]
+    async fn test_toolbox_dispatches_and_maps_errors() {
+        let toolbox = ToolBox::new().with_tool(Adder);
+
+        let results = toolbox
+            .invoke_all([
+                call("add", json!({"a": 2, "b": 3})),
+                call("add", json!({"a": i64::MAX, "b": 1})),
+                call("add", json!({"a": "two"})),
+                call("subtract", json!({})),
+            ])
+            .await;
+
+        assert_eq!(
+            results[0],
+            ToolResult::text("toolu_1".to_string(), "5".to_string())
+        );
+        assert_eq!(
+            results[1],
+            ToolResult::error("toolu_1".to_string(), "overflow".to_string())
+        );
+        assert_eq!(results[2].is_error, Some(true));
+        assert_eq!(
+            results[3],
+            ToolResult::error("toolu_1".to_string(), "Unknown tool: subtract".to_string())
+        );
     }
 }
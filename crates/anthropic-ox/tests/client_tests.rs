@@ -42,6 +42,7 @@ fn test_beta_features() {
         fine_grained_tool_streaming: true,
         interleaved_thinking: true,
         computer_use: false,
+        files_api: false,
     };
 
     let client = Anthropic::new("test-key").with_beta_features(beta_features);
@@ -51,6 +52,22 @@ fn test_beta_features() {
     assert!(debug_str.contains("Anthropic"));
 }
 
+#[test]
+fn test_betas_are_merged() {
+    use anthropic_ox::client::{BetaFeatures, FILES_API_BETA};
+
+    let client = Anthropic::new("test-key")
+        .with_beta_features(BetaFeatures {
+            interleaved_thinking: true,
+            ..BetaFeatures::default()
+        })
+        .with_beta(FILES_API_BETA)
+        .with_beta(FILES_API_BETA);
+
+    let debug_str = format!("{:?}", client);
+    assert!(debug_str.contains("\"interleaved-thinking-2025-05-14,files-api-2025-04-14\""));
+}
+
 #[test]
 fn test_custom_headers() {
     let client = Anthropic::new("test-key")
@@ -297,6 +297,7 @@ mod real_api_tests {
             object_type: "custom".to_string(),
             name: "get_weather".to_string(),
             description: "Get the current weather for a location".to_string(),
+            cache_control: None,
             input_schema: serde_json::json!({
                 "type": "object",
                 "properties": {
@@ -0,0 +1,56 @@
+use ai_ox_common::Transport;
+use anthropic_ox::{
+    Anthropic,
+    message::Message,
+    request::ChatRequest,
+    response::{ContentBlockDelta, StreamEvent},
+};
+use futures_util::StreamExt;
+
+/// Client that serves responses from a cassette at `tests/cassettes/{name}.json`
+///
+/// The cassettes are hand-written fixtures in the recorded format, not
+/// recordings. Replay checks the method, URL and JSON body of every request.
+/// Set `AOX_TRANSPORT=record` and `ANTHROPIC_API_KEY` to replace one with a recording.
+fn client(name: &str) -> Anthropic {
+    let path = format!("{}/tests/cassettes/{name}.json", env!("CARGO_MANIFEST_DIR"));
+    let api_key = std::env::var("ANTHROPIC_API_KEY").unwrap_or_else(|_| "test-key".to_string());
+    Anthropic::builder()
+        .api_key(api_key)
+        .transport(Transport::from_env(path).expect("cassette should load"))
+        .build()
+}
+
+fn request() -> ChatRequest {
+    ChatRequest::builder()
+        .model("claude-3-haiku-20240307")
+        .messages(vec![Message::user(vec!["Say 'hello' in one word"])])
+        .max_tokens(5)
+        .build()
+}
+
+#[tokio::test]
+async fn test_replayed_chat_and_stream() {
+    let client = client("messages");
+
+    let response = client.send(&request()).await.expect("send should replay");
+    assert_eq!(response.text_content(), vec!["hello"]);
+
+    let events: Vec<StreamEvent> = client
+        .stream(&request())
+        .map(|event| event.expect("stream should replay"))
+        .collect()
+        .await;
+    let text: String = events
+        .iter()
+        .filter_map(|event| match event {
+            StreamEvent::ContentBlockDelta {
+                delta: ContentBlockDelta::TextDelta { text },
+                ..
+            } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(text, "hello");
+    assert!(matches!(events.last(), Some(StreamEvent::MessageStop)));
+}
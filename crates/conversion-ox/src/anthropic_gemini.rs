@@ -169,6 +169,10 @@ pub fn gemini_to_anthropic_response(
             input_tokens: usage.prompt_token_count.try_into().ok(),
             output_tokens: usage.candidates_token_count.and_then(|t| t.try_into().ok()),
             thinking_tokens: usage.thoughts_token_count.and_then(|t| t.try_into().ok()),
+            cache_creation_input_tokens: None,
+            cache_read_input_tokens: usage
+                .cached_content_token_count
+                .and_then(|t| t.try_into().ok()),
         })
         .unwrap_or_default();
 
@@ -219,6 +223,12 @@ fn convert_anthropic_content_to_parts(
                         data.clone(),
                     ))))
                 }
+                anthropic_ox::message::ImageSource::File { file_id } => {
+                    log::warn!(
+                        "Anthropic file {file_id} can't be referenced from Gemini; skipping"
+                    );
+                    None
+                }
             },
             AnthropicContent::ToolUse(tool_use) => Some(GeminiPart::new(PartData::FunctionCall(
                 gemini_ox::content::FunctionCall {
@@ -244,6 +254,12 @@ fn convert_anthropic_content_to_parts(
                                     "data": data
                                 }));
                             }
+                            anthropic_ox::message::ImageSource::File { file_id } => {
+                                content_parts.push(serde_json::json!({
+                                    "type": "image",
+                                    "file_id": file_id
+                                }));
+                            }
                         },
                     }
                 }
@@ -284,6 +300,8 @@ fn convert_anthropic_content_to_parts(
 
                 Some(part)
             }
+            // Encrypted thinking can only be verified by Anthropic.
+            AnthropicContent::RedactedThinking { .. } => None,
             AnthropicContent::SearchResult(search_result) => {
                 // Convert search result to text format for Gemini
                 let text_content = format!(
@@ -1063,6 +1081,12 @@ pub fn anthropic_to_gemini_response(
                                     "data": data
                                 }));
                             }
+                            anthropic_ox::message::ImageSource::File { file_id } => {
+                                content_parts.push(serde_json::json!({
+                                    "type": "image",
+                                    "file_id": file_id
+                                }));
+                            }
                         },
                     }
                 }
@@ -1102,6 +1126,8 @@ pub fn anthropic_to_gemini_response(
                     "Cannot convert Anthropic SearchResult to Gemini format".to_string(),
                 ));
             }
+            // Encrypted thinking can only be verified by Anthropic.
+            AnthropicContent::RedactedThinking { .. } => {}
         }
     }
 
@@ -1153,6 +1179,7 @@ fn convert_tool_choice_to_tool_config(tool_choice: &AnthropicToolChoice) -> Gemi
     match tool_choice {
         AnthropicToolChoice::Auto => GeminiToolConfig::new().mode(GeminiFunctionMode::Auto),
         AnthropicToolChoice::Any => GeminiToolConfig::new().mode(GeminiFunctionMode::Any),
+        AnthropicToolChoice::None => GeminiToolConfig::new().mode(GeminiFunctionMode::None),
         AnthropicToolChoice::Tool { name } => GeminiToolConfig::new()
             .mode(GeminiFunctionMode::Any)
             .allowed_function_names([name.clone()]),
@@ -1168,7 +1195,7 @@ fn convert_tool_config_to_tool_choice(
         GeminiFunctionMode::Auto | GeminiFunctionMode::ModeUnspecified => {
             Some(AnthropicToolChoice::Auto)
         }
-        GeminiFunctionMode::None => Some(AnthropicToolChoice::Auto),
+        GeminiFunctionMode::None => Some(AnthropicToolChoice::None),
         GeminiFunctionMode::Any => {
             if let Some(names) = &function_config.allowed_function_names {
                 if names.len() == 1 {
@@ -278,6 +278,7 @@ fn map_anthropic_tool_choice_to_openai(
     match choice {
         AnthropicToolChoice::Auto => (OpenAIToolChoice::Auto, None),
         AnthropicToolChoice::Any => (OpenAIToolChoice::Required, Some(false)),
+        AnthropicToolChoice::None => (OpenAIToolChoice::None, None),
         AnthropicToolChoice::Tool { name } => {
             let function_name = sanitize_tool_function_name(name);
             (
@@ -299,6 +300,7 @@ fn map_anthropic_tool_choice_to_responses(choice: &AnthropicToolChoice) -> (Stri
     match choice {
         AnthropicToolChoice::Auto => ("auto".to_string(), None),
         AnthropicToolChoice::Any => ("any".to_string(), Some(false)),
+        AnthropicToolChoice::None => ("none".to_string(), None),
         AnthropicToolChoice::Tool { name } => (sanitize_tool_function_name(name), Some(false)),
     }
 }
@@ -639,6 +641,7 @@ pub fn anthropic_to_openai_responses_request(
                             description: Some(custom_tool.description),
                             format: None,     // No grammar format for now
                             parameters: None, // Responses API doesn't support parameters field
+                            options: Default::default(),
                         })
                     }
                     // Skip computer tools as they don't map to OpenAI
@@ -678,7 +681,7 @@ pub fn anthropic_to_openai_responses_request(
                 Some(choice) => map_anthropic_tool_choice_to_responses(choice),
                 None => ("auto".to_string(), None),
             };
-            request.tool_choice = Some(choice_value);
+            request.tool_choice = Some(choice_value.into());
             request.parallel_tool_calls = parallel_calls;
         }
         // If tools list is empty, don't set tools field at all
@@ -714,9 +717,7 @@ pub fn openai_responses_to_anthropic_response(
     for item in openai_response.output {
         match item {
             ResponseOutputItem::Reasoning {
-                id: _,
-                summary,
-                content,
+                summary, content, ..
             } => {
                 // Convert reasoning to thinking content
                 // Summary is an array of values - try to extract text
@@ -768,7 +769,8 @@ pub fn openai_responses_to_anthropic_response(
                     }
                 }
             }
-            ResponseOutputItem::FunctionToolCall { id, details: _ }
+            ResponseOutputItem::FunctionCall { id, .. }
+            | ResponseOutputItem::FunctionToolCall { id, details: _ }
             | ResponseOutputItem::FileSearchToolCall { id, details: _ }
             | ResponseOutputItem::ComputerToolCall { id, details: _ }
             | ResponseOutputItem::CodeInterpreterToolCall { id, details: _ }
@@ -779,6 +781,7 @@ pub fn openai_responses_to_anthropic_response(
                     id
                 )));
             }
+            ResponseOutputItem::Other => {}
         }
     }
 
@@ -808,6 +811,8 @@ pub fn openai_responses_to_anthropic_response(
                 input_tokens: Some(u.input_tokens),
                 output_tokens: Some(u.output_tokens),
                 thinking_tokens: None,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
             })
             .unwrap_or_default(),
     };
@@ -848,6 +853,7 @@ pub fn anthropic_to_openai_responses_response(
                         .signature
                         .as_ref()
                         .map(|sig| vec![serde_json::Value::String(sig.clone())]),
+                    encrypted_content: None,
                 });
             }
             AnthropicContent::Text(text) => {
@@ -980,6 +986,11 @@ pub fn openai_responses_to_anthropic_request(
             }]
         }
         ResponsesInput::Messages(messages) => messages,
+        ResponsesInput::Items(_) => {
+            return Err(ConversionError::UnsupportedConversion(
+                "Typed Responses input items cannot be converted to Anthropic messages".to_string(),
+            ));
+        }
         ResponsesInput::Mixed(parts) => {
             // Convert mixed input to messages
             let text_parts: Vec<String> = parts.into_iter().filter_map(|part| part.text).collect();
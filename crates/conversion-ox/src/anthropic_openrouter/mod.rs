@@ -182,11 +182,23 @@ pub fn openrouter_to_anthropic_response(
 
     let mut content = Vec::new();
 
+    // Reasoning is reported on the message, older responses put it on the choice
+    let reasoning = first_choice
+        .message
+        .reasoning
+        .as_ref()
+        .or(first_choice.reasoning.as_ref());
+    let reasoning_details = first_choice
+        .message
+        .reasoning_details
+        .as_ref()
+        .or(first_choice.reasoning_details.as_ref());
+
     // Convert reasoning to thinking content if present
-    if let Some(reasoning) = &first_choice.reasoning {
+    if let Some(reasoning) = reasoning {
         let mut thinking = anthropic_ox::message::ThinkingContent::new(reasoning.clone());
         // If we have reasoning_details, use the first one as the main thinking text
-        if let Some(details) = &first_choice.reasoning_details {
+        if let Some(details) = reasoning_details {
             if let Some(first_detail) = details.first() {
                 // Use summary first, then text, then data as fallback
                 if let Some(summary) = &first_detail.summary {
@@ -266,6 +278,8 @@ pub fn openrouter_to_anthropic_response(
                 .completion_tokens
                 .map(|t| t as u32),
             thinking_tokens: openrouter_response.usage.reasoning_tokens.map(|t| t as u32),
+            cache_creation_input_tokens: None,
+            cache_read_input_tokens: None,
         },
     })
 }
@@ -295,6 +309,11 @@ fn convert_anthropic_messages_to_openrouter(
                                     openrouter_ox::message::ImageContent::new(data_url),
                                 ));
                             }
+                            anthropic_ox::message::ImageSource::File { file_id } => {
+                                log::warn!(
+                                    "Anthropic file {file_id} can't be referenced from OpenRouter; skipping"
+                                );
+                            }
                         },
                         AnthropicContent::ToolResult(tool_result) => {
                             if tool_result.content.is_empty() {
@@ -327,6 +346,9 @@ fn convert_anthropic_messages_to_openrouter(
                             log::debug!("Converting thinking content in user message to text");
                             text_parts.push(ContentPart::Text(thinking.text.into()));
                         }
+                        AnthropicContent::RedactedThinking { .. } => {
+                            log::debug!("Skipping redacted thinking content in user message");
+                        }
                         AnthropicContent::SearchResult(search_result) => {
                             log::warn!("SearchResult content converted to text for OpenRouter");
                             let text_content = format!(
@@ -362,6 +384,11 @@ fn convert_anthropic_messages_to_openrouter(
                                     openrouter_ox::message::ImageContent::new(data_url),
                                 ));
                             }
+                            anthropic_ox::message::ImageSource::File { file_id } => {
+                                log::warn!(
+                                    "Anthropic file {file_id} can't be referenced from OpenRouter; skipping"
+                                );
+                            }
                         },
                         AnthropicContent::ToolUse(tool_use) => {
                             tool_calls.push(openrouter_ox::response::ToolCall {
@@ -382,6 +409,9 @@ fn convert_anthropic_messages_to_openrouter(
                             log::debug!("Converting thinking content in assistant message to text");
                             text_parts.push(ContentPart::Text(thinking.text.into()));
                         }
+                        AnthropicContent::RedactedThinking { .. } => {
+                            log::debug!("Skipping redacted thinking content in assistant message");
+                        }
                         AnthropicContent::SearchResult(search_result) => {
                             log::warn!("SearchResult content converted to text for OpenRouter");
                             let text_content = format!(
@@ -452,6 +482,7 @@ fn map_tool_choice(choice: &AnthropicToolChoice) -> OpenAiToolChoice {
     match choice {
         AnthropicToolChoice::Auto => OpenAiToolChoice::Auto,
         AnthropicToolChoice::Any => OpenAiToolChoice::Required,
+        AnthropicToolChoice::None => OpenAiToolChoice::None,
         AnthropicToolChoice::Tool { name } => OpenAiToolChoice::Specific {
             r#type: "function".to_string(),
             function: OpenAiFunction {
@@ -63,6 +63,8 @@ impl AnthropicOpenRouterStreamConverter {
                         input_tokens: None,
                         output_tokens: None,
                         thinking_tokens: None,
+                        cache_creation_input_tokens: None,
+                        cache_read_input_tokens: None,
                     },
                 };
 
@@ -126,6 +128,8 @@ impl AnthropicOpenRouterStreamConverter {
                         input_tokens: usage.prompt_tokens.map(|t| t as u32),
                         output_tokens: usage.completion_tokens.map(|t| t as u32),
                         thinking_tokens: usage.reasoning_tokens.map(|t| t as u32),
+                        cache_creation_input_tokens: None,
+                        cache_read_input_tokens: None,
                     }),
                 });
 
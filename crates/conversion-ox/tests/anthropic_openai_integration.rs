@@ -721,6 +721,7 @@ fn test_openai_responses_to_anthropic_response_with_reasoning() {
                 id: "reasoning-1".to_string(),
                 summary: vec![serde_json::json!("Let me think about quantum computing step by step...")],
                 content: Some(vec![serde_json::json!("encrypted_reasoning_data")]),
+                encrypted_content: None,
             },
             ResponseOutputItem::Message {
                 id: "msg-1".to_string(),
@@ -1028,6 +1029,8 @@ fn test_responses_api_response_roundtrip() {
             input_tokens: Some(100),
             output_tokens: Some(200),
             thinking_tokens: Some(50),
+            cache_creation_input_tokens: None,
+            cache_read_input_tokens: None,
         },
     };
 
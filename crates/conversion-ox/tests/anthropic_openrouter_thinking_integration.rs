@@ -85,6 +85,7 @@ fn test_tool_choice_is_preserved_when_converting_to_openrouter() {
         object_type: "custom".to_string(),
         name: "weather_tool".to_string(),
         description: "Weather lookup".to_string(),
+        cache_control: None,
         input_schema: json!({"type":"object"}),
     });
 
@@ -160,6 +161,7 @@ fn test_openrouter_to_anthropic_thinking_conversion() {
         text: Some("Let me work through this step by step. 15 × 23 means I need to multiply these two numbers. I can break this down: 15 × 20 = 300, and 15 × 3 = 45. So 300 + 45 = 345.".to_string()),
         summary: None,
         data: None,
+        signature: None,
         id: None,
         format: Some("unknown".to_string()),
         index: Some(0),
@@ -261,6 +263,7 @@ fn test_anthropic_to_openrouter_to_anthropic_round_trip() {
         ),
         summary: None,
         data: None,
+        signature: None,
         id: None,
         format: Some("unknown".to_string()),
         index: Some(0),
@@ -323,6 +326,7 @@ fn test_openrouter_to_anthropic_to_openrouter_round_trip() {
         ),
         summary: None,
         data: None,
+        signature: None,
         id: None,
         format: Some("unknown".to_string()),
         index: Some(0),
@@ -455,6 +459,7 @@ fn test_full_round_trip_thinking_preservation() {
             text: Some("Let me think about this problem systematically. First, I need to understand what is being asked.".to_string()),
             summary: None,
             data: None,
+            signature: None,
             id: None,
             format: Some("unknown".to_string()),
             index: Some(0),
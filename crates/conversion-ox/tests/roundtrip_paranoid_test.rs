@@ -142,6 +142,18 @@ fn anthropic_gemini_roundtrip_toolresult_must_preserve_every_byte() {
                             "Image base64 data changed on roundtrip (data lost or truncated)"
                         );
                     }
+                    (
+                        AnthropicImageSource::File { file_id: orig_id },
+                        AnthropicImageSource::File { file_id: round_id },
+                    ) => {
+                        assert_eq!(orig_id, round_id, "Image file_id changed on roundtrip");
+                    }
+                    (o, r) => {
+                        panic!(
+                            "Image source kind changed at index {}: original={:?} roundtrip={:?}",
+                            idx, o, r
+                        );
+                    }
                 }
             }
             (o, r) => {
@@ -102,9 +102,7 @@ fn test_cache_control_is_dropped_in_conversion() {
         id: "test_id".to_string(),
         name: "TestTool".to_string(),
         input: serde_json::json!({"param": "value"}),
-        cache_control: Some(CacheControl {
-            cache_type: "ephemeral".to_string(),
-        }),
+        cache_control: Some(CacheControl::ephemeral()),
     };
 
     let messages = Messages(vec![Message {
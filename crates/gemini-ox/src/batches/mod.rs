@@ -0,0 +1,599 @@
+use crate::{
+    Gemini, GeminiRequestError,
+    files::{deserialize_int64, resource_name},
+    generate_content::{request::GenerateContentRequest, response::GenerateContentResponse},
+};
+use ai_ox_common::request_builder::{Endpoint, HttpMethod};
+use async_stream::try_stream;
+use bon::Builder;
+use futures_util::{StreamExt, stream::BoxStream};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+/// A handler for the batch mode endpoints of the Gemini API.
+///
+/// Batch jobs run asynchronously at a reduced price and are tracked as
+/// long-running operations named `batches/{id}`.
+///
+/// Created via the `gemini.batches()` method.
+#[derive(Debug, Clone)]
+pub struct Batches {
+    gemini: Gemini,
+}
+
+impl Batches {
+    /// Creates a new `Batches` instance.
+    /// This is not intended to be called directly, but rather through `gemini.batches()`.
+    pub(crate) fn new(gemini: Gemini) -> Self {
+        Self { gemini }
+    }
+
+    /// Creates a new batch job.
+    ///
+    /// Returns a builder that can be used to configure and send the request.
+    pub fn create(&self) -> CreateBatchRequestBuilder<create_batch_request_builder::SetGemini> {
+        CreateBatchRequest::builder().gemini(self.gemini.clone())
+    }
+
+    /// Gets the current state of a batch job.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The resource name of the batch, e.g., "batches/abc-123".
+    pub async fn get(&self, name: &str) -> Result<BatchOperation, GeminiRequestError> {
+        let helper = self.gemini.request_helper()?;
+        let endpoint = Endpoint::new(self.path(name), HttpMethod::Get);
+        helper.request(endpoint).await
+    }
+
+    /// Lists batch jobs.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_size` - Optional. The maximum number of batches to return.
+    /// * `page_token` - Optional. A page token, received from a previous `list` call.
+    pub async fn list(
+        &self,
+        page_size: Option<u32>,
+        page_token: Option<String>,
+    ) -> Result<ListBatchesResponse, GeminiRequestError> {
+        let helper = self.gemini.request_helper()?;
+        let mut endpoint = Endpoint::new(self.path("batches"), HttpMethod::Get);
+
+        let mut params = Vec::new();
+        if let Some(size) = page_size {
+            params.push(("pageSize".to_string(), size.to_string()));
+        }
+        if let Some(token) = page_token {
+            params.push(("pageToken".to_string(), token));
+        }
+        if !params.is_empty() {
+            endpoint = endpoint.with_query_params(params);
+        }
+
+        helper.request(endpoint).await
+    }
+
+    /// Starts cancelling a batch job.
+    ///
+    /// Requests that have not run yet are skipped; the job then ends in the
+    /// [`BatchState::Cancelled`] state.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The resource name of the batch to cancel.
+    pub async fn cancel(&self, name: &str) -> Result<(), GeminiRequestError> {
+        let helper = self.gemini.request_helper()?;
+        let endpoint = Endpoint::new(self.path(&format!("{name}:cancel")), HttpMethod::Post);
+        helper.request_unit(endpoint).await
+    }
+
+    /// Deletes a batch job.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The resource name of the batch to delete.
+    pub async fn delete(&self, name: &str) -> Result<(), GeminiRequestError> {
+        let helper = self.gemini.request_helper()?;
+        let endpoint = Endpoint::new(self.path(name), HttpMethod::Delete);
+        helper.request_unit(endpoint).await
+    }
+
+    /// Polls a batch job every `poll_interval` until it has finished.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GeminiRequestError::BatchTimeout`] if the job has not
+    /// finished within `timeout`.
+    pub async fn wait_until_done(
+        &self,
+        name: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<BatchOperation, GeminiRequestError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let operation = self.get(name).await?;
+            if operation.is_done() {
+                return Ok(operation);
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(GeminiRequestError::BatchTimeout {
+                    name: operation.name,
+                    timeout,
+                });
+            }
+            tokio::time::sleep(poll_interval.min(deadline - now)).await;
+        }
+    }
+
+    /// Streams the per-request results of a finished batch job.
+    ///
+    /// Inlined responses are yielded directly; a results file is downloaded
+    /// and parsed line by line. Yields nothing for a job without output, such
+    /// as one that is still running.
+    pub fn results(
+        &self,
+        batch: &BatchOperation,
+    ) -> BoxStream<'static, Result<BatchResult, GeminiRequestError>> {
+        let output = batch.output().cloned();
+        let files = self.gemini.files();
+
+        try_stream! {
+            let Some(output) = output else {
+                return;
+            };
+            if let Some(inlined) = output.inlined_responses {
+                for response in inlined.inlined_responses {
+                    yield BatchResult::from(response);
+                }
+            }
+            if let Some(file) = output.responses_file {
+                let bytes = files.download(&file).await?;
+                for line in bytes.split(|&byte| byte == b'\n') {
+                    if line.iter().all(u8::is_ascii_whitespace) {
+                        continue;
+                    }
+                    let line: ResultLine = serde_json::from_slice(line)?;
+                    yield BatchResult::from(line);
+                }
+            }
+        }
+        .boxed()
+    }
+
+    fn path(&self, resource: &str) -> String {
+        format!("{}/{}", self.gemini.api_version, resource)
+    }
+}
+
+/// A request to create a batch job.
+#[derive(Debug, Clone, Builder)]
+pub struct CreateBatchRequest {
+    /// Inline requests, each with the key it is reported under.
+    #[builder(field)]
+    requests: Vec<InlinedRequest>,
+
+    /// Required. The model that runs the requests, e.g. "gemini-2.5-flash".
+    #[builder(into)]
+    model: String,
+
+    /// Optional. A name for the batch job.
+    #[builder(into)]
+    display_name: Option<String>,
+
+    /// The uploaded JSONL file holding the requests, used instead of inline
+    /// requests. See [`to_jsonl`] for its format.
+    #[builder(into)]
+    input_file: Option<String>,
+
+    /// The Gemini client instance.
+    gemini: Gemini,
+}
+
+impl<S: create_batch_request_builder::State> CreateBatchRequestBuilder<S> {
+    /// Adds an inline request reported under `key`.
+    pub fn request(mut self, key: impl Into<String>, request: GenerateContentRequest) -> Self {
+        self.requests.push(InlinedRequest::new(key, request));
+        self
+    }
+
+    /// Adds inline requests, each reported under its key.
+    pub fn requests(
+        mut self,
+        requests: impl IntoIterator<Item = (impl Into<String>, GenerateContentRequest)>,
+    ) -> Self {
+        self.requests.extend(
+            requests
+                .into_iter()
+                .map(|(key, request)| InlinedRequest::new(key, request)),
+        );
+        self
+    }
+}
+
+impl CreateBatchRequest {
+    /// Sends the request to create the batch job.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if both or neither of inline requests and an input
+    /// file are given, or if the API call fails.
+    pub async fn send(&self) -> Result<BatchOperation, GeminiRequestError> {
+        let input_config = match (&self.input_file, self.requests.is_empty()) {
+            (Some(file), true) => json!({ "fileName": resource_name(file) }),
+            (None, false) => json!({ "requests": { "requests": self.requests } }),
+            (Some(_), false) => {
+                return Err(invalid_input(
+                    "A batch takes either inline requests or an input file, not both",
+                ));
+            }
+            (None, true) => {
+                return Err(invalid_input(
+                    "A batch needs inline requests or an input file",
+                ));
+            }
+        };
+        let mut batch = json!({ "inputConfig": input_config });
+        if let Some(display_name) = &self.display_name {
+            batch["displayName"] = json!(display_name);
+        }
+
+        let model = self.model.strip_prefix("models/").unwrap_or(&self.model);
+        let helper = self.gemini.request_helper()?;
+        let endpoint = Endpoint::new(
+            format!(
+                "{}/models/{model}:batchGenerateContent",
+                self.gemini.api_version
+            ),
+            HttpMethod::Post,
+        );
+        helper
+            .request_json(endpoint, Some(&json!({ "batch": batch })))
+            .await
+    }
+}
+
+fn invalid_input(message: &str) -> GeminiRequestError {
+    GeminiRequestError::InvalidRequestError {
+        code: None,
+        details: json!({}),
+        message: message.to_string(),
+        status: None,
+    }
+}
+
+/// Encodes requests as the JSONL input file of a batch job.
+///
+/// Upload the result with [`Gemini::upload_file`] and pass the file to
+/// [`CreateBatchRequestBuilder::input_file`]. Large batches must use a file
+/// rather than inline requests.
+///
+/// # Errors
+///
+/// Returns an error if a request can't be serialized.
+pub fn to_jsonl(
+    requests: impl IntoIterator<Item = (impl Into<String>, GenerateContentRequest)>,
+) -> Result<Vec<u8>, GeminiRequestError> {
+    let mut jsonl = Vec::new();
+    for (key, request) in requests {
+        serde_json::to_writer(
+            &mut jsonl,
+            &json!({ "key": key.into(), "request": request }),
+        )?;
+        jsonl.push(b'\n');
+    }
+    Ok(jsonl)
+}
+
+/// A request within a batch, with the key its result is reported under.
+#[derive(Debug, Clone, Serialize)]
+pub struct InlinedRequest {
+    /// The request to run.
+    pub request: GenerateContentRequest,
+    /// Metadata identifying the request.
+    pub metadata: RequestMetadata,
+}
+
+impl InlinedRequest {
+    /// Creates an inline request reported under `key`.
+    pub fn new(key: impl Into<String>, request: GenerateContentRequest) -> Self {
+        Self {
+            request,
+            metadata: RequestMetadata { key: key.into() },
+        }
+    }
+}
+
+/// Metadata attached to a request in a batch.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RequestMetadata {
+    /// The key the request's result is reported under.
+    pub key: String,
+}
+
+/// The long-running operation tracking a batch job.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchOperation {
+    /// The resource name of the batch.
+    /// Format: `batches/{id}`
+    pub name: String,
+
+    /// The state of the batch job.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<GenerateContentBatch>,
+
+    /// Whether the operation has finished.
+    #[serde(default)]
+    pub done: bool,
+
+    /// Why the operation failed, if it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<BatchError>,
+
+    /// The output of a finished job.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<BatchOutput>,
+}
+
+impl BatchOperation {
+    /// Returns the state of the job.
+    pub fn state(&self) -> BatchState {
+        self.metadata
+            .as_ref()
+            .map_or(BatchState::Unspecified, |batch| batch.state)
+    }
+
+    /// Returns whether the job has finished, successfully or not.
+    pub fn is_done(&self) -> bool {
+        self.done || self.state().is_terminal()
+    }
+
+    /// Returns the output of the job, once it has some.
+    pub fn output(&self) -> Option<&BatchOutput> {
+        self.response
+            .as_ref()
+            .or_else(|| self.metadata.as_ref()?.output.as_ref())
+    }
+}
+
+/// A batch job.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateContentBatch {
+    /// The model running the requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    /// The name of the batch job.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+
+    /// When the job was created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub create_time: Option<String>, // Using String for RFC 3339 timestamp
+
+    /// When the job was last updated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_time: Option<String>, // Using String for RFC 3339 timestamp
+
+    /// When the job finished.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<String>, // Using String for RFC 3339 timestamp
+
+    /// The state of the job.
+    #[serde(default)]
+    pub state: BatchState,
+
+    /// Counts of the job's requests by outcome.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_stats: Option<BatchStats>,
+
+    /// The results of the job.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<BatchOutput>,
+}
+
+/// The state of a batch job.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BatchState {
+    /// The state is not specified.
+    #[default]
+    #[serde(rename = "BATCH_STATE_UNSPECIFIED")]
+    Unspecified,
+    /// The job is queued.
+    #[serde(rename = "BATCH_STATE_PENDING")]
+    Pending,
+    /// The job is running.
+    #[serde(rename = "BATCH_STATE_RUNNING")]
+    Running,
+    /// The job finished; individual requests may still have failed.
+    #[serde(rename = "BATCH_STATE_SUCCEEDED")]
+    Succeeded,
+    /// The job failed.
+    #[serde(rename = "BATCH_STATE_FAILED")]
+    Failed,
+    /// The job was cancelled.
+    #[serde(rename = "BATCH_STATE_CANCELLED")]
+    Cancelled,
+    /// The job did not finish within its time limit.
+    #[serde(rename = "BATCH_STATE_EXPIRED")]
+    Expired,
+}
+
+impl BatchState {
+    /// Returns whether the job has stopped running.
+    pub fn is_terminal(self) -> bool {
+        matches!(
+            self,
+            Self::Succeeded | Self::Failed | Self::Cancelled | Self::Expired
+        )
+    }
+}
+
+/// Counts of a batch job's requests by outcome.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchStats {
+    /// The number of requests in the batch.
+    #[serde(default, deserialize_with = "deserialize_int64")]
+    pub request_count: Option<u64>,
+    /// The number of requests that succeeded.
+    #[serde(default, deserialize_with = "deserialize_int64")]
+    pub successful_request_count: Option<u64>,
+    /// The number of requests that failed.
+    #[serde(default, deserialize_with = "deserialize_int64")]
+    pub failed_request_count: Option<u64>,
+    /// The number of requests still to run.
+    #[serde(default, deserialize_with = "deserialize_int64")]
+    pub pending_request_count: Option<u64>,
+}
+
+/// Where the results of a batch job are.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchOutput {
+    /// The JSONL file holding the results, for jobs created from a file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub responses_file: Option<String>,
+
+    /// The results, for jobs created from inline requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inlined_responses: Option<InlinedResponses>,
+}
+
+/// The results of a batch job created from inline requests.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct InlinedResponses {
+    /// The result of each request, in request order.
+    #[serde(default)]
+    pub inlined_responses: Vec<InlinedResponse>,
+}
+
+/// The result of one inline request.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InlinedResponse {
+    /// Metadata of the request this result belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<RequestMetadata>,
+    /// The response, if the request succeeded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<GenerateContentResponse>,
+    /// The error, if the request failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<BatchError>,
+}
+
+/// The error status of a failed batch job or request.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BatchError {
+    /// The status code.
+    #[serde(default)]
+    pub code: i32,
+    /// A developer-facing error message.
+    #[serde(default)]
+    pub message: String,
+    /// Details about the error.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<serde_json::Value>,
+}
+
+/// The result of one request in a batch job.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchResult {
+    /// The key the request was submitted under.
+    pub key: Option<String>,
+    /// The response, or why the request failed.
+    pub response: Result<GenerateContentResponse, BatchError>,
+}
+
+impl From<InlinedResponse> for BatchResult {
+    fn from(inlined: InlinedResponse) -> Self {
+        Self {
+            key: inlined.metadata.map(|metadata| metadata.key),
+            response: outcome(inlined.response, inlined.error),
+        }
+    }
+}
+
+/// A line of a batch results file.
+#[derive(Debug, Deserialize)]
+struct ResultLine {
+    /// The key of the request.
+    #[serde(default)]
+    key: Option<String>,
+    /// The response, if the request succeeded.
+    #[serde(default)]
+    response: Option<GenerateContentResponse>,
+    /// The error, if the request failed.
+    #[serde(default)]
+    error: Option<BatchError>,
+}
+
+impl From<ResultLine> for BatchResult {
+    fn from(line: ResultLine) -> Self {
+        Self {
+            key: line.key,
+            response: outcome(line.response, line.error),
+        }
+    }
+}
+
+/// Combines the two halves of a result into one.
+fn outcome(
+    response: Option<GenerateContentResponse>,
+    error: Option<BatchError>,
+) -> Result<GenerateContentResponse, BatchError> {
+    match (response, error) {
+        (_, Some(error)) => Err(error),
+        (Some(response), None) => Ok(response),
+        (None, None) => Err(BatchError {
+            code: 0,
+            message: "Result has neither a response nor an error".to_string(),
+            details: Vec::new(),
+        }),
+    }
+}
+
+/// The response for a `batches.list` request.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ListBatchesResponse {
+    /// List of batch jobs.
+    #[serde(default)]
+    pub operations: Vec<BatchOperation>,
+
+    /// A token, which can be sent as `pageToken` to retrieve the next page.
+    /// If this field is omitted, there are no subsequent pages.
+    #[serde(default)]
+    pub next_page_token: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jsonl_has_one_keyed_request_per_line() {
+        let request = GenerateContentRequest::builder()
+            .model("gemini-2.5-flash")
+            .content("Hi")
+            .build();
+        let jsonl =
+            to_jsonl([("a", request.clone()), ("b", request)]).expect("requests should encode");
+
+        let lines: Vec<serde_json::Value> = jsonl
+            .split(|&byte| byte == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice(line).expect("line should be JSON"))
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1]["key"], "b");
+        assert_eq!(lines[0]["request"]["contents"][0]["parts"][0]["text"], "Hi");
+    }
+}
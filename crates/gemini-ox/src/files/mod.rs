@@ -1,13 +1,12 @@
+use ai_ox_common::request_builder::{Endpoint, HttpMethod};
 use bon::Builder;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::json;
 use std::path::PathBuf;
-use tokio::fs::File;
+use std::time::{Duration, Instant};
 
 use crate::{Gemini, GeminiRequestError};
 
-const STANDARD_BASE_URL: &str = "https://generativelanguage.googleapis.com";
-
 #[derive(Debug, Deserialize, Serialize)]
 pub struct FileInfo {
     pub file: FileData,
@@ -42,68 +41,21 @@ pub struct FileStreamUploadRequest {
 
 impl FileUploadRequest {
     pub async fn send(self) -> Result<String, GeminiRequestError> {
-        let num_bytes = self.data.len();
-
-        let api_key = self
-            .gemini
-            .api_key
-            .as_ref()
-            .ok_or(GeminiRequestError::AuthenticationMissing)?;
-
-        let init_url = format!(
-            "{}/upload/{}/files",
-            STANDARD_BASE_URL,
-            self.gemini.api_version
-        );
-
-        let init_response = self
-            .gemini
-            .client
-            .post(&init_url)
-            .query(&[("key", api_key.as_str())])
-            .header("X-Goog-Upload-Protocol", "resumable")
-            .header("X-Goog-Upload-Command", "start")
-            .header("X-Goog-Upload-Header-Content-Length", num_bytes.to_string())
-            .header("X-Goog-Upload-Header-Content-Type", &self.mime_type)
-            .json(&json!({
-                "file": {
-                    "display_name": self.file_name
-                }
-            }))
-            .send()
-            .await?;
-
-        let upload_url = init_response
-            .headers()
-            .get("X-Goog-Upload-URL")
-            .and_then(|h| h.to_str().ok())
-            .ok_or_else(|| GeminiRequestError::InvalidRequestError {
-                code: None,
-                details: json!({}),
-                message: "Missing upload URL in response".to_string(),
-                status: None,
-            })?
-            .to_string();
-
-        let upload_response = self
-            .gemini
-            .client
-            .post(&upload_url)
-            .header("Content-Length", num_bytes.to_string())
-            .header("X-Goog-Upload-Offset", "0")
-            .header("X-Goog-Upload-Command", "upload, finalize")
-            .body(self.data)
-            .send()
-            .await?;
-
-        let file_info: FileInfo = upload_response.json().await?;
-        Ok(file_info.file.uri)
+        let num_bytes = self.data.len() as u64;
+        upload(
+            &self.gemini,
+            &self.file_name,
+            &self.mime_type,
+            num_bytes,
+            self.data.into(),
+        )
+        .await
     }
 }
 
 impl FileStreamUploadRequest {
     pub async fn send(self) -> Result<String, GeminiRequestError> {
-        let file = File::open(&self.file_path).await.map_err(|e| {
+        let file = tokio::fs::File::open(&self.file_path).await.map_err(|e| {
             GeminiRequestError::InvalidRequestError {
                 code: None,
                 details: json!({}),
@@ -129,64 +81,397 @@ impl FileStreamUploadRequest {
             .and_then(|name| name.to_str())
             .unwrap_or("unnamed_file");
 
-        let api_key = self
-            .gemini
-            .api_key
-            .as_ref()
-            .ok_or(GeminiRequestError::AuthenticationMissing)?;
+        upload(
+            &self.gemini,
+            file_name,
+            &self.mime_type,
+            num_bytes,
+            reqwest::Body::from(file),
+        )
+        .await
+    }
+}
+
+/// Uploads `body` with the resumable upload protocol, returning the URI of
+/// the new file.
+///
+/// The start request returns the URL the bytes are then sent to; both go
+/// through the client's transport.
+async fn upload(
+    gemini: &Gemini,
+    display_name: &str,
+    mime_type: &str,
+    num_bytes: u64,
+    body: reqwest::Body,
+) -> Result<String, GeminiRequestError> {
+    let helper = gemini.request_helper_for_api_key()?;
+
+    let endpoint = Endpoint::new(
+        format!("upload/{}/files", gemini.api_version),
+        HttpMethod::Post,
+    )
+    .with_header("X-Goog-Upload-Protocol", "resumable")
+    .with_header("X-Goog-Upload-Command", "start")
+    .with_header("X-Goog-Upload-Header-Content-Length", num_bytes.to_string())
+    .with_header("X-Goog-Upload-Header-Content-Type", mime_type);
+    let start = helper.build_request(&endpoint)?.json(&json!({
+        "file": {
+            "display_name": display_name
+        }
+    }));
+    let init_response = checked(helper.send(start).await?).await?;
+
+    let upload_url = init_response
+        .headers()
+        .get("X-Goog-Upload-URL")
+        .and_then(|h| h.to_str().ok())
+        .ok_or_else(|| GeminiRequestError::InvalidRequestError {
+            code: None,
+            details: json!({}),
+            message: "Missing upload URL in response".to_string(),
+            status: None,
+        })?
+        .to_string();
+
+    let finish = gemini
+        .client
+        .post(&upload_url)
+        .header("Content-Length", num_bytes.to_string())
+        .header("X-Goog-Upload-Offset", "0")
+        .header("X-Goog-Upload-Command", "upload, finalize")
+        .body(body);
+    let upload_response = checked(helper.send(finish).await?).await?;
+
+    let file_info: FileInfo = upload_response.json().await?;
+    Ok(file_info.file.uri)
+}
 
-        let init_url = format!(
-            "{}/upload/{}/files",
-            STANDARD_BASE_URL,
-            self.gemini.api_version
-        );
+/// Turns an unsuccessful upload response into an error.
+async fn checked(response: reqwest::Response) -> Result<reqwest::Response, GeminiRequestError> {
+    let status = response.status();
+    if status.is_success() {
+        Ok(response)
+    } else {
+        Err(crate::parse_error_response(status, response.bytes().await?))
+    }
+}
+
+/// Longest pause between polls while waiting for a file to become active.
+const MAX_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A handler for the Files API endpoints.
+///
+/// Created via the `gemini.files()` method. File names may be given as
+/// `files/{id}`, as a bare `{id}` or as the URI returned by an upload.
+#[derive(Debug, Clone)]
+pub struct Files {
+    gemini: Gemini,
+}
 
-        let init_response = self
-            .gemini
-            .client
-            .post(&init_url)
-            .query(&[("key", api_key.as_str())])
-            .header("X-Goog-Upload-Protocol", "resumable")
-            .header("X-Goog-Upload-Command", "start")
-            .header("X-Goog-Upload-Header-Content-Length", num_bytes.to_string())
-            .header("X-Goog-Upload-Header-Content-Type", &self.mime_type)
-            .json(&json!({
-                "file": {
-                    "display_name": file_name
+impl Files {
+    /// Creates a new `Files` instance.
+    /// This is not intended to be called directly, but rather through `gemini.files()`.
+    pub(crate) fn new(gemini: Gemini) -> Self {
+        Self { gemini }
+    }
+
+    /// Lists one page of the files owned by the project.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_size` - Optional. The maximum number of files to return (at most 100).
+    /// * `page_token` - Optional. A page token, received from a previous `list` call.
+    pub async fn list(
+        &self,
+        page_size: Option<u32>,
+        page_token: Option<String>,
+    ) -> Result<ListFilesResponse, GeminiRequestError> {
+        let helper = self.gemini.request_helper()?;
+        let mut endpoint = Endpoint::new(self.path("files"), HttpMethod::Get);
+
+        let mut params = Vec::new();
+        if let Some(size) = page_size {
+            params.push(("pageSize".to_string(), size.to_string()));
+        }
+        if let Some(token) = page_token {
+            params.push(("pageToken".to_string(), token));
+        }
+        if !params.is_empty() {
+            endpoint = endpoint.with_query_params(params);
+        }
+
+        helper.request(endpoint).await
+    }
+
+    /// Lists every file owned by the project, following page tokens.
+    pub async fn list_all(&self) -> Result<Vec<File>, GeminiRequestError> {
+        let mut files = Vec::new();
+        let mut page_token = None;
+        loop {
+            let page = self.list(None, page_token).await?;
+            files.extend(page.files);
+            match page.next_page_token {
+                Some(token) if !token.is_empty() => page_token = Some(token),
+                _ => return Ok(files),
+            }
+        }
+    }
+
+    /// Gets the metadata of a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The file to retrieve, e.g. "files/abc-123".
+    pub async fn get(&self, name: &str) -> Result<File, GeminiRequestError> {
+        let helper = self.gemini.request_helper()?;
+        let endpoint = Endpoint::new(self.path(&resource_name(name)), HttpMethod::Get);
+        helper.request(endpoint).await
+    }
+
+    /// Deletes a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The file to delete, e.g. "files/abc-123".
+    pub async fn delete(&self, name: &str) -> Result<(), GeminiRequestError> {
+        let helper = self.gemini.request_helper()?;
+        let endpoint = Endpoint::new(self.path(&resource_name(name)), HttpMethod::Delete);
+        helper.request_unit(endpoint).await
+    }
+
+    /// Downloads the contents of a file.
+    ///
+    /// Only files with a [`download_uri`](File::download_uri), such as those
+    /// generated by the API, can be downloaded; uploaded files can't.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The file to download, e.g. "files/abc-123".
+    pub async fn download(&self, name: &str) -> Result<bytes::Bytes, GeminiRequestError> {
+        let helper = self.gemini.request_helper()?;
+        let endpoint = Endpoint::new(
+            format!(
+                "download/{}",
+                self.path(&format!("{}:download", resource_name(name)))
+            ),
+            HttpMethod::Get,
+        )
+        .with_query_params(vec![("alt".to_string(), "media".to_string())]);
+        helper.request_bytes(endpoint).await
+    }
+
+    /// Polls a file until it has finished processing.
+    ///
+    /// Uploaded videos and large documents are `PROCESSING` for a while and
+    /// can't be referenced in a request until they are `ACTIVE`. Polls start
+    /// after half a second and back off to at most ten seconds apart.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GeminiRequestError::FileProcessingFailed`] if processing
+    /// fails and [`GeminiRequestError::FileProcessingTimeout`] if the file is
+    /// not active within `timeout`.
+    pub async fn wait_until_active(
+        &self,
+        name: &str,
+        timeout: Duration,
+    ) -> Result<File, GeminiRequestError> {
+        let deadline = Instant::now() + timeout;
+        let mut interval = Duration::from_millis(500);
+        loop {
+            let file = self.get(name).await?;
+            match file.state {
+                FileState::Active => return Ok(file),
+                FileState::Failed => {
+                    return Err(GeminiRequestError::FileProcessingFailed {
+                        message: file
+                            .error
+                            .map_or_else(|| "unknown error".to_string(), |error| error.message),
+                        name: file.name,
+                    });
                 }
-            }))
-            .send()
-            .await?;
-
-        let upload_url = init_response
-            .headers()
-            .get("X-Goog-Upload-URL")
-            .and_then(|h| h.to_str().ok())
-            .ok_or_else(|| GeminiRequestError::InvalidRequestError {
-                code: None,
-                details: json!({}),
-                message: "Missing upload URL in response".to_string(),
-                status: None,
-            })?
-            .to_string();
-
-        let upload_response = self
-            .gemini
-            .client
-            .post(&upload_url)
-            .header("Content-Length", num_bytes.to_string())
-            .header("X-Goog-Upload-Offset", "0")
-            .header("X-Goog-Upload-Command", "upload, finalize")
-            .body(reqwest::Body::from(file))
-            .send()
-            .await?;
-
-        let file_info: FileInfo = upload_response.json().await?;
-        Ok(file_info.file.uri)
+                FileState::Processing | FileState::StateUnspecified => {}
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(GeminiRequestError::FileProcessingTimeout {
+                    name: file.name,
+                    timeout,
+                });
+            }
+            tokio::time::sleep(interval.min(deadline - now)).await;
+            interval = (interval * 2).min(MAX_POLL_INTERVAL);
+        }
+    }
+
+    fn path(&self, resource: &str) -> String {
+        format!("{}/{}", self.gemini.api_version, resource)
+    }
+}
+
+/// Normalizes a file name, bare ID or file URI to `files/{id}`.
+pub(crate) fn resource_name(name: &str) -> String {
+    match name.rfind("files/") {
+        Some(start) => name[start..].to_string(),
+        None => format!("files/{name}"),
+    }
+}
+
+/// A file uploaded to or generated by the Files API.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct File {
+    /// The resource name of the file.
+    /// Format: `files/{id}`
+    pub name: String,
+
+    /// Optional. The human-readable display name of the file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+
+    /// Output only. MIME type of the file.
+    #[serde(default)]
+    pub mime_type: String,
+
+    /// Output only. Size of the file in bytes.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_int64",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub size_bytes: Option<u64>,
+
+    /// Output only. Creation time of the file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub create_time: Option<String>, // Using String for RFC 3339 timestamp
+
+    /// Output only. When the file was last updated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_time: Option<String>, // Using String for RFC 3339 timestamp
+
+    /// Output only. When the file will be deleted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiration_time: Option<String>, // Using String for RFC 3339 timestamp
+
+    /// Output only. Base64-encoded SHA-256 hash of the uploaded bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256_hash: Option<String>,
+
+    /// Output only. The URI used to reference the file in requests.
+    #[serde(default)]
+    pub uri: String,
+
+    /// Output only. The URI the file can be downloaded from, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download_uri: Option<String>,
+
+    /// Output only. Processing state of the file.
+    #[serde(default)]
+    pub state: FileState,
+
+    /// Output only. Where the file came from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<FileSource>,
+
+    /// Output only. Why processing failed, if the state is `FAILED`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<FileError>,
+
+    /// Output only. Metadata for a video, such as its duration.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub video_metadata: Option<serde_json::Value>,
+}
+
+impl File {
+    /// Returns whether the file can be referenced in requests.
+    pub fn is_active(&self) -> bool {
+        self.state == FileState::Active
+    }
+}
+
+/// Processing state of a [`File`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FileState {
+    /// The default value, used when the state is omitted.
+    #[default]
+    StateUnspecified,
+    /// The file is being processed and can't be used yet.
+    Processing,
+    /// The file is processed and available for use.
+    Active,
+    /// The file failed processing.
+    Failed,
+}
+
+/// Where a [`File`] came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FileSource {
+    /// Used if the source is not specified.
+    SourceUnspecified,
+    /// The file was uploaded by the user.
+    Uploaded,
+    /// The file was generated by Google.
+    Generated,
+    /// The file was registered from an existing location.
+    Registered,
+}
+
+/// The error status of a [`File`] that failed processing.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FileError {
+    /// The status code.
+    #[serde(default)]
+    pub code: i32,
+    /// A developer-facing error message.
+    #[serde(default)]
+    pub message: String,
+    /// Details about the error.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<serde_json::Value>,
+}
+
+/// The response for a `files.list` request.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ListFilesResponse {
+    /// List of files.
+    #[serde(default)]
+    pub files: Vec<File>,
+
+    /// A token, which can be sent as `pageToken` to retrieve the next page.
+    /// If this field is omitted, there are no subsequent pages.
+    #[serde(default)]
+    pub next_page_token: Option<String>,
+}
+
+/// Reads an int64, which the API encodes as a JSON string.
+pub(crate) fn deserialize_int64<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Int64 {
+        Number(u64),
+        String(String),
+    }
+
+    match Option::<Int64>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(Int64::Number(n)) => Ok(Some(n)),
+        Some(Int64::String(s)) => s.parse().map(Some).map_err(serde::de::Error::custom),
     }
 }
 
 impl Gemini {
+    /// Returns a handler for the Files API.
+    pub fn files(&self) -> Files {
+        Files::new(self.clone())
+    }
+
     pub fn upload_file(&self) -> FileUploadRequestBuilder<file_upload_request_builder::SetGemini> {
         FileUploadRequest::builder().gemini(self.clone())
     }
@@ -203,6 +488,17 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    #[test]
+    fn test_resource_name_accepts_ids_and_uris() {
+        for name in [
+            "abc-123",
+            "files/abc-123",
+            "https://generativelanguage.googleapis.com/v1beta/files/abc-123",
+        ] {
+            assert_eq!(resource_name(name), "files/abc-123");
+        }
+    }
+
     fn get_api_key() -> String {
         std::env::var("GOOGLE_AI_API_KEY").expect("GOOGLE_AI_API_KEY must be set")
     }
@@ -475,6 +475,9 @@ pub struct GenerationConfig {
     /// Note: The default value varies by model, see the `Model.top_k` attribute of the Model returned from the getModel function. Empty topK field in Model indicates the model doesn't apply top-k sampling and doesn't allow setting topK on requests.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub top_k: Option<u64>,
+    /// Seed used in decoding. If not set, the request uses a randomly generated seed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u32>,
     /// Config for thinking features.
     /// Note: An error will be returned if this field is set for models that don't support thinking.
     #[serde(skip_serializing_if = "Option::is_none")]
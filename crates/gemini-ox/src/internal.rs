@@ -37,7 +37,7 @@ impl From<CommonRequestError> for GeminiRequestError {
                 status: None,
                 details: details.unwrap_or(Value::Null),
             },
-            CommonRequestError::RateLimit => GeminiRequestError::RateLimit,
+            CommonRequestError::RateLimit(_) => GeminiRequestError::RateLimit,
             CommonRequestError::AuthenticationMissing => GeminiRequestError::AuthenticationMissing,
             CommonRequestError::InvalidModel(model) => GeminiRequestError::InvalidRequestError {
                 code: Some("INVALID_MODEL".to_string()),
@@ -111,7 +111,8 @@ impl GeminiRequestHelper {
         let (auth_method, is_oauth) = Self::select_auth(gemini, allow_oauth)?;
         let config = RequestConfig::new(base_url.to_string())
             .with_auth(auth_method)
-            .with_header("content-type", "application/json");
+            .with_header("content-type", "application/json")
+            .with_transport(gemini.transport.clone());
 
         Ok(Self {
             client: gemini.client.clone(),
@@ -176,6 +177,35 @@ impl GeminiRequestHelper {
             .map_err(GeminiRequestError::from)
     }
 
+    pub fn build_request(
+        &self,
+        endpoint: &Endpoint,
+    ) -> Result<reqwest::RequestBuilder, GeminiRequestError> {
+        self.builder()
+            .build_request(endpoint)
+            .map_err(GeminiRequestError::from)
+    }
+
+    pub async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, GeminiRequestError> {
+        self.builder()
+            .send(request)
+            .await
+            .map_err(GeminiRequestError::from)
+    }
+
+    pub async fn request_bytes(
+        &self,
+        endpoint: Endpoint,
+    ) -> Result<bytes::Bytes, GeminiRequestError> {
+        self.builder()
+            .request_bytes(&endpoint)
+            .await
+            .map_err(GeminiRequestError::from)
+    }
+
     fn build_generate_content_body(
         &self,
         request: &GenerateContentRequest,
@@ -6,9 +6,10 @@
 )]
 
 // pub mod agent;
+pub mod batches;
 pub mod cache;
 pub mod content;
-// pub mod files;
+pub mod files;
 pub mod embedding;
 pub mod generate_content;
 mod internal;
@@ -48,6 +49,7 @@ use core::fmt;
 // use std::sync::Arc; // Unused import removed - ensure this line is gone
 
 use crate::internal::GeminiRequestHelper;
+use ai_ox_common::Transport;
 use bon::Builder;
 #[cfg(feature = "leaky-bucket")] // Add cfg attribute here
 use leaky_bucket::RateLimiter;
@@ -239,6 +241,9 @@ pub struct Gemini {
     pub(crate) leaky_bucket: Option<Arc<RateLimiter>>,
     #[builder(default = "v1beta".to_string(), into)]
     pub(crate) api_version: String,
+    /// Live, recording or replaying transport for API requests
+    #[builder(default)]
+    pub(crate) transport: Transport,
 }
 
 impl Gemini {
@@ -252,6 +257,7 @@ impl Gemini {
             #[cfg(feature = "leaky-bucket")]
             leaky_bucket: None,
             api_version: "v1beta".to_string(),
+            transport: Transport::default(),
         }
     }
 
@@ -265,6 +271,7 @@ impl Gemini {
             #[cfg(feature = "leaky-bucket")]
             leaky_bucket: None,
             api_version: "v1beta".to_string(),
+            transport: Transport::default(),
         }
     }
 
@@ -281,6 +288,7 @@ impl Gemini {
             #[cfg(feature = "leaky-bucket")]
             leaky_bucket: None,
             api_version: "v1beta".to_string(),
+            transport: Transport::default(),
         }
     }
 
@@ -318,6 +326,11 @@ impl Gemini {
         crate::live::LiveOperation::builder().gemini(self.clone())
     }
 
+    /// Returns a handler for batch mode operations.
+    pub fn batches(&self) -> crate::batches::Batches {
+        crate::batches::Batches::new(self.clone())
+    }
+
     /// Returns a handler for caching operations.
     pub fn caches(&self) -> crate::cache::Caches {
         crate::cache::Caches::new(self.clone())
@@ -360,6 +373,7 @@ impl fmt::Debug for Gemini {
             .field("project_id", &self.project_id)
             .field("client", &self.client)
             .field("api_version", &self.api_version)
+            .field("transport", &self.transport)
             .finish_non_exhaustive()
     }
 }
@@ -447,6 +461,24 @@ pub enum GeminiRequestError {
     /// I/O errors
     #[error(transparent)]
     IoError(#[from] std::io::Error),
+
+    /// A file failed processing
+    #[error("File {name} failed processing: {message}")]
+    FileProcessingFailed { name: String, message: String },
+
+    /// A file was still processing when the wait timed out
+    #[error("File {name} still processing after {timeout:?}")]
+    FileProcessingTimeout {
+        name: String,
+        timeout: std::time::Duration,
+    },
+
+    /// A batch job was still running when the wait timed out
+    #[error("Batch {name} still running after {timeout:?}")]
+    BatchTimeout {
+        name: String,
+        timeout: std::time::Duration,
+    },
 }
 
 impl Serialize for GeminiRequestError {
@@ -530,6 +562,27 @@ impl Serialize for GeminiRequestError {
                 state.serialize_field("error", &e.to_string())?;
                 state.end()
             }
+            GeminiRequestError::FileProcessingFailed { name, message } => {
+                let mut state = serializer.serialize_struct("GeminiRequestError", 3)?;
+                state.serialize_field("type", "FileProcessingFailed")?;
+                state.serialize_field("name", name)?;
+                state.serialize_field("message", message)?;
+                state.end()
+            }
+            GeminiRequestError::FileProcessingTimeout { name, timeout } => {
+                let mut state = serializer.serialize_struct("GeminiRequestError", 3)?;
+                state.serialize_field("type", "FileProcessingTimeout")?;
+                state.serialize_field("name", name)?;
+                state.serialize_field("timeout_secs", &timeout.as_secs_f64())?;
+                state.end()
+            }
+            GeminiRequestError::BatchTimeout { name, timeout } => {
+                let mut state = serializer.serialize_struct("GeminiRequestError", 3)?;
+                state.serialize_field("type", "BatchTimeout")?;
+                state.serialize_field("name", name)?;
+                state.serialize_field("timeout_secs", &timeout.as_secs_f64())?;
+                state.end()
+            }
         }
     }
 }
@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use ai_ox_common::Transport;
+use futures_util::TryStreamExt;
+use gemini_ox::{
+    Gemini, GeminiRequestError, GenerateContentRequest,
+    batches::{BatchResult, BatchState},
+};
+
+/// Client that serves responses from a cassette at `tests/cassettes/{name}.json`
+///
+/// The cassettes are hand-written fixtures in the recorded format, not
+/// recordings. Replay checks the method, URL and JSON body of every request.
+/// Set `AOX_TRANSPORT=record` and `GEMINI_API_KEY` to replace one with a recording.
+fn client(name: &str) -> Gemini {
+    let path = format!("{}/tests/cassettes/{name}.json", env!("CARGO_MANIFEST_DIR"));
+    let api_key = std::env::var("GEMINI_API_KEY").unwrap_or_else(|_| "test-key".to_string());
+    Gemini::builder()
+        .api_key(api_key)
+        .transport(Transport::from_env(path).expect("cassette should load"))
+        .build()
+}
+
+fn request(prompt: &str) -> GenerateContentRequest {
+    GenerateContentRequest::builder()
+        .model("gemini-2.5-flash")
+        .content(prompt)
+        .build()
+}
+
+fn text(result: &BatchResult) -> Option<&str> {
+    let response = result.response.as_ref().ok()?;
+    let text = response
+        .candidates
+        .first()?
+        .content
+        .parts()
+        .first()?
+        .as_text()?;
+    Some(text.as_str())
+}
+
+#[tokio::test]
+async fn test_replayed_batch_lifecycle() {
+    let batches = client("batches").batches();
+
+    let job = batches
+        .create()
+        .model("gemini-2.5-flash")
+        .display_name("nightly-eval")
+        .request("sum", request("What is 2 + 2? Answer with a number."))
+        .request("broken", request(""))
+        .build()
+        .send()
+        .await
+        .expect("create should replay");
+    assert_eq!(job.name, "batches/job-1");
+    assert_eq!(job.state(), BatchState::Pending);
+
+    let job = batches
+        .wait_until_done(&job.name, Duration::from_millis(10), Duration::from_secs(5))
+        .await
+        .expect("batch should finish");
+    assert_eq!(job.state(), BatchState::Succeeded);
+    let stats = job.metadata.as_ref().and_then(|m| m.batch_stats.clone());
+    assert_eq!(stats.and_then(|stats| stats.failed_request_count), Some(1));
+
+    let results: Vec<BatchResult> = batches
+        .results(&job)
+        .try_collect()
+        .await
+        .expect("inline results should parse");
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].key.as_deref(), Some("sum"));
+    assert_eq!(text(&results[0]), Some("4"));
+    assert!(matches!(&results[1].response, Err(error) if error.code == 3));
+
+    let listed = batches
+        .list(Some(10), None)
+        .await
+        .expect("list should replay");
+    let file_job = &listed.operations[0];
+    let results: Vec<BatchResult> = batches
+        .results(file_job)
+        .try_collect()
+        .await
+        .expect("file results should parse");
+    let keys: Vec<_> = results.iter().map(|r| r.key.as_deref()).collect();
+    assert_eq!(keys, [Some("first"), Some("second")]);
+    assert_eq!(text(&results[1]), Some("Rome"));
+
+    batches
+        .cancel("batches/job-3")
+        .await
+        .expect("cancel should replay");
+}
+
+#[tokio::test]
+async fn test_batch_needs_exactly_one_input() {
+    let batches = Gemini::new("test-key").batches();
+
+    let empty = batches
+        .create()
+        .model("gemini-2.5-flash")
+        .build()
+        .send()
+        .await;
+    assert!(matches!(
+        empty,
+        Err(GeminiRequestError::InvalidRequestError { .. })
+    ));
+
+    let both = batches
+        .create()
+        .model("gemini-2.5-flash")
+        .request("a", request("Hi"))
+        .input_file("files/input-1")
+        .build()
+        .send()
+        .await;
+    assert!(matches!(
+        both,
+        Err(GeminiRequestError::InvalidRequestError { .. })
+    ));
+}
@@ -1,4 +1,5 @@
 use crate::GroqRequestError;
+use ai_ox_common::request_builder::{Endpoint, HttpMethod};
 use bon::Builder;
 use serde::{Deserialize, Serialize};
 
@@ -52,15 +53,8 @@ impl crate::Groq {
         &self,
         request: &SpeechRequest,
     ) -> Result<SpeechResponse, GroqRequestError> {
-        let url = format!("{}/openai/v1/audio/speech", self.base_url);
-
-        let res = self
-            .client
-            .post(&url)
-            .bearer_auth(&self.api_key)
-            .json(request)
-            .send()
-            .await?;
+        let endpoint = Endpoint::new("openai/v1/audio/speech", HttpMethod::Post);
+        let res = self.request_helper().send_json(&endpoint, request).await?;
 
         if res.status().is_success() {
             let content_type = res
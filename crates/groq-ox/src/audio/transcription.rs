@@ -1,4 +1,5 @@
 use crate::GroqRequestError;
+use ai_ox_common::request_builder::{Endpoint, HttpMethod};
 use bon::Builder;
 use serde::{Deserialize, Serialize};
 
@@ -137,8 +138,6 @@ impl crate::Groq {
             });
         }
 
-        let url = format!("{}/openai/v1/audio/transcriptions", self.base_url);
-
         // Create multipart form
         let mut form = reqwest::multipart::Form::new().text("model", request.model.clone());
 
@@ -168,12 +167,10 @@ impl crate::Groq {
         let part = reqwest::multipart::Part::bytes(request.file.clone()).file_name("audio.mp3");
         form = form.part("file", part);
 
+        let endpoint = Endpoint::new("openai/v1/audio/transcriptions", HttpMethod::Post);
         let res = self
-            .client
-            .post(&url)
-            .bearer_auth(&self.api_key)
-            .multipart(form)
-            .send()
+            .request_helper()
+            .send_multipart(&endpoint, form)
             .await?;
 
         if res.status().is_success() {
@@ -1,10 +1,11 @@
 use crate::{ChatRequest, ChatResponse, GroqRequestError, response::ChatCompletionChunk};
 use ai_ox_common::{
-    BoxStream,
+    BoxStream, Transport,
     error::ProviderError,
     request_builder::{AuthMethod, Endpoint, HttpMethod, RequestBuilder, RequestConfig},
 };
 use futures_util::stream::BoxStream as FuturesBoxStream;
+use serde::{Serialize, de::DeserializeOwned};
 
 /// Groq client helper methods using the common RequestBuilder
 pub struct GroqRequestHelper {
@@ -12,10 +13,15 @@ pub struct GroqRequestHelper {
 }
 
 impl GroqRequestHelper {
-    pub fn new(client: reqwest::Client, base_url: &str, api_key: &str) -> Self {
+    pub fn new(
+        client: reqwest::Client,
+        base_url: &str,
+        api_key: &str,
+        transport: &Transport,
+    ) -> Self {
         let config = RequestConfig::new(base_url)
             .with_auth(AuthMethod::Bearer(api_key.to_string()))
-            .with_header("content-type", "application/json");
+            .with_transport(transport.clone());
 
         let request_builder = RequestBuilder::new(client, config);
 
@@ -49,4 +55,35 @@ impl GroqRequestHelper {
         // Direct cast since GroqRequestError = ProviderError
         stream
     }
+
+    /// Execute a GET request and return the deserialized response
+    pub async fn request<T: DeserializeOwned>(
+        &self,
+        endpoint: &Endpoint,
+    ) -> Result<T, GroqRequestError> {
+        Ok(self.request_builder.request(endpoint).await?)
+    }
+
+    /// Send a JSON request and return the raw response
+    pub async fn send_json<B: Serialize>(
+        &self,
+        endpoint: &Endpoint,
+        body: &B,
+    ) -> Result<reqwest::Response, GroqRequestError> {
+        let request = self.request_builder.build_request(endpoint)?.json(body);
+        Ok(self.request_builder.send(request).await?)
+    }
+
+    /// Send a multipart form request and return the raw response
+    pub async fn send_multipart(
+        &self,
+        endpoint: &Endpoint,
+        form: reqwest::multipart::Form,
+    ) -> Result<reqwest::Response, GroqRequestError> {
+        let request = self
+            .request_builder
+            .build_request_with_options(endpoint, false)?
+            .multipart(form);
+        Ok(self.request_builder.send(request).await?)
+    }
 }
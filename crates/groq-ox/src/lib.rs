@@ -50,6 +50,7 @@ pub mod tool {
     }
 }
 
+use ai_ox_common::Transport;
 use bon::Builder;
 use core::fmt;
 use futures_util::stream::BoxStream;
@@ -72,6 +73,9 @@ pub struct Groq {
     pub(crate) leaky_bucket: Option<Arc<RateLimiter>>,
     #[builder(default = BASE_URL.to_string(), into)]
     pub(crate) base_url: String,
+    /// Live, recording or replaying transport for API requests
+    #[builder(default)]
+    pub(crate) transport: Transport,
 }
 
 impl Groq {
@@ -83,6 +87,7 @@ impl Groq {
             #[cfg(feature = "leaky-bucket")]
             leaky_bucket: None,
             base_url: BASE_URL.to_string(),
+            transport: Transport::default(),
         }
     }
 
@@ -93,7 +98,12 @@ impl Groq {
 
     /// Create request helper for internal use
     fn request_helper(&self) -> GroqRequestHelper {
-        GroqRequestHelper::new(self.client.clone(), &self.base_url, &self.api_key)
+        GroqRequestHelper::new(
+            self.client.clone(),
+            &self.base_url,
+            &self.api_key,
+            &self.transport,
+        )
     }
 }
 
@@ -145,6 +155,7 @@ impl fmt::Debug for Groq {
             .field("api_key", &"[REDACTED]")
             .field("client", &self.client)
             .field("base_url", &self.base_url)
+            .field("transport", &self.transport)
             .finish_non_exhaustive()
     }
 }
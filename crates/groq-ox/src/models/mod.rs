@@ -2,6 +2,7 @@ pub mod response;
 
 use self::response::{ListModelsResponse, ModelInfo};
 use crate::{Groq, GroqRequestError};
+use ai_ox_common::request_builder::{Endpoint, HttpMethod};
 
 const MODELS_URL: &str = "openai/v1/models";
 
@@ -27,22 +28,8 @@ impl Groq {
     /// # }
     /// ```
     pub async fn get_model(&self, model_id: &str) -> Result<ModelInfo, GroqRequestError> {
-        let url = format!("{}/{}/{}", self.base_url, MODELS_URL, model_id);
-
-        let res = self
-            .client
-            .get(&url)
-            .bearer_auth(&self.api_key)
-            .send()
-            .await?;
-
-        if res.status().is_success() {
-            Ok(res.json::<ModelInfo>().await?)
-        } else {
-            let status = res.status();
-            let bytes = res.bytes().await?;
-            Err(crate::error::parse_error_response(status, bytes))
-        }
+        let endpoint = Endpoint::new(format!("{}/{}", MODELS_URL, model_id), HttpMethod::Get);
+        self.request_helper().request(&endpoint).await
     }
 
     /// List all available models
@@ -64,21 +51,7 @@ impl Groq {
     /// # }
     /// ```
     pub async fn list_models(&self) -> Result<ListModelsResponse, GroqRequestError> {
-        let url = format!("{}/{}", self.base_url, MODELS_URL);
-
-        let res = self
-            .client
-            .get(&url)
-            .bearer_auth(&self.api_key)
-            .send()
-            .await?;
-
-        if res.status().is_success() {
-            Ok(res.json::<ListModelsResponse>().await?)
-        } else {
-            let status = res.status();
-            let bytes = res.bytes().await?;
-            Err(crate::error::parse_error_response(status, bytes))
-        }
+        let endpoint = Endpoint::new(MODELS_URL, HttpMethod::Get);
+        self.request_helper().request(&endpoint).await
     }
 }
@@ -1,9 +1,20 @@
 use std::time::Duration;
 
 use crate::{
-    ChatRequest, ChatResponse, MistralRequestError, audio::TranscriptionRequest,
-    audio::TranscriptionResponse, internal::MistralRequestHelper, response::ChatCompletionChunk,
+    ChatRequest, ChatResponse, MistralRequestError,
+    audio::{TranscriptionRequest, TranscriptionResponse},
+    internal::MistralRequestHelper,
+    request::{
+        AgentsRequest, BatchJobRequest, ChatModerationRequest, EmbeddingsRequest, FimRequest,
+        FineTuningRequest, ModerationRequest, Pagination,
+    },
+    response::{
+        BatchJob, BatchJobsResponse, ChatCompletionChunk, EmbeddingsResponse, FileDeleteResponse,
+        FileInfo, FileUploadResponse, FilesResponse, FineTuningJob, FineTuningJobsResponse,
+        ModelsResponse, ModerationResponse,
+    },
 };
+use ai_ox_common::Transport;
 use futures_util::stream::BoxStream;
 
 /// Mistral AI API client
@@ -63,6 +74,13 @@ impl Mistral {
         }
     }
 
+    /// Send requests through `transport`, e.g. to record or replay a cassette
+    #[must_use]
+    pub fn with_transport(mut self, transport: Transport) -> Self {
+        self.helper = self.helper.with_transport(transport);
+        self
+    }
+
     /// Get API key (for testing)
     pub fn api_key(&self) -> &str {
         &self.api_key
@@ -89,6 +107,162 @@ impl Mistral {
         self.helper.send_transcription_request(request).await
     }
 
+    /// List the models available to this API key
+    pub async fn list_models(&self) -> Result<ModelsResponse, MistralRequestError> {
+        self.helper.list_models().await
+    }
+
+    /// Generate embeddings for one or more texts
+    pub async fn create_embeddings(
+        &self,
+        request: &EmbeddingsRequest,
+    ) -> Result<EmbeddingsResponse, MistralRequestError> {
+        self.helper.create_embeddings(request).await
+    }
+
+    /// Classify texts against Mistral's moderation categories
+    pub async fn create_moderation(
+        &self,
+        request: &ModerationRequest,
+    ) -> Result<ModerationResponse, MistralRequestError> {
+        self.helper.create_moderation(request).await
+    }
+
+    /// Classify a conversation against Mistral's moderation categories
+    pub async fn create_chat_moderation(
+        &self,
+        request: &ChatModerationRequest,
+    ) -> Result<ModerationResponse, MistralRequestError> {
+        self.helper.create_chat_moderation(request).await
+    }
+
+    /// Send a fill-in-the-middle completion request, e.g. to Codestral
+    pub async fn create_fim_completion(
+        &self,
+        request: &FimRequest,
+    ) -> Result<ChatResponse, MistralRequestError> {
+        self.helper.create_fim_completion(request).await
+    }
+
+    /// Stream a fill-in-the-middle completion request
+    pub fn stream_fim_completion(
+        &self,
+        request: &FimRequest,
+    ) -> BoxStream<'static, Result<ChatCompletionChunk, MistralRequestError>> {
+        self.helper.stream_fim_completion(request)
+    }
+
+    /// Send a completion request to an agent
+    pub async fn create_agents_completion(
+        &self,
+        request: &AgentsRequest,
+    ) -> Result<ChatResponse, MistralRequestError> {
+        self.helper.create_agents_completion(request).await
+    }
+
+    /// Stream a completion request to an agent
+    pub fn stream_agents_completion(
+        &self,
+        request: &AgentsRequest,
+    ) -> BoxStream<'static, Result<ChatCompletionChunk, MistralRequestError>> {
+        self.helper.stream_agents_completion(request)
+    }
+
+    /// List one page of uploaded files
+    pub async fn list_files(
+        &self,
+        pagination: &Pagination,
+    ) -> Result<FilesResponse, MistralRequestError> {
+        self.helper.list_files(pagination).await
+    }
+
+    /// Upload a file, e.g. batch input with purpose `batch` or training data
+    /// with purpose `fine-tune`
+    pub async fn upload_file(
+        &self,
+        filename: &str,
+        data: Vec<u8>,
+        purpose: &str,
+    ) -> Result<FileUploadResponse, MistralRequestError> {
+        self.helper.upload_file(filename, data, purpose).await
+    }
+
+    /// Get information about an uploaded file
+    pub async fn retrieve_file(&self, file_id: &str) -> Result<FileInfo, MistralRequestError> {
+        self.helper.retrieve_file(file_id).await
+    }
+
+    /// Download the content of a file, e.g. the output of a batch job
+    pub async fn download_file(&self, file_id: &str) -> Result<bytes::Bytes, MistralRequestError> {
+        self.helper.download_file(file_id).await
+    }
+
+    /// Delete an uploaded file
+    pub async fn delete_file(
+        &self,
+        file_id: &str,
+    ) -> Result<FileDeleteResponse, MistralRequestError> {
+        self.helper.delete_file(file_id).await
+    }
+
+    /// List one page of batch jobs
+    pub async fn list_batch_jobs(
+        &self,
+        pagination: &Pagination,
+    ) -> Result<BatchJobsResponse, MistralRequestError> {
+        self.helper.list_batch_jobs(pagination).await
+    }
+
+    /// Create a batch job over uploaded input files
+    pub async fn create_batch_job(
+        &self,
+        request: &BatchJobRequest,
+    ) -> Result<BatchJob, MistralRequestError> {
+        self.helper.create_batch_job(request).await
+    }
+
+    /// Get a batch job
+    pub async fn retrieve_batch_job(&self, job_id: &str) -> Result<BatchJob, MistralRequestError> {
+        self.helper.retrieve_batch_job(job_id).await
+    }
+
+    /// Request cancellation of a batch job
+    pub async fn cancel_batch_job(&self, job_id: &str) -> Result<BatchJob, MistralRequestError> {
+        self.helper.cancel_batch_job(job_id).await
+    }
+
+    /// List one page of fine-tuning jobs
+    pub async fn list_fine_tuning_jobs(
+        &self,
+        pagination: &Pagination,
+    ) -> Result<FineTuningJobsResponse, MistralRequestError> {
+        self.helper.list_fine_tuning_jobs(pagination).await
+    }
+
+    /// Create a fine-tuning job
+    pub async fn create_fine_tuning_job(
+        &self,
+        request: &FineTuningRequest,
+    ) -> Result<FineTuningJob, MistralRequestError> {
+        self.helper.create_fine_tuning_job(request).await
+    }
+
+    /// Get a fine-tuning job
+    pub async fn retrieve_fine_tuning_job(
+        &self,
+        job_id: &str,
+    ) -> Result<FineTuningJob, MistralRequestError> {
+        self.helper.retrieve_fine_tuning_job(job_id).await
+    }
+
+    /// Cancel a fine-tuning job
+    pub async fn cancel_fine_tuning_job(
+        &self,
+        job_id: &str,
+    ) -> Result<FineTuningJob, MistralRequestError> {
+        self.helper.cancel_fine_tuning_job(job_id).await
+    }
+
     /// Send a chat request (alias for chat method for compatibility)
     pub async fn send(&self, request: &ChatRequest) -> Result<ChatResponse, MistralRequestError> {
         self.chat(request).await
@@ -1,8 +1,13 @@
-use crate::{ChatRequest, ChatResponse, MistralRequestError, response::ChatCompletionChunk};
+use crate::{
+    ChatRequest, ChatResponse, MistralRequestError, request::Pagination,
+    response::ChatCompletionChunk,
+};
 use ai_ox_common::{
-    BoxStream,
+    BoxStream, Transport,
     error::ProviderError,
-    request_builder::{AuthMethod, Endpoint, HttpMethod, RequestBuilder, RequestConfig},
+    request_builder::{
+        AuthMethod, Endpoint, HttpMethod, MultipartForm, RequestBuilder, RequestConfig,
+    },
 };
 use futures_util::stream::BoxStream as FuturesBoxStream;
 
@@ -50,6 +55,19 @@ impl MistralRequestHelper {
         }
     }
 
+    /// Send requests through `transport` instead of the current one
+    #[must_use]
+    pub fn with_transport(self, transport: Transport) -> Self {
+        let config = self.config.with_transport(transport);
+        let request_builder = RequestBuilder::new(self.client.clone(), config.clone());
+
+        Self {
+            client: self.client,
+            config,
+            request_builder,
+        }
+    }
+
     /// Send a chat completion request
     pub async fn send_chat_request(
         &self,
@@ -128,8 +146,10 @@ impl MistralRequestHelper {
     /// List fine-tuning jobs
     pub async fn list_fine_tuning_jobs(
         &self,
+        pagination: &Pagination,
     ) -> Result<crate::response::FineTuningJobsResponse, MistralRequestError> {
-        let endpoint = Endpoint::new("v1/fine_tuning/jobs", HttpMethod::Get);
+        let endpoint = Endpoint::new("v1/fine_tuning/jobs", HttpMethod::Get)
+            .with_query_params(pagination.query_params());
         Ok(self
             .request_builder
             .request_json(&endpoint, None::<&()>)
@@ -190,8 +210,10 @@ impl MistralRequestHelper {
     /// List batch jobs
     pub async fn list_batch_jobs(
         &self,
+        pagination: &Pagination,
     ) -> Result<crate::response::BatchJobsResponse, MistralRequestError> {
-        let endpoint = Endpoint::new("v1/batch/jobs", HttpMethod::Get);
+        let endpoint = Endpoint::new("v1/batch/jobs", HttpMethod::Get)
+            .with_query_params(pagination.query_params());
         Ok(self
             .request_builder
             .request_json(&endpoint, None::<&()>)
@@ -235,14 +257,41 @@ impl MistralRequestHelper {
     }
 
     /// List files
-    pub async fn list_files(&self) -> Result<crate::response::FilesResponse, MistralRequestError> {
-        let endpoint = Endpoint::new("v1/files", HttpMethod::Get);
+    pub async fn list_files(
+        &self,
+        pagination: &Pagination,
+    ) -> Result<crate::response::FilesResponse, MistralRequestError> {
+        let endpoint =
+            Endpoint::new("v1/files", HttpMethod::Get).with_query_params(pagination.query_params());
         Ok(self
             .request_builder
             .request_json(&endpoint, None::<&()>)
             .await?)
     }
 
+    /// Upload a file
+    pub async fn upload_file(
+        &self,
+        filename: &str,
+        data: Vec<u8>,
+        purpose: &str,
+    ) -> Result<crate::response::FileUploadResponse, MistralRequestError> {
+        let form = MultipartForm::new()
+            .text("purpose", purpose)
+            .file_from_bytes("file", filename, data);
+        let endpoint = Endpoint::new("v1/files", HttpMethod::Post);
+        Ok(self
+            .request_builder
+            .request_multipart(&endpoint, form.build())
+            .await?)
+    }
+
+    /// Download file content
+    pub async fn download_file(&self, file_id: &str) -> Result<bytes::Bytes, MistralRequestError> {
+        let endpoint = Endpoint::new(format!("v1/files/{file_id}/content"), HttpMethod::Get);
+        Ok(self.request_builder.request_bytes(&endpoint).await?)
+    }
+
     /// Get file information
     pub async fn retrieve_file(
         &self,
@@ -279,6 +328,15 @@ impl MistralRequestHelper {
             .await?)
     }
 
+    /// Stream a fill-in-the-middle completion
+    pub fn stream_fim_completion(
+        &self,
+        request: &crate::request::FimRequest,
+    ) -> FuturesBoxStream<'static, Result<ChatCompletionChunk, MistralRequestError>> {
+        let endpoint = Endpoint::new("v1/fim/completions", HttpMethod::Post);
+        self.request_builder.stream(&endpoint, Some(request))
+    }
+
     /// Agents completion
     pub async fn create_agents_completion(
         &self,
@@ -290,4 +348,13 @@ impl MistralRequestHelper {
             .request_json(&endpoint, Some(request))
             .await?)
     }
+
+    /// Stream an agents completion
+    pub fn stream_agents_completion(
+        &self,
+        request: &crate::request::AgentsRequest,
+    ) -> FuturesBoxStream<'static, Result<ChatCompletionChunk, MistralRequestError>> {
+        let endpoint = Endpoint::new("v1/agents/completions", HttpMethod::Post);
+        self.request_builder.stream(&endpoint, Some(request))
+    }
 }
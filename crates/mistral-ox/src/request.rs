@@ -195,20 +195,61 @@ pub struct FineTuningHyperparameters {
 /// Request for batch job creation
 #[derive(Debug, Clone, Serialize, Deserialize, Builder)]
 pub struct BatchJobRequest {
-    /// Input file ID containing requests
-    pub input_file_id: String,
+    /// IDs of the uploaded JSONL files containing the requests
+    #[builder(into)]
+    pub input_files: Vec<String>,
 
-    /// API endpoint to use for batch processing
+    /// API endpoint to use for batch processing, e.g. `/v1/chat/completions`
     #[builder(into)]
     pub endpoint: String,
 
-    /// Completion window (24h)
+    /// Model to run the requests against
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(into)]
+    pub model: Option<String>,
+
+    /// Agent to run the requests against, instead of a model
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[builder(into)]
-    pub completion_window: String,
+    pub agent_id: Option<String>,
 
     /// Optional metadata
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Value>,
+
+    /// Hours after which unfinished requests are abandoned (default 24)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_hours: Option<u32>,
+}
+
+/// Page selection for list endpoints
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Builder)]
+pub struct Pagination {
+    /// Zero-based page index
+    pub page: Option<u32>,
+
+    /// Number of items per page
+    pub page_size: Option<u32>,
+}
+
+impl Pagination {
+    /// Select page `page` of `page_size` items
+    #[must_use]
+    pub fn new(page: u32, page_size: u32) -> Self {
+        Self {
+            page: Some(page),
+            page_size: Some(page_size),
+        }
+    }
+
+    /// Query parameters selecting this page
+    #[must_use]
+    pub fn query_params(&self) -> Vec<(String, String)> {
+        [("page", self.page), ("page_size", self.page_size)]
+            .into_iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value?.to_string())))
+            .collect()
+    }
 }
 
 /// Request for fill-in-the-middle completion
@@ -219,10 +260,12 @@ pub struct FimRequest {
     pub model: String,
 
     /// Text before the completion
+    #[builder(into)]
     pub prompt: String,
 
     /// Text after the completion
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(into)]
     pub suffix: Option<String>,
 
     /// Maximum tokens to generate
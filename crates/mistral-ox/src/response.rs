@@ -262,43 +262,66 @@ pub struct BatchJobsResponse {
 pub struct BatchJob {
     pub id: String,
     pub object: String,
+    pub input_files: Vec<String>,
     pub endpoint: String,
-    pub input_file_id: String,
-    pub completion_window: String,
-    pub status: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub output_file_id: Option<String>,
+    pub model: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub error_file_id: Option<String>,
-    pub created_at: u64,
+    pub agent_id: Option<String>,
+    pub status: BatchJobStatus,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub in_progress_at: Option<u64>,
+    pub output_file: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub expires_at: Option<u64>,
+    pub error_file: Option<String>,
+    #[serde(default)]
+    pub errors: Vec<BatchJobError>,
+    pub created_at: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub finalizing_at: Option<u64>,
+    pub started_at: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub completed_at: Option<u64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub failed_at: Option<u64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub expired_at: Option<u64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub cancelling_at: Option<u64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub cancelled_at: Option<u64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub request_counts: Option<BatchRequestCounts>,
+    #[serde(default)]
+    pub total_requests: u64,
+    #[serde(default)]
+    pub completed_requests: u64,
+    #[serde(default)]
+    pub succeeded_requests: u64,
+    #[serde(default)]
+    pub failed_requests: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<serde_json::Value>,
 }
 
-/// Batch request counts
+/// Status of a batch job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BatchJobStatus {
+    Queued,
+    Running,
+    Success,
+    Failed,
+    TimeoutExceeded,
+    CancellationRequested,
+    Cancelled,
+}
+
+impl BatchJobStatus {
+    /// Whether the job has stopped and won't change status again
+    #[must_use]
+    pub fn is_terminal(self) -> bool {
+        matches!(
+            self,
+            Self::Success | Self::Failed | Self::TimeoutExceeded | Self::Cancelled
+        )
+    }
+}
+
+/// Error reported by a batch job, with how many requests it affected
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BatchRequestCounts {
-    pub total: u32,
-    pub completed: u32,
-    pub failed: u32,
+pub struct BatchJobError {
+    pub message: String,
+    #[serde(default)]
+    pub count: u64,
 }
 
 /// Response from files list endpoint
@@ -306,6 +329,8 @@ pub struct BatchRequestCounts {
 pub struct FilesResponse {
     pub data: Vec<FileInfo>,
     pub object: String,
+    #[serde(default)]
+    pub total: u32,
 }
 
 /// File information
@@ -334,6 +359,12 @@ pub struct FileUploadResponse {
     pub created_at: u64,
     pub filename: String,
     pub purpose: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_lines: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
 }
 
 /// Response from file delete endpoint
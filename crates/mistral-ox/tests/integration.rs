@@ -147,7 +147,9 @@ mod tests {
     async fn test_list_fine_tuning_jobs() {
         let client = get_client();
 
-        let response = client.list_fine_tuning_jobs().await;
+        let response = client
+            .list_fine_tuning_jobs(&request::Pagination::default())
+            .await;
         // This should succeed even if there are no jobs
         assert!(response.is_ok());
 
@@ -161,7 +163,9 @@ mod tests {
     async fn test_list_batch_jobs() {
         let client = get_client();
 
-        let response = client.list_batch_jobs().await;
+        let response = client
+            .list_batch_jobs(&request::Pagination::default())
+            .await;
         // This should succeed even if there are no jobs
         assert!(response.is_ok());
 
@@ -175,7 +179,7 @@ mod tests {
     async fn test_list_files() {
         let client = get_client();
 
-        let response = client.list_files().await;
+        let response = client.list_files(&request::Pagination::default()).await;
         // This should succeed even if there are no files
         assert!(response.is_ok());
 
@@ -0,0 +1,80 @@
+use ai_ox_common::Transport;
+use futures_util::StreamExt;
+use mistral_ox::{
+    Mistral,
+    request::{BatchJobRequest, FimRequest, Pagination},
+    response::BatchJobStatus,
+};
+
+/// Client that serves responses from a cassette at `tests/cassettes/{name}.json`
+///
+/// The cassettes are hand-written fixtures in the recorded format, not
+/// recordings. Replay checks the method, URL and JSON body of every request.
+/// Set `AOX_TRANSPORT=record` and `MISTRAL_API_KEY` to replace one with a recording.
+fn client(name: &str) -> Mistral {
+    let path = format!("{}/tests/cassettes/{name}.json", env!("CARGO_MANIFEST_DIR"));
+    let api_key = std::env::var("MISTRAL_API_KEY").unwrap_or_else(|_| "test-key".to_string());
+    Mistral::new(api_key).with_transport(Transport::from_env(path).expect("cassette should load"))
+}
+
+#[tokio::test]
+async fn test_replayed_fim_stream_and_batch_job() {
+    let client = client("platform");
+
+    let request = FimRequest::builder()
+        .model("codestral-latest")
+        .prompt("def add(a, b):\n    return ")
+        .suffix("\n")
+        .build();
+    let completion: String = client
+        .stream_fim_completion(&request)
+        .map(|chunk| chunk.expect("stream should replay"))
+        .filter_map(|chunk| async move { chunk.choices.into_iter().next()?.delta.content })
+        .collect()
+        .await;
+    assert_eq!(completion, "a + b");
+
+    let input = b"{\"custom_id\": \"add\", \"body\": {}}\n{\"custom_id\": \"mul\", \"body\": {}}\n";
+    let file = client
+        .upload_file("requests.jsonl", input.to_vec(), "batch")
+        .await
+        .expect("upload should replay");
+    assert_eq!(file.num_lines, Some(2));
+
+    let job = client
+        .create_batch_job(
+            &BatchJobRequest::builder()
+                .input_files(vec![file.id])
+                .endpoint("/v1/fim/completions")
+                .model("codestral-latest")
+                .build(),
+        )
+        .await
+        .expect("create should replay");
+    assert_eq!(job.status, BatchJobStatus::Queued);
+
+    let page = client
+        .list_batch_jobs(&Pagination::new(0, 1))
+        .await
+        .expect("list should replay");
+    assert_eq!((page.data.len(), page.total), (1, 3));
+
+    let job = client
+        .retrieve_batch_job(&job.id)
+        .await
+        .expect("retrieve should replay");
+    assert!(job.status.is_terminal());
+    assert_eq!(job.succeeded_requests, 2);
+
+    let output = client
+        .download_file(job.output_file.as_deref().expect("job should have output"))
+        .await
+        .expect("download should replay");
+    assert_eq!(
+        output
+            .split(|&byte| byte == b'\n')
+            .filter(|line| !line.is_empty())
+            .count(),
+        2
+    );
+}
@@ -78,14 +78,16 @@ mod tests {
     #[test]
     fn test_batch_job_request_builder() {
         let request = BatchJobRequest::builder()
-            .input_file_id("file-batch123".to_string())
+            .input_files(vec!["file-batch123".to_string()])
             .endpoint("/v1/chat/completions")
-            .completion_window("24h")
+            .model("mistral-small-latest")
+            .timeout_hours(24)
             .build();
 
-        assert_eq!(request.input_file_id, "file-batch123");
+        assert_eq!(request.input_files, ["file-batch123"]);
         assert_eq!(request.endpoint, "/v1/chat/completions");
-        assert_eq!(request.completion_window, "24h");
+        assert_eq!(request.model.as_deref(), Some("mistral-small-latest"));
+        assert_eq!(request.timeout_hours, Some(24));
     }
 
     #[test]
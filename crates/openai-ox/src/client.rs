@@ -1,3 +1,4 @@
+use ai_ox_common::Transport;
 use bon::Builder;
 use std::time::Duration;
 
@@ -5,8 +6,8 @@ use std::time::Duration;
 use std::sync::Arc;
 
 use crate::{
-    ChatRequest, ChatResponse, OpenAIRequestError, ResponsesRequest, ResponsesResponse,
-    ResponsesStreamChunk, internal::OpenAIRequestHelper,
+    ChatCompletionChunk, ChatRequest, ChatResponse, OpenAIRequestError, ResponsesRequest,
+    ResponsesResponse, ResponsesStreamEvent, internal::OpenAIRequestHelper,
 };
 
 /// OpenAI AI API client
@@ -27,6 +28,10 @@ pub struct OpenAI {
     #[cfg(feature = "leaky-bucket")]
     #[builder(skip)]
     rate_limiter: Option<Arc<leaky_bucket::RateLimiter>>,
+
+    /// Live, recording or replaying transport for API requests
+    #[builder(default)]
+    transport: Transport,
 }
 
 impl OpenAI {
@@ -43,6 +48,7 @@ impl OpenAI {
             client,
             #[cfg(feature = "leaky-bucket")]
             rate_limiter: None,
+            transport: Transport::default(),
         }
     }
 
@@ -58,7 +64,12 @@ impl OpenAI {
 
     /// Create request helper for internal use
     fn request_helper(&self) -> OpenAIRequestHelper {
-        OpenAIRequestHelper::new(self.client.clone(), &self.base_url, &self.api_key)
+        OpenAIRequestHelper::new(
+            self.client.clone(),
+            &self.base_url,
+            &self.api_key,
+            &self.transport,
+        )
     }
 
     /// Create a new OpenAI client from environment variable
@@ -105,7 +116,8 @@ impl OpenAI {
     pub fn stream(
         &self,
         request: &ChatRequest,
-    ) -> futures_util::stream::BoxStream<'static, Result<ChatResponse, OpenAIRequestError>> {
+    ) -> futures_util::stream::BoxStream<'static, Result<ChatCompletionChunk, OpenAIRequestError>>
+    {
         use async_stream::try_stream;
 
         let helper = self.request_helper();
@@ -134,7 +146,7 @@ impl OpenAI {
     pub fn stream_responses(
         &self,
         request: &ResponsesRequest,
-    ) -> futures_util::stream::BoxStream<'static, Result<ResponsesStreamChunk, OpenAIRequestError>>
+    ) -> futures_util::stream::BoxStream<'static, Result<ResponsesStreamEvent, OpenAIRequestError>>
     {
         use async_stream::try_stream;
 
@@ -401,6 +413,45 @@ impl OpenAI {
 
         self.request_helper().create_translation(request).await
     }
+
+    /// Synthesize speech, returning the whole audio file
+    pub async fn create_speech(
+        &self,
+        request: &crate::request::SpeechRequest,
+    ) -> Result<bytes::Bytes, OpenAIRequestError> {
+        use futures_util::TryStreamExt;
+
+        let chunks: Vec<bytes::Bytes> = self.stream_speech(request).try_collect().await?;
+        Ok(chunks.concat().into())
+    }
+
+    /// Synthesize speech, streaming the audio as it is generated
+    pub fn stream_speech(
+        &self,
+        request: &crate::request::SpeechRequest,
+    ) -> futures_util::stream::BoxStream<'static, Result<bytes::Bytes, OpenAIRequestError>> {
+        use async_stream::try_stream;
+
+        let helper = self.request_helper();
+        let request = request.clone();
+
+        #[cfg(feature = "leaky-bucket")]
+        let rate_limiter = self.rate_limiter.clone();
+
+        Box::pin(try_stream! {
+            #[cfg(feature = "leaky-bucket")]
+            if let Some(ref limiter) = rate_limiter {
+                limiter.acquire_one().await;
+            }
+
+            let mut stream = helper.stream_speech(&request);
+            use futures_util::StreamExt;
+
+            while let Some(result) = stream.next().await {
+                yield result?;
+            }
+        })
+    }
 }
 
 #[cfg(feature = "leaky-bucket")]
@@ -1,9 +1,9 @@
 use crate::{
-    ChatRequest, ChatResponse, OpenAIRequestError, ResponsesRequest, ResponsesResponse,
-    ResponsesStreamChunk,
+    ChatCompletionChunk, ChatRequest, ChatResponse, OpenAIRequestError, ResponsesRequest,
+    ResponsesResponse, ResponsesStreamEvent,
 };
 use ai_ox_common::{
-    BoxStream,
+    BoxStream, Transport,
     error::ProviderError,
     request_builder::{
         AuthMethod, Endpoint, HttpMethod, MultipartForm, RequestBuilder, RequestConfig,
@@ -17,9 +17,15 @@ pub struct OpenAIRequestHelper {
 }
 
 impl OpenAIRequestHelper {
-    pub fn new(client: reqwest::Client, base_url: &str, api_key: &str) -> Self {
-        let config =
-            RequestConfig::new(base_url).with_auth(AuthMethod::Bearer(api_key.to_string()));
+    pub fn new(
+        client: reqwest::Client,
+        base_url: &str,
+        api_key: &str,
+        transport: &Transport,
+    ) -> Self {
+        let config = RequestConfig::new(base_url)
+            .with_auth(AuthMethod::Bearer(api_key.to_string()))
+            .with_transport(transport.clone());
 
         let request_builder = RequestBuilder::new(client, config);
 
@@ -43,11 +49,11 @@ impl OpenAIRequestHelper {
     pub fn stream_chat_request(
         &self,
         request: &ChatRequest,
-    ) -> FuturesBoxStream<'static, Result<ChatResponse, OpenAIRequestError>> {
+    ) -> FuturesBoxStream<'static, Result<ChatCompletionChunk, OpenAIRequestError>> {
         let endpoint = Endpoint::new("chat/completions", HttpMethod::Post);
 
         // Use the common streaming implementation (no conversion needed - same type)
-        let stream: BoxStream<'static, Result<ChatResponse, ProviderError>> =
+        let stream: BoxStream<'static, Result<ChatCompletionChunk, ProviderError>> =
             self.request_builder.stream(&endpoint, Some(request));
 
         // Direct cast since OpenAIRequestError = ProviderError
@@ -315,6 +321,14 @@ impl OpenAIRequestHelper {
             form
         };
 
+        let form = request
+            .timestamp_granularities
+            .iter()
+            .flatten()
+            .fold(form, |form, granularity| {
+                form.text("timestamp_granularities[]", granularity)
+            });
+
         let endpoint = Endpoint::new("audio/transcriptions", HttpMethod::Post);
         Ok(self
             .request_builder
@@ -356,6 +370,17 @@ impl OpenAIRequestHelper {
             .await?)
     }
 
+    /// Synthesize speech, streaming the audio as it is generated
+    pub fn stream_speech(
+        &self,
+        request: &crate::request::SpeechRequest,
+    ) -> FuturesBoxStream<'static, Result<bytes::Bytes, OpenAIRequestError>> {
+        let endpoint = Endpoint::new("audio/speech", HttpMethod::Post);
+
+        // Direct cast since OpenAIRequestError = ProviderError
+        self.request_builder.stream_bytes(&endpoint, Some(request))
+    }
+
     /// Send a Responses API request
     pub async fn send_responses_request(
         &self,
@@ -379,13 +404,13 @@ impl OpenAIRequestHelper {
     pub fn stream_responses_request(
         &self,
         request: &ResponsesRequest,
-    ) -> FuturesBoxStream<'static, Result<ResponsesStreamChunk, OpenAIRequestError>> {
+    ) -> FuturesBoxStream<'static, Result<ResponsesStreamEvent, OpenAIRequestError>> {
         let endpoint = Endpoint::new("responses", HttpMethod::Post)
             .with_header("OpenAI-Beta", "responses=experimental")
             .with_header("Accept", "text/event-stream");
 
         // Use the common streaming implementation
-        let stream: BoxStream<'static, Result<ResponsesStreamChunk, ProviderError>> =
+        let stream: BoxStream<'static, Result<ResponsesStreamEvent, ProviderError>> =
             self.request_builder.stream(&endpoint, Some(request));
 
         // Direct cast since OpenAIRequestError = ProviderError
@@ -6,7 +6,7 @@
 //! - Text embeddings
 //! - Content moderation  
 //! - Image generation (DALL-E)
-//! - Audio transcription/translation (Whisper)
+//! - Audio transcription/translation (Whisper) and speech synthesis
 //! - File management
 //! - Fine-tuning jobs
 //! - Assistants API
@@ -56,21 +56,23 @@ pub use ai_ox_common::openai_format::{Message, Tool, ToolCall, ToolChoice};
 // Re-export request types
 pub use request::{
     AssistantRequest, AudioRequest, ChatRequest, EmbeddingInput, EmbeddingsRequest,
-    FineTuningRequest, ImageRequest, ModerationInput, ModerationRequest,
+    FineTuningRequest, ImageRequest, ModerationInput, ModerationRequest, SpeechRequest,
+    StreamOptions,
 };
 
 // Re-export response types
 pub use response::{
-    AssistantInfo, AssistantsResponse, AudioResponse, AudioSegment, ChatResponse, EmbeddingData,
-    EmbeddingsResponse, FileInfo, FileUploadResponse, FilesResponse, FineTuningJob,
-    FineTuningJobsResponse, ImageData, ImageResponse, ModelInfo, ModelsResponse,
+    AssistantInfo, AssistantsResponse, AudioResponse, AudioSegment, ChatCompletionChunk,
+    ChatResponse, EmbeddingData, EmbeddingsResponse, FileInfo, FileUploadResponse, FilesResponse,
+    FineTuningJob, FineTuningJobsResponse, ImageData, ImageResponse, ModelInfo, ModelsResponse,
     ModerationResponse, ModerationResult,
 };
 
 // Re-export Responses API types
 pub use responses::{
-    Conversation, IncompleteDetails, OutputDelta, ReasoningConfig, ReasoningItem, ResponseError,
-    ResponseMessage, ResponseOutputContent, ResponseOutputItem, ResponsesInput, ResponsesRequest,
-    ResponsesRequestBuilder, ResponsesResponse, ResponsesStreamChunk, ResponsesUsage, TextConfig,
+    Conversation, IncompleteDetails, InputContent, InputItem, OutputDelta, ReasoningConfig,
+    ReasoningItem, ReasoningSummary, ResponseError, ResponseMessage, ResponseOutputContent,
+    ResponseOutputItem, ResponsesInput, ResponsesRequest, ResponsesRequestBuilder,
+    ResponsesResponse, ResponsesStreamChunk, ResponsesStreamEvent, ResponsesUsage, TextConfig,
     ToolCallItem,
 };
@@ -36,6 +36,8 @@ pub struct ChatRequest {
     pub stream: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stop: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_options: Option<StreamOptions>,
 
     // OpenAI-specific extensions
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -56,6 +58,13 @@ pub struct ChatRequest {
     pub parallel_tool_calls: Option<bool>,
 }
 
+/// Options for streaming responses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamOptions {
+    /// Send a final chunk with token usage before the end of the stream
+    pub include_usage: bool,
+}
+
 /// Request for embeddings
 #[derive(Debug, Clone, Serialize, Deserialize, Builder)]
 #[builder(builder_type(vis = "pub"), state_mod(vis = "pub"))]
@@ -172,6 +181,41 @@ pub struct AudioRequest {
 
     /// Temperature for transcription
     pub temperature: Option<f32>,
+
+    /// Timestamp granularities, `word` and/or `segment` (transcription with
+    /// `verbose_json` only)
+    pub timestamp_granularities: Option<Vec<String>>,
+}
+
+/// Request for speech synthesis
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+pub struct SpeechRequest {
+    /// Model to use
+    #[builder(into)]
+    pub model: String,
+
+    /// Text to speak
+    #[builder(into)]
+    pub input: String,
+
+    /// Voice to speak with
+    #[builder(into)]
+    pub voice: String,
+
+    /// Instructions for the tone and delivery (not supported by `tts-1` and
+    /// `tts-1-hd`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(into)]
+    pub instructions: Option<String>,
+
+    /// Audio format: `mp3`, `opus`, `aac`, `flac`, `wav` or `pcm`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(into)]
+    pub response_format: Option<String>,
+
+    /// Speed of the speech, from 0.25 to 4.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f32>,
 }
 
 /// Request for fine-tuning
@@ -74,7 +74,81 @@ pub struct MessageDelta {
 
     /// Tool calls (if any)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tool_calls: Option<Vec<serde_json::Value>>,
+    pub tool_calls: Option<Vec<ToolCallDelta>>,
+}
+
+/// Partial tool call for streaming, keyed by its position in the final message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolCallDelta {
+    /// Index of the tool call this fragment belongs to
+    pub index: u32,
+
+    /// Tool call ID (only present in the first fragment)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Tool call type (only present in the first fragment)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+
+    /// Partial function call
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<FunctionCallDelta>,
+}
+
+/// Partial function call for streaming
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionCallDelta {
+    /// Function name (only present in the first fragment)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Fragment of the JSON-encoded arguments
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<String>,
+}
+
+/// Streaming chunk from chat completion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatCompletionChunk {
+    /// Unique identifier for the response
+    pub id: String,
+
+    /// Object type (usually "chat.completion.chunk")
+    pub object: String,
+
+    /// Unix timestamp of creation
+    pub created: u64,
+
+    /// Model used for the completion
+    pub model: String,
+
+    /// List of choice deltas (empty for the trailing usage chunk)
+    pub choices: Vec<ChoiceDelta>,
+
+    /// Usage statistics (only present when `stream_options.include_usage` is set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<Usage>,
+
+    /// System fingerprint
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_fingerprint: Option<String>,
+}
+
+impl ChatCompletionChunk {
+    /// Get the content delta of the first choice, if available
+    pub fn content(&self) -> Option<&str> {
+        self.choices
+            .first()
+            .and_then(|choice| choice.delta.content.as_deref())
+    }
+
+    /// Get the finish reason of the first choice
+    pub fn finish_reason(&self) -> Option<&str> {
+        self.choices
+            .first()
+            .and_then(|choice| choice.finish_reason.as_deref())
+    }
 }
 
 impl ChatResponse {
@@ -246,6 +320,8 @@ pub struct AudioResponse {
     pub duration: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub segments: Option<Vec<AudioSegment>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub words: Option<Vec<AudioWord>>,
 }
 
 /// Audio segment (for detailed transcription)
@@ -263,6 +339,14 @@ pub struct AudioSegment {
     pub no_speech_prob: f64,
 }
 
+/// Word with timestamps (for transcription with word granularity)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioWord {
+    pub word: String,
+    pub start: f64,
+    pub end: f64,
+}
+
 /// Response from files list endpoint
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FilesResponse {
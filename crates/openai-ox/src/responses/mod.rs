@@ -2,11 +2,13 @@ pub mod request;
 pub mod response;
 
 pub use request::{
-    InputPart, ReasoningConfig, ResponsesInput, ResponsesRequest, ResponsesRequestBuilder,
-    ResponsesTool, TextConfig, ToolFormat,
+    InputContent, InputItem, InputPart, ReasoningConfig, ReasoningSummary, ResponsesInput,
+    ResponsesRequest, ResponsesRequestBuilder, ResponsesTool, ResponsesToolChoice, TextConfig,
+    ToolFormat,
 };
 pub use response::{
     Conversation, IncompleteDetails, InputTokensDetails, OutputDelta, OutputTokensDetails,
     ReasoningItem, ResponseError, ResponseMessage, ResponseOutputContent, ResponseOutputItem,
-    ResponsesResponse, ResponsesStreamChunk, ResponsesUsage, ToolCallItem, add_output_text,
+    ResponsesResponse, ResponsesStreamChunk, ResponsesStreamEvent, ResponsesUsage, ToolCallItem,
+    add_output_text,
 };
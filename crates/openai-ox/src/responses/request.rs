@@ -1,16 +1,17 @@
 use ai_ox_common::openai_format::Message;
 use bon::Builder;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
 
 /// Tool definition for OpenAI Responses API - supports custom types
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ResponsesTool {
-    /// Type of tool (e.g., "custom", "function")
+    /// Type of tool (e.g., "custom", "function", "web_search_preview")
     #[serde(rename = "type")]
     pub tool_type: String,
 
-    /// Name of the tool
+    /// Name of the tool; empty for built-in tools
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub name: String,
 
     /// Optional description
@@ -24,10 +25,87 @@ pub struct ResponsesTool {
     /// Parameters schema (for simple tools without grammar)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parameters: Option<Value>,
+
+    /// Settings of built-in tools (e.g., `vector_store_ids` for "file_search")
+    #[serde(flatten)]
+    pub options: Map<String, Value>,
+}
+
+impl ResponsesTool {
+    /// Creates a built-in tool such as "web_search_preview" or "code_interpreter"
+    pub fn built_in(tool_type: impl Into<String>) -> Self {
+        Self {
+            tool_type: tool_type.into(),
+            name: String::new(),
+            description: None,
+            format: None,
+            parameters: None,
+            options: Map::new(),
+        }
+    }
+
+    /// Adds a tool-specific setting
+    pub fn with_option(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.options.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// Tool choice for the Responses API
+///
+/// Dereferences to the mode, or to the name (or type) of the forced tool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ResponsesToolChoice {
+    /// "auto", "none" or "required"
+    Mode(String),
+    /// A specific tool the model must call
+    Tool {
+        #[serde(rename = "type")]
+        tool_type: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+}
+
+impl ResponsesToolChoice {
+    /// Forces a call to the named function
+    pub fn function(name: impl Into<String>) -> Self {
+        Self::Tool {
+            tool_type: "function".to_string(),
+            name: Some(name.into()),
+        }
+    }
+}
+
+impl std::ops::Deref for ResponsesToolChoice {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match self {
+            Self::Mode(mode) => mode,
+            Self::Tool {
+                name: Some(name), ..
+            } => name,
+            Self::Tool { tool_type, .. } => tool_type,
+        }
+    }
+}
+
+impl From<String> for ResponsesToolChoice {
+    fn from(mode: String) -> Self {
+        Self::Mode(mode)
+    }
+}
+
+impl From<&str> for ResponsesToolChoice {
+    fn from(mode: &str) -> Self {
+        Self::Mode(mode.to_string())
+    }
 }
 
 /// Tool format configuration for grammar-based tools
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ToolFormat {
     /// Format type (e.g., "grammar")
     #[serde(rename = "type")]
@@ -71,7 +149,8 @@ pub struct ResponsesRequest {
 
     /// Tool choice configuration (e.g., "auto", "none", or specific tool)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tool_choice: Option<String>,
+    #[builder(into)]
+    pub tool_choice: Option<ResponsesToolChoice>,
 
     /// Whether to allow parallel tool calls
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -93,6 +172,14 @@ pub struct ResponsesRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_output_tokens: Option<u32>,
 
+    /// Sampling temperature
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+
+    /// Nucleus sampling probability mass
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+
     /// Previous response ID for conversation chaining
     #[serde(skip_serializing_if = "Option::is_none")]
     pub previous_response_id: Option<String>,
@@ -120,6 +207,10 @@ pub struct TextConfig {
     /// Verbosity level: "low", "medium", "high"
     #[serde(skip_serializing_if = "Option::is_none")]
     pub verbosity: Option<String>,
+
+    /// Output format (e.g., `{"type": "json_schema", "name": ..., "schema": ...}`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<Value>,
 }
 
 /// Input for the Responses API - flexible input types
@@ -128,12 +219,88 @@ pub struct TextConfig {
 pub enum ResponsesInput {
     /// Simple text input
     Text(String),
+    /// Array of typed input items (messages, function calls, reasoning)
+    Items(Vec<InputItem>),
     /// Array of messages (chat format)
     Messages(Vec<Message>),
     /// Mixed content with files, images, etc.
     Mixed(Vec<InputPart>),
 }
 
+/// Typed input item for the Responses API
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InputItem {
+    /// Message with structured content
+    Message {
+        /// Message role: "user", "assistant", "system" or "developer"
+        role: String,
+        /// Message content
+        content: Vec<InputContent>,
+    },
+    /// Function call previously made by the model
+    FunctionCall {
+        /// Call ID used to correlate the output
+        call_id: String,
+        /// Function name
+        name: String,
+        /// JSON-encoded arguments
+        arguments: String,
+    },
+    /// Output of a function call
+    FunctionCallOutput {
+        /// Call ID of the function call this output answers
+        call_id: String,
+        /// Function output
+        output: String,
+    },
+    /// Reasoning item carried over from a previous response
+    Reasoning {
+        /// Reasoning item ID
+        id: String,
+        /// Reasoning summary content
+        summary: Vec<ReasoningSummary>,
+        /// Encrypted reasoning content (when `reasoning.encrypted_content` was included)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        encrypted_content: Option<String>,
+    },
+}
+
+/// Content of a message input item
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InputContent {
+    /// Text provided by the user
+    InputText {
+        /// Text content
+        text: String,
+    },
+    /// Text previously produced by the model
+    OutputText {
+        /// Text content
+        text: String,
+    },
+    /// Image provided by URL or data URL
+    InputImage {
+        /// Image URL or `data:` URL
+        image_url: String,
+        /// Optional detail level
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        detail: Option<String>,
+    },
+}
+
+/// Reasoning summary entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReasoningSummary {
+    /// Summary text
+    SummaryText {
+        /// Text content
+        text: String,
+    },
+}
+
 /// Input part for mixed content
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InputPart {
@@ -178,6 +345,11 @@ impl ResponsesInput {
         Self::Text(content.into())
     }
 
+    /// Create typed items input
+    pub fn items(items: Vec<InputItem>) -> Self {
+        Self::Items(items)
+    }
+
     /// Create messages input
     pub fn messages(messages: Vec<Message>) -> Self {
         Self::Messages(messages)
@@ -258,6 +430,19 @@ impl TextConfig {
     pub fn with_verbosity(verbosity: impl Into<String>) -> Self {
         Self {
             verbosity: Some(verbosity.into()),
+            format: None,
+        }
+    }
+
+    /// Create text config requesting output that matches a JSON schema
+    pub fn json_schema(name: impl Into<String>, schema: Value) -> Self {
+        Self {
+            verbosity: None,
+            format: Some(serde_json::json!({
+                "type": "json_schema",
+                "name": name.into(),
+                "schema": schema,
+            })),
         }
     }
 }
@@ -177,6 +177,25 @@ pub enum ResponseOutputItem {
         /// Reasoning text content (optional)
         #[serde(skip_serializing_if = "Option::is_none")]
         content: Option<Vec<serde_json::Value>>,
+        /// Encrypted reasoning content (when `reasoning.encrypted_content` was included)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        encrypted_content: Option<String>,
+    },
+
+    /// Function call requested by the model
+    #[serde(rename = "function_call")]
+    FunctionCall {
+        /// The unique ID of the function call item
+        id: String,
+        /// Call ID used to correlate the function output
+        call_id: String,
+        /// Function name
+        name: String,
+        /// JSON-encoded arguments
+        arguments: String,
+        /// The status of the item
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        status: Option<String>,
     },
 
     /// File search tool call
@@ -218,6 +237,10 @@ pub enum ResponseOutputItem {
         #[serde(flatten)]
         details: serde_json::Value,
     },
+
+    /// Output item of a type not modelled here (e.g., built-in tool calls)
+    #[serde(other)]
+    Other,
 }
 
 /// Content types that can appear in a response output message
@@ -225,7 +248,7 @@ pub enum ResponseOutputItem {
 #[serde(tag = "type")]
 pub enum ResponseOutputContent {
     /// Text output with annotations
-    #[serde(rename = "text")]
+    #[serde(rename = "output_text", alias = "text")]
     Text {
         /// The text output from the model
         text: String,
@@ -382,6 +405,97 @@ pub struct ResponsesStreamChunk {
     pub usage: Option<ResponsesUsage>,
 }
 
+/// Server-sent event emitted by the streaming Responses API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ResponsesStreamEvent {
+    /// A new output item was added
+    #[serde(rename = "response.output_item.added")]
+    OutputItemAdded {
+        /// Index of the output item
+        output_index: u32,
+        /// The added item
+        item: ResponseOutputItem,
+    },
+
+    /// An output item is complete
+    #[serde(rename = "response.output_item.done")]
+    OutputItemDone {
+        /// Index of the output item
+        output_index: u32,
+        /// The completed item
+        item: ResponseOutputItem,
+    },
+
+    /// Partial output text
+    #[serde(rename = "response.output_text.delta")]
+    OutputTextDelta {
+        /// ID of the message item
+        item_id: String,
+        /// Index of the output item
+        output_index: u32,
+        /// The text delta
+        delta: String,
+    },
+
+    /// Partial reasoning summary text
+    #[serde(rename = "response.reasoning_summary_text.delta")]
+    ReasoningSummaryTextDelta {
+        /// ID of the reasoning item
+        item_id: String,
+        /// Index of the output item
+        output_index: u32,
+        /// The summary text delta
+        delta: String,
+    },
+
+    /// Partial function call arguments
+    #[serde(rename = "response.function_call_arguments.delta")]
+    FunctionCallArgumentsDelta {
+        /// ID of the function call item
+        item_id: String,
+        /// Index of the output item
+        output_index: u32,
+        /// The arguments delta
+        delta: String,
+    },
+
+    /// The response finished successfully
+    #[serde(rename = "response.completed")]
+    Completed {
+        /// The final response
+        response: Box<ResponsesResponse>,
+    },
+
+    /// The response finished early (e.g., max output tokens reached)
+    #[serde(rename = "response.incomplete")]
+    Incomplete {
+        /// The final response
+        response: Box<ResponsesResponse>,
+    },
+
+    /// The response failed
+    #[serde(rename = "response.failed")]
+    Failed {
+        /// The final response, carrying the error
+        response: Box<ResponsesResponse>,
+    },
+
+    /// A streaming error occurred
+    #[serde(rename = "error")]
+    Error {
+        /// Error code
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        code: Option<String>,
+        /// Error message
+        message: String,
+    },
+
+    /// Any other event (e.g., `response.created`, content part lifecycle events)
+    #[serde(other)]
+    Other,
+}
+
 /// Delta for streaming output items
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -65,7 +65,7 @@ mod tests {
         assert!(
             responses
                 .iter()
-                .any(|r: &openai_ox::ChatResponse| r.content().is_some())
+                .any(|r: &openai_ox::ChatCompletionChunk| r.content().is_some())
         );
     }
 
@@ -130,38 +130,25 @@ async fn test_real_responses_api_streaming() {
 
     while let Some(result) = stream.next().await {
         match result {
-            Ok(chunk) => {
+            Ok(event) => {
                 chunks_received += 1;
-                println!("Chunk {}: status={}", chunks_received, chunk.status);
-
-                // Print any output in this chunk
-                for output in &chunk.output {
-                    match output {
-                        openai_ox::OutputDelta::MessageDelta(msg_delta) => {
-                            if let Some(content) = &msg_delta.content {
-                                print!("{}", content);
-                            }
-                        }
-                        openai_ox::OutputDelta::TextDelta(text_delta) => {
-                            print!("{}", text_delta.text);
-                        }
-                        openai_ox::OutputDelta::ReasoningDelta(reasoning_delta) => {
-                            if let Some(summary) = &reasoning_delta.summary {
-                                println!("\nReasoning: {}", summary);
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-
-                // Capture final usage stats
-                if let Some(usage) = chunk.usage {
-                    final_usage = Some(usage);
-                }
 
-                // Break if completed to avoid infinite loop
-                if chunk.status == "completed" {
-                    break;
+                match event {
+                    openai_ox::ResponsesStreamEvent::OutputTextDelta { delta, .. } => {
+                        print!("{}", delta);
+                    }
+                    openai_ox::ResponsesStreamEvent::ReasoningSummaryTextDelta {
+                        delta, ..
+                    } => {
+                        println!("\nReasoning: {}", delta);
+                    }
+                    openai_ox::ResponsesStreamEvent::Completed { response }
+                    | openai_ox::ResponsesStreamEvent::Incomplete { response } => {
+                        // Capture final usage stats and stop
+                        final_usage = response.usage;
+                        break;
+                    }
+                    _ => {}
                 }
             }
             Err(e) => {
@@ -1,5 +1,5 @@
 use ai_ox_common::{
-    BoxStream,
+    BoxStream, Transport,
     error::ProviderError,
     openai_format::{ChatCompletionChunk, ChatCompletionResponse, ChatRequest},
     request_builder::{AuthMethod, Endpoint, HttpMethod, RequestBuilder, RequestConfig},
@@ -10,9 +10,15 @@ pub(crate) struct OpencodeRequestHelper {
 }
 
 impl OpencodeRequestHelper {
-    pub(crate) fn new(client: reqwest::Client, base_url: &str, api_key: Option<&str>) -> Self {
-        let mut config =
-            RequestConfig::new(base_url).with_header("content-type", "application/json");
+    pub(crate) fn new(
+        client: reqwest::Client,
+        base_url: &str,
+        api_key: Option<&str>,
+        transport: &Transport,
+    ) -> Self {
+        let mut config = RequestConfig::new(base_url)
+            .with_header("content-type", "application/json")
+            .with_transport(transport.clone());
 
         if let Some(token) = api_key {
             config = config.with_auth(AuthMethod::Bearer(token.to_string()));
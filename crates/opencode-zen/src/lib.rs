@@ -6,7 +6,7 @@
 )]
 
 use ai_ox_common::{
-    BoxStream,
+    BoxStream, Transport,
     openai_format::{ChatCompletionChunk, ChatCompletionResponse, ChatRequest, ChatRequestBuilder},
 };
 #[cfg(feature = "leaky-bucket")]
@@ -26,6 +26,8 @@ pub struct OpencodeZen {
     client: reqwest::Client,
     #[cfg(feature = "leaky-bucket")]
     rate_limiter: Option<std::sync::Arc<leaky_bucket::RateLimiter>>,
+    /// Live, recording or replaying transport for API requests
+    transport: Transport,
 }
 
 impl Default for OpencodeZen {
@@ -36,6 +38,7 @@ impl Default for OpencodeZen {
             client: reqwest::Client::new(),
             #[cfg(feature = "leaky-bucket")]
             rate_limiter: None,
+            transport: Transport::default(),
         }
     }
 }
@@ -77,8 +80,20 @@ impl OpencodeZen {
         self
     }
 
+    /// Send requests through `transport`, e.g. to record or replay a cassette
+    #[must_use]
+    pub fn with_transport(mut self, transport: Transport) -> Self {
+        self.transport = transport;
+        self
+    }
+
     fn request_helper(&self) -> OpencodeRequestHelper {
-        OpencodeRequestHelper::new(self.client.clone(), &self.base_url, self.api_key.as_deref())
+        OpencodeRequestHelper::new(
+            self.client.clone(),
+            &self.base_url,
+            self.api_key.as_deref(),
+            &self.transport,
+        )
     }
 
     pub fn chat(&self) -> ChatRequestBuilder {
@@ -6,11 +6,12 @@ use crate::{
     },
 };
 use ai_ox_common::{
-    BoxStream,
+    BoxStream, Transport,
     error::ProviderError,
     request_builder::{AuthMethod, Endpoint, HttpMethod, RequestBuilder, RequestConfig},
 };
 use futures_util::stream::BoxStream as FuturesBoxStream;
+use serde::de::DeserializeOwned;
 
 /// OpenRouter client helper methods using the common RequestBuilder
 pub struct OpenRouterRequestHelper {
@@ -18,10 +19,16 @@ pub struct OpenRouterRequestHelper {
 }
 
 impl OpenRouterRequestHelper {
-    pub fn new(client: reqwest::Client, base_url: &str, api_key: &str) -> Self {
+    pub fn new(
+        client: reqwest::Client,
+        base_url: &str,
+        api_key: &str,
+        transport: &Transport,
+    ) -> Self {
         let config = RequestConfig::new(base_url)
             .with_auth(AuthMethod::Bearer(api_key.to_string()))
-            .with_header("content-type", "application/json");
+            .with_header("content-type", "application/json")
+            .with_transport(transport.clone());
 
         let request_builder = RequestBuilder::new(client, config);
 
@@ -56,6 +63,14 @@ impl OpenRouterRequestHelper {
         stream
     }
 
+    /// Execute a GET request and return the deserialized response
+    pub async fn request<T: DeserializeOwned>(
+        &self,
+        endpoint: &Endpoint,
+    ) -> Result<T, OpenRouterRequestError> {
+        Ok(self.request_builder.request(endpoint).await?)
+    }
+
     /// List available models
     pub async fn list_models(&self) -> Result<ModelsResponse, OpenRouterRequestError> {
         let endpoint = Endpoint::new("api/v1/models", HttpMethod::Get);
@@ -5,6 +5,7 @@
     clippy::missing_docs_in_private_items
 )]
 
+use ai_ox_common::Transport;
 use async_stream::try_stream;
 use bon::Builder;
 use futures_util::stream::BoxStream;
@@ -44,6 +45,9 @@ pub struct OpenRouter {
     #[cfg(feature = "leaky-bucket")]
     #[allow(dead_code)]
     leaky_bucket: Option<Arc<RateLimiter>>,
+    /// Live, recording or replaying transport for API requests
+    #[builder(default)]
+    transport: Transport,
 }
 
 impl OpenRouter {
@@ -56,6 +60,7 @@ impl OpenRouter {
             client: reqwest::Client::new(),
             #[cfg(feature = "leaky-bucket")]
             leaky_bucket: None,
+            transport: Transport::default(),
         }
     }
 
@@ -66,7 +71,12 @@ impl OpenRouter {
 
     /// Create request helper for internal use
     fn request_helper(&self) -> OpenRouterRequestHelper {
-        OpenRouterRequestHelper::new(self.client.clone(), &self.base_url, &self.api_key)
+        OpenRouterRequestHelper::new(
+            self.client.clone(),
+            &self.base_url,
+            &self.api_key,
+            &self.transport,
+        )
     }
 
     pub async fn send(
@@ -4,7 +4,7 @@ use bon::Builder;
 // Removed unused import
 use serde::{Deserialize, Serialize};
 
-use crate::response::ToolCall;
+use crate::response::{ReasoningDetail, ToolCall};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -360,6 +360,13 @@ pub struct AssistantMessage {
     pub refusal: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    /// Plain-text reasoning returned by reasoning models.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<String>,
+    /// Structured reasoning blocks, replayed verbatim to preserve reasoning
+    /// across tool calls.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_details: Option<Vec<ReasoningDetail>>,
 }
 
 impl AssistantMessage {
@@ -374,6 +381,8 @@ impl AssistantMessage {
             name: None,
             tool_calls: None,
             refusal: None,
+            reasoning: None,
+            reasoning_details: None,
         }
     }
 
@@ -385,6 +394,8 @@ impl AssistantMessage {
             name: None,
             tool_calls: None,
             refusal: None,
+            reasoning: None,
+            reasoning_details: None,
         }
     }
 
@@ -396,6 +407,8 @@ impl AssistantMessage {
             name: None,
             tool_calls: None,
             refusal: None,
+            reasoning: None,
+            reasoning_details: None,
         }
     }
 
@@ -407,6 +420,8 @@ impl AssistantMessage {
             name: None,
             tool_calls: None,
             refusal: None,
+            reasoning: None,
+            reasoning_details: None,
         }
     }
 
@@ -418,6 +433,8 @@ impl AssistantMessage {
             name: None,
             tool_calls: None,
             refusal: None,
+            reasoning: None,
+            reasoning_details: None,
         }
     }
 
@@ -469,6 +486,8 @@ impl From<&str> for AssistantMessage {
             name: None,
             tool_calls: None,
             refusal: None,
+            reasoning: None,
+            reasoning_details: None,
         }
     }
 }
@@ -480,6 +499,8 @@ impl From<String> for AssistantMessage {
             name: None,
             tool_calls: None,
             refusal: None,
+            reasoning: None,
+            reasoning_details: None,
         }
     }
 }
@@ -497,7 +518,7 @@ impl Serialize for AssistantMessage {
             .as_ref()
             .map_or(false, |calls| !calls.is_empty());
 
-        let mut state = serializer.serialize_struct("AssistantMessage", 4)?;
+        let mut state = serializer.serialize_struct("AssistantMessage", 6)?;
 
         if has_tool_calls {
             // For messages with tool calls, serialize content as a simple string
@@ -533,6 +554,14 @@ impl Serialize for AssistantMessage {
             state.serialize_field("name", name)?;
         }
 
+        if let Some(ref reasoning) = self.reasoning {
+            state.serialize_field("reasoning", reasoning)?;
+        }
+
+        if let Some(ref reasoning_details) = self.reasoning_details {
+            state.serialize_field("reasoning_details", reasoning_details)?;
+        }
+
         state.end()
     }
 }
@@ -1,7 +1,9 @@
 use bon::Builder;
 use serde::{Deserialize, Serialize};
 
-use crate::OpenRouter;
+use ai_ox_common::request_builder::{Endpoint, HttpMethod};
+
+use crate::{OpenRouter, OpenRouterRequestError};
 
 /// Represents a model pricing structure.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -85,13 +87,9 @@ pub struct ModelsRequest {}
 
 impl ModelsRequest {
     /// Retrieves the list of available models from the API.
-    pub async fn fetch(&self, client: &reqwest::Client) -> Result<Vec<Model>, reqwest::Error> {
-        let response = client
-            .get("https://openrouter.ai/api/v1/models")
-            .send()
-            .await?
-            .json::<ModelsResponseRaw>()
-            .await?;
+    pub async fn fetch(&self, client: &OpenRouter) -> Result<Vec<Model>, OpenRouterRequestError> {
+        let endpoint = Endpoint::new("api/v1/models", HttpMethod::Get);
+        let response: ModelsResponseRaw = client.request_helper().request(&endpoint).await?;
 
         Ok(response.data)
     }
@@ -99,8 +97,8 @@ impl ModelsRequest {
 
 impl OpenRouter {
     /// Retrieves the list of available models from the API.
-    pub async fn models(&self) -> Result<Vec<Model>, reqwest::Error> {
+    pub async fn models(&self) -> Result<Vec<Model>, OpenRouterRequestError> {
         let request = ModelsRequest::default();
-        request.fetch(&self.client).await
+        request.fetch(self).await
     }
 }
@@ -45,6 +45,8 @@ impl From<ChatCompletionResponse> for Message {
                 tool_calls: None,
                 name: None,
                 refusal: None,
+                reasoning: None,
+                reasoning_details: None,
             })
         }
     }
@@ -204,6 +206,8 @@ pub struct ReasoningDetail {
     pub summary: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -226,64 +230,24 @@ pub struct ResponseMessage {
     pub reasoning_details: Option<Vec<ReasoningDetail>>,
 }
 
-/// Extract content from OpenRouter response, handling various content patterns.
-///
-/// GPT-5 and some other models return empty `content` but provide actual text in:
-/// - `reasoning` field (some models)
-/// - `reasoning_details` array with summary/text/data fields (GPT-5)
-///
-/// This function implements a cascade: content → reasoning → reasoning_details
-fn extract_reasoning_content(
-    content: Option<String>,
-    reasoning: Option<String>,
-    reasoning_details: Option<Vec<ReasoningDetail>>,
-) -> Vec<ContentPart> {
-    match (content, reasoning, reasoning_details) {
-        // Normal content (most models)
-        (Some(text), _, _) if !text.is_empty() => vec![ContentPart::Text(text.into())],
-
-        // Reasoning field (some models like o1)
-        (_, Some(reasoning), _) if !reasoning.is_empty() => {
-            vec![ContentPart::Text(reasoning.into())]
-        }
-
-        // Reasoning details (GPT-5 pattern) - extract from first detail
-        (_, _, Some(details)) if !details.is_empty() => {
-            if let Some(first_detail) = details.first() {
-                // Try summary first (human-readable reasoning)
-                if let Some(summary) = &first_detail.summary {
-                    vec![ContentPart::Text(summary.into())]
-                }
-                // Then text field
-                else if let Some(text) = &first_detail.text {
-                    vec![ContentPart::Text(text.into())]
-                }
-                // Finally encrypted data (show placeholder)
-                else if let Some(_data) = &first_detail.data {
-                    vec![ContentPart::Text("[Encrypted reasoning data]".into())]
-                } else {
-                    vec![]
-                }
-            } else {
-                vec![]
-            }
-        }
-
-        // No content found
-        _ => vec![],
-    }
-}
-
+/// Keeps reasoning separate from the visible content so it can be replayed
+/// verbatim in later requests.
 impl From<ResponseMessage> for AssistantMessage {
     fn from(resp: ResponseMessage) -> Self {
-        let content_parts =
-            extract_reasoning_content(resp.content, resp.reasoning, resp.reasoning_details);
+        let content_parts = resp
+            .content
+            .filter(|text| !text.is_empty())
+            .map(|text| ContentPart::Text(text.into()))
+            .into_iter()
+            .collect();
 
         AssistantMessage {
             content: Content(content_parts),
             tool_calls: resp.tool_calls,
             name: None,
             refusal: resp.refusal,
+            reasoning: resp.reasoning.filter(|reasoning| !reasoning.is_empty()),
+            reasoning_details: resp.reasoning_details.filter(|details| !details.is_empty()),
         }
     }
 }
@@ -298,73 +262,52 @@ impl From<ResponseMessage> for Message {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_extract_reasoning_content_normal_content() {
-        let result =
-            extract_reasoning_content(Some("Regular text content".to_string()), None, None);
-        assert_eq!(result.len(), 1);
-        if let ContentPart::Text(text) = &result[0] {
-            assert_eq!(text.text, "Regular text content");
-        } else {
-            panic!("Expected text content");
-        }
-    }
-
-    #[test]
-    fn test_extract_reasoning_content_from_reasoning_field() {
-        let result = extract_reasoning_content(
-            Some("".to_string()), // Empty content
-            Some("Reasoning text here".to_string()),
-            None,
-        );
-        assert_eq!(result.len(), 1);
-        if let ContentPart::Text(text) = &result[0] {
-            assert_eq!(text.text, "Reasoning text here");
-        } else {
-            panic!("Expected reasoning content");
-        }
+    fn response_message(json: serde_json::Value) -> AssistantMessage {
+        serde_json::from_value::<ResponseMessage>(json)
+            .unwrap()
+            .into()
     }
 
     #[test]
-    fn test_extract_reasoning_content_from_reasoning_details_summary() {
-        let details = vec![ReasoningDetail {
-            detail_type: "reasoning.summary".to_string(),
-            text: None,
-            summary: Some("Summary text".to_string()),
-            data: Some("ignored-data".to_string()),
-            id: None,
-            format: None,
-            index: None,
-        }];
-
-        let result = extract_reasoning_content(None, None, Some(details));
-        assert_eq!(result.len(), 1);
-        if let ContentPart::Text(text) = &result[0] {
-            assert_eq!(text.text, "Summary text");
+    fn test_response_message_keeps_content() {
+        let message = response_message(serde_json::json!({
+            "role": "assistant",
+            "content": "Regular text content",
+            "refusal": null
+        }));
+        assert_eq!(message.content.len(), 1);
+        if let ContentPart::Text(text) = &message.content[0] {
+            assert_eq!(text.text, "Regular text content");
         } else {
-            panic!("Expected summary content");
+            panic!("Expected text content");
         }
+        assert!(message.reasoning.is_none());
+        assert!(message.reasoning_details.is_none());
     }
 
     #[test]
-    fn test_extract_reasoning_content_from_reasoning_details_encrypted() {
-        let details = vec![ReasoningDetail {
-            detail_type: "reasoning.encrypted".to_string(),
-            text: None,
-            summary: None,
-            data: Some("encrypted-data-here".to_string()),
-            id: None,
-            format: None,
-            index: None,
-        }];
-
-        let result = extract_reasoning_content(None, None, Some(details));
-        assert_eq!(result.len(), 1);
-        if let ContentPart::Text(text) = &result[0] {
-            assert_eq!(text.text, "[Encrypted reasoning data]");
-        } else {
-            panic!("Expected encrypted data placeholder");
-        }
+    fn test_response_message_keeps_reasoning_separate() {
+        let message = response_message(serde_json::json!({
+            "role": "assistant",
+            "content": "",
+            "refusal": null,
+            "reasoning": "Reasoning text here",
+            "reasoning_details": [{
+                "type": "reasoning.text",
+                "text": "Reasoning text here",
+                "signature": "sig",
+                "format": "anthropic-claude-v1"
+            }]
+        }));
+        assert!(message.content.is_empty());
+        assert_eq!(message.reasoning.as_deref(), Some("Reasoning text here"));
+
+        let details = message.reasoning_details.as_ref().unwrap();
+        assert_eq!(details[0].signature.as_deref(), Some("sig"));
+
+        // Reasoning is sent back with the assistant message.
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(json["reasoning_details"][0]["signature"], "sig");
     }
 
     #[test]
@@ -384,12 +327,6 @@ mod tests {
             serde_json::from_str(&raw).expect("integer timestamp should deserialize");
         assert_eq!(chunk.created.to_unix_timestamp_i64(), 1_758_887_156);
     }
-
-    #[test]
-    fn test_extract_reasoning_content_empty() {
-        let result = extract_reasoning_content(None, None, None);
-        assert_eq!(result.len(), 0);
-    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -505,6 +442,10 @@ pub struct Delta {
     pub content: Option<String>,
     #[serde(default)]
     pub tool_calls: Option<Vec<ToolCall>>,
+    #[serde(default)]
+    pub reasoning: Option<String>,
+    #[serde(default)]
+    pub reasoning_details: Option<Vec<ReasoningDetail>>,
 }
 
 // Additional API endpoint response types
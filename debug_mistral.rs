@@ -24,6 +24,7 @@ async fn main() {
         messages: vec![message],
         system_message: None,
         tools: None,
+        generation_config: None,
     };
 
     // Create Mistral model (this will fail with dummy key but we can see the JSON)
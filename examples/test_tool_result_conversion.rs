@@ -85,6 +85,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         ],
         system_message: None,
         tools: Some(vec![]),
+        generation_config: None,
     };
 
     println!("Sending request with complex tool result to OpenRouter...");